@@ -0,0 +1,44 @@
+//! Reserve/commit/decommit throughput, without external benchmarking crates.
+//!
+//! Run with `cargo bench --bench reserve`.
+
+use staticbip::StaticBip;
+use std::{hint::black_box, time::Instant};
+
+const ROUNDS: usize = 10_000_000;
+const SAMPLES: usize = 5;
+
+/// Measures `ROUNDS` calls of `op` several times and prints the fastest time per call.
+fn bench(name: &str, mut op: impl FnMut()) {
+    let nanos = (0..SAMPLES)
+        .map(|_| {
+            let start = Instant::now();
+            for _ in 0..ROUNDS {
+                op();
+            }
+            start.elapsed().as_nanos() as f64 / ROUNDS as f64
+        })
+        .fold(f64::INFINITY, f64::min);
+    println!("{:<24} {:>6.2} ns/op", name, nanos);
+}
+
+fn main() {
+    // Single bytes through a buffer that is mostly far from wrapping
+    let mut buffer = StaticBip::<u8, 1024>::default();
+    bench("byte, no wrap", || {
+        let reserved = buffer.reserve(black_box(1));
+        let len = reserved.len();
+        reserved.fill(1);
+        buffer.commit(len);
+        buffer.decommit(black_box(1));
+    });
+
+    // Chunks that leave the buffer wrapped half of the time
+    let mut buffer = StaticBip::<u8, 1024>::default();
+    buffer.extend(&[0; 512]);
+    bench("chunk, wrapping", || {
+        let len = buffer.reserve(black_box(96)).len();
+        buffer.commit(len);
+        buffer.decommit(black_box(96));
+    });
+}
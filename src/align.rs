@@ -0,0 +1,84 @@
+//! Alignment guarantees for backing storage.
+//!
+//! ```rust
+//! use staticbip::{align::{A32, Aligned}, StaticBip};
+//!
+//! let mut buffer = Aligned::<A32, StaticBip<u8, 64>>::default();
+//! assert_eq!(buffer.reserve(16).as_ptr() as usize % 32, 0);
+//! ```
+
+use core::{
+    fmt,
+    ops::{Deref, DerefMut},
+};
+
+macro_rules! alignments {
+    ($($name:ident = $align:literal),*) => {$(
+        #[doc = concat!("Marker for ", stringify!($align), "-byte alignment.")]
+        #[derive(Debug, Clone, Copy)]
+        #[repr(align($align))]
+        pub struct $name;
+    )*};
+}
+
+alignments!(
+    A2 = 2,
+    A4 = 4,
+    A8 = 8,
+    A16 = 16,
+    A32 = 32,
+    A64 = 64,
+    A128 = 128
+);
+
+/// A value stored at an address aligned to the alignment of `A`.
+///
+/// `A` is one of the marker types of this module, such as [`A32`].
+#[repr(C)]
+pub struct Aligned<A, T> {
+    align: [A; 0],
+    value: T,
+}
+
+impl<A, T> Aligned<A, T> {
+    /// Wraps `value`.
+    #[inline]
+    pub const fn new(value: T) -> Self {
+        Self { align: [], value }
+    }
+
+    /// Returns the wrapped value.
+    #[inline]
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+}
+
+impl<A, T: Default> Default for Aligned<A, T> {
+    #[inline]
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+
+impl<A, T: fmt::Debug> fmt::Debug for Aligned<A, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.value.fmt(f)
+    }
+}
+
+impl<A, T> Deref for Aligned<A, T> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<A, T> DerefMut for Aligned<A, T> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.value
+    }
+}
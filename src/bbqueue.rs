@@ -0,0 +1,1084 @@
+//! Lock-free byte queue with the API of the `bbqueue` crate.
+//!
+//! A [`BufStorage`] is split once into a [`Producer`] and a [`Consumer`], which can live in
+//! different threads or interrupt priorities. The producer writes through a [`GrantW`] and
+//! the consumer reads through a [`GrantR`]; each grant is one contiguous slice of the
+//! storage, as in any Bip-Buffer.
+//!
+//! ```rust
+//! use staticbip::bbqueue::BufStorage;
+//!
+//! static STORAGE: BufStorage<8> = BufStorage::new();
+//!
+//! let (mut producer, mut consumer) = STORAGE.try_split().unwrap();
+//!
+//! let mut grant = producer.grant_exact(4).unwrap();
+//! grant.copy_from_slice(&[1, 2, 3, 4]);
+//! grant.commit(4);
+//!
+//! let grant = consumer.read().unwrap();
+//! assert_eq!(&*grant, &[1, 2, 3, 4]);
+//! grant.release(4);
+//! ```
+//!
+//! # Static halves
+//!
+//! Splitting storage in a `static`, or a `&'static mut` handed out at init time, yields
+//! `Producer<'static, N>` and `Consumer<'static, N>`. Both are `Send`, so they can be moved
+//! into separate task resources or interrupt priorities.
+//!
+//! ```rust
+//! use staticbip::bbqueue::{BufStorage, Consumer, Producer};
+//!
+//! fn init(storage: &'static mut BufStorage<8>) -> (Producer<'static, 8>, Consumer<'static, 8>) {
+//!     storage.split_mut()
+//! }
+//!
+//! let (mut producer, mut consumer) = init(Box::leak(Box::new(BufStorage::new())));
+//! std::thread::spawn(move || producer.grant_exact(1).unwrap().commit(1))
+//!     .join()
+//!     .unwrap();
+//! assert_eq!(consumer.read().unwrap().len(), 1);
+//! ```
+//!
+//! # Owned halves
+//!
+//! With the `alloc` feature, storage in an `Arc` is split with
+//! [`split_owned`](BufStorage::split_owned) into an [`OwnedProducer`] and an
+//! [`OwnedConsumer`]. Each keeps the storage alive, so the halves are `'static` without the
+//! storage being `static` itself.
+//!
+//! # Waking
+//!
+//! [`Consumer::register_read_waker`] stores a waker that is woken once the producer commits,
+//! and [`Producer::register_write_waker`] one that is woken once the consumer releases. A
+//! half registers before finding nothing to do, so any executor can wait on the other half.
+//!
+//! ```rust
+//! use core::{future::poll_fn, task::Poll};
+//! use staticbip::bbqueue::{BufStorage, Consumer};
+//!
+//! async fn next_byte(consumer: &mut Consumer<'_, 8>) -> u8 {
+//!     poll_fn(|cx| {
+//!         consumer.register_read_waker(cx.waker());
+//!         match consumer.read() {
+//!             Ok(grant) => {
+//!                 let byte = grant[0];
+//!                 grant.release(1);
+//!                 Poll::Ready(byte)
+//!             }
+//!             Err(_) => Poll::Pending,
+//!         }
+//!     })
+//!     .await
+//! }
+//!
+//! let storage = BufStorage::<8>::new();
+//! let (mut producer, mut consumer) = storage.try_split().unwrap();
+//! producer.grant_exact(1).unwrap().commit(1);
+//! # use core::future::Future;
+//! # let mut task = Box::pin(next_byte(&mut consumer));
+//! # let mut cx = core::task::Context::from_waker(core::task::Waker::noop());
+//! # assert_eq!(task.as_mut().poll(&mut cx), Poll::Ready(0));
+//! ```
+//!
+//! # WebAssembly workers
+//!
+//! On `wasm32` built with the `atomics` and `bulk-memory` target features, the module's
+//! linear memory is a `SharedArrayBuffer` shared by every worker that instantiates it, so
+//! storage in a `static` or leaked from a `Box` is already shared. Only the halves have to
+//! cross the worker boundary, and `postMessage` cannot move Rust values:
+//! [`Producer::into_raw`] turns a half into a pointer that is sent as a number, and
+//! [`Producer::from_raw`] turns it back into the half on the other side. Positions are
+//! published with release stores and read with acquire loads, which order the data written
+//! by one worker before the other reads it. Without the `atomics` feature the module is
+//! single-threaded and the halves cannot be shared.
+//!
+//! ```rust
+//! use staticbip::bbqueue::{BufStorage, Producer};
+//!
+//! static SAMPLES: BufStorage<64> = BufStorage::new();
+//!
+//! let (producer, mut consumer) = SAMPLES.try_split().unwrap();
+//! // Sent to the audio worker with `postMessage`
+//! let message = producer.into_raw() as usize;
+//!
+//! // In the worker
+//! let mut producer = unsafe { Producer::from_raw(message as *const BufStorage<64>) };
+//! producer.grant_exact(2).unwrap().commit(2);
+//!
+//! assert_eq!(consumer.read().unwrap().len(), 2);
+//! ```
+//!
+//! # Multi-core targets
+//!
+//! Every position and counter has a single writer: the producer publishes `write`, `last`
+//! and its commit count, the consumer `read` and its release count. Each half writes the
+//! bytes it owns before a release store of its position, and the other half loads that
+//! position with acquire ordering before touching those bytes. On Armv6-M and Armv7-M,
+//! including dual-core parts such as the RP2040, these orderings compile to `DMB` barriers
+//! around the accesses, which is what a queue between two cores needs; relaxed accesses
+//! would let one core see a position before the bytes it covers. With the `seqcst` feature,
+//! every access is `SeqCst` instead.
+//!
+//! The protocol is stress-tested across threads, but has not been model-checked with `loom`.
+//!
+//! Since no read-modify-write is used, the halves work on cores without compare-and-swap,
+//! such as the Cortex-M0+. There, [`try_split`](BufStorage::try_split), owned halves and
+//! waker registration are not available; split storage with
+//! [`split_mut`](BufStorage::split_mut) from a `&'static mut`, and hand one half to the
+//! other core at startup.
+//!
+//! # Differences from `bbqueue`
+//!
+//! - Grants borrow their half mutably, so holding two grants from the same half is a compile
+//!   error rather than an [`Error::GrantInProgress`] at runtime. The variant is kept for code
+//!   matching on it.
+//! - There is no framed mode or `split_read`; use [`frame`](crate::frame) on a
+//!   [`StaticBip`](crate::StaticBip) for framing.
+//! - The storage is only split once; halves are not returned to it.
+
+#[cfg(target_has_atomic = "ptr")]
+use crate::waker::AtomicWaker;
+#[cfg(all(feature = "alloc", target_has_atomic = "ptr"))]
+use alloc::sync::Arc;
+#[cfg(target_has_atomic = "ptr")]
+use core::task::Waker;
+use core::{
+    cell::UnsafeCell,
+    fmt,
+    mem::offset_of,
+    ops::{Deref, DerefMut},
+    slice,
+    sync::atomic::{AtomicBool, AtomicUsize},
+};
+
+/// Memory orderings of the queue, chosen in one place.
+///
+/// Every position and counter has a single writer. The writer publishes with a release
+/// store after writing the bytes the position covers, and the other half observes it with
+/// an acquire load before touching them. A half reads back its own positions relaxed, since
+/// no other thread writes them. Claiming the halves is a read-modify-write on the split flag.
+///
+/// The `seqcst` feature replaces all of them with `SeqCst`, for review processes that
+/// require the conservative profile. It adds barriers and changes no behavior.
+mod ordering {
+    use core::sync::atomic::Ordering;
+
+    /// Picks `tuned` unless the `seqcst` feature is enabled.
+    const fn profile(tuned: Ordering) -> Ordering {
+        if cfg!(feature = "seqcst") {
+            Ordering::SeqCst
+        } else {
+            tuned
+        }
+    }
+
+    /// Loads of a position or counter written by the other half.
+    pub(super) const OBSERVE: Ordering = profile(Ordering::Acquire);
+    /// Stores of a position or counter, after the bytes it covers.
+    pub(super) const PUBLISH: Ordering = profile(Ordering::Release);
+    /// Loads of a position or counter written by the same half.
+    pub(super) const OWN: Ordering = profile(Ordering::Relaxed);
+    /// Claiming the halves of the storage.
+    pub(super) const CLAIM: Ordering = profile(Ordering::AcqRel);
+}
+
+/// Reasons a grant or split was refused.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Error {
+    /// Not enough contiguous space or data is available.
+    InsufficientSize,
+    /// A grant is already held by the same half.
+    GrantInProgress,
+    /// The storage has already been split.
+    AlreadySplit,
+    /// A position loaded from the storage lies outside it, as when a peer sharing the
+    /// storage through memory, such as C firmware or another process, corrupted it.
+    Corrupted,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::InsufficientSize => "insufficient contiguous space or data",
+            Self::GrantInProgress => "grant already in progress",
+            Self::AlreadySplit => "storage already split",
+            Self::Corrupted => "storage positions out of range",
+        })
+    }
+}
+
+/// Backing storage of `N` bytes shared by a producer and a consumer.
+///
+/// With the `cache-padding` feature, the positions written by the producer and by the
+/// consumer sit on separate cache lines so the two halves do not contend for one line.
+///
+/// The storage is `#[repr(C)]`, so code outside Rust, such as C firmware on another core or
+/// a DSP, can take one half. Without `cache-padding` it starts like
+///
+/// ```c
+/// struct bbq_storage {
+///     size_t write;     /* end of the committed data, written by the producer */
+///     size_t last;      /* end of the data before the producer wrapped */
+///     size_t committed; /* total bytes ever committed, wrapping */
+///     size_t read;      /* start of the unread data, written by the consumer */
+///     size_t released;  /* total bytes ever released, wrapping */
+///     uint8_t split;
+///     uint8_t buffer[N];
+///     /* waker slots used only by Rust */
+/// };
+/// ```
+///
+/// With `cache-padding`, `read` and `split` each start a new 64-byte line. The offsets are
+/// available as constants such as [`WRITE_OFFSET`](Self::WRITE_OFFSET) either way.
+#[repr(C)]
+pub struct BufStorage<const N: usize> {
+    producer: CachePadded<ProducerState>,
+    consumer: CachePadded<ConsumerState>,
+    /// Whether the storage has been split
+    split: AtomicBool,
+    buffer: UnsafeCell<[u8; N]>,
+    /// Producer waiting for a release
+    #[cfg(target_has_atomic = "ptr")]
+    write_waker: AtomicWaker,
+    /// Consumer waiting for a commit
+    #[cfg(target_has_atomic = "ptr")]
+    read_waker: AtomicWaker,
+}
+
+/// Positions written by the producer.
+#[repr(C)]
+struct ProducerState {
+    /// End of the committed data
+    write: AtomicUsize,
+    /// End of the data before the producer wrapped to the start
+    last: AtomicUsize,
+    /// Total bytes ever committed, wrapping
+    committed: AtomicUsize,
+}
+
+/// Positions written by the consumer.
+#[repr(C)]
+struct ConsumerState {
+    /// Start of the unread data
+    read: AtomicUsize,
+    /// Total bytes ever released, wrapping
+    released: AtomicUsize,
+}
+
+/// Aligns its contents to a cache line with the `cache-padding` feature.
+#[repr(C)]
+#[cfg_attr(feature = "cache-padding", repr(align(64)))]
+struct CachePadded<T>(T);
+
+impl<T> Deref for CachePadded<T> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+// SAFETY: the producer and consumer only access disjoint parts of the buffer, handed over
+// through the atomic positions.
+unsafe impl<const N: usize> Sync for BufStorage<N> {}
+
+impl<const N: usize> Default for BufStorage<N> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> BufStorage<N> {
+    /// Byte offset of the `write` position.
+    pub const WRITE_OFFSET: usize = offset_of!(Self, producer.0.write);
+    /// Byte offset of the `last` position.
+    pub const LAST_OFFSET: usize = offset_of!(Self, producer.0.last);
+    /// Byte offset of the count of committed bytes.
+    pub const COMMITTED_OFFSET: usize = offset_of!(Self, producer.0.committed);
+    /// Byte offset of the `read` position.
+    pub const READ_OFFSET: usize = offset_of!(Self, consumer.0.read);
+    /// Byte offset of the count of released bytes.
+    pub const RELEASED_OFFSET: usize = offset_of!(Self, consumer.0.released);
+    /// Byte offset of the flag set once the storage is split.
+    pub const SPLIT_OFFSET: usize = offset_of!(Self, split);
+    /// Byte offset of the data.
+    pub const BUFFER_OFFSET: usize = offset_of!(Self, buffer);
+
+    /// Creates empty storage, usable in a `static`.
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            producer: CachePadded(ProducerState {
+                write: AtomicUsize::new(0),
+                last: AtomicUsize::new(0),
+                committed: AtomicUsize::new(0),
+            }),
+            consumer: CachePadded(ConsumerState {
+                read: AtomicUsize::new(0),
+                released: AtomicUsize::new(0),
+            }),
+            split: AtomicBool::new(false),
+            buffer: UnsafeCell::new([0; N]),
+            #[cfg(target_has_atomic = "ptr")]
+            write_waker: AtomicWaker::new(),
+            #[cfg(target_has_atomic = "ptr")]
+            read_waker: AtomicWaker::new(),
+        }
+    }
+
+    /// Splits the storage into its producer and consumer halves.
+    ///
+    /// Fails if the storage has already been split. Only available on targets with
+    /// compare-and-swap; see [`split_mut`](Self::split_mut) for the others.
+    #[cfg(target_has_atomic = "ptr")]
+    pub fn try_split(&self) -> Result<(Producer<'_, N>, Consumer<'_, N>), Error> {
+        if self.split.swap(true, ordering::CLAIM) {
+            return Err(Error::AlreadySplit);
+        }
+        Ok((Producer { storage: self }, Consumer { storage: self }))
+    }
+
+    /// Splits storage the caller holds exclusively into its producer and consumer halves.
+    ///
+    /// Unlike [`try_split`](Self::try_split) this cannot fail, since halves from an earlier
+    /// split no longer borrow the storage. Given a `&'static mut`, such as an RTIC local
+    /// resource in `init`, the halves are `'static`.
+    #[inline]
+    pub fn split_mut(&mut self) -> (Producer<'_, N>, Consumer<'_, N>) {
+        *self.split.get_mut() = true;
+        let storage = &*self;
+        (Producer { storage }, Consumer { storage })
+    }
+
+    /// Splits shared storage into halves that own a reference to it.
+    ///
+    /// The halves are `'static` and can be sent to threads or tasks that outlive the scope
+    /// creating the storage. Fails if the storage has already been split.
+    #[cfg(all(feature = "alloc", target_has_atomic = "ptr"))]
+    pub fn split_owned(self: Arc<Self>) -> Result<(OwnedProducer<N>, OwnedConsumer<N>), Error> {
+        // SAFETY: both halves hold the `Arc`, so the storage outlives the references.
+        let storage = unsafe { &*Arc::as_ptr(&self) };
+        let (producer, consumer) = storage.try_split()?;
+        Ok((
+            OwnedProducer {
+                producer,
+                _storage: self.clone(),
+            },
+            OwnedConsumer {
+                consumer,
+                _storage: self,
+            },
+        ))
+    }
+
+    /// Size of the storage.
+    #[inline]
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Returns `len` bytes starting at `start`.
+    ///
+    /// # Safety
+    ///
+    /// The range must be within the storage and owned by the caller's half.
+    #[allow(clippy::mut_from_ref)]
+    unsafe fn slice(&self, start: usize, len: usize) -> &mut [u8] {
+        slice::from_raw_parts_mut(self.buffer.get().cast::<u8>().add(start), len)
+    }
+
+    /// Bytes committed and not yet released, from the sequence counters.
+    #[inline]
+    fn pending(&self) -> usize {
+        let committed = self.producer.committed.load(ordering::OBSERVE);
+        let released = self.consumer.released.load(ordering::OBSERVE);
+        committed.wrapping_sub(released)
+    }
+
+    /// Fails with [`Error::Corrupted`] unless every position is within the storage.
+    ///
+    /// Positions are loaded from memory that a peer outside Rust may write, so each one is
+    /// checked before it bounds a slice.
+    #[inline]
+    fn check(positions: &[usize]) -> Result<(), Error> {
+        if positions.iter().all(|&position| position <= N) {
+            Ok(())
+        } else {
+            Err(Error::Corrupted)
+        }
+    }
+
+    /// Start of a grant of exactly `sz` bytes for a producer at `write`.
+    fn exact_start(&self, write: usize, sz: usize) -> Result<usize, Error> {
+        let read = self.consumer.read.load(ordering::OBSERVE);
+        Self::check(&[write, read])?;
+        if write < read {
+            // Already wrapped: the gap before the reader, keeping one byte to tell full
+            // from empty
+            // Compared as differences, since `write + sz` overflows for a huge `sz`
+            if sz < read - write {
+                Ok(write)
+            } else {
+                Err(Error::InsufficientSize)
+            }
+        } else if sz <= N - write {
+            Ok(write)
+        } else if sz < read {
+            Ok(0)
+        } else {
+            Err(Error::InsufficientSize)
+        }
+    }
+
+    /// Start and size of a grant of up to `max_sz` bytes for a producer at `write`.
+    fn max_remaining(&self, write: usize, max_sz: usize) -> Result<(usize, usize), Error> {
+        let read = self.consumer.read.load(ordering::OBSERVE);
+        Self::check(&[write, read])?;
+        let (start, sz) = if write < read {
+            (write, (read - write - 1).min(max_sz))
+        } else if write != N {
+            (write, (N - write).min(max_sz))
+        } else if read > 1 {
+            (0, (read - 1).min(max_sz))
+        } else {
+            // Full: even an empty grant would wrap the write position to the start, which
+            // the reader takes for an empty queue
+            return Err(Error::InsufficientSize);
+        };
+        if sz == 0 && max_sz != 0 {
+            return Err(Error::InsufficientSize);
+        }
+        Ok((start, sz))
+    }
+
+    /// Grants `sz` bytes at `start`, committing into `staged` if given and publishing
+    /// otherwise.
+    fn grant<'a>(
+        &'a self,
+        start: usize,
+        sz: usize,
+        staged: Option<&'a mut Staged>,
+    ) -> GrantW<'a, N> {
+        GrantW {
+            // SAFETY: the range is free space, which only the producer accesses.
+            buf: unsafe { self.slice(start, sz) },
+            storage: self,
+            staged,
+            start,
+            to_commit: 0,
+        }
+    }
+
+    /// Start and end of the next block to read for a consumer at `read`.
+    ///
+    /// The start is `0` once everything before the wrap has been read.
+    fn readable(&self, read: usize) -> Result<(usize, usize), Error> {
+        let write = self.producer.write.load(ordering::OBSERVE);
+        let last = self.producer.last.load(ordering::OBSERVE);
+        Self::check(&[write, last, read])?;
+        // Everything before the wrap has been read
+        let read = if read == last && write < read {
+            0
+        } else {
+            read
+        };
+        let end = if write < read { last } else { write };
+        if end < read {
+            return Err(Error::Corrupted);
+        }
+        if end == read {
+            return Err(Error::InsufficientSize);
+        }
+        // The producer counts a commit before publishing its position
+        debug_assert!(end - read <= self.pending(), "torn queue positions");
+        Ok((read, end))
+    }
+
+    /// Grants the bytes from `start` to `end`, releasing into `staged` if given and
+    /// publishing otherwise.
+    fn grant_read<'a>(
+        &'a self,
+        start: usize,
+        end: usize,
+        staged: Option<&'a mut Released>,
+    ) -> GrantR<'a, N> {
+        GrantR {
+            // SAFETY: the range is committed data, which only the consumer accesses.
+            buf: unsafe { self.slice(start, end - start) },
+            storage: self,
+            staged,
+            to_release: 0,
+        }
+    }
+
+    /// Positions last published by the consumer, which is the only one writing them.
+    #[inline]
+    fn released(&self) -> Released {
+        Released {
+            read: self.consumer.read.load(ordering::OWN),
+            released: self.consumer.released.load(ordering::OWN),
+        }
+    }
+
+    /// Publishes the consumer positions that changed from `old` to `new`.
+    fn publish_released(&self, old: Released, new: Released) {
+        if new.released != old.released {
+            self.consumer
+                .released
+                .store(new.released, ordering::PUBLISH);
+        }
+        if new.read != old.read {
+            self.consumer.read.store(new.read, ordering::PUBLISH);
+        }
+        #[cfg(target_has_atomic = "ptr")]
+        if new.released != old.released {
+            self.write_waker.wake();
+        }
+    }
+
+    /// Positions last published by the producer, which is the only one writing them.
+    #[inline]
+    fn staged(&self) -> Staged {
+        Staged {
+            write: self.producer.write.load(ordering::OWN),
+            last: self.producer.last.load(ordering::OWN),
+            committed: self.producer.committed.load(ordering::OWN),
+        }
+    }
+
+    /// Publishes the positions that changed from `old` to `new`.
+    fn publish(&self, old: Staged, new: Staged) {
+        if new.last != old.last {
+            self.producer.last.store(new.last, ordering::PUBLISH);
+        }
+        // Each counter has a single writer, so a store replaces a read-modify-write, which
+        // targets such as Cortex-M0+ do not have
+        if new.committed != old.committed {
+            self.producer
+                .committed
+                .store(new.committed, ordering::PUBLISH);
+        }
+        if new.write != old.write {
+            self.producer.write.store(new.write, ordering::PUBLISH);
+        }
+        #[cfg(target_has_atomic = "ptr")]
+        if new.committed != old.committed {
+            self.read_waker.wake();
+        }
+    }
+}
+
+/// Consumer positions, as published or as staged by a [`ReleaseBatch`].
+#[derive(Clone, Copy)]
+struct Released {
+    read: usize,
+    released: usize,
+}
+
+impl Released {
+    /// Moves the positions past `len` bytes released at the read position.
+    #[inline]
+    fn release(&mut self, len: usize) {
+        self.released = self.released.wrapping_add(len);
+        self.read += len;
+    }
+}
+
+/// Producer positions, as published or as staged by a [`Batch`].
+#[derive(Clone, Copy)]
+struct Staged {
+    write: usize,
+    last: usize,
+    committed: usize,
+}
+
+impl Staged {
+    /// Moves the positions past `len` bytes committed at `start` of storage of `n` bytes.
+    fn commit(&mut self, start: usize, len: usize, n: usize) {
+        let write = start + len;
+        if write < self.write && self.write != n {
+            // Wrapped to the start: the reader stops at the old end
+            self.last = self.write;
+        } else if write > self.last {
+            self.last = n;
+        }
+        self.committed = self.committed.wrapping_add(len);
+        self.write = write;
+    }
+}
+
+/// Writing half of a [`BufStorage`].
+pub struct Producer<'a, const N: usize> {
+    storage: &'a BufStorage<N>,
+}
+
+impl<'a, const N: usize> Producer<'a, N> {
+    /// Grants exactly `sz` contiguous bytes for writing.
+    pub fn grant_exact(&mut self, sz: usize) -> Result<GrantW<'_, N>, Error> {
+        let write = self.storage.producer.write.load(ordering::OWN);
+        let start = self.storage.exact_start(write, sz)?;
+        Ok(self.storage.grant(start, sz, None))
+    }
+
+    /// Grants up to `max_sz` contiguous bytes for writing.
+    pub fn grant_max_remaining(&mut self, max_sz: usize) -> Result<GrantW<'_, N>, Error> {
+        let write = self.storage.producer.write.load(ordering::OWN);
+        let (start, sz) = self.storage.max_remaining(write, max_sz)?;
+        Ok(self.storage.grant(start, sz, None))
+    }
+
+    /// Starts a batch of commits that the consumer sees all at once.
+    ///
+    /// Grants from the [`Batch`] commit into positions kept by the batch, and
+    /// [`commit_batch`](Batch::commit_batch) publishes them with one store per position and a
+    /// single wake-up. Dropping the batch publishes as well.
+    #[inline]
+    pub fn batch(&mut self) -> Batch<'_, N> {
+        let staged = self.storage.staged();
+        Batch {
+            storage: self.storage,
+            published: staged,
+            staged,
+        }
+    }
+
+    /// Commits all of `src`, calling `idle` whenever the storage has no room left.
+    ///
+    /// Bytes are committed as they fit, so the consumer can read the first ones while later
+    /// ones are still waiting. `idle` is where the caller waits for the consumer to release
+    /// bytes, such as with `WFI`, a scheduler yield or a delay; it is called repeatedly until
+    /// room appears.
+    pub fn write_all(&mut self, mut src: &[u8], mut idle: impl FnMut()) {
+        while !src.is_empty() {
+            match self.grant_max_remaining(src.len()) {
+                Ok(mut grant) => {
+                    let len = grant.len();
+                    grant.copy_from_slice(&src[..len]);
+                    grant.commit(len);
+                    src = &src[len..];
+                }
+                Err(_) => idle(),
+            }
+        }
+    }
+
+    /// Total number of bytes ever committed, wrapping on overflow.
+    ///
+    /// Unlike the positions in the storage, the count keeps increasing across wrap-arounds, so
+    /// two observations tell exactly how much was committed in between.
+    #[inline]
+    pub fn sequence(&self) -> usize {
+        self.storage.producer.committed.load(ordering::OWN)
+    }
+
+    /// Number of bytes committed and not yet released.
+    ///
+    /// This tells a full storage from an empty one without looking at the positions.
+    #[inline]
+    pub fn pending(&self) -> usize {
+        self.storage.pending()
+    }
+
+    /// Registers `waker` to be woken the next time the consumer releases bytes.
+    ///
+    /// Only the most recently registered waker is kept.
+    #[inline]
+    #[cfg(target_has_atomic = "ptr")]
+    pub fn register_write_waker(&self, waker: &Waker) {
+        self.storage.write_waker.register(waker);
+    }
+
+    /// Turns the half into a pointer to its storage, for handing it to code that cannot
+    /// receive Rust values, such as another WebAssembly worker.
+    #[inline]
+    pub fn into_raw(self) -> *const BufStorage<N> {
+        self.storage
+    }
+
+    /// Takes back a half turned into a pointer by [`into_raw`](Self::into_raw).
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must come from `into_raw` on a producer, and be passed to `from_raw` only once.
+    /// The storage must stay valid and unmoved for `'a`.
+    #[inline]
+    pub unsafe fn from_raw(ptr: *const BufStorage<N>) -> Self {
+        Self { storage: &*ptr }
+    }
+}
+
+/// Commits of a [`Producer`] published together.
+///
+/// Created by [`Producer::batch`]. Small records committed one by one each cost a few
+/// atomic stores and a wake-up; a batch pays that once.
+pub struct Batch<'a, const N: usize> {
+    storage: &'a BufStorage<N>,
+    /// Positions visible to the consumer
+    published: Staged,
+    /// Positions including the commits of the batch
+    staged: Staged,
+}
+
+impl<const N: usize> Batch<'_, N> {
+    /// Grants exactly `sz` contiguous bytes for writing, committed into the batch.
+    pub fn grant_exact(&mut self, sz: usize) -> Result<GrantW<'_, N>, Error> {
+        let start = self.storage.exact_start(self.staged.write, sz)?;
+        Ok(self.storage.grant(start, sz, Some(&mut self.staged)))
+    }
+
+    /// Grants up to `max_sz` contiguous bytes for writing, committed into the batch.
+    pub fn grant_max_remaining(&mut self, max_sz: usize) -> Result<GrantW<'_, N>, Error> {
+        let (start, sz) = self.storage.max_remaining(self.staged.write, max_sz)?;
+        Ok(self.storage.grant(start, sz, Some(&mut self.staged)))
+    }
+
+    /// Number of bytes committed into the batch and not yet published.
+    #[inline]
+    pub fn staged(&self) -> usize {
+        self.staged.committed.wrapping_sub(self.published.committed)
+    }
+
+    /// Publishes the commits of the batch to the consumer.
+    #[inline]
+    pub fn commit_batch(self) {}
+}
+
+impl<const N: usize> Drop for Batch<'_, N> {
+    #[inline]
+    fn drop(&mut self) {
+        self.storage.publish(self.published, self.staged);
+    }
+}
+
+/// Releases of a [`Consumer`] published together.
+///
+/// Created by [`Consumer::batch`], for consumers that take a few bytes at a time.
+pub struct ReleaseBatch<'a, const N: usize> {
+    storage: &'a BufStorage<N>,
+    /// Positions visible to the producer
+    published: Released,
+    /// Positions including the releases of the batch
+    staged: Released,
+}
+
+impl<const N: usize> ReleaseBatch<'_, N> {
+    /// Grants the next contiguous block of committed bytes for reading, released into the
+    /// batch.
+    pub fn read(&mut self) -> Result<GrantR<'_, N>, Error> {
+        let (start, end) = self.storage.readable(self.staged.read)?;
+        self.staged.read = start;
+        Ok(self.storage.grant_read(start, end, Some(&mut self.staged)))
+    }
+
+    /// Number of bytes released into the batch and not yet published.
+    #[inline]
+    pub fn staged(&self) -> usize {
+        self.staged.released.wrapping_sub(self.published.released)
+    }
+
+    /// Publishes the releases of the batch to the producer.
+    #[inline]
+    pub fn release_batch(self) {}
+}
+
+impl<const N: usize> Drop for ReleaseBatch<'_, N> {
+    #[inline]
+    fn drop(&mut self) {
+        self.storage.publish_released(self.published, self.staged);
+    }
+}
+
+/// Reading half of a [`BufStorage`].
+pub struct Consumer<'a, const N: usize> {
+    storage: &'a BufStorage<N>,
+}
+
+impl<'a, const N: usize> Consumer<'a, N> {
+    /// Grants the next contiguous block of committed bytes for reading.
+    pub fn read(&mut self) -> Result<GrantR<'_, N>, Error> {
+        let read = self.storage.consumer.read.load(ordering::OWN);
+        let (start, end) = self.storage.readable(read)?;
+        if start != read {
+            self.storage.consumer.read.store(start, ordering::PUBLISH);
+        }
+        Ok(self.storage.grant_read(start, end, None))
+    }
+
+    /// Starts a batch of releases that the producer sees all at once.
+    ///
+    /// Grants from the [`ReleaseBatch`] release into positions kept by the batch, and
+    /// [`release_batch`](ReleaseBatch::release_batch) publishes them with one store per
+    /// position and a single wake-up. Dropping the batch publishes as well. Until then, the
+    /// producer cannot reuse the released bytes.
+    #[inline]
+    pub fn batch(&mut self) -> ReleaseBatch<'_, N> {
+        let released = self.storage.released();
+        ReleaseBatch {
+            storage: self.storage,
+            published: released,
+            staged: released,
+        }
+    }
+
+    /// Total number of bytes ever released, wrapping on overflow.
+    #[inline]
+    pub fn sequence(&self) -> usize {
+        self.storage.consumer.released.load(ordering::OWN)
+    }
+
+    /// Registers `waker` to be woken the next time the producer commits bytes.
+    ///
+    /// Only the most recently registered waker is kept.
+    #[inline]
+    #[cfg(target_has_atomic = "ptr")]
+    pub fn register_read_waker(&self, waker: &Waker) {
+        self.storage.read_waker.register(waker);
+    }
+
+    /// Turns the half into a pointer to its storage, for handing it to code that cannot
+    /// receive Rust values, such as another WebAssembly worker.
+    #[inline]
+    pub fn into_raw(self) -> *const BufStorage<N> {
+        self.storage
+    }
+
+    /// Takes back a half turned into a pointer by [`into_raw`](Self::into_raw).
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must come from `into_raw` on a consumer, and be passed to `from_raw` only once.
+    /// The storage must stay valid and unmoved for `'a`.
+    #[inline]
+    pub unsafe fn from_raw(ptr: *const BufStorage<N>) -> Self {
+        Self { storage: &*ptr }
+    }
+
+    /// Number of bytes committed and not yet released.
+    #[inline]
+    pub fn pending(&self) -> usize {
+        self.storage.pending()
+    }
+}
+
+/// Writing half of a [`BufStorage`] that keeps the storage alive.
+#[cfg(all(feature = "alloc", target_has_atomic = "ptr"))]
+pub struct OwnedProducer<const N: usize> {
+    producer: Producer<'static, N>,
+    /// Keeps the storage referenced by `producer` alive
+    _storage: Arc<BufStorage<N>>,
+}
+
+#[cfg(all(feature = "alloc", target_has_atomic = "ptr"))]
+impl<const N: usize> OwnedProducer<N> {
+    /// Grants exactly `sz` contiguous bytes for writing.
+    #[inline]
+    pub fn grant_exact(&mut self, sz: usize) -> Result<GrantW<'_, N>, Error> {
+        self.producer.grant_exact(sz)
+    }
+
+    /// Grants up to `max_sz` contiguous bytes for writing.
+    #[inline]
+    pub fn grant_max_remaining(&mut self, max_sz: usize) -> Result<GrantW<'_, N>, Error> {
+        self.producer.grant_max_remaining(max_sz)
+    }
+
+    /// Commits all of `src`, calling `idle` whenever the storage has no room left.
+    #[inline]
+    pub fn write_all(&mut self, src: &[u8], idle: impl FnMut()) {
+        self.producer.write_all(src, idle)
+    }
+
+    /// Starts a batch of commits that the consumer sees all at once.
+    #[inline]
+    pub fn batch(&mut self) -> Batch<'_, N> {
+        self.producer.batch()
+    }
+
+    /// Total number of bytes ever committed, wrapping on overflow.
+    #[inline]
+    pub fn sequence(&self) -> usize {
+        self.producer.sequence()
+    }
+
+    /// Number of bytes committed and not yet released.
+    #[inline]
+    pub fn pending(&self) -> usize {
+        self.producer.pending()
+    }
+
+    /// Registers `waker` to be woken the next time the consumer releases bytes.
+    #[inline]
+    #[cfg(target_has_atomic = "ptr")]
+    pub fn register_write_waker(&self, waker: &Waker) {
+        self.producer.register_write_waker(waker)
+    }
+}
+
+/// Reading half of a [`BufStorage`] that keeps the storage alive.
+#[cfg(all(feature = "alloc", target_has_atomic = "ptr"))]
+pub struct OwnedConsumer<const N: usize> {
+    consumer: Consumer<'static, N>,
+    /// Keeps the storage referenced by `consumer` alive
+    _storage: Arc<BufStorage<N>>,
+}
+
+#[cfg(all(feature = "alloc", target_has_atomic = "ptr"))]
+impl<const N: usize> OwnedConsumer<N> {
+    /// Grants the next contiguous block of committed bytes for reading.
+    #[inline]
+    pub fn read(&mut self) -> Result<GrantR<'_, N>, Error> {
+        self.consumer.read()
+    }
+
+    /// Starts a batch of releases that the producer sees all at once.
+    #[inline]
+    pub fn batch(&mut self) -> ReleaseBatch<'_, N> {
+        self.consumer.batch()
+    }
+
+    /// Total number of bytes ever released, wrapping on overflow.
+    #[inline]
+    pub fn sequence(&self) -> usize {
+        self.consumer.sequence()
+    }
+
+    /// Number of bytes committed and not yet released.
+    #[inline]
+    pub fn pending(&self) -> usize {
+        self.consumer.pending()
+    }
+
+    /// Registers `waker` to be woken the next time the producer commits bytes.
+    #[inline]
+    #[cfg(target_has_atomic = "ptr")]
+    pub fn register_read_waker(&self, waker: &Waker) {
+        self.consumer.register_read_waker(waker)
+    }
+}
+
+/// Bytes granted for writing, committed when the grant is dropped.
+pub struct GrantW<'a, const N: usize> {
+    buf: &'a mut [u8],
+    storage: &'a BufStorage<N>,
+    /// Positions of the batch the grant belongs to
+    staged: Option<&'a mut Staged>,
+    /// Position of the grant
+    start: usize,
+    /// Number of bytes to commit on drop
+    to_commit: usize,
+}
+
+impl<const N: usize> GrantW<'_, N> {
+    /// Commits the first `used` bytes of the grant.
+    #[inline]
+    pub fn commit(mut self, used: usize) {
+        self.to_commit(used);
+    }
+
+    /// Sets the number of bytes committed when the grant is dropped, `0` by default.
+    #[inline]
+    pub fn to_commit(&mut self, used: usize) {
+        self.to_commit = used.min(self.buf.len());
+    }
+
+    /// Returns the granted bytes.
+    #[inline]
+    pub fn buf(&mut self) -> &mut [u8] {
+        self.buf
+    }
+}
+
+impl<const N: usize> Deref for GrantW<'_, N> {
+    type Target = [u8];
+
+    #[inline]
+    fn deref(&self) -> &[u8] {
+        self.buf
+    }
+}
+
+impl<const N: usize> DerefMut for GrantW<'_, N> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut [u8] {
+        self.buf
+    }
+}
+
+impl<const N: usize> Drop for GrantW<'_, N> {
+    fn drop(&mut self) {
+        match &mut self.staged {
+            Some(staged) => staged.commit(self.start, self.to_commit, N),
+            None => {
+                let old = self.storage.staged();
+                let mut new = old;
+                new.commit(self.start, self.to_commit, N);
+                self.storage.publish(old, new);
+            }
+        }
+    }
+}
+
+/// Bytes granted for reading, released when the grant is dropped.
+pub struct GrantR<'a, const N: usize> {
+    buf: &'a mut [u8],
+    storage: &'a BufStorage<N>,
+    /// Positions of the batch the grant belongs to
+    staged: Option<&'a mut Released>,
+    /// Number of bytes to release on drop
+    to_release: usize,
+}
+
+impl<const N: usize> GrantR<'_, N> {
+    /// Releases the first `used` bytes of the grant.
+    #[inline]
+    pub fn release(mut self, used: usize) {
+        self.to_release(used);
+    }
+
+    /// Sets the number of bytes released when the grant is dropped, `0` by default.
+    #[inline]
+    pub fn to_release(&mut self, used: usize) {
+        self.to_release = used.min(self.buf.len());
+    }
+
+    /// Returns the granted bytes.
+    #[inline]
+    pub fn buf(&mut self) -> &mut [u8] {
+        self.buf
+    }
+}
+
+impl<const N: usize> Deref for GrantR<'_, N> {
+    type Target = [u8];
+
+    #[inline]
+    fn deref(&self) -> &[u8] {
+        self.buf
+    }
+}
+
+impl<const N: usize> DerefMut for GrantR<'_, N> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut [u8] {
+        self.buf
+    }
+}
+
+impl<const N: usize> Drop for GrantR<'_, N> {
+    #[inline]
+    fn drop(&mut self) {
+        match &mut self.staged {
+            Some(staged) => staged.release(self.to_release),
+            None => {
+                let old = self.storage.released();
+                let mut new = old;
+                new.release(self.to_release);
+                self.storage.publish_released(old, new);
+            }
+        }
+    }
+}
@@ -0,0 +1,104 @@
+//! Bip-Buffer shared by several readers.
+//!
+//! Every reader sees all committed data through its own cursor. Space is only reclaimed once
+//! every reader has moved past it.
+//!
+//! ```rust
+//! use staticbip::broadcast::BroadcastBip;
+//!
+//! // Two readers over an 8-element buffer
+//! let mut buffer = BroadcastBip::<u8, 8, 2>::default();
+//!
+//! buffer.reserve(3).copy_from_slice(&[1, 2, 3]);
+//! buffer.commit(3);
+//!
+//! buffer.decommit(0, 2);
+//! assert_eq!(buffer.read(0), &[3]);
+//! assert_eq!(buffer.read(1), &[1, 2, 3]);
+//! ```
+
+use crate::StaticBip;
+use core::array;
+
+/// A Bip-Buffer with a fixed capacity and `N` independent readers.
+#[derive(Debug)]
+pub struct BroadcastBip<T, const CAP: usize, const N: usize> {
+    /// Data not yet seen by every reader
+    bip: StaticBip<T, CAP>,
+    /// Logical read position of each reader
+    cursors: [usize; N],
+}
+
+impl<T: Default, const CAP: usize, const N: usize> Default for BroadcastBip<T, CAP, N> {
+    #[inline]
+    fn default() -> Self {
+        Self::new(array::from_fn(|_| T::default()))
+    }
+}
+
+impl<T, const CAP: usize, const N: usize> BroadcastBip<T, CAP, N> {
+    /// Creates and allocates a new buffer of `T` elements.
+    #[inline]
+    pub const fn new(buffer: [T; CAP]) -> Self {
+        Self {
+            bip: StaticBip::new(buffer),
+            cursors: [0; N],
+        }
+    }
+
+    /// Size of the backing store.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.bip.capacity()
+    }
+
+    /// Number of elements `reader` has not seen yet.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `reader` is not less than `N`.
+    #[inline]
+    pub fn committed(&self, reader: usize) -> usize {
+        self.bip.committed() - self.cursors[reader]
+    }
+
+    /// Returns a mutable buffer containing up to `count` slots for storing data.
+    #[inline]
+    pub fn reserve(&mut self, count: usize) -> &mut [T] {
+        self.bip.reserve(count)
+    }
+
+    /// Commits the data in the reservation, making it visible to every reader.
+    #[inline]
+    pub fn commit(&mut self, len: usize) {
+        self.bip.commit(len)
+    }
+
+    /// Retrieves the next contiguous block of data `reader` has not seen yet.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `reader` is not less than `N`.
+    #[inline]
+    pub fn read(&self, reader: usize) -> &[T] {
+        let block = self.bip.regions.block_at(self.cursors[reader]);
+        self.bip.slice(block)
+    }
+
+    /// Marks the first `len` elements of `reader`'s block as seen.
+    ///
+    /// Space is released once every reader has seen it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `reader` is not less than `N`.
+    pub fn decommit(&mut self, reader: usize, len: usize) {
+        self.cursors[reader] += len.min(self.read(reader).len());
+
+        let seen = self.cursors.iter().copied().min().unwrap_or(0);
+        self.bip.regions.consume(seen);
+        for cursor in &mut self.cursors {
+            *cursor -= seen;
+        }
+    }
+}
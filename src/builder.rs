@@ -0,0 +1,114 @@
+//! One construction path for configured buffers.
+//!
+//! A [`BipBuilder`] collects the options set when a buffer is created and ends in a method
+//! that takes the backing store and returns the buffer, optionally inside one of the wrappers
+//! adding alignment, hooks or timing. Every option has a default, so only the ones that
+//! differ are named. The builder is `Copy` and `const`, so one configuration can be shared
+//! by several buffers and initialize `static`s.
+//!
+//! ```rust
+//! use staticbip::{align::A32, builder::BipBuilder, StaticBip, WrapPolicy};
+//!
+//! const CONFIG: BipBuilder = BipBuilder::new().wrap_policy(WrapPolicy::Fit);
+//!
+//! static mut RX: StaticBip<u8, 64> = CONFIG.build([0; 64]);
+//!
+//! let mut dma = CONFIG.build_aligned::<A32, _, 64>([0u8; 64]);
+//! assert_eq!(dma.reserve(16).as_ptr() as usize % 32, 0);
+//! assert_eq!(dma.wrap_policy(), WrapPolicy::Fit);
+//! ```
+//!
+//! What happens to writes that do not fit is chosen per call, by the [`Overflow`] parameter
+//! of [`write_slice`](StaticBip::write_slice), rather than stored in the buffer.
+//!
+//! [`Overflow`]: crate::overflow::Overflow
+
+use crate::{
+    align::Aligned,
+    hook::{Hooked, Hooks},
+    regions::Regions,
+    StaticBip, WrapPolicy,
+};
+
+/// Options for creating a [`StaticBip`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BipBuilder {
+    /// Whether the whole backing store starts out committed
+    committed: bool,
+    policy: WrapPolicy,
+}
+
+impl Default for BipBuilder {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BipBuilder {
+    /// Options for an empty buffer wrapping with [`WrapPolicy::Largest`].
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            committed: false,
+            policy: WrapPolicy::Largest,
+        }
+    }
+
+    /// Sets how reservations choose between the end and the start of the backing store.
+    #[inline]
+    pub const fn wrap_policy(mut self, policy: WrapPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Commits the entire backing store, as [`StaticBip::new_committed`] does.
+    #[inline]
+    pub const fn committed(mut self) -> Self {
+        self.committed = true;
+        self
+    }
+
+    /// Creates a buffer over `buffer`.
+    #[inline]
+    pub const fn build<T, const CAP: usize>(self, buffer: [T; CAP]) -> StaticBip<T, CAP> {
+        let mut regions = if self.committed {
+            Regions::full(CAP)
+        } else {
+            Regions::new()
+        };
+        regions.policy = self.policy;
+        StaticBip { buffer, regions }
+    }
+
+    /// Creates a buffer over `buffer` at an address aligned to the alignment of `A`, one of
+    /// the markers of [`align`](crate::align).
+    #[inline]
+    pub const fn build_aligned<A, T, const CAP: usize>(
+        self,
+        buffer: [T; CAP],
+    ) -> Aligned<A, StaticBip<T, CAP>> {
+        Aligned::new(self.build(buffer))
+    }
+
+    /// Creates a buffer over `buffer`, reporting its activity to `hooks`.
+    #[inline]
+    pub fn build_hooked<T, H: Hooks, const CAP: usize>(
+        self,
+        buffer: [T; CAP],
+        hooks: H,
+    ) -> Hooked<T, StaticBip<T, CAP>, H> {
+        Hooked::new(self.build(buffer), hooks)
+    }
+
+    /// Creates a buffer over `buffer`, timing its operations with `counter`.
+    #[cfg(feature = "latency")]
+    #[inline]
+    pub fn build_instrumented<T, C: crate::latency::CycleCounter, const CAP: usize>(
+        self,
+        buffer: [T; CAP],
+        counter: C,
+    ) -> crate::latency::Instrumented<T, StaticBip<T, CAP>, C> {
+        crate::latency::Instrumented::new(self.build(buffer), counter)
+    }
+}
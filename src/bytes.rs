@@ -0,0 +1,86 @@
+//! Integer accessors for byte buffers.
+//!
+//! Reads take a value from the front of the committed data, even when its bytes straddle
+//! the end of one region and the start of the next. Writes append a value through a single
+//! reservation.
+//!
+//! ```rust
+//! use staticbip::StaticBip;
+//!
+//! let mut buffer = StaticBip::<u8, 6>::default();
+//! buffer.write_u32_be(0x0102_0304).unwrap();
+//! buffer.decommit(3);
+//! buffer.write_u16_le(0x0605).unwrap();
+//!
+//! // Straddles both regions
+//! assert_eq!(buffer.read_u16_le(), Some(0x0504));
+//! assert_eq!(buffer.read_u16_le(), None);
+//! ```
+
+use crate::{nb::WouldBlock, StaticBip};
+
+macro_rules! accessors {
+    ($($ty:ty: $read_le:ident $read_be:ident $write_le:ident $write_be:ident,)*) => {
+        impl<const CAP: usize> StaticBip<u8, CAP> {
+            $(
+                #[doc = concat!("Removes a little-endian `", stringify!($ty), "` from the front.")]
+                #[inline]
+                pub fn $read_le(&mut self) -> Option<$ty> {
+                    self.read_array().map(<$ty>::from_le_bytes)
+                }
+
+                #[doc = concat!("Removes a big-endian `", stringify!($ty), "` from the front.")]
+                #[inline]
+                pub fn $read_be(&mut self) -> Option<$ty> {
+                    self.read_array().map(<$ty>::from_be_bytes)
+                }
+
+                #[doc = concat!("Appends a little-endian `", stringify!($ty), "`.")]
+                #[inline]
+                pub fn $write_le(&mut self, value: $ty) -> Result<(), WouldBlock> {
+                    self.write_array(value.to_le_bytes())
+                }
+
+                #[doc = concat!("Appends a big-endian `", stringify!($ty), "`.")]
+                #[inline]
+                pub fn $write_be(&mut self, value: $ty) -> Result<(), WouldBlock> {
+                    self.write_array(value.to_be_bytes())
+                }
+            )*
+        }
+    };
+}
+
+accessors! {
+    u16: read_u16_le read_u16_be write_u16_le write_u16_be,
+    u32: read_u32_le read_u32_be write_u32_le write_u32_be,
+    u64: read_u64_le read_u64_be write_u64_le write_u64_be,
+    i16: read_i16_le read_i16_be write_i16_le write_i16_be,
+    i32: read_i32_le read_i32_be write_i32_le write_i32_be,
+    i64: read_i64_le read_i64_be write_i64_le write_i64_be,
+}
+
+impl<const CAP: usize> StaticBip<u8, CAP> {
+    /// Removes the first `N` committed bytes, if there are enough.
+    fn read_array<const N: usize>(&mut self) -> Option<[u8; N]> {
+        let mut bytes = [0; N];
+        let (a, b) = self.as_slices();
+        let split = a.len().min(N);
+        bytes[..split].copy_from_slice(&a[..split]);
+        bytes[split..].copy_from_slice(b.get(..N - split)?);
+        self.regions.consume(N);
+        Some(bytes)
+    }
+
+    /// Appends `bytes` through a single reservation, if they fit.
+    fn write_array<const N: usize>(&mut self, bytes: [u8; N]) -> Result<(), WouldBlock> {
+        let reserved = self.reserve(N);
+        if reserved.len() < N {
+            self.commit(0);
+            return Err(WouldBlock);
+        }
+        reserved.copy_from_slice(&bytes);
+        self.commit(N);
+        Ok(())
+    }
+}
@@ -0,0 +1,122 @@
+//! Typed views of byte buffers.
+//!
+//! [`read_as`](StaticBip::read_as) and [`reserve_as`](StaticBip::reserve_as) cast the
+//! committed bytes or a reservation to a slice of any [`Pod`] type, such as `u32` samples or
+//! a `#[repr(C)]` struct of them, without copying. The cast only succeeds when the bytes
+//! start at an address aligned for the type; keeping the backing store in an
+//! [`Aligned`](crate::align::Aligned) and moving whole elements only keeps every region
+//! start aligned.
+//!
+//! ```rust
+//! use staticbip::{align::{A4, Aligned}, StaticBip};
+//!
+//! let mut buffer = Aligned::<A4, StaticBip<u8, 64>>::default();
+//! buffer.reserve_as::<u32>(2).unwrap().copy_from_slice(&[7, 9]);
+//! buffer.commit_as::<u32>(2);
+//!
+//! let samples = buffer.read_as::<u32>().unwrap();
+//! assert_eq!(samples, &[7, 9]);
+//! samples[0] *= 2;
+//! assert_eq!(buffer.read_as::<[u16; 2]>().unwrap().len(), 2);
+//! buffer.decommit_as::<u32>(1);
+//! assert_eq!(buffer.read_as::<u32>(), Ok(&mut [9][..]));
+//! ```
+
+use crate::StaticBip;
+use core::{fmt, mem, slice};
+
+/// Types for which every bit pattern is a valid value and which have no padding, so any
+/// properly aligned bytes of the right length can be viewed as them.
+///
+/// This has the shape of `bytemuck::Pod`, and a type implementing that trait can implement
+/// this one the same way.
+///
+/// # Safety
+///
+/// The type must be inhabited, have no padding bytes, no interior mutability and no
+/// pointers, and every bit pattern of its size must be a valid value, as for
+/// `bytemuck::Pod`. A `#[repr(C)]` or `#[repr(transparent)]` struct whose fields are all
+/// `Pod` and which has no padding between or after them satisfies this.
+pub unsafe trait Pod: Copy + 'static {}
+
+macro_rules! pod {
+    ($($ty:ty),*) => {$(
+        unsafe impl Pod for $ty {}
+    )*};
+}
+
+pod!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64);
+
+unsafe impl<P: Pod, const N: usize> Pod for [P; N] {}
+
+/// Reasons bytes could not be viewed as a typed slice.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CastError {
+    /// The bytes do not start at an address aligned for the type.
+    Alignment,
+    /// The bytes are not a whole number of elements of the type.
+    Size,
+}
+
+impl fmt::Display for CastError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Alignment => "data is not aligned for the type",
+            Self::Size => "data is not a whole number of elements",
+        })
+    }
+}
+
+impl<const CAP: usize> StaticBip<u8, CAP> {
+    /// Retrieves the oldest committed bytes, as [`read`](Self::read) does, viewed as
+    /// elements of `P`.
+    ///
+    /// Fails if the block does not start at an address aligned for `P`, or ends partway
+    /// through an element, as when only part of a sample has been received.
+    #[inline]
+    pub fn read_as<P: Pod>(&mut self) -> Result<&mut [P], CastError> {
+        cast_mut(self.read())
+    }
+
+    /// Reserves room for up to `count` elements of `P`, as [`reserve`](Self::reserve) does
+    /// for bytes.
+    ///
+    /// The reservation is shortened to a whole number of elements, and may hold none. Fails
+    /// if the free space does not start at an address aligned for `P`.
+    pub fn reserve_as<P: Pod>(&mut self, count: usize) -> Result<&mut [P], CastError> {
+        let size = mem::size_of::<P>();
+        let reserved = self.reserve(count.saturating_mul(size));
+        let whole = reserved.len() - reserved.len().checked_rem(size).unwrap_or(0);
+        cast_mut(&mut reserved[..whole])
+    }
+
+    /// Commits the first `count` elements of [`reserve_as`](Self::reserve_as).
+    #[inline]
+    pub fn commit_as<P: Pod>(&mut self, count: usize) {
+        self.commit(count.saturating_mul(mem::size_of::<P>()));
+    }
+
+    /// Marks the first `count` elements of [`read_as`](Self::read_as) as seen.
+    #[inline]
+    pub fn decommit_as<P: Pod>(&mut self, count: usize) {
+        self.decommit(count.saturating_mul(mem::size_of::<P>()));
+    }
+}
+
+/// Views `bytes` as elements of `P`.
+fn cast_mut<P: Pod>(bytes: &mut [u8]) -> Result<&mut [P], CastError> {
+    let size = mem::size_of::<P>();
+    if bytes.is_empty() {
+        return Ok(&mut []);
+    }
+    if !bytes.as_ptr().cast::<P>().is_aligned() {
+        return Err(CastError::Alignment);
+    }
+    if size == 0 || !bytes.len().is_multiple_of(size) {
+        return Err(CastError::Size);
+    }
+    // SAFETY: the bytes are aligned for `P` and hold exactly `len / size` elements, and `P`
+    // is valid for any bit pattern. The returned slice borrows `bytes` mutably, so nothing
+    // else can observe them as bytes while it is alive.
+    Ok(unsafe { slice::from_raw_parts_mut(bytes.as_mut_ptr().cast(), bytes.len() / size) })
+}
@@ -0,0 +1,84 @@
+//! One-time access to a buffer stored in a `static`.
+//!
+//! ```rust
+//! use staticbip::{cell::StaticBipCell, StaticBip};
+//!
+//! static BUFFER: StaticBipCell<u8, 64> = StaticBipCell::new([0; 64]);
+//!
+//! let buffer: &'static mut StaticBip<u8, 64> = BUFFER.take().unwrap();
+//! buffer.reserve(1).copy_from_slice(&[1]);
+//! buffer.commit(1);
+//!
+//! assert!(BUFFER.take().is_none());
+//! ```
+
+use crate::StaticBip;
+use core::{
+    cell::UnsafeCell,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+/// A Bip-Buffer that hands out a single mutable reference to itself.
+pub struct StaticBipCell<T, const CAP: usize> {
+    bip: UnsafeCell<StaticBip<T, CAP>>,
+    taken: AtomicBool,
+}
+
+// SAFETY: the buffer is only reachable through the one reference returned by `take`.
+unsafe impl<T: Send, const CAP: usize> Sync for StaticBipCell<T, CAP> {}
+
+impl<T, const CAP: usize> StaticBipCell<T, CAP> {
+    /// Creates a cell around a new buffer of `T` elements.
+    #[inline]
+    pub const fn new(buffer: [T; CAP]) -> Self {
+        Self {
+            bip: UnsafeCell::new(StaticBip::new(buffer)),
+            taken: AtomicBool::new(false),
+        }
+    }
+
+    /// Returns the buffer the first time it is called, and `None` afterwards.
+    #[inline]
+    #[allow(clippy::mut_from_ref)]
+    pub fn take(&self) -> Option<&mut StaticBip<T, CAP>> {
+        if self.taken.swap(true, Ordering::Acquire) {
+            None
+        } else {
+            // SAFETY: the flag was clear, so this is the only reference ever handed out.
+            Some(unsafe { &mut *self.bip.get() })
+        }
+    }
+}
+
+/// Declares a `static` [`StaticBipCell`], optionally placed in a linker section.
+///
+/// Elements start out as `init`, which defaults to `0`. A buffer placed in a custom section
+/// relies on the startup code copying or zeroing that section like `.data`.
+///
+/// ```rust
+/// use staticbip::static_bip;
+///
+/// static_bip!(RX, u8, 256, section = ".data.rx");
+/// static_bip!(pub(crate) EVENTS, (u16, u16), 8, init = (0, 0));
+///
+/// let rx = RX.take().unwrap();
+/// assert_eq!(rx.capacity(), 256);
+/// ```
+#[macro_export]
+macro_rules! static_bip {
+    (
+        $(#[$attr:meta])* $vis:vis $name:ident, $ty:ty, $cap:expr
+        $(, init = $init:expr)? $(, section = $section:literal)? $(,)?
+    ) => {
+        $(#[$attr])*
+        $(#[link_section = $section])?
+        $vis static $name: $crate::cell::StaticBipCell<$ty, $cap> =
+            $crate::cell::StaticBipCell::new([$crate::static_bip!(@init $($init)?); $cap]);
+    };
+    (@init) => {
+        0
+    };
+    (@init $init:expr) => {
+        $init
+    };
+}
@@ -0,0 +1,119 @@
+//! Two Bip-Buffers read as one stream.
+//!
+//! A [`Chain`] is itself a [`BipBuf`], so longer chains are built by nesting.
+//!
+//! ```rust
+//! use staticbip::{chain::Chain, StaticBip};
+//!
+//! let mut first = StaticBip::<u8, 4>::default();
+//! let mut second = StaticBip::<u8, 8>::default();
+//!
+//! first.reserve(2).copy_from_slice(&[1, 2]);
+//! first.commit(2);
+//! second.reserve(1).copy_from_slice(&[3]);
+//! second.commit(1);
+//!
+//! let mut chain = Chain::new(&mut first, &mut second);
+//! assert_eq!(chain.committed(), 3);
+//! assert_eq!(chain.read(), &[1, 2]);
+//!
+//! chain.decommit(2);
+//! assert_eq!(chain.read(), &[3]);
+//! ```
+
+use crate::BipBuf;
+use core::marker::PhantomData;
+
+/// Consumer view of two buffers, yielding all data of the first before the second.
+///
+/// New data is reserved in the second buffer, so it always follows everything already
+/// committed to the chain.
+#[derive(Debug)]
+pub struct Chain<T, F, S> {
+    first: F,
+    second: S,
+    item: PhantomData<fn() -> T>,
+}
+
+impl<T, F: BipBuf<T>, S: BipBuf<T>> Chain<T, F, S> {
+    /// Chains `second` after `first`.
+    #[inline]
+    pub fn new(first: F, second: S) -> Self {
+        Self {
+            first,
+            second,
+            item: PhantomData,
+        }
+    }
+
+    /// Number of committed elements in both buffers.
+    #[inline]
+    pub fn committed(&self) -> usize {
+        self.first.committed() + self.second.committed()
+    }
+
+    /// Retrieves the next block of committed data.
+    ///
+    /// The second buffer is only read once the first one has no committed data.
+    #[inline]
+    pub fn read(&mut self) -> &mut [T] {
+        if self.first.committed() != 0 {
+            self.first.read()
+        } else {
+            self.second.read()
+        }
+    }
+
+    /// Marks the first `len` elements of the current block as seen.
+    #[inline]
+    pub fn decommit(&mut self, len: usize) {
+        if self.first.committed() != 0 {
+            self.first.decommit(len)
+        } else {
+            self.second.decommit(len)
+        }
+    }
+
+    /// Returns the chained buffers.
+    #[inline]
+    pub fn into_inner(self) -> (F, S) {
+        (self.first, self.second)
+    }
+}
+
+impl<T, F: BipBuf<T>, S: BipBuf<T>> BipBuf<T> for Chain<T, F, S> {
+    #[inline]
+    fn capacity(&self) -> usize {
+        self.first.capacity() + self.second.capacity()
+    }
+
+    #[inline]
+    fn committed(&self) -> usize {
+        self.committed()
+    }
+
+    #[inline]
+    fn reserved(&self) -> usize {
+        self.second.reserved()
+    }
+
+    #[inline]
+    fn reserve(&mut self, count: usize) -> &mut [T] {
+        self.second.reserve(count)
+    }
+
+    #[inline]
+    fn commit(&mut self, len: usize) {
+        self.second.commit(len)
+    }
+
+    #[inline]
+    fn read(&mut self) -> &mut [T] {
+        self.read()
+    }
+
+    #[inline]
+    fn decommit(&mut self, len: usize) {
+        self.decommit(len)
+    }
+}
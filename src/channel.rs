@@ -0,0 +1,442 @@
+//! Bounded asynchronous channel over a Bip-Buffer.
+//!
+//! A [`Channel`] is [`split`](Channel::split) into a [`Sender`] and a [`Receiver`], which wake
+//! each other as elements are committed and consumed. Their poll methods have the signatures
+//! of the futures `Sink` and `Stream` traits, so implementations of those traits forward to
+//! them directly.
+//!
+//! ```rust
+//! use staticbip::channel::Channel;
+//!
+//! let mut channel = Channel::<u32, 4>::default();
+//! let (mut sender, mut receiver) = channel.split();
+//!
+//! let task = async move {
+//!     sender.send(1).await.unwrap();
+//!     sender.send(2).await.unwrap();
+//!     drop(sender);
+//!
+//!     assert_eq!(receiver.recv().await, Some(1));
+//!     assert_eq!(receiver.recv().await, Some(2));
+//!     assert_eq!(receiver.recv().await, None);
+//! };
+//! # use core::future::Future;
+//! # let mut task = Box::pin(task);
+//! # let mut cx = core::task::Context::from_waker(core::task::Waker::noop());
+//! # assert!(task.as_mut().poll(&mut cx).is_ready());
+//! ```
+//!
+//! [`try_send`](Sender::try_send) and [`try_recv`](Receiver::try_recv) never wait. With the
+//! `std` feature, [`send_blocking`](Sender::send_blocking) and
+//! [`recv_blocking`](Receiver::recv_blocking) park the calling thread until the other half
+//! wakes it.
+//!
+//! ```rust
+//! use staticbip::channel::{Channel, TryRecvError};
+//!
+//! let mut channel = Channel::<u32, 4>::default();
+//! let (mut sender, mut receiver) = channel.split();
+//!
+//! assert_eq!(receiver.try_recv(), Err(TryRecvError::Empty));
+//! sender.try_send(1).unwrap();
+//! assert_eq!(receiver.try_recv(), Ok(1));
+//! ```
+//!
+//! # Cancellation
+//!
+//! [`recv`](Receiver::recv) removes an element in the same poll that returns it, so dropping
+//! the future, as a `select!` does with the branches that lose, never loses an element. The
+//! `wait_*` futures change nothing and [`send`](Sender::send) only stores its element once it
+//! completes; a dropped `send` drops the element it was given, which was never in the
+//! channel. To keep the element, wait with [`poll_ready`](Sender::poll_ready) or
+//! [`wait_until_free`](Sender::wait_until_free) in the `select!` and send it afterwards.
+
+use crate::StaticBip;
+use core::{
+    array,
+    cell::UnsafeCell,
+    fmt,
+    future::poll_fn,
+    hint,
+    sync::atomic::{AtomicBool, Ordering},
+    task::{Context, Poll, Waker},
+};
+
+/// The other half of the channel has been dropped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Closed;
+
+impl fmt::Display for Closed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("channel closed")
+    }
+}
+
+/// Reasons an element could not be sent without waiting.
+///
+/// Both variants hand the element back.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrySendError<T> {
+    /// The channel is full.
+    Full(T),
+    /// The receiver has been dropped.
+    Closed(T),
+}
+
+impl<T> fmt::Display for TrySendError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Full(_) => "channel full",
+            Self::Closed(_) => "channel closed",
+        })
+    }
+}
+
+/// Reasons an element could not be received without waiting.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TryRecvError {
+    /// The channel is empty.
+    Empty,
+    /// The channel is empty and the sender has been dropped.
+    Closed,
+}
+
+impl fmt::Display for TryRecvError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Empty => "channel empty",
+            Self::Closed => "channel closed",
+        })
+    }
+}
+
+/// State shared by both halves.
+#[derive(Debug)]
+struct Shared<T, const CAP: usize> {
+    bip: StaticBip<T, CAP>,
+    /// Receiver waiting for data
+    rx_waker: Option<Waker>,
+    /// Number of committed elements the receiver is waiting for
+    rx_threshold: usize,
+    /// Sender waiting for space
+    tx_waker: Option<Waker>,
+    /// Number of contiguous free slots the sender is waiting for
+    tx_threshold: usize,
+    /// Whether the sender is alive
+    sender: bool,
+    /// Whether the receiver is alive
+    receiver: bool,
+}
+
+impl<T, const CAP: usize> Shared<T, CAP> {
+    /// Whether `n` contiguous slots can be reserved.
+    fn has_free(&mut self, n: usize) -> bool {
+        let free = self.bip.reserve(n).len() >= n;
+        self.bip.commit(0);
+        free
+    }
+}
+
+impl<T: Copy, const CAP: usize> Shared<T, CAP> {
+    /// Removes the oldest element, along with the sender's waker if it now has the space it
+    /// waits for.
+    fn try_recv(&mut self) -> (Result<T, TryRecvError>, Option<Waker>) {
+        match self.bip.pop() {
+            Some(&mut item) => {
+                let waker = if self.has_free(self.tx_threshold) {
+                    self.tx_waker.take()
+                } else {
+                    None
+                };
+                (Ok(item), waker)
+            }
+            None if !self.sender => (Err(TryRecvError::Closed), None),
+            None => (Err(TryRecvError::Empty), None),
+        }
+    }
+}
+
+/// Wakes `waker`, if any.
+///
+/// Wakers are taken out of the shared state under the lock and woken after it is released,
+/// since a waker may poll its task inline, and the task would spin on the lock forever.
+#[inline]
+fn wake(waker: Option<Waker>) {
+    if let Some(waker) = waker {
+        waker.wake();
+    }
+}
+
+/// A bounded channel of `CAP` elements.
+#[derive(Debug)]
+pub struct Channel<T, const CAP: usize> {
+    shared: UnsafeCell<Shared<T, CAP>>,
+    locked: AtomicBool,
+}
+
+// SAFETY: the shared state is only accessed while holding `locked`.
+unsafe impl<T: Send, const CAP: usize> Sync for Channel<T, CAP> {}
+
+impl<T: Default, const CAP: usize> Default for Channel<T, CAP> {
+    #[inline]
+    fn default() -> Self {
+        Self::new(array::from_fn(|_| T::default()))
+    }
+}
+
+impl<T, const CAP: usize> Channel<T, CAP> {
+    /// Creates a channel over a backing store.
+    #[inline]
+    pub const fn new(buffer: [T; CAP]) -> Self {
+        Self {
+            shared: UnsafeCell::new(Shared {
+                bip: StaticBip::new(buffer),
+                rx_waker: None,
+                rx_threshold: 1,
+                tx_waker: None,
+                tx_threshold: 1,
+                sender: false,
+                receiver: false,
+            }),
+            locked: AtomicBool::new(false),
+        }
+    }
+
+    /// Splits the channel into its sending and receiving halves.
+    ///
+    /// Elements left over from a previous split are kept.
+    pub fn split(&mut self) -> (Sender<'_, T, CAP>, Receiver<'_, T, CAP>) {
+        let shared = self.shared.get_mut();
+        shared.sender = true;
+        shared.receiver = true;
+        (Sender { channel: self }, Receiver { channel: self })
+    }
+
+    /// Runs `f` on the shared state.
+    fn lock<R>(&self, f: impl FnOnce(&mut Shared<T, CAP>) -> R) -> R {
+        while self
+            .locked
+            .compare_exchange_weak(false, true, Ordering::Acquire, Ordering::Relaxed)
+            .is_err()
+        {
+            hint::spin_loop();
+        }
+        // SAFETY: `locked` was clear, so nothing else refers to the shared state.
+        let result = f(unsafe { &mut *self.shared.get() });
+        self.locked.store(false, Ordering::Release);
+        result
+    }
+}
+
+/// Sending half of a [`Channel`].
+#[derive(Debug)]
+pub struct Sender<'a, T, const CAP: usize> {
+    channel: &'a Channel<T, CAP>,
+}
+
+impl<T: Copy, const CAP: usize> Sender<'_, T, CAP> {
+    /// Waits until an element can be sent.
+    pub fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Closed>> {
+        self.channel.lock(|shared| {
+            if !shared.receiver {
+                return Poll::Ready(Err(Closed));
+            }
+            if shared.has_free(1) {
+                Poll::Ready(Ok(()))
+            } else {
+                shared.tx_waker = Some(cx.waker().clone());
+                shared.tx_threshold = 1;
+                Poll::Pending
+            }
+        })
+    }
+
+    /// Waits until `n` contiguous slots are free, without waking for less.
+    ///
+    /// An `n` above the capacity waits for an empty channel.
+    pub fn poll_free(&mut self, n: usize, cx: &mut Context<'_>) -> Poll<Result<(), Closed>> {
+        let n = n.min(CAP);
+        self.channel.lock(|shared| {
+            if !shared.receiver {
+                Poll::Ready(Err(Closed))
+            } else if shared.has_free(n) {
+                Poll::Ready(Ok(()))
+            } else {
+                shared.tx_waker = Some(cx.waker().clone());
+                shared.tx_threshold = n;
+                Poll::Pending
+            }
+        })
+    }
+
+    /// Sends `item`, after [`poll_ready`](Self::poll_ready) has returned `Ready`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the channel is full.
+    pub fn start_send(&mut self, item: T) -> Result<(), Closed> {
+        match self.try_send(item) {
+            Err(TrySendError::Full(_)) => panic!("start_send called on a full channel"),
+            Err(TrySendError::Closed(_)) => Err(Closed),
+            Ok(()) => Ok(()),
+        }
+    }
+
+    /// Sends `item`, waiting for space if the channel is full.
+    pub async fn send(&mut self, item: T) -> Result<(), Closed> {
+        poll_fn(|cx| self.poll_ready(cx)).await?;
+        self.start_send(item)
+    }
+
+    /// Sends `item` if there is room, without waiting.
+    pub fn try_send(&mut self, item: T) -> Result<(), TrySendError<T>> {
+        let waker = self.channel.lock(|shared| {
+            if !shared.receiver {
+                return Err(TrySendError::Closed(item));
+            }
+            if shared.bip.enqueue(item).is_err() {
+                return Err(TrySendError::Full(item));
+            }
+            if shared.bip.committed() >= shared.rx_threshold {
+                Ok(shared.rx_waker.take())
+            } else {
+                Ok(None)
+            }
+        })?;
+        wake(waker);
+        Ok(())
+    }
+
+    /// Sends `item`, parking the thread while the channel is full.
+    #[cfg(feature = "std")]
+    pub fn send_blocking(&mut self, item: T) -> Result<(), Closed> {
+        block_on(|cx| self.poll_ready(cx))?;
+        self.start_send(item)
+    }
+
+    /// Waits until `n` contiguous slots are free, so `n` elements can be sent without
+    /// waiting.
+    pub async fn wait_until_free(&mut self, n: usize) -> Result<(), Closed> {
+        poll_fn(|cx| self.poll_free(n, cx)).await
+    }
+}
+
+impl<T, const CAP: usize> Drop for Sender<'_, T, CAP> {
+    fn drop(&mut self) {
+        wake(self.channel.lock(|shared| {
+            shared.sender = false;
+            shared.rx_waker.take()
+        }))
+    }
+}
+
+/// Receiving half of a [`Channel`].
+#[derive(Debug)]
+pub struct Receiver<'a, T, const CAP: usize> {
+    channel: &'a Channel<T, CAP>,
+}
+
+impl<T: Copy, const CAP: usize> Receiver<'_, T, CAP> {
+    /// Waits for the next element.
+    ///
+    /// Returns `None` once the sender has been dropped and the channel is empty.
+    pub fn poll_next(&mut self, cx: &mut Context<'_>) -> Poll<Option<T>> {
+        let (result, waker) = self.channel.lock(|shared| {
+            let (result, waker) = shared.try_recv();
+            let poll = match result {
+                Ok(item) => Poll::Ready(Some(item)),
+                Err(TryRecvError::Closed) => Poll::Ready(None),
+                Err(TryRecvError::Empty) => {
+                    shared.rx_waker = Some(cx.waker().clone());
+                    shared.rx_threshold = 1;
+                    Poll::Pending
+                }
+            };
+            (poll, waker)
+        });
+        wake(waker);
+        result
+    }
+
+    /// Receives the next element if there is one, without waiting.
+    #[inline]
+    pub fn try_recv(&mut self) -> Result<T, TryRecvError> {
+        let (result, waker) = self.channel.lock(Shared::try_recv);
+        wake(waker);
+        result
+    }
+
+    /// Receives the next element, parking the thread while the channel is empty.
+    ///
+    /// Returns `None` once the sender has been dropped and the channel is empty.
+    #[cfg(feature = "std")]
+    pub fn recv_blocking(&mut self) -> Option<T> {
+        block_on(|cx| self.poll_next(cx))
+    }
+
+    /// Receives the next element, waiting for one if the channel is empty.
+    pub async fn recv(&mut self) -> Option<T> {
+        poll_fn(|cx| self.poll_next(cx)).await
+    }
+
+    /// Waits until at least `n` elements are committed, without waking for fewer.
+    ///
+    /// An `n` above the capacity waits for a full channel. Fails if the sender is dropped
+    /// before enough elements arrive.
+    pub fn poll_at_least(&mut self, n: usize, cx: &mut Context<'_>) -> Poll<Result<(), Closed>> {
+        let n = n.min(CAP);
+        self.channel.lock(|shared| {
+            if shared.bip.committed() >= n {
+                Poll::Ready(Ok(()))
+            } else if !shared.sender {
+                Poll::Ready(Err(Closed))
+            } else {
+                shared.rx_waker = Some(cx.waker().clone());
+                shared.rx_threshold = n;
+                Poll::Pending
+            }
+        })
+    }
+
+    /// Waits until at least `n` elements are committed, so they can be received without
+    /// waiting.
+    pub async fn wait_for_at_least(&mut self, n: usize) -> Result<(), Closed> {
+        poll_fn(|cx| self.poll_at_least(n, cx)).await
+    }
+}
+
+impl<T, const CAP: usize> Drop for Receiver<'_, T, CAP> {
+    fn drop(&mut self) {
+        wake(self.channel.lock(|shared| {
+            shared.receiver = false;
+            shared.tx_waker.take()
+        }))
+    }
+}
+
+/// Polls `poll` on the current thread, parking it until woken while it is pending.
+#[cfg(feature = "std")]
+fn block_on<R>(mut poll: impl FnMut(&mut Context<'_>) -> Poll<R>) -> R {
+    use std::{
+        sync::Arc,
+        task::Wake,
+        thread::{self, Thread},
+    };
+
+    struct Unpark(Thread);
+
+    impl Wake for Unpark {
+        fn wake(self: Arc<Self>) {
+            self.0.unpark();
+        }
+    }
+
+    let waker = Waker::from(Arc::new(Unpark(thread::current())));
+    let mut cx = Context::from_waker(&waker);
+    loop {
+        if let Poll::Ready(result) = poll(&mut cx) {
+            return result;
+        }
+        thread::park();
+    }
+}
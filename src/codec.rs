@@ -0,0 +1,213 @@
+//! Decoding and encoding frames in place.
+//!
+//! [`Decoder`] and [`Encoder`] follow the shape of the `tokio-util` codec traits, but work on
+//! the committed bytes and reservations of a [`StaticBip`] instead of a `BytesMut`, so frames
+//! are parsed and serialized without copying between buffers. An existing codec adapts by
+//! forwarding to its own `decode` and `encode` logic.
+//!
+//! ```rust
+//! use staticbip::{codec::{Decoder, Encoder}, StaticBip};
+//! use core::convert::Infallible;
+//!
+//! /// Lines terminated by `\n`.
+//! struct Lines;
+//!
+//! impl Decoder for Lines {
+//!     type Item = usize;
+//!     type Error = Infallible;
+//!
+//!     fn decode(&mut self, src: &[u8]) -> Result<Option<(usize, usize)>, Infallible> {
+//!         Ok(src.iter().position(|&b| b == b'\n').map(|end| (end, end + 1)))
+//!     }
+//! }
+//!
+//! impl Encoder<&str> for Lines {
+//!     type Error = Infallible;
+//!
+//!     fn encoded_len(&self, line: &&str) -> usize {
+//!         line.len() + 1
+//!     }
+//!
+//!     fn encode(&mut self, line: &str, dst: &mut [u8]) -> Result<(), Infallible> {
+//!         let (text, end) = dst.split_at_mut(line.len());
+//!         text.copy_from_slice(line.as_bytes());
+//!         end[0] = b'\n';
+//!         Ok(())
+//!     }
+//! }
+//!
+//! let mut buffer = StaticBip::<u8, 16>::default();
+//! buffer.encode_with(&mut Lines, "hello").unwrap();
+//! assert_eq!(buffer.decode_with(&mut Lines), Ok(Some(5)));
+//! assert_eq!(buffer.decode_with(&mut Lines), Ok(None));
+//! ```
+//!
+//! Streaming parsers such as those written with `nom` plug in through
+//! [`parse_with`](StaticBip::parse_with), which runs the incremental parsing loop: present the
+//! committed bytes, decommit what a step consumed, and stop once the parser needs more input.
+//!
+//! ```rust
+//! use staticbip::{codec::Step, StaticBip};
+//! use core::convert::Infallible;
+//!
+//! let mut buffer = StaticBip::<u8, 16>::default();
+//! buffer.extend(b"12,345,6");
+//!
+//! // Comma-terminated numbers
+//! let mut numbers = Vec::new();
+//! let parsed = buffer.parse_with(
+//!     |src| {
+//!         Ok::<_, Infallible>(match src.iter().position(|&b| b == b',') {
+//!             Some(end) => Step::Consumed(end + 1, Some(src[..end].to_vec())),
+//!             None => Step::Incomplete,
+//!         })
+//!     },
+//!     |number| numbers.push(number),
+//! );
+//!
+//! assert_eq!(parsed, Ok(2));
+//! assert_eq!(numbers, [b"12".to_vec(), b"345".to_vec()]);
+//! assert_eq!(buffer.read(), b"6");
+//! ```
+
+use crate::StaticBip;
+use core::fmt;
+
+/// Parses frames from committed bytes.
+pub trait Decoder {
+    /// A decoded frame.
+    type Item;
+    /// Error raised on malformed input.
+    type Error;
+
+    /// Decodes a frame from the start of `src`.
+    ///
+    /// Returns the frame and the number of bytes it occupied, or `None` if `src` does not yet
+    /// hold a complete frame.
+    fn decode(&mut self, src: &[u8]) -> Result<Option<(Self::Item, usize)>, Self::Error>;
+}
+
+/// Serializes frames into reservations.
+pub trait Encoder<Item> {
+    /// Error raised when `item` cannot be encoded.
+    type Error;
+
+    /// Number of bytes `item` encodes to.
+    fn encoded_len(&self, item: &Item) -> usize;
+
+    /// Encodes `item` into `dst`, which is exactly [`encoded_len`](Self::encoded_len) bytes
+    /// long.
+    fn encode(&mut self, item: Item, dst: &mut [u8]) -> Result<(), Self::Error>;
+}
+
+/// Reasons an item was not encoded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EncodeError<I, E> {
+    /// There is no contiguous space for the encoded item, which is handed back.
+    Full(I),
+    /// The encoder failed.
+    Codec(E),
+}
+
+impl<I, E: fmt::Display> fmt::Display for EncodeError<I, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Full(_) => f.write_str("no space for the encoded item"),
+            Self::Codec(error) => error.fmt(f),
+        }
+    }
+}
+
+/// Result of one parser step in [`parse_with`](StaticBip::parse_with).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Step<O> {
+    /// The parser used up this many bytes from the start of its input, producing an output or,
+    /// for input it skips, none.
+    Consumed(usize, Option<O>),
+    /// The input ends before anything can be consumed.
+    Incomplete,
+}
+
+impl<const CAP: usize> StaticBip<u8, CAP> {
+    /// Decodes the oldest frame and decommits the bytes it occupied.
+    ///
+    /// A frame that spans both regions is first moved together, which cancels any
+    /// reservation. Returns `None` if the committed bytes do not hold a complete frame.
+    pub fn decode_with<D: Decoder>(
+        &mut self,
+        decoder: &mut D,
+    ) -> Result<Option<D::Item>, D::Error> {
+        let mut decoded = decoder.decode(self.read())?;
+        if decoded.is_none() && self.read().len() < self.committed() {
+            let committed = self.committed();
+            let src = self.read_exact(committed).unwrap_or_default();
+            decoded = decoder.decode(src)?;
+        }
+        Ok(decoded.map(|(item, len)| {
+            self.decommit(len);
+            item
+        }))
+    }
+
+    /// Encodes `item` into a single reservation and commits it.
+    ///
+    /// Nothing is committed if the encoder fails.
+    pub fn encode_with<I, E: Encoder<I>>(
+        &mut self,
+        encoder: &mut E,
+        item: I,
+    ) -> Result<(), EncodeError<I, E::Error>> {
+        let len = encoder.encoded_len(&item);
+        let reserved = self.reserve(len);
+        if reserved.len() < len {
+            self.commit(0);
+            return Err(EncodeError::Full(item));
+        }
+        match encoder.encode(item, reserved) {
+            Ok(()) => {
+                self.commit(len);
+                Ok(())
+            }
+            Err(error) => {
+                self.commit(0);
+                Err(EncodeError::Codec(error))
+            }
+        }
+    }
+
+    /// Runs `parser` over the committed bytes until it needs more input, passing each output
+    /// to `output` and decommitting exactly what each step consumed.
+    ///
+    /// A step that is incomplete on the first block of committed data is retried on all of
+    /// it once moved together, which cancels any reservation. Parsing stops when a step is
+    /// incomplete, consumes nothing, or fails; nothing is decommitted for a failed step.
+    /// Returns the number of outputs produced.
+    pub fn parse_with<O, E>(
+        &mut self,
+        mut parser: impl FnMut(&[u8]) -> Result<Step<O>, E>,
+        mut output: impl FnMut(O),
+    ) -> Result<usize, E> {
+        let mut count = 0;
+        while !self.is_empty() {
+            let mut step = parser(self.read())?;
+            if matches!(step, Step::Incomplete) && self.read().len() < self.committed() {
+                let committed = self.committed();
+                let src = self.read_exact(committed).unwrap_or_default();
+                step = parser(src)?;
+            }
+            let (len, item) = match step {
+                Step::Consumed(len, item) => (len, item),
+                Step::Incomplete => break,
+            };
+            self.consume(len);
+            if let Some(item) = item {
+                output(item);
+                count += 1;
+            }
+            if len == 0 {
+                break;
+            }
+        }
+        Ok(count)
+    }
+}
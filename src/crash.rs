@@ -0,0 +1,105 @@
+//! Panic messages kept across a reset.
+//!
+//! A [`CrashLog`] is a byte buffer in RAM that is not initialized at startup, written through
+//! `fmt::Write`. The application writes recent log output into it as it runs and the panic
+//! handler adds the panic message with [`capture_panic`](CrashLog::capture_panic). Once full,
+//! the oldest bytes are overwritten, so the log always ends with the newest output. After the
+//! reset that follows the panic, [`recover`](CrashLog::recover) finds the bytes intact, ready
+//! to be sent out or inspected with a debugger.
+//!
+//! ```rust
+//! use core::{fmt::Write, mem::MaybeUninit};
+//! use staticbip::crash::CrashLog;
+//!
+//! // In firmware:
+//! // #[link_section = ".noinit"]
+//! // static mut CRASH: MaybeUninit<CrashLog<256>> = MaybeUninit::uninit();
+//! //
+//! // #[panic_handler]
+//! // fn panic(info: &core::panic::PanicInfo) -> ! {
+//! //     let (log, _) = unsafe { CrashLog::recover(&mut *core::ptr::addr_of_mut!(CRASH)) };
+//! //     log.capture_panic(info);
+//! //     cortex_m::peripheral::SCB::sys_reset()
+//! // }
+//! let mut slot = MaybeUninit::<CrashLog<256>>::zeroed();
+//!
+//! let (log, _) = unsafe { CrashLog::recover(&mut slot) };
+//! writeln!(log, "sensor init").unwrap();
+//! log.capture_panic(&"panicked at src/main.rs:7:5:\nout of range");
+//!
+//! // After the reset
+//! let (log, recovered) = unsafe { CrashLog::recover(&mut slot) };
+//! assert!(recovered);
+//! let (a, b) = log.contents();
+//! assert_eq!([a, b].concat(), b"sensor init\npanicked at src/main.rs:7:5:\nout of range\n");
+//! log.clear();
+//! ```
+//!
+//! The checksum of a [`Retained`] buffer is updated after every write, so a reset in the
+//! middle of the panic message loses at most that message, never the log before it.
+
+use crate::{overflow::Overwrite, retained::Retained, StaticBip};
+use core::{fmt, mem::MaybeUninit};
+
+/// Log output and panic messages kept across a reset.
+#[derive(Debug)]
+#[repr(transparent)]
+pub struct CrashLog<const CAP: usize>(Retained<u8, CAP>);
+
+impl<const CAP: usize> CrashLog<CAP> {
+    /// Recovers the log in `slot`, or starts an empty one if its contents are not
+    /// trustworthy.
+    ///
+    /// Returns the log and whether its contents were recovered.
+    ///
+    /// # Safety
+    ///
+    /// `slot` must be readable as plain bytes, as RAM is after a reset even if it has not been
+    /// initialized.
+    pub unsafe fn recover(slot: &mut MaybeUninit<Self>) -> (&mut Self, bool) {
+        let slot = &mut *(slot as *mut MaybeUninit<Self>).cast::<MaybeUninit<Retained<u8, CAP>>>();
+        let (log, recovered) = Retained::recover(slot, 0);
+        // SAFETY: `CrashLog` is a transparent wrapper of `Retained`.
+        (
+            &mut *(log as *mut Retained<u8, CAP>).cast::<Self>(),
+            recovered,
+        )
+    }
+
+    /// Appends the panic message `info`, followed by a newline.
+    ///
+    /// Meant for the panic handler, where `info` is the `PanicInfo` passed to it.
+    #[inline]
+    pub fn capture_panic(&mut self, info: &impl fmt::Display) {
+        // Writing never fails, since old bytes are overwritten instead
+        let _ = fmt::Write::write_fmt(self, format_args!("{}\n", info));
+    }
+
+    /// Returns the logged bytes as two slices, in order.
+    #[inline]
+    pub fn contents(&self) -> (&[u8], &[u8]) {
+        self.0.get_ref().as_slices()
+    }
+
+    /// Discards the logged bytes, as after they have been read out.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.0.with(StaticBip::clear);
+    }
+
+    /// Returns the buffer.
+    #[inline]
+    pub fn get_ref(&self) -> &StaticBip<u8, CAP> {
+        self.0.get_ref()
+    }
+}
+
+impl<const CAP: usize> fmt::Write for CrashLog<CAP> {
+    #[inline]
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.0
+            .with(|bip| bip.write_slice::<Overwrite>(s.as_bytes()))
+            .map_err(|_| fmt::Error)?;
+        Ok(())
+    }
+}
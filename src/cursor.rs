@@ -0,0 +1,82 @@
+//! Non-consuming reads over committed data.
+//!
+//! A [`Cursor`] walks the committed data of both regions in order without releasing it, so a
+//! parser can look ahead and back up. Only [`consume_to_position`](Cursor::consume_to_position)
+//! releases what was read.
+//!
+//! ```rust
+//! use staticbip::StaticBip;
+//!
+//! let mut buffer = StaticBip::<u8, 8>::default();
+//! buffer.reserve(4).copy_from_slice(b"AT\r\n");
+//! buffer.commit(4);
+//!
+//! let mut cursor = buffer.cursor();
+//! assert_eq!(cursor.read(2), b"AT");
+//! cursor.seek(0);
+//! assert_eq!(cursor.read(8), b"AT\r\n");
+//! cursor.consume_to_position();
+//!
+//! assert!(buffer.read().is_empty());
+//! ```
+
+use crate::StaticBip;
+
+/// A read position over the committed data of a buffer.
+#[derive(Debug)]
+pub struct Cursor<'a, T, const CAP: usize> {
+    bip: &'a mut StaticBip<T, CAP>,
+    pos: usize,
+}
+
+impl<'a, T, const CAP: usize> Cursor<'a, T, CAP> {
+    /// Creates a cursor at the oldest committed element.
+    #[inline]
+    pub fn new(bip: &'a mut StaticBip<T, CAP>) -> Self {
+        Self { bip, pos: 0 }
+    }
+
+    /// Number of committed elements before the cursor.
+    #[inline]
+    pub fn position(&self) -> usize {
+        self.pos
+    }
+
+    /// Number of committed elements after the cursor.
+    #[inline]
+    pub fn remaining(&self) -> usize {
+        self.bip.committed() - self.pos
+    }
+
+    /// Moves the cursor to `pos`, clamped to the committed data.
+    #[inline]
+    pub fn seek(&mut self, pos: usize) {
+        self.pos = pos.min(self.bip.committed());
+    }
+
+    /// Returns up to `len` contiguous elements after the cursor and moves past them.
+    ///
+    /// The result is shorter than `len` at the end of a region; reading again continues in
+    /// the next one.
+    #[inline]
+    pub fn read(&mut self, len: usize) -> &[T] {
+        let block = self.bip.regions.block_at(self.pos);
+        let end = block.start + block.len().min(len);
+        self.pos += end - block.start;
+        self.bip.slice(block.start..end)
+    }
+
+    /// Releases every element before the cursor.
+    #[inline]
+    pub fn consume_to_position(self) {
+        self.bip.regions.consume(self.pos);
+    }
+}
+
+impl<T, const CAP: usize> StaticBip<T, CAP> {
+    /// Returns a [`Cursor`] over the committed data.
+    #[inline]
+    pub fn cursor(&mut self) -> Cursor<'_, T, CAP> {
+        Cursor::new(self)
+    }
+}
@@ -0,0 +1,145 @@
+//! Transport for `defmt` log frames.
+//!
+//! A [`DefmtBuffer`] stores the output of defmt's encoder one frame at a time: a global
+//! logger calls [`start_frame`](DefmtBuffer::start_frame) in `acquire`,
+//! [`write`](DefmtBuffer::write) in `write` and [`end_frame`](DefmtBuffer::end_frame) in
+//! `release`. A frame is only committed once complete, and a frame that does not fit is
+//! dropped as a whole, so the bytes [`drain`](DefmtBuffer::drain) hands out are always a
+//! sequence of whole frames that `defmt-print` or `defmt-decoder` decode from any byte
+//! transport, such as a UART or a USB serial port.
+//!
+//! With defmt's default rzCOBS encoding, every frame ends with a zero byte and contains no
+//! other, so a host that starts reading in the middle of a frame resynchronizes at the next
+//! one. The raw encoding has no such marker and needs the host to read from the start.
+//!
+//! ```rust
+//! use staticbip::defmt::DefmtBuffer;
+//!
+//! // In firmware, inside a critical section:
+//! // #[defmt::global_logger]
+//! // struct Logger;
+//! //
+//! // unsafe impl defmt::Logger for Logger {
+//! //     fn acquire() { /* take the lock */ BUFFER.start_frame(); ENCODER.start_frame(|b| BUFFER.write(b)) }
+//! //     unsafe fn write(bytes: &[u8]) { ENCODER.write(bytes, |b| BUFFER.write(b)) }
+//! //     unsafe fn release() { ENCODER.end_frame(|b| BUFFER.write(b)); BUFFER.end_frame(); /* unlock */ }
+//! //     unsafe fn flush() {}
+//! // }
+//! let mut buffer = DefmtBuffer::<16>::new();
+//!
+//! // rzCOBS frames as produced by the encoder
+//! buffer.start_frame();
+//! buffer.write(&[0x03, 0x01]);
+//! buffer.write(&[0xfe, 0x00]);
+//! assert!(buffer.end_frame());
+//!
+//! let mut sent = Vec::new();
+//! buffer.drain(|bytes| {
+//!     sent.extend_from_slice(bytes);
+//!     bytes.len()
+//! });
+//! assert_eq!(sent, [0x03, 0x01, 0xfe, 0x00]);
+//! ```
+
+use crate::StaticBip;
+
+/// A buffer of encoded `defmt` frames.
+#[derive(Debug)]
+pub struct DefmtBuffer<const CAP: usize> {
+    bip: StaticBip<u8, CAP>,
+    /// Bytes of the current frame written to the reservation, or `None` once it overflowed
+    written: Option<usize>,
+    /// Number of frames that did not fit
+    dropped: usize,
+}
+
+impl<const CAP: usize> Default for DefmtBuffer<CAP> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const CAP: usize> DefmtBuffer<CAP> {
+    /// Creates an empty buffer.
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            bip: StaticBip::new([0; CAP]),
+            written: None,
+            dropped: 0,
+        }
+    }
+
+    /// Number of frames that were dropped because they did not fit.
+    #[inline]
+    pub fn dropped(&self) -> usize {
+        self.dropped
+    }
+
+    /// Starts a frame, reserving the largest free block for it.
+    ///
+    /// A frame that was started and not ended is discarded.
+    #[inline]
+    pub fn start_frame(&mut self) {
+        self.bip.commit(0);
+        self.bip.reserve_max();
+        self.written = Some(0);
+    }
+
+    /// Appends encoded bytes to the current frame.
+    ///
+    /// Once the frame outgrows its reservation, the rest of it is ignored and the frame is
+    /// dropped when it ends.
+    pub fn write(&mut self, bytes: &[u8]) {
+        let Some(written) = self.written else { return };
+        let end = written + bytes.len();
+        match self.bip.extend_reserve(0).get_mut(written..end) {
+            Some(slots) => {
+                slots.copy_from_slice(bytes);
+                self.written = Some(end);
+            }
+            None => self.written = None,
+        }
+    }
+
+    /// Commits the current frame.
+    ///
+    /// Returns whether the frame was stored; a frame that did not fit is counted as
+    /// [`dropped`](Self::dropped).
+    pub fn end_frame(&mut self) -> bool {
+        match self.written.take() {
+            Some(len) => {
+                self.bip.commit(len);
+                true
+            }
+            None => {
+                self.bip.commit(0);
+                self.dropped += 1;
+                false
+            }
+        }
+    }
+
+    /// Passes stored bytes to `f` in contiguous blocks, removing as many as `f` returns,
+    /// until it takes less than a whole block.
+    ///
+    /// Returns the number of bytes removed.
+    pub fn drain(&mut self, mut f: impl FnMut(&[u8]) -> usize) -> usize {
+        let mut total = 0;
+        loop {
+            let block = self.bip.read();
+            let len = block.len();
+            if len == 0 {
+                break;
+            }
+            let taken = f(block).min(len);
+            self.bip.decommit(taken);
+            total += taken;
+            if taken < len {
+                break;
+            }
+        }
+        total
+    }
+}
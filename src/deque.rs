@@ -0,0 +1,58 @@
+//! Element operations named after `VecDeque`.
+//!
+//! Together with [`iter`](StaticBip::iter), [`as_slices`](StaticBip::as_slices),
+//! [`front`](StaticBip::front), [`back`](StaticBip::back), [`clear`](StaticBip::clear) and
+//! [`is_empty`](StaticBip::is_empty), these cover the part of `VecDeque` used as a FIFO, so
+//! such code can switch to a buffer by changing its type. Elements come out in the order they
+//! were pushed and [`as_slices`](StaticBip::as_slices) splits them the same way at the wrap.
+//!
+//! ```rust
+//! use staticbip::StaticBip;
+//!
+//! let mut deque = StaticBip::<u8, 4>::default();
+//! deque.push_back(1).unwrap();
+//! deque.push_back(2).unwrap();
+//! deque.push_back(3).unwrap();
+//! assert_eq!(deque.pop_front(), Some(1));
+//!
+//! assert_eq!(deque.len(), 2);
+//! assert!(deque.iter().eq(&[2, 3]));
+//! assert_eq!(deque.as_slices(), (&[2, 3][..], &[][..]));
+//! ```
+//!
+//! The capacity is fixed, so [`push_back`](StaticBip::push_back) hands the element back when
+//! the buffer holds `CAP` elements instead of growing, and [`pop_front`](StaticBip::pop_front) leaves
+//! `T::default()` in the slot it moves the element out of.
+
+use crate::StaticBip;
+
+impl<T, const CAP: usize> StaticBip<T, CAP> {
+    /// Appends `item` after the newest element, handing it back if the buffer is full.
+    ///
+    /// Unlike [`enqueue`](Self::enqueue), this only fails when all `CAP` slots are taken: if
+    /// the free slots are stranded behind region `A` while region `B` is in use, the committed
+    /// data is first moved together. Any outstanding reservation is replaced.
+    pub fn push_back(&mut self, item: T) -> Result<(), T> {
+        match self.enqueue(item) {
+            Err(item) if self.committed() < CAP => {
+                self.defragment();
+                self.enqueue(item)
+            }
+            result => result,
+        }
+    }
+
+    /// Number of committed elements.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.committed()
+    }
+}
+
+impl<T: Default, const CAP: usize> StaticBip<T, CAP> {
+    /// Removes the oldest element and returns it.
+    #[inline]
+    pub fn pop_front(&mut self) -> Option<T> {
+        self.pop_value()
+    }
+}
@@ -0,0 +1,149 @@
+//! Network device buffers handing out frames through tokens.
+//!
+//! [`FrameDevice`] follows the token pattern of smoltcp's `phy::Device`: the stack asks for
+//! an [`RxToken`] or [`TxToken`] and consumes it with a closure that works directly on the
+//! frame inside the buffer, so no interim copy is needed. A smoltcp `Device` implementation
+//! forwards to these methods, while the MAC driver moves frames through
+//! [`rx`](FrameDevice::rx) and [`tx`](FrameDevice::tx).
+//!
+//! ```rust
+//! use staticbip::{device::FrameDevice, StaticBip};
+//!
+//! let mut rx = StaticBip::<u8, 64>::default();
+//! let mut tx = StaticBip::<u8, 64>::default();
+//! let mut device = FrameDevice::new(&mut rx, &mut tx, 16);
+//!
+//! // Driver receives a frame
+//! let mut frames = device.rx();
+//! frames.reserve(4).copy_from_slice(b"ping");
+//! frames.commit(4);
+//!
+//! // Stack answers it
+//! let (rx_token, tx_token) = device.receive().unwrap();
+//! let len = rx_token.consume(|frame| frame.len());
+//! tx_token.consume(len, |frame| frame.copy_from_slice(b"pong"));
+//!
+//! // Driver transmits the answer
+//! assert_eq!(device.tx().read().unwrap().unwrap(), b"pong");
+//! ```
+
+use crate::{frame::Framed, StaticBip};
+
+/// Receive and transmit frame buffers of a network device.
+pub struct FrameDevice<'a, const RX: usize, const TX: usize> {
+    rx: &'a mut StaticBip<u8, RX>,
+    tx: &'a mut StaticBip<u8, TX>,
+    /// Largest frame the stack may transmit
+    mtu: usize,
+}
+
+impl<'a, const RX: usize, const TX: usize> FrameDevice<'a, RX, TX> {
+    /// Creates a device over a receive and a transmit buffer, sending frames of up to `mtu`
+    /// bytes.
+    #[inline]
+    pub fn new(rx: &'a mut StaticBip<u8, RX>, tx: &'a mut StaticBip<u8, TX>, mtu: usize) -> Self {
+        Self { rx, tx, mtu }
+    }
+
+    /// Largest frame the stack may transmit.
+    #[inline]
+    pub fn mtu(&self) -> usize {
+        self.mtu
+    }
+
+    /// Frames received by the driver.
+    #[inline]
+    pub fn rx(&mut self) -> Framed<'_, StaticBip<u8, RX>> {
+        Framed::new(self.rx)
+    }
+
+    /// Frames waiting to be transmitted by the driver.
+    #[inline]
+    pub fn tx(&mut self) -> Framed<'_, StaticBip<u8, TX>> {
+        Framed::new(self.tx)
+    }
+
+    /// Takes the oldest received frame, along with room for a reply.
+    ///
+    /// Frames with a corrupt length are dropped. Returns `None` if there is no frame or no
+    /// room for a frame of [`mtu`](Self::mtu) bytes.
+    pub fn receive(&mut self) -> Option<(RxToken<'_, RX>, TxToken<'_, TX>)> {
+        if !self.can_transmit() {
+            return None;
+        }
+        let mut frames = Framed::<_>::new(&mut *self.rx);
+        loop {
+            match frames.read()? {
+                Ok(_) => break,
+                Err(_) => frames.decommit(),
+            }
+        }
+        Some((
+            RxToken { bip: self.rx },
+            TxToken {
+                bip: self.tx,
+                mtu: self.mtu,
+            },
+        ))
+    }
+
+    /// Takes room for a frame of up to [`mtu`](Self::mtu) bytes.
+    ///
+    /// Returns `None` if the transmit buffer is too full.
+    pub fn transmit(&mut self) -> Option<TxToken<'_, TX>> {
+        if !self.can_transmit() {
+            return None;
+        }
+        Some(TxToken {
+            bip: self.tx,
+            mtu: self.mtu,
+        })
+    }
+
+    /// Whether a frame of `mtu` bytes fits in the transmit buffer.
+    fn can_transmit(&mut self) -> bool {
+        let mut frames = Framed::<_>::new(&mut *self.tx);
+        let fits = frames.reserve(self.mtu).len() == self.mtu;
+        frames.commit(0);
+        fits
+    }
+}
+
+/// A received frame, removed from the buffer once consumed.
+pub struct RxToken<'a, const CAP: usize> {
+    bip: &'a mut StaticBip<u8, CAP>,
+}
+
+impl<const CAP: usize> RxToken<'_, CAP> {
+    /// Passes the frame to `f` and removes it from the buffer.
+    pub fn consume<R>(self, f: impl FnOnce(&mut [u8]) -> R) -> R {
+        let mut frames = Framed::<_>::new(self.bip);
+        let result = match frames.read() {
+            Some(Ok(frame)) => f(frame),
+            _ => f(&mut []),
+        };
+        frames.decommit();
+        result
+    }
+}
+
+/// Room for a frame to transmit.
+pub struct TxToken<'a, const CAP: usize> {
+    bip: &'a mut StaticBip<u8, CAP>,
+    mtu: usize,
+}
+
+impl<const CAP: usize> TxToken<'_, CAP> {
+    /// Passes a `len` byte frame to `f` and queues it for transmission.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `len` exceeds the device [`mtu`](FrameDevice::mtu).
+    pub fn consume<R>(self, len: usize, f: impl FnOnce(&mut [u8]) -> R) -> R {
+        assert!(len <= self.mtu, "frame exceeds the device MTU");
+        let mut frames = Framed::<_>::new(self.bip);
+        let result = f(frames.reserve(len));
+        frames.commit(len);
+        result
+    }
+}
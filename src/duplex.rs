@@ -0,0 +1,171 @@
+//! Paired transmit and receive channels for serial drivers.
+//!
+//! A [`Duplex`] holds a transmit and a receive [`Channel`] and [`split`](Duplex::split)s
+//! into two [`Port`]s facing each other: the application's port sends into the transmit
+//! channel and receives from the receive channel, and the driver's port does the opposite.
+//! Each port sends and receives through its [`sender`](Port::sender) and
+//! [`receiver`](Port::receiver), or hands both out with [`into_parts`](Port::into_parts) when
+//! they are driven from different places, such as two interrupt handlers.
+//!
+//! ```rust
+//! use staticbip::duplex::Duplex;
+//!
+//! let mut duplex = Duplex::<u8, 16, 8>::default();
+//! let (mut app, mut driver) = duplex.split();
+//!
+//! app.sender().try_send(b'a').unwrap();
+//! assert_eq!(driver.receiver().try_recv(), Ok(b'a'));
+//!
+//! driver.sender().try_send(b'b').unwrap();
+//! assert_eq!(app.receiver().try_recv(), Ok(b'b'));
+//! ```
+//!
+//! With the `std` feature, the ports of a byte duplex implement [`std::io::Read`] and
+//! [`std::io::Write`], parking the thread until at least one byte can be transferred.
+//!
+//! ```rust
+//! # #[cfg(feature = "std")]
+//! # {
+//! use std::io::{Read, Write};
+//! use staticbip::duplex::Duplex;
+//!
+//! let mut duplex = Duplex::<u8, 16, 16>::default();
+//! let (mut app, mut driver) = duplex.split();
+//!
+//! app.write_all(b"AT\r").unwrap();
+//! let mut command = [0; 3];
+//! driver.read_exact(&mut command).unwrap();
+//! assert_eq!(&command, b"AT\r");
+//! # }
+//! ```
+
+use crate::channel::{Channel, Receiver, Sender};
+use core::array;
+
+/// A transmit channel of `TX_CAP` elements and a receive channel of `RX_CAP` elements.
+#[derive(Debug)]
+pub struct Duplex<T, const TX_CAP: usize, const RX_CAP: usize> {
+    tx: Channel<T, TX_CAP>,
+    rx: Channel<T, RX_CAP>,
+}
+
+impl<T: Default, const TX_CAP: usize, const RX_CAP: usize> Default for Duplex<T, TX_CAP, RX_CAP> {
+    #[inline]
+    fn default() -> Self {
+        Self::new(
+            array::from_fn(|_| T::default()),
+            array::from_fn(|_| T::default()),
+        )
+    }
+}
+
+impl<T, const TX_CAP: usize, const RX_CAP: usize> Duplex<T, TX_CAP, RX_CAP> {
+    /// Creates a duplex over the backing stores of its transmit and receive channels.
+    #[inline]
+    pub const fn new(tx: [T; TX_CAP], rx: [T; RX_CAP]) -> Self {
+        Self {
+            tx: Channel::new(tx),
+            rx: Channel::new(rx),
+        }
+    }
+
+    /// Splits the duplex into the application's port and the driver's port.
+    ///
+    /// Elements left over from a previous split are kept.
+    pub fn split(&mut self) -> (Port<'_, T, TX_CAP, RX_CAP>, Port<'_, T, RX_CAP, TX_CAP>) {
+        let (tx_sender, tx_receiver) = self.tx.split();
+        let (rx_sender, rx_receiver) = self.rx.split();
+        (
+            Port {
+                sender: tx_sender,
+                receiver: rx_receiver,
+            },
+            Port {
+                sender: rx_sender,
+                receiver: tx_receiver,
+            },
+        )
+    }
+}
+
+/// One side of a [`Duplex`], sending into a channel of `OUT_CAP` elements and receiving from
+/// one of `IN_CAP` elements.
+#[derive(Debug)]
+pub struct Port<'a, T, const OUT_CAP: usize, const IN_CAP: usize> {
+    sender: Sender<'a, T, OUT_CAP>,
+    receiver: Receiver<'a, T, IN_CAP>,
+}
+
+impl<'a, T, const OUT_CAP: usize, const IN_CAP: usize> Port<'a, T, OUT_CAP, IN_CAP> {
+    /// Returns the half sending to the other port.
+    #[inline]
+    pub fn sender(&mut self) -> &mut Sender<'a, T, OUT_CAP> {
+        &mut self.sender
+    }
+
+    /// Returns the half receiving from the other port.
+    #[inline]
+    pub fn receiver(&mut self) -> &mut Receiver<'a, T, IN_CAP> {
+        &mut self.receiver
+    }
+
+    /// Returns both halves.
+    #[inline]
+    pub fn into_parts(self) -> (Sender<'a, T, OUT_CAP>, Receiver<'a, T, IN_CAP>) {
+        (self.sender, self.receiver)
+    }
+}
+
+/// Waits for one byte, then reads as many more as have been received.
+///
+/// Returns `Ok(0)` once the other port is dropped and every byte has been read.
+#[cfg(feature = "std")]
+impl<const OUT_CAP: usize, const IN_CAP: usize> std::io::Read for Port<'_, u8, OUT_CAP, IN_CAP> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let Some((first, rest)) = buf.split_first_mut() else {
+            return Ok(0);
+        };
+        match self.receiver.recv_blocking() {
+            Some(byte) => *first = byte,
+            None => return Ok(0),
+        }
+        let mut len = 1;
+        for slot in rest {
+            match self.receiver.try_recv() {
+                Ok(byte) => *slot = byte,
+                Err(_) => break,
+            }
+            len += 1;
+        }
+        Ok(len)
+    }
+}
+
+/// Waits for room for one byte, then writes as many more as fit.
+///
+/// Fails with [`std::io::ErrorKind::BrokenPipe`] once the other port is dropped.
+#[cfg(feature = "std")]
+impl<const OUT_CAP: usize, const IN_CAP: usize> std::io::Write for Port<'_, u8, OUT_CAP, IN_CAP> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let Some((&first, rest)) = buf.split_first() else {
+            return Ok(0);
+        };
+        if self.sender.send_blocking(first).is_err() {
+            return Err(std::io::ErrorKind::BrokenPipe.into());
+        }
+        let mut len = 1;
+        for &byte in rest {
+            if self.sender.try_send(byte).is_err() {
+                break;
+            }
+            len += 1;
+        }
+        Ok(len)
+    }
+
+    /// Does nothing, since written bytes are visible to the other port right away.
+    #[inline]
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
@@ -0,0 +1,21 @@
+//! Errors reported by the buffer.
+
+use core::fmt;
+
+/// Reasons an exact commit or decommit was rejected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CommitError {
+    /// The length exceeds the reservation.
+    Reservation,
+    /// The length exceeds the data returned by `read`.
+    Available,
+}
+
+impl fmt::Display for CommitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Reservation => "length exceeds the reservation",
+            Self::Available => "length exceeds the available data",
+        })
+    }
+}
@@ -0,0 +1,120 @@
+//! C interface over a byte buffer.
+//!
+//! C code owns a [`BipHandle`] and the storage it points to, typically both as statics, and
+//! drives the buffer through the `staticbip_*` functions. The handle has a stable layout, so
+//! its declaration can be generated with cbindgen.
+//!
+//! ```rust
+//! use core::{mem::MaybeUninit, slice};
+//! use staticbip::ffi::*;
+//!
+//! let mut storage = [0u8; 8];
+//! let mut handle = MaybeUninit::<BipHandle>::uninit();
+//! unsafe {
+//!     staticbip_init(handle.as_mut_ptr(), storage.as_mut_ptr(), storage.len());
+//!     let handle = handle.as_mut_ptr();
+//!
+//!     let mut len = 0;
+//!     let ptr = staticbip_reserve(handle, 3, &mut len);
+//!     slice::from_raw_parts_mut(ptr, len).copy_from_slice(&[1, 2, 3]);
+//!     staticbip_commit(handle, len);
+//!
+//!     let ptr = staticbip_read(handle, &mut len);
+//!     assert_eq!(slice::from_raw_parts(ptr, len), &[1, 2, 3]);
+//!     staticbip_decommit(handle, len);
+//! }
+//! ```
+
+use crate::{regions::Regions, BipState};
+
+/// Buffer state shared with C.
+///
+/// The fields are only meant to be accessed through the `staticbip_*` functions.
+#[derive(Debug)]
+#[repr(C)]
+pub struct BipHandle {
+    /// First element of the backing store
+    ptr: *mut u8,
+    /// Number of elements in the backing store
+    capacity: usize,
+    /// Positions of the regions
+    state: BipState,
+}
+
+impl BipHandle {
+    /// Runs `f` on the regions of the handle and stores them back.
+    #[inline]
+    fn update<R>(&mut self, f: impl FnOnce(&mut Regions, usize) -> R) -> R {
+        let mut regions = Regions::from(self.state);
+        let result = f(&mut regions, self.capacity);
+        self.state = BipState::from(&regions);
+        result
+    }
+}
+
+/// Initializes `bip` as an empty buffer over `len` bytes at `buffer`.
+///
+/// # Safety
+///
+/// `bip` must be valid for writes, and `buffer` must be valid for reads and writes of `len`
+/// bytes for as long as the handle is used.
+#[no_mangle]
+pub unsafe extern "C" fn staticbip_init(bip: *mut BipHandle, buffer: *mut u8, len: usize) {
+    bip.write(BipHandle {
+        ptr: buffer,
+        capacity: len,
+        state: BipState::from(&Regions::new()),
+    });
+}
+
+/// Reserves up to `count` contiguous bytes, storing the reserved length in `len`.
+///
+/// # Safety
+///
+/// `bip` must point to a handle initialized by [`staticbip_init`] and `len` must be valid
+/// for writes.
+#[no_mangle]
+pub unsafe extern "C" fn staticbip_reserve(
+    bip: *mut BipHandle,
+    count: usize,
+    len: *mut usize,
+) -> *mut u8 {
+    let bip = &mut *bip;
+    let reserve = bip.update(|regions, capacity| regions.reserve(capacity, count));
+    len.write(reserve.len());
+    bip.ptr.add(reserve.start)
+}
+
+/// Commits the first `len` reserved bytes.
+///
+/// # Safety
+///
+/// `bip` must point to a handle initialized by [`staticbip_init`].
+#[no_mangle]
+pub unsafe extern "C" fn staticbip_commit(bip: *mut BipHandle, len: usize) {
+    (*bip).update(|regions, _| regions.commit(len));
+}
+
+/// Retrieves the first block of committed bytes, storing its length in `len`.
+///
+/// # Safety
+///
+/// `bip` must point to a handle initialized by [`staticbip_init`] and `len` must be valid
+/// for writes.
+#[no_mangle]
+pub unsafe extern "C" fn staticbip_read(bip: *mut BipHandle, len: *mut usize) -> *mut u8 {
+    let bip = &mut *bip;
+    let a = bip.state.a;
+    len.write(a.len);
+    bip.ptr.add(a.offset)
+}
+
+/// Marks the first `len` bytes of the block returned by [`staticbip_read`] as seen.
+///
+/// # Safety
+///
+/// `bip` must point to a handle initialized by [`staticbip_init`].
+#[no_mangle]
+pub unsafe extern "C" fn staticbip_decommit(bip: *mut BipHandle, len: usize) {
+    (*bip).update(|regions, _| regions.decommit(len));
+}
@@ -0,0 +1,86 @@
+//! Flow control from buffer occupancy.
+//!
+//! [`Watermarks`] turns the number of committed elements into [`FlowEvent`]s with
+//! hysteresis: the sender is stopped once occupancy reaches the high watermark, and started
+//! again only after it falls to the low one. Each event is reported once, which maps directly
+//! onto sending XOFF/XON or driving RTS.
+//!
+//! ```rust
+//! use staticbip::{flow::{FlowEvent, Watermarks}, StaticBip};
+//!
+//! let mut buffer = StaticBip::<u8, 8>::default();
+//! let mut watermarks = Watermarks::new(2, 6);
+//!
+//! buffer.extend(&[0; 6]);
+//! assert_eq!(buffer.flow(&mut watermarks), Some(FlowEvent::Stop));
+//! assert_eq!(buffer.flow(&mut watermarks), None);
+//!
+//! buffer.decommit(3);
+//! assert_eq!(buffer.flow(&mut watermarks), None);
+//! buffer.decommit(1);
+//! assert_eq!(buffer.flow(&mut watermarks), Some(FlowEvent::Start));
+//! ```
+
+use crate::StaticBip;
+
+/// A change in whether the sender should transmit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FlowEvent {
+    /// Occupancy reached the high watermark; the sender should pause.
+    Stop,
+    /// Occupancy fell to the low watermark; the sender may resume.
+    Start,
+}
+
+/// High and low occupancy thresholds with the current flow state.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Watermarks {
+    low: usize,
+    high: usize,
+    stopped: bool,
+}
+
+impl Watermarks {
+    /// Creates thresholds that stop at `high` elements and start again at `low`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `low` is not below `high`.
+    #[inline]
+    pub const fn new(low: usize, high: usize) -> Self {
+        assert!(low < high, "low watermark must be below the high watermark");
+        Self {
+            low,
+            high,
+            stopped: false,
+        }
+    }
+
+    /// Whether the sender is currently stopped.
+    #[inline]
+    pub fn is_stopped(&self) -> bool {
+        self.stopped
+    }
+
+    /// Updates the flow state for `occupancy` elements, returning the event if it changed.
+    #[inline]
+    pub fn update(&mut self, occupancy: usize) -> Option<FlowEvent> {
+        if !self.stopped && occupancy >= self.high {
+            self.stopped = true;
+            Some(FlowEvent::Stop)
+        } else if self.stopped && occupancy <= self.low {
+            self.stopped = false;
+            Some(FlowEvent::Start)
+        } else {
+            None
+        }
+    }
+}
+
+impl<T, const CAP: usize> StaticBip<T, CAP> {
+    /// Updates `watermarks` with the number of committed elements.
+    #[inline]
+    pub fn flow(&self, watermarks: &mut Watermarks) -> Option<FlowEvent> {
+        watermarks.update(self.committed())
+    }
+}
@@ -0,0 +1,336 @@
+//! Length-prefixed frames over byte buffers.
+//!
+//! Each frame is written through a single reservation, so it always sits inside one region
+//! and can be read back as a contiguous slice. A frame is laid out as a little-endian `u16`
+//! payload length, the payload, and an optional checksum selected by the [`Checksum`]
+//! parameter of [`Framed`].
+//!
+//! ```rust
+//! use staticbip::{StaticBip, frame::{Ccitt, Crc16, Framed}};
+//!
+//! let mut buffer = StaticBip::<u8, 32>::default();
+//! let mut framed = Framed::<_, Crc16<Ccitt>>::new(&mut buffer);
+//!
+//! framed.reserve(5).copy_from_slice(b"hello");
+//! framed.commit(5);
+//!
+//! assert_eq!(framed.read().unwrap().unwrap(), b"hello");
+//! framed.decommit();
+//! assert_eq!(framed.read(), None);
+//! ```
+
+use crate::StaticBip;
+use core::{
+    fmt,
+    marker::PhantomData,
+    ops::{Deref, DerefMut, Range},
+};
+
+/// Size of the length prefix in bytes.
+pub const HEADER_LEN: usize = 2;
+
+/// Largest payload a single frame can carry.
+pub const MAX_PAYLOAD: usize = u16::MAX as usize;
+
+/// A checksum appended to each frame and verified when it is read.
+///
+/// `()` disables checksumming.
+pub trait Checksum {
+    /// Number of bytes the checksum occupies in a frame.
+    const LEN: usize;
+
+    /// Computes the checksum of `data` into `out`, which is exactly [`LEN`](Self::LEN) bytes long.
+    fn compute(data: &[u8], out: &mut [u8]);
+
+    /// Whether `checksum` matches `data`.
+    fn verify(data: &[u8], checksum: &[u8]) -> bool;
+}
+
+impl Checksum for () {
+    const LEN: usize = 0;
+
+    #[inline]
+    fn compute(_: &[u8], _: &mut [u8]) {}
+
+    #[inline]
+    fn verify(_: &[u8], _: &[u8]) -> bool {
+        true
+    }
+}
+
+/// Parameters of a CRC-16 variant.
+pub trait Crc16Poly {
+    /// Generator polynomial, most significant bit first.
+    const POLY: u16;
+    /// Initial register value.
+    const INIT: u16;
+    /// Value XORed into the final register.
+    const XOROUT: u16 = 0;
+}
+
+/// CRC-16 with the polynomial chosen by `P`, stored big-endian after the payload.
+///
+/// The checksum covers the length prefix as well as the payload.
+pub struct Crc16<P>(PhantomData<P>);
+
+impl<P: Crc16Poly> Crc16<P> {
+    /// Computes the CRC of `data`.
+    pub fn checksum(data: &[u8]) -> u16 {
+        let mut crc = P::INIT;
+        for &byte in data {
+            crc ^= u16::from(byte) << 8;
+            for _ in 0..8 {
+                crc = if crc & 0x8000 != 0 {
+                    (crc << 1) ^ P::POLY
+                } else {
+                    crc << 1
+                };
+            }
+        }
+        crc ^ P::XOROUT
+    }
+}
+
+impl<P: Crc16Poly> Checksum for Crc16<P> {
+    const LEN: usize = 2;
+
+    #[inline]
+    fn compute(data: &[u8], out: &mut [u8]) {
+        out.copy_from_slice(&Self::checksum(data).to_be_bytes());
+    }
+
+    #[inline]
+    fn verify(data: &[u8], checksum: &[u8]) -> bool {
+        checksum == Self::checksum(data).to_be_bytes()
+    }
+}
+
+/// CRC-16/CCITT-FALSE (`0x1021`, initial value `0xFFFF`).
+pub struct Ccitt;
+
+impl Crc16Poly for Ccitt {
+    const POLY: u16 = 0x1021;
+    const INIT: u16 = 0xFFFF;
+}
+
+/// Reasons a committed frame could not be returned.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FrameError {
+    /// The length prefix points past the committed data.
+    Length,
+    /// The checksum does not match the frame contents.
+    Checksum,
+}
+
+impl fmt::Display for FrameError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Length => "frame length exceeds committed data",
+            Self::Checksum => "frame checksum mismatch",
+        })
+    }
+}
+
+/// Frame-oriented view of a byte buffer.
+pub struct Framed<'a, B, C = ()> {
+    bip: &'a mut B,
+    checksum: PhantomData<C>,
+}
+
+impl<'a, C: Checksum, const CAP: usize> Framed<'a, StaticBip<u8, CAP>, C> {
+    /// Bytes each frame occupies in addition to its payload.
+    pub const OVERHEAD: usize = HEADER_LEN + C::LEN;
+
+    /// Wraps a byte buffer.
+    #[inline]
+    pub fn new(bip: &'a mut StaticBip<u8, CAP>) -> Self {
+        Self {
+            bip,
+            checksum: PhantomData,
+        }
+    }
+
+    /// Returns a mutable payload buffer of up to `max_len` bytes for the next frame.
+    ///
+    /// The buffer is empty if there is no room for the frame overhead.
+    pub fn reserve(&mut self, max_len: usize) -> &mut [u8] {
+        let max_len = max_len.min(MAX_PAYLOAD);
+        let reserved = self.bip.reserve(Self::OVERHEAD + max_len);
+        let payload = reserved.len().saturating_sub(Self::OVERHEAD);
+        reserved
+            .get_mut(HEADER_LEN..HEADER_LEN + payload)
+            .unwrap_or_default()
+    }
+
+    /// Completes the frame with the first `len` bytes of the payload.
+    ///
+    /// If a `len` of `0` is passed in, the reservation will be cleared without writing a frame.
+    pub fn commit(&mut self, len: usize) {
+        let reserved = self.bip.reserved();
+        if len == 0 || reserved < Self::OVERHEAD {
+            self.bip.commit(0);
+            return;
+        }
+
+        let len = len.min(reserved - Self::OVERHEAD);
+        let start = self.bip.regions.reservation().start;
+        let frame = self.bip.slice_mut(start..start + Self::OVERHEAD + len);
+        frame[..HEADER_LEN].copy_from_slice(&(len as u16).to_le_bytes());
+        let (data, checksum) = frame.split_at_mut(HEADER_LEN + len);
+        C::compute(data, checksum);
+        self.bip.commit(Self::OVERHEAD + len);
+    }
+
+    /// Returns the payload of the oldest committed frame.
+    ///
+    /// Returns `None` if there are no frames, and an error if the frame is corrupt.
+    /// The frame stays in the buffer until [`decommit`](Self::decommit) is called.
+    pub fn read(&mut self) -> Option<Result<&mut [u8], FrameError>> {
+        let block = self.bip.read();
+        if block.is_empty() {
+            return None;
+        }
+        let end = match frame_len::<C>(block) {
+            Some(end) => end,
+            None => return Some(Err(FrameError::Length)),
+        };
+
+        let (data, checksum) = block[..end].split_at_mut(end - C::LEN);
+        if !C::verify(data, checksum) {
+            return Some(Err(FrameError::Checksum));
+        }
+        Some(Ok(&mut data[HEADER_LEN..]))
+    }
+
+    /// Removes the oldest committed frame.
+    ///
+    /// A frame with a corrupt length discards the rest of the contiguous block.
+    pub fn decommit(&mut self) {
+        let block = self.bip.read();
+        let len = frame_len::<C>(block).unwrap_or(block.len());
+        self.bip.decommit(len);
+    }
+
+    /// Returns the oldest complete frame, which is removed when the returned guard drops.
+    ///
+    /// Returns `None` if there are no frames, or only the start of one whose remaining bytes
+    /// have not been committed yet, as when frames arrive through [`read_from`]. A corrupt
+    /// frame is removed as by [`decommit`](Self::decommit) and reported as an error, so
+    /// looping until `None` drains every complete frame. As with [`read`](Self::read), a frame
+    /// split across the end of the backing store is reported as [`FrameError::Length`].
+    ///
+    /// ```rust
+    /// use staticbip::{StaticBip, frame::Framed};
+    ///
+    /// let mut buffer = StaticBip::<u8, 16>::default();
+    /// // Two frames and the header of a third, as received from a transport
+    /// buffer.extend(&[1, 0, b'a', 2, 0, b'b', b'c', 3, 0]);
+    ///
+    /// let mut framed = Framed::<_>::new(&mut buffer);
+    /// while let Some(frame) = framed.next_frame() {
+    ///     let frame = frame.unwrap();
+    ///     assert!(frame.len() < 3);
+    /// }
+    /// assert_eq!(buffer.committed(), 2);
+    /// ```
+    ///
+    /// [`read_from`]: StaticBip::read_from
+    pub fn next_frame(&mut self) -> Option<Result<Frame<'_, CAP>, FrameError>> {
+        let a = self.bip.regions.a();
+        let last = self.bip.committed() == a.len();
+        let block = self.bip.read();
+        let end = match complete::<C>(block, last, CAP)? {
+            Ok(end) => end,
+            Err(err) => {
+                self.decommit();
+                return Some(Err(err));
+            }
+        };
+
+        let (data, checksum) = block[..end].split_at(end - C::LEN);
+        if !C::verify(data, checksum) {
+            self.bip.decommit(end);
+            return Some(Err(FrameError::Checksum));
+        }
+        Some(Ok(Frame {
+            bip: self.bip,
+            payload: a.start + HEADER_LEN..a.start + end - C::LEN,
+            len: end,
+        }))
+    }
+}
+
+/// A complete frame returned by [`Framed::next_frame`], dereferencing to its payload.
+///
+/// The frame is removed from the buffer when the guard drops.
+pub struct Frame<'b, const CAP: usize> {
+    bip: &'b mut StaticBip<u8, CAP>,
+    /// Payload within the backing store
+    payload: Range<usize>,
+    /// Size of the whole frame
+    len: usize,
+}
+
+impl<const CAP: usize> Deref for Frame<'_, CAP> {
+    type Target = [u8];
+
+    #[inline]
+    fn deref(&self) -> &[u8] {
+        self.bip.slice(self.payload.clone())
+    }
+}
+
+impl<const CAP: usize> DerefMut for Frame<'_, CAP> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut [u8] {
+        self.bip.slice_mut(self.payload.clone())
+    }
+}
+
+impl<const CAP: usize> Drop for Frame<'_, CAP> {
+    #[inline]
+    fn drop(&mut self) {
+        self.bip.decommit(self.len);
+    }
+}
+
+impl<const CAP: usize> fmt::Debug for Frame<'_, CAP> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Frame").field(&&**self).finish()
+    }
+}
+
+/// Total size of the complete frame at the start of `block`.
+///
+/// `last` is whether no committed data follows the block, in which case a frame running past
+/// its end is still being received, unless it could never fit a backing store of `capacity`.
+fn complete<C: Checksum>(
+    block: &[u8],
+    last: bool,
+    capacity: usize,
+) -> Option<Result<usize, FrameError>> {
+    if block.is_empty() {
+        return None;
+    }
+    match frame_len::<C>(block) {
+        Some(end) => Some(Ok(end)),
+        None if last && declared_len::<C>(block).is_none_or(|len| len <= capacity) => None,
+        None => Some(Err(FrameError::Length)),
+    }
+}
+
+/// Total size of the frame at the start of `block` according to its header.
+fn declared_len<C: Checksum>(block: &[u8]) -> Option<usize> {
+    let header = block.get(..HEADER_LEN)?;
+    Some(HEADER_LEN + usize::from(u16::from_le_bytes([header[0], header[1]])) + C::LEN)
+}
+
+/// Total size of the frame at the start of `block`, if it fits.
+fn frame_len<C: Checksum>(block: &[u8]) -> Option<usize> {
+    let len = declared_len::<C>(block)?;
+    if len <= block.len() {
+        Some(len)
+    } else {
+        None
+    }
+}
@@ -0,0 +1,125 @@
+//! Buffers in varied states built from fuzzer input.
+//!
+//! [`StaticBip::from_fuzz`] turns raw fuzzer bytes into a buffer that may be empty,
+//! contiguous or wrapped, with or without an active reservation.
+//! An `arbitrary::Arbitrary` implementation forwards its input bytes here.
+//!
+//! [`ops`] decodes the same kind of input into a sequence of [`Op`]s, which drive a buffer
+//! through realistic wrap and fragmentation scenarios. Property tests generate a byte
+//! vector and map it through either function, so no strategy code has to be written.
+//!
+//! ```rust
+//! use staticbip::{fuzz, StaticBip};
+//!
+//! let mut buffer = StaticBip::from_fuzz([0u8; 16], &[2, 9, 0, 3, 0, 1, 0, 2]);
+//! for op in fuzz::ops(16, &[0, 12, 0, 1, 12, 0, 2, 5, 0]) {
+//!     buffer.apply(op);
+//! }
+//! assert!(buffer.state().is_valid(16));
+//! ```
+
+use crate::{regions::Regions, BipState, StaticBip};
+
+/// Fuzzer input consumed as bounded numbers.
+struct Input<'a>(core::slice::Iter<'a, u8>);
+
+impl Input<'_> {
+    /// A number in `0..=max`, or `0` once the input runs out.
+    fn up_to(&mut self, max: usize) -> usize {
+        let low = self.0.next().copied().unwrap_or(0);
+        let high = self.0.next().copied().unwrap_or(0);
+        usize::from(u16::from_le_bytes([low, high])) % (max + 1)
+    }
+}
+
+/// A buffer operation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Op {
+    /// [`StaticBip::reserve`]
+    Reserve(usize),
+    /// [`StaticBip::commit`]
+    Commit(usize),
+    /// [`StaticBip::decommit`]
+    Decommit(usize),
+    /// [`StaticBip::pop`]
+    Pop,
+}
+
+/// Decodes fuzzer input into operations on a buffer of `capacity` elements.
+pub fn ops(capacity: usize, data: &[u8]) -> impl Iterator<Item = Op> + '_ {
+    data.chunks(3).map(move |chunk| {
+        let mut input = Input(chunk[1..].iter());
+        let len = input.up_to(capacity);
+        match chunk[0] % 4 {
+            0 => Op::Reserve(len),
+            1 => Op::Commit(len),
+            2 => Op::Decommit(len),
+            _ => Op::Pop,
+        }
+    })
+}
+
+impl BipState {
+    /// Builds a valid state for a backing store of `capacity` elements from fuzzer input.
+    pub fn from_fuzz(capacity: usize, data: &[u8]) -> Self {
+        let mut input = Input(data.iter());
+        let mut regions = Regions::new();
+        match input.up_to(2) {
+            // Contiguous
+            1 => {
+                let start = input.up_to(capacity);
+                regions.set_a(start..start + input.up_to(capacity - start));
+            }
+            // Wrapped
+            2 if capacity > 1 => {
+                let start = 1 + input.up_to(capacity - 2);
+                regions.set_a(start..start + input.up_to(capacity - start).max(1));
+                regions.set_b_end(1 + input.up_to(start - 1));
+            }
+            _ => {}
+        }
+        if input.up_to(1) == 1 {
+            let free = regions.free_space(capacity);
+            regions.set_reservation(free.start..free.start + input.up_to(free.len()));
+        }
+        Self::from(&regions)
+    }
+}
+
+impl<T, const CAP: usize> StaticBip<T, CAP> {
+    /// Creates a buffer over `buffer` in a state built from fuzzer input.
+    #[inline]
+    pub fn from_fuzz(buffer: [T; CAP], data: &[u8]) -> Self {
+        Self {
+            buffer,
+            regions: BipState::from_fuzz(CAP, data).into(),
+        }
+    }
+
+    /// Performs `op` on the buffer.
+    ///
+    /// With the `strict` feature, lengths are clamped and an outstanding reservation is
+    /// dropped before reserving again, so arbitrary input does not trip its assertions.
+    pub fn apply(&mut self, op: Op) {
+        #[cfg(feature = "strict")]
+        let op = match op {
+            Op::Reserve(count) => {
+                self.commit(0);
+                Op::Reserve(count)
+            }
+            Op::Commit(len) => Op::Commit(len.min(self.reserved())),
+            Op::Decommit(len) => Op::Decommit(len.min(self.regions.a().len())),
+            Op::Pop => Op::Pop,
+        };
+        match op {
+            Op::Reserve(count) => {
+                self.reserve(count);
+            }
+            Op::Commit(len) => self.commit(len),
+            Op::Decommit(len) => self.decommit(len),
+            Op::Pop => {
+                self.pop();
+            }
+        }
+    }
+}
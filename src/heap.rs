@@ -0,0 +1,338 @@
+//! Bip-Buffer over heap storage from a chosen allocator.
+//!
+//! The capacity of a [`HeapBip`] is picked at runtime and its storage is allocated once, when
+//! the buffer is created, from the [`Allocator`] it is given. Real-time code can pass an
+//! allocator over a dedicated arena or pool, so the allocation happens up front and from a
+//! known region; [`Global`] uses the global allocator.
+//!
+//! ```rust
+//! use staticbip::heap::HeapBip;
+//!
+//! let mut buffer = HeapBip::<u8>::with_capacity(4);
+//!
+//! buffer.reserve(2).copy_from_slice(&[1, 2]);
+//! buffer.commit(2);
+//! assert_eq!(buffer.read(), &[1, 2]);
+//! ```
+
+use crate::{range_mut, regions::Regions, BipBuf, BipState, RawParts};
+use alloc::alloc::{alloc, dealloc, handle_alloc_error};
+use core::{
+    alloc::Layout,
+    fmt, mem,
+    ops::Range,
+    ptr::{self, NonNull},
+};
+
+/// Source of the storage of a [`HeapBip`].
+///
+/// This has the shape of the unstable `core::alloc::Allocator` trait, reduced to what the
+/// buffer needs, so arena and pool allocators are adapted in a few lines.
+///
+/// # Safety
+///
+/// A block returned by [`allocate`](Self::allocate) must be valid for `layout` and stay
+/// valid until it is passed to [`deallocate`](Self::deallocate) with the same layout.
+pub unsafe trait Allocator {
+    /// Allocates a block for `layout`, which has a non-zero size.
+    ///
+    /// Returns `None` if the allocator is exhausted.
+    fn allocate(&self, layout: Layout) -> Option<NonNull<u8>>;
+
+    /// Frees a block returned by [`allocate`](Self::allocate).
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must have been allocated by this allocator with `layout` and not freed since.
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout);
+}
+
+unsafe impl<A: Allocator + ?Sized> Allocator for &A {
+    #[inline]
+    fn allocate(&self, layout: Layout) -> Option<NonNull<u8>> {
+        (**self).allocate(layout)
+    }
+
+    #[inline]
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        (**self).deallocate(ptr, layout)
+    }
+}
+
+/// The global allocator.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Global;
+
+unsafe impl Allocator for Global {
+    #[inline]
+    fn allocate(&self, layout: Layout) -> Option<NonNull<u8>> {
+        // SAFETY: callers pass a layout with a non-zero size.
+        NonNull::new(unsafe { alloc(layout) })
+    }
+
+    #[inline]
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        dealloc(ptr.as_ptr(), layout)
+    }
+}
+
+/// The allocator could not provide the storage.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AllocError;
+
+impl fmt::Display for AllocError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("storage allocation failed")
+    }
+}
+
+/// A Bip-Buffer over storage allocated from `A`.
+pub struct HeapBip<T, A: Allocator = Global> {
+    /// Region bookkeeping
+    regions: Regions,
+    /// Backing store, `capacity` initialized elements
+    ptr: NonNull<T>,
+    /// Size of the backing store
+    capacity: usize,
+    /// Allocator owning the backing store
+    alloc: A,
+}
+
+// SAFETY: the buffer owns its elements like a `Box<[T]>` does.
+unsafe impl<T: Send, A: Allocator + Send> Send for HeapBip<T, A> {}
+// SAFETY: shared access only reads the elements.
+unsafe impl<T: Sync, A: Allocator + Sync> Sync for HeapBip<T, A> {}
+
+impl<T: Default> HeapBip<T> {
+    /// Creates a buffer of `capacity` default elements from the global allocator.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the storage is larger than `isize::MAX` bytes, and aborts if the allocation
+    /// fails.
+    pub fn with_capacity(capacity: usize) -> Self {
+        let layout = Layout::array::<T>(capacity).expect("capacity overflow");
+        Self::try_with_capacity_in(capacity, Global).unwrap_or_else(|_| handle_alloc_error(layout))
+    }
+}
+
+impl<T: Default, A: Allocator> HeapBip<T, A> {
+    /// Creates a buffer of `capacity` default elements from `alloc`.
+    ///
+    /// Fails if `alloc` cannot provide the storage or it is larger than `isize::MAX` bytes.
+    pub fn try_with_capacity_in(capacity: usize, alloc: A) -> Result<Self, AllocError> {
+        let layout = Layout::array::<T>(capacity).map_err(|_| AllocError)?;
+        let ptr: NonNull<T> = if layout.size() == 0 {
+            NonNull::dangling()
+        } else {
+            alloc.allocate(layout).ok_or(AllocError)?.cast()
+        };
+        let mut filling = Filling {
+            ptr,
+            initialized: 0,
+            layout,
+            alloc: &alloc,
+        };
+        while filling.initialized < capacity {
+            // SAFETY: the index is within the block just allocated for `capacity` elements.
+            unsafe { ptr.as_ptr().add(filling.initialized).write(T::default()) };
+            filling.initialized += 1;
+        }
+        mem::forget(filling);
+        Ok(Self {
+            regions: Regions::new(),
+            ptr,
+            capacity,
+            alloc,
+        })
+    }
+}
+
+/// Storage being filled with default elements, released if `T::default` panics.
+struct Filling<'a, T, A: Allocator> {
+    ptr: NonNull<T>,
+    /// Number of elements written at the start of the block
+    initialized: usize,
+    layout: Layout,
+    alloc: &'a A,
+}
+
+impl<T, A: Allocator> Drop for Filling<'_, T, A> {
+    fn drop(&mut self) {
+        let elements = ptr::slice_from_raw_parts_mut(self.ptr.as_ptr(), self.initialized);
+        // SAFETY: the first `initialized` elements were written and are not used again.
+        unsafe { elements.drop_in_place() };
+        if self.layout.size() != 0 {
+            // SAFETY: the block was allocated from `alloc` with this layout.
+            unsafe { self.alloc.deallocate(self.ptr.cast(), self.layout) };
+        }
+    }
+}
+
+impl<T, A: Allocator> HeapBip<T, A> {
+    /// Returns the allocator.
+    #[inline]
+    pub fn allocator(&self) -> &A {
+        &self.alloc
+    }
+
+    /// Size of the backing store.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Returns the elements in `range` of the backing store mutably, or an empty slice if
+    /// `range` does not fit it.
+    ///
+    /// Only the elements in `range` are borrowed, as for
+    /// [`StaticBip`](crate::StaticBip).
+    #[inline]
+    fn slice_mut(&mut self, range: Range<usize>) -> &mut [T] {
+        // SAFETY: the block holds `capacity` initialized elements owned by the buffer, and
+        // `self` borrows them mutably for the returned lifetime.
+        unsafe { range_mut(self.ptr.as_ptr(), self.capacity, range) }
+    }
+
+    /// Returns the region layout, without the data.
+    #[inline]
+    pub fn state(&self) -> BipState {
+        BipState::from(&self.regions)
+    }
+
+    /// Returns the base pointer of the backing store along with the region layout.
+    #[inline]
+    pub fn as_raw_parts(&mut self) -> RawParts<T> {
+        RawParts {
+            ptr: self.ptr.as_ptr(),
+            capacity: self.capacity,
+            state: BipState::from(&self.regions),
+        }
+    }
+
+    /// Number of committed elements.
+    #[inline]
+    pub fn committed(&self) -> usize {
+        self.regions.committed()
+    }
+
+    /// Number of reserved elements.
+    #[inline]
+    pub fn reserved(&self) -> usize {
+        self.regions.reserved()
+    }
+
+    /// Whether any space has been reserved or committed in the buffer.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.reserved() == 0 && self.committed() == 0
+    }
+
+    /// Clears all regions and reservations.
+    ///
+    /// Data in the underlying buffer is unchanged.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.regions.clear();
+    }
+
+    /// Returns a mutable buffer containing up to maximum slots for storing data.
+    #[inline]
+    pub fn reserve_max(&mut self) -> &mut [T] {
+        self.reserve(self.capacity)
+    }
+
+    /// Returns a mutable buffer containing up to `count` slots for storing data.
+    #[inline]
+    pub fn reserve(&mut self, count: usize) -> &mut [T] {
+        let reserve = self.regions.reserve(self.capacity, count);
+        self.slice_mut(reserve)
+    }
+
+    /// Commits the data in the reservation, allowing it to be read later.
+    ///
+    /// If a `len` of `0` is passed in, the reservation will be cleared without making any other changes.
+    #[inline]
+    pub fn commit(&mut self, len: usize) {
+        self.regions.commit(len);
+    }
+
+    /// Retrieves available (committed) data as a contiguous block.
+    #[inline]
+    pub fn read(&mut self) -> &mut [T] {
+        self.slice_mut(self.regions.a())
+    }
+
+    /// Marks the first `len` elements of the available data as seen.
+    #[inline]
+    pub fn decommit(&mut self, len: usize) {
+        self.regions.decommit(len);
+    }
+
+    /// Removes the oldest committed element and returns it.
+    #[inline]
+    pub fn pop(&mut self) -> Option<&mut T> {
+        let index = self.regions.pop()?;
+        self.slice_mut(index..index + 1).first_mut()
+    }
+}
+
+impl<T, A: Allocator> Drop for HeapBip<T, A> {
+    fn drop(&mut self) {
+        let elements = ptr::slice_from_raw_parts_mut(self.ptr.as_ptr(), self.capacity);
+        // SAFETY: the elements are initialized and not used again.
+        unsafe { elements.drop_in_place() };
+
+        let layout = Layout::array::<T>(self.capacity).unwrap();
+        if layout.size() != 0 {
+            // SAFETY: the block was allocated from `alloc` with this layout.
+            unsafe { self.alloc.deallocate(self.ptr.cast(), layout) };
+        }
+    }
+}
+
+impl<T: fmt::Debug, A: Allocator> fmt::Debug for HeapBip<T, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HeapBip")
+            .field("regions", &self.regions)
+            .field("capacity", &self.capacity)
+            .finish()
+    }
+}
+
+impl<T, A: Allocator> BipBuf<T> for HeapBip<T, A> {
+    #[inline]
+    fn capacity(&self) -> usize {
+        self.capacity()
+    }
+
+    #[inline]
+    fn committed(&self) -> usize {
+        self.committed()
+    }
+
+    #[inline]
+    fn reserved(&self) -> usize {
+        self.reserved()
+    }
+
+    #[inline]
+    fn reserve(&mut self, count: usize) -> &mut [T] {
+        self.reserve(count)
+    }
+
+    #[inline]
+    fn commit(&mut self, len: usize) {
+        self.commit(len)
+    }
+
+    #[inline]
+    fn read(&mut self) -> &mut [T] {
+        self.read()
+    }
+
+    #[inline]
+    fn decommit(&mut self, len: usize) {
+        self.decommit(len)
+    }
+}
@@ -0,0 +1,84 @@
+//! Hex dumps of committed bytes.
+//!
+//! [`StaticBip::hexdump`] formats the committed bytes in the layout of `hexdump -C`: sixteen
+//! bytes per line after their logical offset, followed by the printable ones as ASCII. Offsets
+//! count from the oldest committed byte, so data that wraps to the start of the backing store
+//! reads as one continuous dump.
+//!
+//! ```rust
+//! use staticbip::StaticBip;
+//!
+//! let mut buffer = StaticBip::<u8, 32>::default();
+//! buffer.extend(b"0123456789abcdef\x00\xff!");
+//!
+//! assert_eq!(
+//!     buffer.hexdump().to_string(),
+//!     "00000000  30 31 32 33 34 35 36 37  38 39 61 62 63 64 65 66  |0123456789abcdef|\n\
+//!      00000010  00 ff 21                                          |..!|\n"
+//! );
+//! ```
+
+use crate::StaticBip;
+use core::fmt;
+
+/// Bytes per line of a dump
+const LINE: usize = 16;
+
+/// Committed bytes formatted as a hex dump.
+///
+/// Created by [`StaticBip::hexdump`].
+#[derive(Debug, Clone, Copy)]
+pub struct HexDump<'a> {
+    a: &'a [u8],
+    b: &'a [u8],
+}
+
+impl<const CAP: usize> StaticBip<u8, CAP> {
+    /// Returns the committed bytes, oldest first, formatted as a hex dump.
+    #[inline]
+    pub fn hexdump(&self) -> HexDump<'_> {
+        let (a, b) = self.as_slices();
+        HexDump { a, b }
+    }
+}
+
+impl fmt::Display for HexDump<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut bytes = self.a.iter().chain(self.b).copied();
+        let mut offset = 0;
+        loop {
+            let mut line = [0; LINE];
+            let len = line
+                .iter_mut()
+                .zip(&mut bytes)
+                .map(|(slot, byte)| *slot = byte)
+                .count();
+            if len == 0 {
+                return Ok(());
+            }
+
+            write!(f, "{:08x} ", offset)?;
+            for (index, byte) in line.iter().enumerate() {
+                if index % 8 == 0 {
+                    f.write_str(" ")?;
+                }
+                if index < len {
+                    write!(f, "{:02x} ", byte)?;
+                } else {
+                    f.write_str("   ")?;
+                }
+            }
+            f.write_str(" |")?;
+            for &byte in &line[..len] {
+                let c = if byte.is_ascii_graphic() || byte == b' ' {
+                    char::from(byte)
+                } else {
+                    '.'
+                };
+                fmt::Write::write_char(f, c)?;
+            }
+            f.write_str("|\n")?;
+            offset += len;
+        }
+    }
+}
@@ -0,0 +1,155 @@
+//! Callbacks on buffer activity.
+//!
+//! [`Hooked`] wraps any [`BipBuf`] and reports commits, decommits and rejected reservations
+//! to a [`Hooks`] implementation, so counters, indicators or task wakeups are wired up in one
+//! place. All callbacks default to doing nothing, and `()` implements none of them, so unused
+//! callbacks compile away.
+//!
+//! ```rust
+//! use staticbip::{hook::{Hooked, Hooks}, BipBuf, StaticBip};
+//!
+//! #[derive(Default)]
+//! struct Stats {
+//!     written: usize,
+//!     rejected: usize,
+//! }
+//!
+//! impl Hooks for Stats {
+//!     fn on_commit(&mut self, len: usize) {
+//!         self.written += len;
+//!     }
+//!
+//!     fn on_reject(&mut self, _: usize) {
+//!         self.rejected += 1;
+//!     }
+//! }
+//!
+//! let mut buffer = Hooked::new(StaticBip::<u8, 4>::default(), Stats::default());
+//! buffer.reserve(4);
+//! buffer.commit(4);
+//! assert!(buffer.reserve(1).is_empty());
+//!
+//! assert_eq!(buffer.hooks().written, 4);
+//! assert_eq!(buffer.hooks().rejected, 1);
+//! ```
+
+use crate::BipBuf;
+use core::marker::PhantomData;
+
+/// Callbacks invoked by a [`Hooked`] buffer.
+pub trait Hooks {
+    /// Called after `len` elements have been committed.
+    #[inline]
+    fn on_commit(&mut self, len: usize) {
+        let _ = len;
+    }
+
+    /// Called after `len` elements have been decommitted.
+    #[inline]
+    fn on_decommit(&mut self, len: usize) {
+        let _ = len;
+    }
+
+    /// Called when a reservation of `len` elements got no space at all.
+    #[inline]
+    fn on_reject(&mut self, len: usize) {
+        let _ = len;
+    }
+}
+
+impl Hooks for () {}
+
+/// A buffer reporting its activity to hooks.
+#[derive(Debug)]
+pub struct Hooked<T, B, H> {
+    bip: B,
+    hooks: H,
+    item: PhantomData<fn() -> T>,
+}
+
+impl<T, B: BipBuf<T>, H: Hooks> Hooked<T, B, H> {
+    /// Attaches `hooks` to `bip`.
+    #[inline]
+    pub fn new(bip: B, hooks: H) -> Self {
+        Self {
+            bip,
+            hooks,
+            item: PhantomData,
+        }
+    }
+
+    /// Returns the hooks.
+    #[inline]
+    pub fn hooks(&self) -> &H {
+        &self.hooks
+    }
+
+    /// Returns the hooks mutably.
+    #[inline]
+    pub fn hooks_mut(&mut self) -> &mut H {
+        &mut self.hooks
+    }
+
+    /// Returns the wrapped buffer.
+    #[inline]
+    pub fn get_ref(&self) -> &B {
+        &self.bip
+    }
+
+    /// Returns the wrapped buffer and the hooks.
+    #[inline]
+    pub fn into_inner(self) -> (B, H) {
+        (self.bip, self.hooks)
+    }
+}
+
+impl<T, B: BipBuf<T>, H: Hooks> BipBuf<T> for Hooked<T, B, H> {
+    #[inline]
+    fn capacity(&self) -> usize {
+        self.bip.capacity()
+    }
+
+    #[inline]
+    fn committed(&self) -> usize {
+        self.bip.committed()
+    }
+
+    #[inline]
+    fn reserved(&self) -> usize {
+        self.bip.reserved()
+    }
+
+    #[inline]
+    fn reserve(&mut self, count: usize) -> &mut [T] {
+        let reserved = self.bip.reserve(count);
+        if reserved.is_empty() && count != 0 {
+            self.hooks.on_reject(count);
+        }
+        reserved
+    }
+
+    #[inline]
+    fn commit(&mut self, len: usize) {
+        let before = self.bip.committed();
+        self.bip.commit(len);
+        let len = self.bip.committed() - before;
+        if len != 0 {
+            self.hooks.on_commit(len);
+        }
+    }
+
+    #[inline]
+    fn read(&mut self) -> &mut [T] {
+        self.bip.read()
+    }
+
+    #[inline]
+    fn decommit(&mut self, len: usize) {
+        let before = self.bip.committed();
+        self.bip.decommit(len);
+        let len = before - self.bip.committed();
+        if len != 0 {
+            self.hooks.on_decommit(len);
+        }
+    }
+}
@@ -0,0 +1,192 @@
+//! `std::io` support for byte buffers.
+//!
+//! [`BufRead::fill_buf`] hands out the first contiguous block of committed data, so readers
+//! such as `read_line` and `lines` consume straight from the buffer.
+//!
+//! ```rust
+//! use std::io::BufRead;
+//! use staticbip::StaticBip;
+//!
+//! let mut buffer = StaticBip::<u8, 16>::default();
+//! buffer.extend(b"one\ntwo\n");
+//!
+//! let lines: Vec<String> = buffer.lines().map(Result::unwrap).collect();
+//! assert_eq!(lines, ["one", "two"]);
+//! ```
+//!
+//! [`as_io_slices`](StaticBip::as_io_slices) and
+//! [`as_io_slices_mut`](StaticBip::as_io_slices_mut) cover the committed data and the free
+//! space, so a wrapped buffer is flushed or refilled with a single vectored call.
+//!
+//! ```rust
+//! use std::io::{Read, Write};
+//! use staticbip::StaticBip;
+//!
+//! let mut buffer = StaticBip::<u8, 8>::default();
+//! let received = buffer.read_vectored_from(&mut &b"hello"[..]).unwrap();
+//! assert_eq!(received, 5);
+//!
+//! let mut sent = Vec::new();
+//! assert_eq!(sent.write_vectored(&buffer.as_io_slices()).unwrap(), 5);
+//! assert_eq!(sent, b"hello");
+//! ```
+
+use crate::{reserved::ReservationWriter, StaticBip};
+use core::convert::TryFrom;
+use std::io::{self, BufRead, IoSlice, IoSliceMut, Read, Seek, SeekFrom, Write};
+
+impl<const CAP: usize> StaticBip<u8, CAP> {
+    /// Returns the committed data as up to two slices, in order.
+    #[inline]
+    pub fn as_io_slices(&self) -> [IoSlice<'_>; 2] {
+        let (a, b) = self.as_slices();
+        [IoSlice::new(a), IoSlice::new(b)]
+    }
+
+    /// Returns the free space as up to two slices, in the order they are committed by
+    /// [`commit_vectored`](Self::commit_vectored).
+    ///
+    /// Any existing reservation is cleared.
+    pub fn as_io_slices_mut(&mut self) -> [IoSliceMut<'_>; 2] {
+        self.regions.clear_reservation();
+        let (a, b) = (self.regions.a(), self.regions.b());
+        let (first, second) = if b.is_empty() {
+            self.slices_mut(a.end..CAP, 0..a.start)
+        } else {
+            (self.slice_mut(b.end..a.start), &mut [][..])
+        };
+        [IoSliceMut::new(first), IoSliceMut::new(second)]
+    }
+
+    /// Commits the first `len` elements of the slices returned by
+    /// [`as_io_slices_mut`](Self::as_io_slices_mut).
+    pub fn commit_vectored(&mut self, len: usize) {
+        #[cfg(feature = "stats")]
+        let committed = self.committed();
+        let regions = &mut self.regions;
+        regions.clear_reservation();
+        let (a, b) = (regions.a(), regions.b());
+        if !b.is_empty() {
+            regions.set_b_end((b.end + len).min(a.start));
+        } else {
+            let tail = len.min(CAP - a.end);
+            let wrapped = (len - tail).min(a.start);
+            if a.is_empty() && tail == 0 {
+                // Data only in the second slice becomes region `A`, since `B` must not hold
+                // data while `A` is empty
+                regions.set_a(0..wrapped);
+            } else {
+                regions.set_a(a.start..a.end + tail);
+                regions.set_b_end(wrapped);
+            }
+        }
+        #[cfg(feature = "stats")]
+        {
+            regions.written += (regions.committed() - committed) as u64;
+        }
+    }
+
+    /// Fills the free space with a single vectored read from `reader`.
+    ///
+    /// Returns the number of elements committed.
+    pub fn read_vectored_from(&mut self, reader: &mut impl Read) -> io::Result<usize> {
+        let len = reader.read_vectored(&mut self.as_io_slices_mut())?;
+        self.commit_vectored(len);
+        Ok(len)
+    }
+
+    /// Reads from `reader` into reservations and commits what arrives.
+    ///
+    /// Reading continues into the next reservation, which may wrap to the start, as long as
+    /// each read fills its reservation. It stops at a short read, at the end of the input or
+    /// once the buffer is full. Interrupted reads are retried.
+    ///
+    /// Returns the number of elements committed. An error is only returned if nothing was
+    /// committed.
+    pub fn read_from(&mut self, reader: &mut impl Read) -> io::Result<usize> {
+        let mut total = 0;
+        loop {
+            let reserved = self.reserve_max();
+            let capacity = reserved.len();
+            if capacity == 0 {
+                break;
+            }
+            let len = match reader.read(reserved) {
+                Ok(len) => len,
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => {
+                    self.commit(0);
+                    continue;
+                }
+                Err(err) if total == 0 => {
+                    self.commit(0);
+                    return Err(err);
+                }
+                Err(_) => 0,
+            };
+            self.commit(len);
+            total += len;
+            if len < capacity {
+                break;
+            }
+        }
+        Ok(total)
+    }
+}
+
+impl<const CAP: usize> Read for StaticBip<u8, CAP> {
+    #[inline]
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let block = self.fill_buf()?;
+        let len = block.len().min(buf.len());
+        buf[..len].copy_from_slice(&block[..len]);
+        self.consume(len);
+        Ok(len)
+    }
+}
+
+impl<const CAP: usize> BufRead for StaticBip<u8, CAP> {
+    #[inline]
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        Ok(StaticBip::read(self))
+    }
+
+    #[inline]
+    fn consume(&mut self, amt: usize) {
+        self.decommit(amt)
+    }
+}
+
+impl<const CAP: usize> Write for ReservationWriter<'_, u8, CAP> {
+    #[inline]
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        Ok(ReservationWriter::write(self, buf))
+    }
+
+    #[inline]
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Positions are offsets into the reservation, and [`SeekFrom::End`] is relative to the end
+/// of the furthest write, as for a `Cursor<Vec<u8>>`.
+impl<const CAP: usize> Seek for ReservationWriter<'_, u8, CAP> {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        let (base, offset) = match pos {
+            SeekFrom::Start(pos) => (pos, 0),
+            SeekFrom::End(offset) => (self.written() as u64, offset),
+            SeekFrom::Current(offset) => (self.position() as u64, offset),
+        };
+        let pos = base
+            .checked_add_signed(offset)
+            .and_then(|pos| usize::try_from(pos).ok())
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "invalid seek to a negative or overflowing position",
+                )
+            })?;
+        self.set_position(pos);
+        Ok(pos as u64)
+    }
+}
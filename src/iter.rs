@@ -0,0 +1,151 @@
+//! Iterators over committed data.
+
+use crate::StaticBip;
+use core::{
+    iter::{Chain, FusedIterator, Skip, Take},
+    slice,
+};
+
+/// An iterator over the committed elements of a buffer, oldest first.
+///
+/// Created by [`StaticBip::iter`].
+#[derive(Debug)]
+pub struct Iter<'a, T> {
+    inner: Chain<slice::Iter<'a, T>, slice::Iter<'a, T>>,
+}
+
+impl<T> Clone for Iter<'_, T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<'a, T> Iter<'a, T> {
+    /// Iterates over `a` followed by `b`.
+    #[inline]
+    pub(crate) fn new(a: &'a [T], b: &'a [T]) -> Self {
+        Self {
+            inner: a.iter().chain(b),
+        }
+    }
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    #[inline]
+    fn next(&mut self) -> Option<&'a T> {
+        self.inner.next()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<T> DoubleEndedIterator for Iter<'_, T> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back()
+    }
+}
+
+impl<T> ExactSizeIterator for Iter<'_, T> {}
+
+impl<T> FusedIterator for Iter<'_, T> {}
+
+impl<'a, T, const CAP: usize> IntoIterator for &'a StaticBip<T, CAP> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    #[inline]
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+/// An iterator that removes committed elements by value, oldest first.
+///
+/// Created by [`StaticBip::drain`].
+#[derive(Debug)]
+pub struct Drain<'a, T, const CAP: usize> {
+    bip: &'a mut StaticBip<T, CAP>,
+}
+
+impl<'a, T, const CAP: usize> Drain<'a, T, CAP> {
+    #[inline]
+    pub(crate) fn new(bip: &'a mut StaticBip<T, CAP>) -> Self {
+        Self { bip }
+    }
+}
+
+impl<T: Default, const CAP: usize> Iterator for Drain<'_, T, CAP> {
+    type Item = T;
+
+    #[inline]
+    fn next(&mut self) -> Option<T> {
+        self.bip.pop_value()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.bip.committed();
+        (len, Some(len))
+    }
+}
+
+impl<T: Default, const CAP: usize> ExactSizeIterator for Drain<'_, T, CAP> {}
+
+impl<T: Default, const CAP: usize> FusedIterator for Drain<'_, T, CAP> {}
+
+/// An iterator that moves the committed elements out of a buffer, oldest first.
+///
+/// Created by the [`IntoIterator`] implementation of [`StaticBip`]. Elements that are not
+/// committed are dropped.
+#[derive(Debug)]
+pub struct IntoIter<T, const CAP: usize> {
+    inner: Take<Skip<core::array::IntoIter<T, CAP>>>,
+}
+
+impl<T, const CAP: usize> IntoIterator for StaticBip<T, CAP> {
+    type Item = T;
+    type IntoIter = IntoIter<T, CAP>;
+
+    fn into_iter(mut self) -> Self::IntoIter {
+        self.defragment();
+        let start = self.regions.a().start;
+        let len = self.committed();
+        IntoIter {
+            inner: IntoIterator::into_iter(self.buffer).skip(start).take(len),
+        }
+    }
+}
+
+impl<T, const CAP: usize> Iterator for IntoIter<T, CAP> {
+    type Item = T;
+
+    #[inline]
+    fn next(&mut self) -> Option<T> {
+        self.inner.next()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<T, const CAP: usize> DoubleEndedIterator for IntoIter<T, CAP> {
+    #[inline]
+    fn next_back(&mut self) -> Option<T> {
+        self.inner.next_back()
+    }
+}
+
+impl<T, const CAP: usize> ExactSizeIterator for IntoIter<T, CAP> {}
+
+impl<T, const CAP: usize> FusedIterator for IntoIter<T, CAP> {}
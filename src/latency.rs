@@ -0,0 +1,193 @@
+//! Latency measurement with a cycle counter.
+//!
+//! [`Instrumented`] wraps any [`BipBuf`] and times every reservation, commit and decommit with
+//! a [`CycleCounter`], keeping the minimum, maximum and average per operation. On Cortex-M
+//! the counter is typically `DWT::cycle_count` from the `cortex-m` crate, after enabling
+//! `DWT` cycle counting; any closure returning a wrapping `u32` count works.
+//!
+//! ```rust
+//! use core::cell::Cell;
+//! use staticbip::{latency::Instrumented, BipBuf, StaticBip};
+//!
+//! // A fake counter advancing 10 cycles per read
+//! let cycles = Cell::new(0u32);
+//! let counter = || {
+//!     cycles.set(cycles.get() + 10);
+//!     cycles.get()
+//! };
+//!
+//! let mut buffer = Instrumented::new(StaticBip::<u8, 16>::default(), counter);
+//! buffer.reserve(4);
+//! buffer.commit(4);
+//!
+//! let commit = buffer.latency().commit;
+//! assert_eq!((commit.min(), commit.max(), commit.avg()), (Some(10), Some(10), Some(10)));
+//! ```
+//!
+//! Each measurement includes one read of the counter.
+
+use crate::BipBuf;
+use core::marker::PhantomData;
+
+/// A free-running counter, such as the processor cycle counter.
+pub trait CycleCounter {
+    /// Current count, wrapping on overflow.
+    fn cycles(&self) -> u32;
+}
+
+impl<F: Fn() -> u32> CycleCounter for F {
+    #[inline]
+    fn cycles(&self) -> u32 {
+        self()
+    }
+}
+
+/// Cycle counts of one kind of operation.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct OpStats {
+    min: u32,
+    max: u32,
+    total: u64,
+    count: u32,
+}
+
+impl OpStats {
+    /// Adds a measurement of `cycles`.
+    #[inline]
+    fn record(&mut self, cycles: u32) {
+        if self.count == 0 || cycles < self.min {
+            self.min = cycles;
+        }
+        self.max = self.max.max(cycles);
+        self.total += u64::from(cycles);
+        self.count = self.count.saturating_add(1);
+    }
+
+    /// Number of measurements.
+    #[inline]
+    pub fn count(&self) -> u32 {
+        self.count
+    }
+
+    /// Fewest cycles taken, if anything was measured.
+    #[inline]
+    pub fn min(&self) -> Option<u32> {
+        (self.count != 0).then_some(self.min)
+    }
+
+    /// Most cycles taken, if anything was measured.
+    #[inline]
+    pub fn max(&self) -> Option<u32> {
+        (self.count != 0).then_some(self.max)
+    }
+
+    /// Average cycles taken, rounded down, if anything was measured.
+    #[inline]
+    pub fn avg(&self) -> Option<u32> {
+        (self.count != 0).then(|| (self.total / u64::from(self.count)) as u32)
+    }
+}
+
+/// Cycle counts of the operations of an [`Instrumented`] buffer.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Latency {
+    /// [`reserve`](BipBuf::reserve)
+    pub reserve: OpStats,
+    /// [`commit`](BipBuf::commit)
+    pub commit: OpStats,
+    /// [`decommit`](BipBuf::decommit)
+    pub decommit: OpStats,
+}
+
+/// A buffer timing its operations with a cycle counter.
+#[derive(Debug)]
+pub struct Instrumented<T, B, C> {
+    bip: B,
+    counter: C,
+    latency: Latency,
+    item: PhantomData<fn() -> T>,
+}
+
+impl<T, B: BipBuf<T>, C: CycleCounter> Instrumented<T, B, C> {
+    /// Times the operations on `bip` with `counter`.
+    #[inline]
+    pub fn new(bip: B, counter: C) -> Self {
+        Self {
+            bip,
+            counter,
+            latency: Latency::default(),
+            item: PhantomData,
+        }
+    }
+
+    /// Returns the measurements so far.
+    #[inline]
+    pub fn latency(&self) -> &Latency {
+        &self.latency
+    }
+
+    /// Discards the measurements so far.
+    #[inline]
+    pub fn reset_latency(&mut self) {
+        self.latency = Latency::default();
+    }
+
+    /// Returns the wrapped buffer.
+    #[inline]
+    pub fn get_ref(&self) -> &B {
+        &self.bip
+    }
+
+    /// Returns the wrapped buffer and the counter.
+    #[inline]
+    pub fn into_inner(self) -> (B, C) {
+        (self.bip, self.counter)
+    }
+}
+
+impl<T, B: BipBuf<T>, C: CycleCounter> BipBuf<T> for Instrumented<T, B, C> {
+    #[inline]
+    fn capacity(&self) -> usize {
+        self.bip.capacity()
+    }
+
+    #[inline]
+    fn committed(&self) -> usize {
+        self.bip.committed()
+    }
+
+    #[inline]
+    fn reserved(&self) -> usize {
+        self.bip.reserved()
+    }
+
+    #[inline]
+    fn reserve(&mut self, count: usize) -> &mut [T] {
+        let start = self.counter.cycles();
+        let reserved = self.bip.reserve(count);
+        let cycles = self.counter.cycles().wrapping_sub(start);
+        self.latency.reserve.record(cycles);
+        reserved
+    }
+
+    #[inline]
+    fn commit(&mut self, len: usize) {
+        let start = self.counter.cycles();
+        self.bip.commit(len);
+        let cycles = self.counter.cycles().wrapping_sub(start);
+        self.latency.commit.record(cycles);
+    }
+
+    #[inline]
+    fn read(&mut self) -> &mut [T] {
+        self.bip.read()
+    }
+
+    #[inline]
+    fn decommit(&mut self, len: usize) {
+        let start = self.counter.cycles();
+        self.bip.decommit(len);
+        let cycles = self.counter.cycles().wrapping_sub(start);
+        self.latency.decommit.record(cycles);
+    }
+}
@@ -1,4 +1,4 @@
-#![no_std]
+#![cfg_attr(not(feature = "std"), no_std)]
 
 //! # Static Bip-Buffer
 //!
@@ -34,27 +34,358 @@
 //! // The block should now contain only the last two values
 //! assert_eq!(buffer.read(), &[3]);
 //! ```
+//!
+//! # Element types
+//!
+//! Elements need not be `Copy`. The backing store always holds `CAP` initialized elements,
+//! and an element removed by [`decommit`](StaticBip::decommit) or
+//! [`clear`](StaticBip::clear) is dropped when its slot is overwritten or the buffer is
+//! dropped. Elements that own resources can be released right away with
+//! [`decommit_reset`](StaticBip::decommit_reset) and
+//! [`clear_reset`](StaticBip::clear_reset).
+//!
+//! Zero-sized elements such as `()` make the buffer a queue of up to `CAP` tokens.
+//! Reservations, commits and reads count elements exactly as for other types, no memory is
+//! accessed, and a zero-sized type with a `Drop` implementation is dropped once per slot.
+//!
+//! ```rust
+//! use staticbip::StaticBip;
+//!
+//! let mut tokens = StaticBip::<(), 4>::default();
+//! assert_eq!(tokens.reserve(3).len(), 3);
+//! tokens.commit(3);
+//! tokens.decommit(2);
+//! assert_eq!(tokens.committed(), 1);
+//! ```
+//!
+//! # Real-time use
+//!
+//! The following operations take constant time: they contain no loops, never allocate, never
+//! block or spin, and never drop elements. Each one finishes in a bounded number of steps
+//! regardless of what else the buffer has been through, so they are safe to call from audio
+//! callbacks and hard interrupt handlers.
+//!
+//! - [`try_reserve`](StaticBip::try_reserve), [`reserve`](StaticBip::reserve) and
+//!   [`reserve_max`](StaticBip::reserve_max)
+//! - [`commit`](StaticBip::commit) and [`checked_commit`](StaticBip::checked_commit)
+//! - [`peek`](StaticBip::peek) and [`read`](StaticBip::read)
+//! - [`decommit`](StaticBip::decommit) and [`checked_decommit`](StaticBip::checked_decommit)
+//! - [`committed`](StaticBip::committed), [`reserved`](StaticBip::reserved) and
+//!   [`state`](StaticBip::state)
+//!
+//! Other operations may loop over elements, drop them, or move data around, and are not part
+//! of this guarantee even where they happen to be cheap. The halves in [`bbqueue`] give the
+//! same guarantee between contexts: granting, committing, reading and releasing are
+//! wait-free, with neither locks nor compare-and-swap loops.
+//!
+//! # Aliasing
+//!
+//! The backing store is never borrowed as a whole. Each method derives the slices it hands
+//! out, or works on, from a raw pointer to the start of the store, and so only borrows the
+//! elements in those ranges. A pointer obtained through
+//! [`as_raw_parts`](StaticBip::as_raw_parts) or
+//! [`MpscBip::claimed_ptr`](mpsc::MpscBip::claimed_ptr) therefore stays usable, under the
+//! Tree Borrows model, across calls that do not touch the elements it points to. Under
+//! Stacked Borrows, any `&mut` access to the buffer still invalidates such pointers, since
+//! the backing store is stored inline. The concurrent halves in [`bbqueue`] share their
+//! storage through an `UnsafeCell` and never hold references to each other's ranges.
+//!
 //! [1]: https://www.codeproject.com/articles/3479/the-bip-buffer-the-circular-buffer-with-a-twist
 
-use core::ops::Range;
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+use core::{
+    array, fmt,
+    hash::{Hash, Hasher},
+    mem::{self, MaybeUninit},
+    ops::{
+        Bound, Index, IndexMut, Range, RangeBounds, RangeFrom, RangeFull, RangeInclusive, RangeTo,
+        RangeToInclusive,
+    },
+    ptr,
+};
+use regions::Regions;
+
+pub mod align;
+pub mod bbqueue;
+pub mod broadcast;
+pub mod builder;
+mod bytes;
+pub mod cast;
+#[cfg(target_has_atomic = "ptr")]
+pub mod cell;
+pub mod chain;
+#[cfg(target_has_atomic = "ptr")]
+pub mod channel;
+pub mod codec;
+pub mod crash;
+pub mod cursor;
+pub mod defmt;
+mod deque;
+pub mod device;
+#[cfg(target_has_atomic = "ptr")]
+pub mod duplex;
+mod error;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+pub mod flow;
+pub mod frame;
+#[cfg(feature = "fuzzing")]
+pub mod fuzz;
+#[cfg(feature = "alloc")]
+pub mod heap;
+#[cfg(feature = "hexdump")]
+pub mod hexdump;
+pub mod hook;
+#[cfg(feature = "std")]
+mod io;
+pub mod iter;
+#[cfg(feature = "latency")]
+pub mod latency;
+mod line;
+pub mod local;
+pub mod logger;
+#[cfg(target_has_atomic = "ptr")]
+pub mod loopback;
+#[cfg(all(
+    feature = "std",
+    target_os = "linux",
+    any(target_arch = "x86_64", target_arch = "aarch64")
+))]
+pub mod mirror;
+pub mod mpmc;
+pub mod mpsc;
+pub mod multi;
+pub mod mutex;
+pub mod nb;
+pub mod overflow;
+pub mod packet;
+pub mod persist;
+pub mod pingpong;
+mod pipe;
+#[cfg(target_has_atomic = "ptr")]
+pub mod pool;
+mod queue;
+mod regions;
+pub mod replay;
+pub mod reserved;
+pub mod retained;
+#[cfg(target_has_atomic = "32")]
+pub mod rtt;
+#[cfg(feature = "shadow")]
+pub mod shadow;
+#[cfg(all(
+    feature = "std",
+    target_os = "linux",
+    any(target_arch = "x86_64", target_arch = "aarch64")
+))]
+pub mod shm;
+pub mod slice;
+pub mod slip;
+pub mod slot;
+pub mod socket;
+#[cfg(feature = "alloc")]
+pub mod spill;
+#[cfg(target_has_atomic = "ptr")]
+pub mod spin;
+mod state;
+pub mod text;
+pub mod timed;
+pub mod uart;
+pub mod uninit;
+pub mod usb;
+pub mod volatile;
+#[cfg(target_has_atomic = "ptr")]
+pub mod waker;
+pub mod watchdog;
+
+pub use error::CommitError;
+#[cfg(feature = "alloc")]
+pub use heap::HeapBip;
+pub use regions::WrapPolicy;
+pub use slice::SliceBip;
+pub use state::{BipState, LayoutMap, RawParts, Span};
 
 /// A Bip-Buffer with a fixed capacity.
-#[derive(Debug)]
+///
+/// The backing store is the first field, so it starts at the address of the buffer itself.
+/// Wrapping the buffer in [`Aligned`](align::Aligned) therefore aligns the stored elements.
+#[derive(Debug, Clone)]
+#[repr(C)]
 pub struct StaticBip<T, const CAP: usize> {
-    /// `A` region
-    a: Range<usize>,
-    /// `B` region
-    b: Range<usize>,
-    /// Reserved region
-    reserve: Range<usize>,
     /// Backing store
     buffer: [T; CAP],
+    /// Region bookkeeping
+    regions: Regions,
+}
+
+/// Operations shared by every Bip-Buffer variant.
+///
+/// Code that only needs to produce or consume data can accept `impl BipBuf<T>` instead of
+/// naming a concrete buffer type.
+pub trait BipBuf<T> {
+    /// Size of the backing store.
+    fn capacity(&self) -> usize;
+
+    /// Number of committed elements.
+    fn committed(&self) -> usize;
+
+    /// Number of reserved elements.
+    fn reserved(&self) -> usize;
+
+    /// Returns a mutable buffer containing up to `count` slots for storing data.
+    fn reserve(&mut self, count: usize) -> &mut [T];
+
+    /// Commits the data in the reservation, allowing it to be read later.
+    fn commit(&mut self, len: usize);
+
+    /// Retrieves available (committed) data as a contiguous block.
+    fn read(&mut self) -> &mut [T];
+
+    /// Marks the first `len` elements of the available data as seen.
+    fn decommit(&mut self, len: usize);
 }
 
-impl<T: Default + Copy, const CAP: usize> Default for StaticBip<T, CAP> {
+impl<T, B: BipBuf<T> + ?Sized> BipBuf<T> for &mut B {
+    #[inline]
+    fn capacity(&self) -> usize {
+        (**self).capacity()
+    }
+
+    #[inline]
+    fn committed(&self) -> usize {
+        (**self).committed()
+    }
+
+    #[inline]
+    fn reserved(&self) -> usize {
+        (**self).reserved()
+    }
+
+    #[inline]
+    fn reserve(&mut self, count: usize) -> &mut [T] {
+        (**self).reserve(count)
+    }
+
+    #[inline]
+    fn commit(&mut self, len: usize) {
+        (**self).commit(len)
+    }
+
+    #[inline]
+    fn read(&mut self) -> &mut [T] {
+        (**self).read()
+    }
+
+    #[inline]
+    fn decommit(&mut self, len: usize) {
+        (**self).decommit(len)
+    }
+}
+
+/// Byte operations on any byte buffer, usable as a trait object.
+///
+/// Every [`BipBuf<u8>`] implements it, so a driver can store `&mut dyn BipBytes` instead of
+/// being generic over the buffer type and its capacity. [`BipBuf`] itself is also
+/// dyn-compatible, for buffers of other element types.
+///
+/// ```rust
+/// use staticbip::{BipBytes, SliceBip, StaticBip};
+///
+/// struct Driver<'a> {
+///     tx: &'a mut dyn BipBytes,
+/// }
+///
+/// let mut small = StaticBip::<u8, 4>::default();
+/// let mut storage = [0; 64];
+/// let mut large = SliceBip::new(&mut storage);
+///
+/// for tx in [&mut small as &mut dyn BipBytes, &mut large] {
+///     let driver = Driver { tx };
+///     let written = driver.tx.write_bytes(b"hello");
+///     assert_eq!(driver.tx.committed(), written);
+/// }
+/// assert_eq!(small.read(), b"hell");
+/// ```
+pub trait BipBytes: BipBuf<u8> {
+    /// Appends as much of `data` as fits, continuing at the start of the backing store once
+    /// the space after the data runs out.
+    ///
+    /// Returns the number of bytes written.
+    fn write_bytes(&mut self, data: &[u8]) -> usize {
+        let mut written = 0;
+        while written < data.len() {
+            let reserved = self.reserve(data.len() - written);
+            let len = reserved.len();
+            if len == 0 {
+                break;
+            }
+            reserved.copy_from_slice(&data[written..written + len]);
+            self.commit(len);
+            written += len;
+        }
+        written
+    }
+
+    /// Removes up to `out.len()` of the oldest bytes into `out`, from both regions.
+    ///
+    /// Returns the number of bytes read.
+    fn read_bytes(&mut self, out: &mut [u8]) -> usize {
+        let mut read = 0;
+        while read < out.len() {
+            let block = self.read();
+            let len = block.len().min(out.len() - read);
+            if len == 0 {
+                break;
+            }
+            out[read..read + len].copy_from_slice(&block[..len]);
+            self.decommit(len);
+            read += len;
+        }
+        read
+    }
+}
+
+impl<B: BipBuf<u8> + ?Sized> BipBytes for B {}
+
+/// Moves as many elements as fit from `src` to `dst`, oldest first.
+///
+/// Blocks are copied until `src` is empty or `dst` is full, so data in both regions of `src`
+/// goes into both regions of `dst`. Returns the number of elements moved.
+///
+/// ```rust
+/// use staticbip::{copy_between, StaticBip};
+///
+/// let mut rx = StaticBip::<u8, 8>::default();
+/// let mut work = StaticBip::<u8, 4>::default();
+/// rx.extend(&[1, 2, 3, 4, 5, 6]);
+///
+/// assert_eq!(copy_between(&mut rx, &mut work), 4);
+/// assert_eq!(work.read(), &[1, 2, 3, 4]);
+/// assert_eq!(rx.read(), &[5, 6]);
+/// ```
+pub fn copy_between<T: Copy>(src: &mut impl BipBuf<T>, dst: &mut impl BipBuf<T>) -> usize {
+    let mut total = 0;
+    loop {
+        let block = src.read();
+        let reserved = dst.reserve(block.len());
+        let len = reserved.len();
+        if len == 0 {
+            break;
+        }
+        reserved.copy_from_slice(&block[..len]);
+        dst.commit(len);
+        src.decommit(len);
+        total += len;
+    }
+    total
+}
+
+impl<T: Default, const CAP: usize> Default for StaticBip<T, CAP> {
     #[inline]
     fn default() -> Self {
-        Self::new([T::default(); CAP])
+        Self::new(array::from_fn(|_| T::default()))
     }
 }
 
@@ -63,25 +394,166 @@ impl<T, const CAP: usize> StaticBip<T, CAP> {
     #[inline]
     pub const fn new(buffer: [T; CAP]) -> Self {
         Self {
-            a: 0..0,
-            b: 0..0,
-            reserve: 0..0,
             buffer,
+            regions: Regions::new(),
         }
     }
 
+    /// Creates a buffer whose entire backing store is committed.
+    #[inline]
+    pub const fn new_committed(buffer: [T; CAP]) -> Self {
+        Self {
+            buffer,
+            regions: Regions::full(CAP),
+        }
+    }
+
+    /// Creates a buffer from a backing store and the region layout of [`into_parts`](Self::into_parts).
+    ///
+    /// Returns `None` if the layout does not fit the backing store.
+    #[inline]
+    pub fn from_parts(buffer: [T; CAP], state: BipState) -> Option<Self> {
+        if state.is_valid(CAP) {
+            Some(Self {
+                buffer,
+                regions: state.into(),
+            })
+        } else {
+            None
+        }
+    }
+
+    /// Returns the backing store, discarding the region layout.
+    #[inline]
+    pub fn into_inner(self) -> [T; CAP] {
+        self.buffer
+    }
+
+    /// Returns the backing store along with the region layout.
+    #[inline]
+    pub fn into_parts(self) -> ([T; CAP], BipState) {
+        let state = BipState::from(&self.regions);
+        (self.buffer, state)
+    }
+
+    /// Returns the region layout, without the data.
+    ///
+    /// The spans of region `A`, region `B` and the reservation are offsets into the backing
+    /// store, for diagnostics, telemetry or setting up DMA descriptors.
+    ///
+    /// ```rust
+    /// use staticbip::{Span, StaticBip};
+    ///
+    /// let mut buffer = StaticBip::<u8, 8>::default();
+    /// buffer.extend(&[1, 2, 3, 4, 5, 6, 7, 8]);
+    /// buffer.decommit(4);
+    /// buffer.reserve(3);
+    ///
+    /// let state = buffer.state();
+    /// assert_eq!(state.a, Span { offset: 4, len: 4 });
+    /// assert_eq!(state.b.len, 0);
+    /// assert_eq!(state.reserve, Span { offset: 0, len: 3 });
+    /// ```
+    #[inline]
+    pub fn state(&self) -> BipState {
+        BipState::from(&self.regions)
+    }
+
+    /// Returns a map of where the regions and the reservation sit in the backing store.
+    ///
+    /// ```rust
+    /// use staticbip::StaticBip;
+    ///
+    /// let mut buffer = StaticBip::<u8, 8>::default();
+    /// buffer.extend(&[1, 2, 3, 4, 5, 6, 7, 8]);
+    /// buffer.decommit(3);
+    /// buffer.extend(&[9]);
+    /// buffer.reserve(1);
+    /// assert_eq!(buffer.debug_layout().to_string(), "[BR.AAAAA]");
+    /// ```
+    #[inline]
+    pub fn debug_layout(&self) -> LayoutMap {
+        self.state().layout(CAP)
+    }
+
+    /// Replaces the region layout with one taken by [`state`](Self::state), leaving the data
+    /// in place.
+    ///
+    /// Restoring an earlier state rewinds consumption, as long as the producer has not
+    /// overwritten the data since. Returns `None`, changing nothing, if `state` does not fit
+    /// the buffer.
+    #[inline]
+    pub fn restore(&mut self, state: BipState) -> Option<()> {
+        if !state.is_valid(CAP) {
+            return None;
+        }
+        let previous = mem::replace(&mut self.regions, state.into());
+        self.regions.policy = previous.policy;
+        #[cfg(feature = "stats")]
+        {
+            self.regions.abandoned = previous.abandoned;
+            self.regions.written = previous.written;
+            self.regions.read = previous.written.saturating_sub(self.committed() as u64);
+        }
+        Some(())
+    }
+
     /// Size of the backing store.
     #[inline]
     pub fn capacity(&self) -> usize {
         self.buffer.len()
     }
 
+    /// Returns the base pointer of the backing store along with the region layout.
+    #[inline]
+    pub fn as_raw_parts(&mut self) -> RawParts<T> {
+        RawParts {
+            ptr: ptr::addr_of_mut!(self.buffer).cast(),
+            capacity: CAP,
+            state: BipState::from(&self.regions),
+        }
+    }
+
     /// Number of committed elements.
     ///
     /// This approximates the size of the buffer that will be returned on [`read`](Self::read).
     #[inline]
     pub fn committed(&self) -> usize {
-        self.a.end - self.a.start + self.b.end - self.b.start
+        self.regions.committed()
+    }
+
+    /// Number of elements ever committed, as a position in the stream of elements.
+    ///
+    /// [`truncate_back`](Self::truncate_back) moves the position back. Only kept with the
+    /// `stats` feature, so buffers that never ask pay neither the space nor the updates.
+    #[cfg(feature = "stats")]
+    #[inline]
+    pub fn total_written(&self) -> u64 {
+        self.regions.written
+    }
+
+    /// Number of elements ever decommitted, as a position in the stream of elements.
+    ///
+    /// The difference to [`total_written`](Self::total_written) is always
+    /// [`committed`](Self::committed). Elements removed by [`clear`](Self::clear) count as
+    /// read. Only kept with the `stats` feature.
+    #[cfg(feature = "stats")]
+    #[inline]
+    pub fn total_read(&self) -> u64 {
+        self.regions.read
+    }
+
+    /// Number of reservations that were replaced by another [`reserve`](Self::reserve) while
+    /// still outstanding.
+    ///
+    /// A reservation is expected to end in a [`commit`](Self::commit), even `commit(0)` when
+    /// nothing was written. A growing count points at a path, typically an error path, that
+    /// abandons its reservation. With the `strict` feature this panics in debug builds instead.
+    /// Only counted with the `stats` feature.
+    #[cfg(feature = "stats")]
+    #[inline]
+    pub fn abandoned_reservations(&self) -> u64 {
+        self.regions.abandoned
     }
 
     /// Number of reserved elements.
@@ -89,7 +561,7 @@ impl<T, const CAP: usize> StaticBip<T, CAP> {
     /// This is the amount of available space for writing data to the buffer.
     #[inline]
     pub fn reserved(&self) -> usize {
-        self.reserve.end - self.reserve.start
+        self.regions.reserved()
     }
 
     /// Whether any space has been reserved or committed in the buffer.
@@ -103,9 +575,19 @@ impl<T, const CAP: usize> StaticBip<T, CAP> {
     /// Data in the underlying buffer is unchanged.
     #[inline]
     pub fn clear(&mut self) {
-        self.a = 0..0;
-        self.b = 0..0;
-        self.reserve = 0..0;
+        self.regions.clear();
+    }
+
+    /// How reservations choose between the end and the start of the backing store.
+    #[inline]
+    pub fn wrap_policy(&self) -> WrapPolicy {
+        self.regions.policy
+    }
+
+    /// Sets how reservations choose between the end and the start of the backing store.
+    #[inline]
+    pub fn set_wrap_policy(&mut self, policy: WrapPolicy) {
+        self.regions.policy = policy;
     }
 
     /// Returns a mutable buffer containing up to maximum slots for storing data.
@@ -117,16 +599,203 @@ impl<T, const CAP: usize> StaticBip<T, CAP> {
     /// Returns a mutable buffer containing up to `count` slots for storing data.
     #[inline]
     pub fn reserve(&mut self, count: usize) -> &mut [T] {
-        let space_after_a = self.capacity() - self.a.end;
-        let (start, free_space) = if self.b.end > self.b.start {
-            (self.b.end, self.a.start - self.b.end)
-        } else if space_after_a >= self.a.start {
-            (self.a.end, space_after_a)
-        } else {
-            (0, self.a.start)
-        };
-        self.reserve = start..start + free_space.min(count);
-        &mut self.buffer[self.reserve.clone()]
+        let reserve = self.regions.reserve(CAP, count);
+        self.slice_mut(reserve)
+    }
+
+    /// Returns a mutable buffer of exactly `count` slots for storing data.
+    ///
+    /// Returns `None`, leaving nothing reserved, if the block picked by the
+    /// [wrap policy](Self::wrap_policy) is smaller. With [`WrapPolicy::Fit`] that only happens
+    /// when neither free block can hold `count` elements.
+    #[inline]
+    pub fn try_reserve(&mut self, count: usize) -> Option<&mut [T]> {
+        let reserve = self.regions.reserve(CAP, count);
+        if reserve.len() < count {
+            self.regions.clear_reservation();
+            return None;
+        }
+        Some(self.slice_mut(reserve))
+    }
+
+    /// Returns a mutable buffer of up to `max_frames` whole frames of `frame_len` elements.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `frame_len` is `0`.
+    #[inline]
+    pub fn reserve_frames(&mut self, frame_len: usize, max_frames: usize) -> &mut [T] {
+        self.checked_reserve_frames(frame_len, max_frames)
+            .expect("frame length must be non-zero")
+    }
+
+    /// Returns a mutable buffer of up to `max_frames` whole frames of `frame_len` elements.
+    ///
+    /// Returns `None` if `frame_len` is `0`.
+    #[inline]
+    pub fn checked_reserve_frames(
+        &mut self,
+        frame_len: usize,
+        max_frames: usize,
+    ) -> Option<&mut [T]> {
+        // The reservation is placed directly in the block the frames were counted in, since
+        // `reserve` may choose again and pick a block holding fewer whole frames
+        let free = self
+            .regions
+            .free_space_for(CAP, max_frames.saturating_mul(frame_len));
+        let frames = free.len().checked_div(frame_len)?.min(max_frames);
+        self.regions
+            .set_reservation(free.start..free.start + frames * frame_len);
+        Some(self.slice_mut(self.regions.reservation()))
+    }
+
+    /// Returns a mutable buffer of up to `count` slots whose start address is a multiple of
+    /// `align` bytes.
+    ///
+    /// Slots skipped to reach the alignment are committed along with the data, unless the
+    /// buffer is empty, in which case they are left unused.
+    /// The buffer is empty if no aligned slot is available. Zero-sized elements have no
+    /// address to align, so none are skipped.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `align` is not a power of two.
+    #[inline]
+    pub fn reserve_aligned(&mut self, count: usize, align: usize) -> &mut [T] {
+        self.checked_reserve_aligned(count, align)
+            .expect("alignment must be a power of two")
+    }
+
+    /// Returns a mutable buffer of up to `count` slots whose start address is a multiple of
+    /// `align` bytes, as [`reserve_aligned`](Self::reserve_aligned) does.
+    ///
+    /// Returns `None` if `align` is not a power of two.
+    pub fn checked_reserve_aligned(&mut self, count: usize, align: usize) -> Option<&mut [T]> {
+        if !align.is_power_of_two() {
+            return None;
+        }
+        let free = self.regions.free_space(CAP);
+        let base = ptr::addr_of!(self.buffer)
+            .cast::<T>()
+            .wrapping_add(free.start) as usize;
+        let size = mem::size_of::<T>();
+        let skip = (0..align.min(free.len() + 1))
+            .find(|skip| size == 0 || base.wrapping_add(skip * size) & (align - 1) == 0)
+            .unwrap_or(free.len());
+        let start = free.start + skip;
+        self.regions
+            .set_reservation(start..start + (free.end - start).min(count));
+        Some(self.slice_mut(self.regions.reservation()))
+    }
+
+    /// Reserves up to `count` slots, passes them to `f`, and commits the length it returns.
+    ///
+    /// Returns the number of committed elements, which never exceeds the reservation.
+    #[inline]
+    pub fn with_reserve(&mut self, count: usize, f: impl FnOnce(&mut [T]) -> usize) -> usize {
+        let len = f(self.reserve(count)).min(self.reserved());
+        self.commit(len);
+        len
+    }
+
+    /// Commits items from `iter` until it runs out or the buffer is full.
+    ///
+    /// Items are only taken from `iter` while there is room for them. Returns the number of
+    /// committed items.
+    pub fn push_iter(&mut self, mut iter: impl Iterator<Item = T>) -> usize {
+        let mut total = 0;
+        loop {
+            let reserved = self.reserve_max();
+            let free = reserved.len();
+            let len = reserved
+                .iter_mut()
+                .zip(&mut iter)
+                .map(|(slot, item)| *slot = item)
+                .count();
+            self.commit(len);
+            total += len;
+            if len == 0 || len < free {
+                return total;
+            }
+        }
+    }
+
+    /// Fills the current reservation from `iter` and commits the items it produced.
+    ///
+    /// Items are only taken from `iter` while the reservation has room for them. Returns the
+    /// number of committed items.
+    pub fn commit_iter(&mut self, iter: impl IntoIterator<Item = T>) -> usize {
+        let reserve = self.regions.reservation();
+        let len = self
+            .slice_mut(reserve)
+            .iter_mut()
+            .zip(iter)
+            .map(|(slot, item)| *slot = item)
+            .count();
+        self.commit(len);
+        len
+    }
+
+    /// Shrinks the reservation to its first `len` slots and returns them.
+    ///
+    /// A reservation shorter than `len` is left unchanged.
+    #[inline]
+    pub fn shrink_reserve(&mut self, len: usize) -> &mut [T] {
+        let reserve = self.regions.reservation();
+        self.regions
+            .set_reservation(reserve.start..reserve.start + reserve.len().min(len));
+        self.slice_mut(self.regions.reservation())
+    }
+
+    /// Returns the free space the next [`reserve_max`](Self::reserve_max) would cover, without
+    /// reserving it.
+    ///
+    /// Fill it through raw pointers and publish it with [`set_committed`](Self::set_committed).
+    ///
+    /// # Safety
+    ///
+    /// The storage always holds initialized elements, so no slot may be left uninitialized,
+    /// for example by writing [`MaybeUninit::uninit`] into it.
+    #[inline]
+    pub unsafe fn spare_capacity_mut(&mut self) -> &mut [MaybeUninit<T>] {
+        let free = self.regions.free_space(CAP);
+        let spare = self.slice_mut(free);
+        // SAFETY: `MaybeUninit<T>` has the same layout as `T`, and the caller keeps every
+        // element initialized.
+        unsafe { &mut *(spare as *mut [T] as *mut [MaybeUninit<T>]) }
+    }
+
+    /// Commits the first `len` elements of [`spare_capacity_mut`](Self::spare_capacity_mut),
+    /// replacing any reservation.
+    ///
+    /// A `len` longer than the spare capacity commits all of it.
+    ///
+    /// # Safety
+    ///
+    /// The first `len` spare elements must have been written since the spare capacity was
+    /// taken.
+    #[inline]
+    pub unsafe fn set_committed(&mut self, len: usize) {
+        // The block is chosen exactly as by `spare_capacity_mut`, and replacing the
+        // reservation directly leaves the abandoned count, and the wrap choice derived from
+        // it, unchanged
+        let free = self.regions.free_space(CAP);
+        self.regions.set_reservation(free);
+        self.commit(len);
+    }
+
+    /// Grows the reservation by up to `additional` slots and returns all of it.
+    ///
+    /// Only free space directly following the reservation is added, so the slots reserved
+    /// so far keep their contents and position. Without a reservation this is the same as
+    /// [`reserve`](Self::reserve).
+    #[inline]
+    pub fn extend_reserve(&mut self, additional: usize) -> &mut [T] {
+        if self.regions.reservation().is_empty() {
+            return self.reserve(additional);
+        }
+        let reserve = self.regions.extend_reserve(CAP, additional);
+        self.slice_mut(reserve)
     }
 
     /// Commits the data in the reservation, allowing it to be read later.
@@ -134,17 +803,35 @@ impl<T, const CAP: usize> StaticBip<T, CAP> {
     /// If a `len` of `0` is passed in, the reservation will be cleared without making any other changes.
     #[inline]
     pub fn commit(&mut self, len: usize) {
-        if len != 0 {
-            let to_commit = len.min(self.reserve.end - self.reserve.start);
-            if self.a.is_empty() && self.b.is_empty() {
-                self.a = self.reserve.start..self.reserve.start + to_commit;
-            } else if self.reserve.start == self.a.end {
-                self.a.end += to_commit;
-            } else {
-                self.b.end += to_commit;
-            }
+        self.regions.commit(len);
+    }
+
+    /// Commits exactly `len` reserved elements.
+    ///
+    /// Returns `None`, keeping the reservation, if fewer than `len` elements are reserved.
+    #[inline]
+    pub fn checked_commit(&mut self, len: usize) -> Option<()> {
+        self.commit_exact(len).ok()
+    }
+
+    /// Commits exactly `len` reserved elements.
+    ///
+    /// Fails, keeping the reservation, if fewer than `len` elements are reserved.
+    #[inline]
+    pub fn commit_exact(&mut self, len: usize) -> Result<(), CommitError> {
+        if len > self.reserved() {
+            return Err(CommitError::Reservation);
         }
-        self.reserve = 0..0;
+        self.commit(len);
+        Ok(())
+    }
+
+    /// Removes the `len` most recently committed elements, undoing previous commits.
+    ///
+    /// This also clears the reservation.
+    #[inline]
+    pub fn truncate_back(&mut self, len: usize) {
+        self.regions.truncate_back(len);
     }
 
     /// Retrieves available (committed) data as a contiguous block.
@@ -152,7 +839,198 @@ impl<T, const CAP: usize> StaticBip<T, CAP> {
     /// Returns `None` if there is no data available
     #[inline]
     pub fn read(&mut self) -> &mut [T] {
-        &mut self.buffer[self.a.clone()]
+        self.slice_mut(self.regions.a())
+    }
+
+    /// Retrieves the block [`read`](Self::read) returns through a shared reference.
+    #[inline]
+    pub fn peek(&self) -> &[T] {
+        self.slice(self.regions.a())
+    }
+
+    /// Returns the committed data as two slices, in order.
+    ///
+    /// The first slice is what [`read`](Self::read) returns; the second one follows it.
+    #[inline]
+    pub fn as_slices(&self) -> (&[T], &[T]) {
+        (self.slice(self.regions.a()), self.slice(self.regions.b()))
+    }
+
+    /// Returns the committed data as two mutable slices, in order.
+    #[inline]
+    pub fn as_mut_slices(&mut self) -> (&mut [T], &mut [T]) {
+        self.slices_mut(self.regions.a(), self.regions.b())
+    }
+
+    /// Returns the block [`read`](Self::read) would return together with the current
+    /// reservation.
+    ///
+    /// The two never overlap, so old data can be read while new data is written.
+    #[inline]
+    pub fn split_mut(&mut self) -> (&mut [T], &mut [T]) {
+        self.slices_mut(self.regions.a(), self.regions.reservation())
+    }
+
+    /// Passes the committed data to `f` as two slices, in order, and consumes the number of
+    /// elements it returns.
+    ///
+    /// Consumption continues from the first slice into the second. Returns the number of
+    /// consumed elements, which never exceeds the committed data.
+    #[inline]
+    pub fn read_with(&mut self, f: impl FnOnce(&[T], &[T]) -> usize) -> usize {
+        let (a, b) = self.as_slices();
+        let len = f(a, b).min(self.committed());
+        self.regions.consume(len);
+        len
+    }
+
+    /// Returns an iterator over the committed elements, oldest first.
+    #[inline]
+    pub fn iter(&self) -> iter::Iter<'_, T> {
+        let (a, b) = self.as_slices();
+        iter::Iter::new(a, b)
+    }
+
+    /// Returns the oldest committed element.
+    #[inline]
+    pub fn front(&self) -> Option<&T> {
+        let index = self.regions.front()?;
+        self.slice(index..index + 1).first()
+    }
+
+    /// Returns the oldest committed element mutably.
+    #[inline]
+    pub fn front_mut(&mut self) -> Option<&mut T> {
+        let index = self.regions.front()?;
+        self.slice_mut(index..index + 1).first_mut()
+    }
+
+    /// Returns the newest committed element.
+    #[inline]
+    pub fn back(&self) -> Option<&T> {
+        let index = self.regions.back()?;
+        self.slice(index..index + 1).first()
+    }
+
+    /// Returns the newest committed element mutably.
+    #[inline]
+    pub fn back_mut(&mut self) -> Option<&mut T> {
+        let index = self.regions.back()?;
+        self.slice_mut(index..index + 1).first_mut()
+    }
+
+    /// Returns the committed element at logical position `index`, counting from the oldest.
+    #[inline]
+    pub fn get(&self, index: usize) -> Option<&T> {
+        let index = self.regions.logical(index..index.checked_add(1)?)?;
+        self.slice(index).first()
+    }
+
+    /// Returns the committed element at logical position `index` mutably.
+    #[inline]
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        let index = self.regions.logical(index..index.checked_add(1)?)?;
+        self.slice_mut(index).first_mut()
+    }
+
+    /// Returns the committed elements at logical positions `range` as one slice.
+    ///
+    /// Returns `None` if the range is out of bounds or spans both regions; see
+    /// [`read_exact`](Self::read_exact) to move such elements together.
+    #[inline]
+    pub fn get_slice(&self, range: impl RangeBounds<usize>) -> Option<&[T]> {
+        let range = self.regions.logical(bounds(range, self.committed())?)?;
+        Some(self.slice(range))
+    }
+
+    /// Returns the committed elements at logical positions `range` as one mutable slice.
+    #[inline]
+    pub fn get_slice_mut(&mut self, range: impl RangeBounds<usize>) -> Option<&mut [T]> {
+        let range = self.regions.logical(bounds(range, self.committed())?)?;
+        Some(self.slice_mut(range))
+    }
+
+    /// Returns the logical index of the first committed element matching `predicate`.
+    #[inline]
+    pub fn position(&self, predicate: impl FnMut(&T) -> bool) -> Option<usize> {
+        self.iter().position(predicate)
+    }
+
+    /// Retrieves exactly `len` committed elements as a contiguous block.
+    ///
+    /// If the elements span both regions, they are first moved together, which cancels any
+    /// reservation. Returns `None` if fewer than `len` elements are committed.
+    pub fn read_exact(&mut self, len: usize) -> Option<&mut [T]> {
+        if len > self.committed() {
+            return None;
+        }
+        if len > self.regions.a().len() {
+            self.defragment();
+        }
+        let start = self.regions.a().start;
+        Some(self.slice_mut(start..start + len))
+    }
+
+    /// Returns an iterator over consecutive `len`-element chunks of committed data.
+    ///
+    /// A chunk that would span both regions is first moved together, which cancels any
+    /// reservation. Trailing elements that do not fill a chunk are skipped.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `len` is `0`.
+    pub fn chunks_exact(&mut self, len: usize) -> impl Iterator<Item = &[T]> {
+        assert!(len != 0, "chunk size must be non-zero");
+        if !self.regions.a().len().is_multiple_of(len) && !self.regions.b().is_empty() {
+            self.defragment();
+        }
+        let (a, b) = (self.regions.a(), self.regions.b());
+        self.slice(a)
+            .chunks_exact(len)
+            .chain(self.slice(b).chunks_exact(len))
+    }
+
+    /// Passes consecutive `len`-element chunks of committed data to `f`, decommitting each one
+    /// after `f` returns.
+    ///
+    /// Returns the number of chunks processed. Trailing elements that do not fill a chunk stay
+    /// committed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `len` is `0`.
+    pub fn consume_chunks(&mut self, len: usize, mut f: impl FnMut(&mut [T])) -> usize {
+        assert!(len != 0, "chunk size must be non-zero");
+        let mut count = 0;
+        while let Some(chunk) = self.read_exact(len) {
+            f(chunk);
+            self.decommit(len);
+            count += 1;
+        }
+        count
+    }
+
+    /// Retrieves the whole frames of `frame_len` elements at the start of the available data.
+    ///
+    /// Trailing elements that do not form a complete frame are left out.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `frame_len` is `0`.
+    #[inline]
+    pub fn read_frames(&mut self, frame_len: usize) -> &mut [T] {
+        self.checked_read_frames(frame_len)
+            .expect("frame length must be non-zero")
+    }
+
+    /// Retrieves the whole frames of `frame_len` elements at the start of the available data.
+    ///
+    /// Returns `None` if `frame_len` is `0`.
+    #[inline]
+    pub fn checked_read_frames(&mut self, frame_len: usize) -> Option<&mut [T]> {
+        let block = self.read();
+        let len = block.len() - block.len().checked_rem(frame_len)?;
+        block.get_mut(..len)
     }
 
     /// Marks the first `len` elements of the available data is seen.
@@ -160,11 +1038,134 @@ impl<T, const CAP: usize> StaticBip<T, CAP> {
     /// The next time [`read`](Self::read) is called, it will not include these elements.
     #[inline]
     pub fn decommit(&mut self, len: usize) {
-        if len >= self.a.end - self.a.start {
-            self.a = self.b.clone();
-            self.b = 0..0;
+        self.regions.decommit(len);
+    }
+
+    /// Marks the first `len` committed elements as seen, continuing into the second region.
+    ///
+    /// Unlike [`decommit`](Self::decommit), which stops at the end of the block
+    /// [`read`](Self::read) returns, this consumes exactly `len` logical elements, as
+    /// iterated by [`iter`](Self::iter), up to the committed amount.
+    #[inline]
+    pub fn consume(&mut self, len: usize) {
+        self.regions.consume(len);
+    }
+
+    /// Drops the oldest committed elements so that at most `n` remain.
+    ///
+    /// The cut may fall in either region. Returns the number of elements dropped.
+    #[inline]
+    pub fn retain_recent(&mut self, n: usize) -> usize {
+        let excess = self.committed().saturating_sub(n);
+        self.consume(excess);
+        excess
+    }
+
+    /// Marks exactly `len` elements of the available data as seen.
+    ///
+    /// Returns `None`, changing nothing, if [`read`](Self::read) returns fewer than `len`
+    /// elements.
+    #[inline]
+    pub fn checked_decommit(&mut self, len: usize) -> Option<()> {
+        self.decommit_exact(len).ok()
+    }
+
+    /// Marks exactly `len` elements of the available data as seen.
+    ///
+    /// Fails, changing nothing, if [`read`](Self::read) returns fewer than `len` elements.
+    #[inline]
+    pub fn decommit_exact(&mut self, len: usize) -> Result<(), CommitError> {
+        if len > self.regions.a().len() {
+            return Err(CommitError::Available);
+        }
+        self.decommit(len);
+        Ok(())
+    }
+
+    /// Returns the elements in `range` of the backing store, or an empty slice if `range`
+    /// does not fit it.
+    ///
+    /// Only the elements in `range` are borrowed, as described under "Aliasing" in the crate
+    /// documentation.
+    #[inline]
+    pub(crate) fn slice(&self, range: Range<usize>) -> &[T] {
+        if range.start > range.end || range.end > CAP {
+            return &[];
+        }
+        let base = ptr::addr_of!(self.buffer).cast::<T>();
+        // SAFETY: `range` lies within the backing store, which `self` borrows.
+        unsafe { core::slice::from_raw_parts(base.add(range.start), range.len()) }
+    }
+
+    /// Returns the elements in `range` of the backing store mutably, or an empty slice if
+    /// `range` does not fit it.
+    #[inline]
+    pub(crate) fn slice_mut(&mut self, range: Range<usize>) -> &mut [T] {
+        let base = ptr::addr_of_mut!(self.buffer).cast::<T>();
+        // SAFETY: `self` borrows the backing store mutably for the returned lifetime.
+        unsafe { range_mut(base, CAP, range) }
+    }
+
+    /// Returns the elements in two ranges of the backing store mutably.
+    ///
+    /// A range that does not fit the backing store, or overlaps the other one, yields an
+    /// empty slice.
+    #[inline]
+    pub(crate) fn slices_mut(
+        &mut self,
+        first: Range<usize>,
+        second: Range<usize>,
+    ) -> (&mut [T], &mut [T]) {
+        let overlap = first.start < second.end && second.start < first.end;
+        let second = if overlap { 0..0 } else { second };
+        let base = ptr::addr_of_mut!(self.buffer).cast::<T>();
+        // SAFETY: `self` borrows the backing store mutably for the returned lifetime, and the
+        // ranges are disjoint.
+        unsafe { (range_mut(base, CAP, first), range_mut(base, CAP, second)) }
+    }
+
+    /// Moves region `B` behind region `A` so all committed data is contiguous.
+    ///
+    /// This clears the reservation.
+    fn defragment(&mut self) {
+        let (a, b) = (self.regions.a(), self.regions.b());
+        if !b.is_empty() {
+            self.slice_mut(b.start..a.end).rotate_right(a.len());
+            self.regions.set_a(b.start..b.start + a.len() + b.len());
+            self.regions.set_b_end(0);
+        }
+        self.regions.clear_reservation();
+    }
+
+    /// Moves the committed data together if it is fragmented and no reservation is
+    /// outstanding, returning whether it did.
+    ///
+    /// The data counts as fragmented when it spans both regions and [`read`](Self::read)
+    /// returns fewer than `min_contiguous` elements. At most `max_move` elements are moved:
+    /// region `B` is appended behind region `A` when it fits there, otherwise both regions are
+    /// rotated to the start of the backing store, moving everything up to the end of `A`. If
+    /// either would exceed `max_move`, nothing happens.
+    ///
+    /// Calling this from the consumer when it finds the buffer quiescent, such as after
+    /// draining a burst, keeps long-running streams from degrading into short alternating
+    /// blocks.
+    pub fn maybe_compact(&mut self, min_contiguous: usize, max_move: usize) -> bool {
+        let (a, b) = (self.regions.a(), self.regions.b());
+        if b.is_empty() || a.len() >= min_contiguous || self.reserved() != 0 {
+            return false;
+        }
+        if a.end + b.len() <= CAP && b.len() <= max_move {
+            let (src, dst) = self.slices_mut(b.clone(), a.end..a.end + b.len());
+            dst.swap_with_slice(src);
+            self.regions.set_a(a.start..a.end + b.len());
+            self.regions.set_b_end(0);
+            self.regions.clear_reservation();
+            true
+        } else if a.end - b.start <= max_move {
+            self.defragment();
+            true
         } else {
-            self.a.start += len;
+            false
         }
     }
 
@@ -174,9 +1175,369 @@ impl<T, const CAP: usize> StaticBip<T, CAP> {
     /// or `None` if bip doesn't contain commited elements.
     #[inline]
     pub fn pop(&mut self) -> Option<&mut T> {
-        self.a
-            .next()
-            .or_else(|| self.b.next())
-            .map(move |index| &mut self.buffer[index])
+        let index = self.regions.pop()?;
+        self.slice_mut(index..index + 1).first_mut()
+    }
+}
+
+impl<T: Copy, const CAP: usize> StaticBip<T, CAP> {
+    /// Copies `slices` one after another into a single reservation and commits what fit.
+    ///
+    /// Returns the number of elements written, which stops partway through a slice if the
+    /// free space runs out.
+    pub fn write_from_slices(&mut self, slices: &[&[T]]) -> usize {
+        let total = slices.iter().map(|slice| slice.len()).sum();
+        let mut reserved = &mut *self.reserve(total);
+        let mut written = 0;
+        for slice in slices {
+            let len = slice.len().min(reserved.len());
+            let (head, tail) = reserved.split_at_mut(len);
+            head.copy_from_slice(&slice[..len]);
+            reserved = tail;
+            written += len;
+        }
+        self.commit(written);
+        written
+    }
+
+    /// Copies the committed data, oldest first, into `dst` without consuming it.
+    ///
+    /// Returns the number of elements copied, which stops at the end of `dst`.
+    pub fn export_into(&self, dst: &mut [T]) -> usize {
+        let (a, b) = self.as_slices();
+        let head = a.len().min(dst.len());
+        dst[..head].copy_from_slice(&a[..head]);
+        let tail = b.len().min(dst.len() - head);
+        dst[head..head + tail].copy_from_slice(&b[..tail]);
+        head + tail
+    }
+
+    /// Replaces the contents with `src`, committed from the start of the backing store.
+    ///
+    /// Any previous data and reservation are discarded. Returns the number of elements
+    /// loaded, which stops at the capacity.
+    pub fn import(&mut self, src: &[T]) -> usize {
+        self.clear();
+        let reserved = self.reserve(src.len());
+        let len = reserved.len();
+        reserved.copy_from_slice(&src[..len]);
+        self.commit(len);
+        len
+    }
+}
+
+impl<T: PartialEq, const CAP: usize> StaticBip<T, CAP> {
+    /// Returns the logical index of the first occurrence of `needle` in the committed data.
+    ///
+    /// Matches may span the boundary between the regions.
+    pub fn find(&self, needle: &[T]) -> Option<usize> {
+        let mut iter = self.iter();
+        for index in 0..=self.committed().checked_sub(needle.len())? {
+            if iter.clone().take(needle.len()).eq(needle) {
+                return Some(index);
+            }
+            iter.next();
+        }
+        None
+    }
+}
+
+impl<T: Default, const CAP: usize> StaticBip<T, CAP> {
+    /// Creates a buffer with the items of `iter` committed, handing back the first item that
+    /// does not fit.
+    pub fn try_from_iter(iter: impl IntoIterator<Item = T>) -> Result<Self, T> {
+        let mut iter = iter.into_iter();
+        let mut bip = Self::default();
+        bip.push_iter(&mut iter);
+        match iter.next() {
+            Some(item) => Err(item),
+            None => Ok(bip),
+        }
+    }
+
+    /// Removes the oldest element and returns it by value.
+    ///
+    /// Its slot is left holding `T::default()`.
+    #[inline]
+    pub fn pop_value(&mut self) -> Option<T> {
+        self.pop().map(mem::take)
+    }
+
+    /// Returns an iterator that removes committed elements by value, oldest first.
+    ///
+    /// Elements not reached before the iterator is dropped stay in the buffer.
+    #[inline]
+    pub fn drain(&mut self) -> iter::Drain<'_, T, CAP> {
+        iter::Drain::new(self)
+    }
+
+    /// Marks the first `len` elements of the available data as seen, dropping them now.
+    ///
+    /// The removed elements are replaced with `T::default()` instead of staying in the
+    /// backing store until they are overwritten.
+    pub fn decommit_reset(&mut self, len: usize) {
+        let a = self.regions.a();
+        let end = a.start + len.min(a.len());
+        for slot in self.slice_mut(a.start..end) {
+            mem::take(slot);
+        }
+        self.decommit(len);
+    }
+
+    /// Clears all regions and reservations, dropping the committed elements now.
+    ///
+    /// The removed elements are replaced with `T::default()`.
+    pub fn clear_reset(&mut self) {
+        let (a, b) = self.as_mut_slices();
+        for slot in a.iter_mut().chain(b) {
+            mem::take(slot);
+        }
+        self.clear();
+    }
+}
+
+/// Returns the elements in `range` of the `capacity` elements at `base`, or an empty slice if
+/// `range` does not fit them.
+///
+/// # Safety
+///
+/// `base` must be valid for reads and writes of `capacity` elements for `'a`, and nothing
+/// else may access the elements in `range` during `'a`.
+#[inline]
+pub(crate) unsafe fn range_mut<'a, T>(
+    base: *mut T,
+    capacity: usize,
+    range: Range<usize>,
+) -> &'a mut [T] {
+    if range.start > range.end || range.end > capacity {
+        return &mut [];
+    }
+    core::slice::from_raw_parts_mut(base.add(range.start), range.len())
+}
+
+/// Turns `range` into a `Range` within `len` elements, or `None` if it overflows.
+fn bounds(range: impl RangeBounds<usize>, len: usize) -> Option<Range<usize>> {
+    let start = match range.start_bound() {
+        Bound::Included(&start) => start,
+        Bound::Excluded(&start) => start.checked_add(1)?,
+        Bound::Unbounded => 0,
+    };
+    let end = match range.end_bound() {
+        Bound::Included(&end) => end.checked_add(1)?,
+        Bound::Excluded(&end) => end,
+        Bound::Unbounded => len,
+    };
+    Some(start..end)
+}
+
+/// Creates a buffer whose entire backing store is committed.
+impl<T, const CAP: usize> From<[T; CAP]> for StaticBip<T, CAP> {
+    #[inline]
+    fn from(buffer: [T; CAP]) -> Self {
+        Self::new_committed(buffer)
+    }
+}
+
+/// Creates a buffer with the first `CAP` items of the iterator committed.
+///
+/// Items past the first `CAP` are not taken from the iterator.
+/// [`try_from_iter`](StaticBip::try_from_iter) reports them instead.
+impl<T: Default, const CAP: usize> core::iter::FromIterator<T> for StaticBip<T, CAP> {
+    #[inline]
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut bip = Self::default();
+        bip.push_iter(iter.into_iter());
+        bip
+    }
+}
+
+/// Buffers are equal when their committed elements are equal, regardless of where the
+/// elements sit in the backing store.
+impl<T: PartialEq<U>, U, const CAP: usize, const N: usize> PartialEq<StaticBip<U, N>>
+    for StaticBip<T, CAP>
+{
+    #[inline]
+    fn eq(&self, other: &StaticBip<U, N>) -> bool {
+        self.committed() == other.committed() && self.iter().eq(other.iter())
+    }
+}
+
+impl<T: Eq, const CAP: usize> Eq for StaticBip<T, CAP> {}
+
+impl<T: PartialEq<U>, U, const CAP: usize> PartialEq<[U]> for StaticBip<T, CAP> {
+    #[inline]
+    fn eq(&self, other: &[U]) -> bool {
+        self.committed() == other.len() && self.iter().eq(other)
+    }
+}
+
+impl<T: PartialEq<U>, U, const CAP: usize> PartialEq<&[U]> for StaticBip<T, CAP> {
+    #[inline]
+    fn eq(&self, other: &&[U]) -> bool {
+        *self == **other
+    }
+}
+
+impl<T: PartialEq<U>, U, const CAP: usize, const N: usize> PartialEq<[U; N]> for StaticBip<T, CAP> {
+    #[inline]
+    fn eq(&self, other: &[U; N]) -> bool {
+        *self == other[..]
+    }
+}
+
+/// Returns the first contiguous block of committed data, region `A`, as
+/// [`peek`](StaticBip::peek) does. Data that has wrapped to the start of the
+/// backing store is not included; [`read_exact`](StaticBip::read_exact) makes it contiguous.
+impl<T, const CAP: usize> AsRef<[T]> for StaticBip<T, CAP> {
+    #[inline]
+    fn as_ref(&self) -> &[T] {
+        self.peek()
+    }
+}
+
+/// Returns the block [`read`](StaticBip::read) returns.
+impl<T, const CAP: usize> AsMut<[T]> for StaticBip<T, CAP> {
+    #[inline]
+    fn as_mut(&mut self) -> &mut [T] {
+        self.read()
+    }
+}
+
+/// Indexes the committed elements by logical position, counting from the oldest.
+///
+/// # Panics
+///
+/// Panics if `index` is not below [`committed`](StaticBip::committed).
+impl<T, const CAP: usize> Index<usize> for StaticBip<T, CAP> {
+    type Output = T;
+
+    #[inline]
+    fn index(&self, index: usize) -> &T {
+        let committed = self.committed();
+        self.get(index).unwrap_or_else(|| {
+            panic!(
+                "index {} out of bounds for {} committed elements",
+                index, committed
+            )
+        })
+    }
+}
+
+impl<T, const CAP: usize> IndexMut<usize> for StaticBip<T, CAP> {
+    #[inline]
+    fn index_mut(&mut self, index: usize) -> &mut T {
+        let committed = self.committed();
+        self.get_mut(index).unwrap_or_else(|| {
+            panic!(
+                "index {} out of bounds for {} committed elements",
+                index, committed
+            )
+        })
+    }
+}
+
+/// Indexes ranges of logical positions, as [`get_slice`](StaticBip::get_slice) does.
+///
+/// # Panics
+///
+/// Panics if the range is out of bounds or spans both regions.
+macro_rules! index_ranges {
+    ($($range:ty),*) => {$(
+        impl<T, const CAP: usize> Index<$range> for StaticBip<T, CAP> {
+            type Output = [T];
+
+            #[inline]
+            fn index(&self, range: $range) -> &[T] {
+                self.get_slice(range.clone()).unwrap_or_else(|| range_panic(&range, self.committed()))
+            }
+        }
+
+        impl<T, const CAP: usize> IndexMut<$range> for StaticBip<T, CAP> {
+            #[inline]
+            fn index_mut(&mut self, range: $range) -> &mut [T] {
+                let committed = self.committed();
+                self.get_slice_mut(range.clone()).unwrap_or_else(|| range_panic(&range, committed))
+            }
+        }
+    )*};
+}
+
+index_ranges!(
+    Range<usize>,
+    RangeFrom<usize>,
+    RangeFull,
+    RangeInclusive<usize>,
+    RangeTo<usize>,
+    RangeToInclusive<usize>
+);
+
+#[cold]
+fn range_panic(range: &impl fmt::Debug, committed: usize) -> ! {
+    panic!(
+        "range {:?} is out of bounds for {} committed elements or not contiguous",
+        range, committed
+    )
+}
+
+/// Hashes the committed elements in order, consistently with [`PartialEq`].
+impl<T: Hash, const CAP: usize> Hash for StaticBip<T, CAP> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_usize(self.committed());
+        self.iter().for_each(|item| item.hash(state));
+    }
+}
+
+/// Commits items until the iterator ends or no more space can be reserved.
+///
+/// When the free space after region `A` fills up, writing continues at the start of the
+/// backing store. Items that do not fit are dropped along with the rest of the iterator.
+impl<T, const CAP: usize> Extend<T> for StaticBip<T, CAP> {
+    #[inline]
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        self.push_iter(iter.into_iter());
+    }
+}
+
+impl<'a, T: Copy + 'a, const CAP: usize> Extend<&'a T> for StaticBip<T, CAP> {
+    #[inline]
+    fn extend<I: IntoIterator<Item = &'a T>>(&mut self, iter: I) {
+        self.extend(iter.into_iter().copied());
+    }
+}
+
+impl<T, const CAP: usize> BipBuf<T> for StaticBip<T, CAP> {
+    #[inline]
+    fn capacity(&self) -> usize {
+        self.capacity()
+    }
+
+    #[inline]
+    fn committed(&self) -> usize {
+        self.committed()
+    }
+
+    #[inline]
+    fn reserved(&self) -> usize {
+        self.reserved()
+    }
+
+    #[inline]
+    fn reserve(&mut self, count: usize) -> &mut [T] {
+        self.reserve(count)
+    }
+
+    #[inline]
+    fn commit(&mut self, len: usize) {
+        self.commit(len)
+    }
+
+    #[inline]
+    fn read(&mut self) -> &mut [T] {
+        self.read()
+    }
+
+    #[inline]
+    fn decommit(&mut self, len: usize) {
+        self.decommit(len)
     }
 }
@@ -0,0 +1,48 @@
+//! Delimited reads for byte buffers.
+//!
+//! [`read_until_with`](StaticBip::read_until_with) passes a complete chunk, up to and
+//! including its delimiter, to a closure as two slices that cover the chunk in order, then
+//! consumes it. The second slice is only non-empty if the chunk continues past the end of the
+//! first region. An incomplete chunk is left for later.
+//!
+//! The methods are not named `read_until` and `read_line` to avoid shadowing the
+//! [`BufRead`](https://doc.rust-lang.org/std/io/trait.BufRead.html) methods of byte buffers.
+//!
+//! ```rust
+//! use staticbip::StaticBip;
+//!
+//! let mut buffer = StaticBip::<u8, 16>::default();
+//! buffer.extend(b"OK\r\nERR");
+//!
+//! let line = buffer.read_line_with(|head, tail| [head, tail].concat());
+//! assert_eq!(line.as_deref(), Some(&b"OK\r\n"[..]));
+//! assert_eq!(buffer.read_line_with(|_, _| ()), None);
+//! ```
+
+use crate::StaticBip;
+
+impl<const CAP: usize> StaticBip<u8, CAP> {
+    /// Passes the oldest chunk ending in `delim`, including it, to `f` and consumes it.
+    ///
+    /// Returns `None`, consuming nothing, if no `delim` has been committed yet.
+    pub fn read_until_with<R>(
+        &mut self,
+        delim: u8,
+        f: impl FnOnce(&[u8], &[u8]) -> R,
+    ) -> Option<R> {
+        let len = self.position(|&byte| byte == delim)? + 1;
+        let (a, b) = self.as_slices();
+        let split = a.len().min(len);
+        let result = f(&a[..split], &b[..len - split]);
+        self.regions.consume(len);
+        Some(result)
+    }
+
+    /// Passes the oldest line, including its `\n`, to `f` and consumes it.
+    ///
+    /// Returns `None`, consuming nothing, if no complete line has been committed yet.
+    #[inline]
+    pub fn read_line_with<R>(&mut self, f: impl FnOnce(&[u8], &[u8]) -> R) -> Option<R> {
+        self.read_until_with(b'\n', f)
+    }
+}
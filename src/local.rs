@@ -0,0 +1,107 @@
+//! Shared access from a single thread.
+//!
+//! A [`LocalBip`] produces and consumes through `&self`, for cooperative schedulers that only
+//! hand out shared references. Access is checked at runtime like a [`RefCell`], without
+//! atomics or critical sections, and the buffer cannot be shared between threads.
+//!
+//! ```rust
+//! use staticbip::local::LocalBip;
+//!
+//! let buffer = LocalBip::<u8, 4>::new([0; 4]);
+//! let (producer, consumer) = (&buffer, &buffer);
+//!
+//! producer.push(1).unwrap();
+//! producer.with(|bip| {
+//!     bip.reserve(2).copy_from_slice(&[2, 3]);
+//!     bip.commit(2);
+//! });
+//!
+//! assert_eq!(consumer.pop(), Some(1));
+//! assert_eq!(consumer.with(|bip| bip.read().len()), 2);
+//! ```
+
+use crate::StaticBip;
+use core::cell::RefCell;
+
+/// A Bip-Buffer used through shared references on one thread.
+#[derive(Debug)]
+pub struct LocalBip<T, const CAP: usize> {
+    bip: RefCell<StaticBip<T, CAP>>,
+}
+
+impl<T, const CAP: usize> LocalBip<T, CAP> {
+    /// Creates a new buffer of `T` elements.
+    #[inline]
+    pub const fn new(buffer: [T; CAP]) -> Self {
+        Self {
+            bip: RefCell::new(StaticBip::new(buffer)),
+        }
+    }
+
+    /// Calls `f` with the buffer.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called from inside another `with` on the same buffer.
+    #[inline]
+    pub fn with<R>(&self, f: impl FnOnce(&mut StaticBip<T, CAP>) -> R) -> R {
+        f(&mut self.bip.borrow_mut())
+    }
+
+    /// Calls `f` with the buffer.
+    ///
+    /// Returns `None` if called from inside another `with` on the same buffer.
+    #[inline]
+    pub fn try_with<R>(&self, f: impl FnOnce(&mut StaticBip<T, CAP>) -> R) -> Option<R> {
+        let mut bip = self.bip.try_borrow_mut().ok()?;
+        Some(f(&mut bip))
+    }
+
+    /// Appends `item`, handing it back if the buffer is full.
+    #[inline]
+    pub fn push(&self, item: T) -> Result<(), T> {
+        self.with(|bip| bip.enqueue(item))
+    }
+
+    /// Number of committed elements.
+    #[inline]
+    pub fn committed(&self) -> usize {
+        self.with(|bip| bip.committed())
+    }
+
+    /// Whether there are no committed elements.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.committed() == 0
+    }
+
+    /// Returns the wrapped buffer.
+    #[inline]
+    pub fn into_inner(self) -> StaticBip<T, CAP> {
+        self.bip.into_inner()
+    }
+}
+
+impl<T: Copy, const CAP: usize> LocalBip<T, CAP> {
+    /// Removes the oldest element.
+    #[inline]
+    pub fn pop(&self) -> Option<T> {
+        self.with(|bip| bip.dequeue())
+    }
+}
+
+impl<T: Default, const CAP: usize> Default for LocalBip<T, CAP> {
+    #[inline]
+    fn default() -> Self {
+        Self::from(StaticBip::default())
+    }
+}
+
+impl<T, const CAP: usize> From<StaticBip<T, CAP>> for LocalBip<T, CAP> {
+    #[inline]
+    fn from(bip: StaticBip<T, CAP>) -> Self {
+        Self {
+            bip: RefCell::new(bip),
+        }
+    }
+}
@@ -0,0 +1,199 @@
+//! Deferred log output.
+//!
+//! [`LogBuffer`] formats each record straight into a frame of its buffer, which is cheap
+//! enough for interrupt handlers, and a low-priority task later drains whole records to a
+//! UART or RTT channel. A `log::Log` implementation forwards each record to
+//! [`write_fmt`](LogBuffer::write_fmt) from inside a critical section.
+//!
+//! ```rust
+//! use staticbip::logger::LogBuffer;
+//!
+//! let mut log = LogBuffer::<64>::new();
+//! write!(log, "temperature: {}", 21).unwrap();
+//! write!(log, "humidity: {}", 40).unwrap();
+//!
+//! let mut output = Vec::new();
+//! log.drain(|record| output.push(String::from_utf8(record.to_vec()).unwrap()));
+//! assert_eq!(output, ["temperature: 21", "humidity: 40"]);
+//! ```
+//!
+//! A record that does not fit is never emitted in part. By default it is dropped; with
+//! [`Truncation::Marker`] the part that fits is kept and ends with the marker instead, so a
+//! cut record is recognizable in the output.
+//!
+//! ```rust
+//! use staticbip::logger::{LogBuffer, Truncation};
+//!
+//! let mut log = LogBuffer::<16>::with_truncation(Truncation::Marker("..."));
+//! write!(log, "{}", "a long record").unwrap();
+//! assert_eq!(log.truncated(), 1);
+//! log.drain(|record| assert_eq!(record, b"a long reco..."));
+//! ```
+
+use crate::{
+    frame::{Framed, MAX_PAYLOAD},
+    StaticBip,
+};
+use core::fmt;
+
+/// What happens to a record that does not fit in the free space.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Truncation {
+    /// The record is dropped as a whole.
+    #[default]
+    Drop,
+    /// The part of the record that fits is kept, cut at a character boundary and followed by
+    /// the marker. A record with no room for the marker is dropped.
+    Marker(&'static str),
+}
+
+/// A buffer of formatted log records.
+#[derive(Debug)]
+pub struct LogBuffer<const CAP: usize> {
+    bip: StaticBip<u8, CAP>,
+    truncation: Truncation,
+    /// Number of records that did not fit
+    dropped: usize,
+    /// Number of records cut short
+    truncated: usize,
+}
+
+impl<const CAP: usize> Default for LogBuffer<CAP> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const CAP: usize> LogBuffer<CAP> {
+    /// Creates an empty log buffer that drops records which do not fit.
+    #[inline]
+    pub const fn new() -> Self {
+        Self::with_truncation(Truncation::Drop)
+    }
+
+    /// Creates an empty log buffer handling records which do not fit as `truncation` says.
+    #[inline]
+    pub const fn with_truncation(truncation: Truncation) -> Self {
+        Self {
+            bip: StaticBip::new([0; CAP]),
+            truncation,
+            dropped: 0,
+            truncated: 0,
+        }
+    }
+
+    /// What happens to records that do not fit.
+    #[inline]
+    pub fn truncation(&self) -> Truncation {
+        self.truncation
+    }
+
+    /// Sets what happens to records that do not fit.
+    #[inline]
+    pub fn set_truncation(&mut self, truncation: Truncation) {
+        self.truncation = truncation;
+    }
+
+    /// Number of records that were dropped because they did not fit.
+    #[inline]
+    pub fn dropped(&self) -> usize {
+        self.dropped
+    }
+
+    /// Number of records that were cut short and marked because they did not fit.
+    #[inline]
+    pub fn truncated(&self) -> usize {
+        self.truncated
+    }
+
+    /// Formats `args` as a single record.
+    ///
+    /// A record that does not fit in the free space is handled according to the
+    /// [`truncation`](Self::truncation), so a record is never read back cut short without a
+    /// marker. An error is returned if the record was dropped.
+    pub fn write_fmt(&mut self, args: fmt::Arguments<'_>) -> fmt::Result {
+        let marker = match self.truncation {
+            Truncation::Drop => None,
+            Truncation::Marker(marker) => Some(marker),
+        };
+        let mut framed = Framed::<_>::new(&mut self.bip);
+        let buffer = framed.reserve(MAX_PAYLOAD);
+        let limit = buffer.len().saturating_sub(marker.map_or(0, str::len));
+        let mut record = Record {
+            buffer: &mut buffer[..limit],
+            len: 0,
+            full: false,
+        };
+        let result = fmt::write(&mut record, args);
+        let (len, full) = (record.len, record.full);
+        match (result, marker) {
+            (Ok(()), _) => framed.commit(len),
+            (Err(_), Some(marker)) if full && buffer.len() >= marker.len() => {
+                buffer[len..len + marker.len()].copy_from_slice(marker.as_bytes());
+                framed.commit(len + marker.len());
+                self.truncated += 1;
+            }
+            (Err(error), _) => {
+                framed.commit(0);
+                self.dropped += 1;
+                return Err(error);
+            }
+        }
+        Ok(())
+    }
+
+    /// Passes each complete record to `f`, oldest first, and removes it.
+    ///
+    /// Returns the number of records drained.
+    pub fn drain(&mut self, mut f: impl FnMut(&[u8])) -> usize {
+        let mut framed = Framed::<_>::new(&mut self.bip);
+        let mut count = 0;
+        while let Some(record) = framed.read() {
+            if let Ok(record) = record {
+                f(record);
+                count += 1;
+            }
+            framed.decommit();
+        }
+        count
+    }
+}
+
+impl<const CAP: usize> fmt::Write for LogBuffer<CAP> {
+    #[inline]
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.write_fmt(format_args!("{}", s))
+    }
+
+    #[inline]
+    fn write_fmt(&mut self, args: fmt::Arguments<'_>) -> fmt::Result {
+        LogBuffer::write_fmt(self, args)
+    }
+}
+
+/// Formatter output into a reserved frame payload.
+struct Record<'a> {
+    buffer: &'a mut [u8],
+    len: usize,
+    /// Whether the output ran out of space, as opposed to a formatting error
+    full: bool,
+}
+
+impl fmt::Write for Record<'_> {
+    /// Writes `s`, or as much of it as fits up to a character boundary before failing.
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let free = self.buffer.len() - self.len;
+        let mut fits = s.len().min(free);
+        while !s.is_char_boundary(fits) {
+            fits -= 1;
+        }
+        self.buffer[self.len..self.len + fits].copy_from_slice(&s.as_bytes()[..fits]);
+        self.len += fits;
+        if fits < s.len() {
+            self.full = true;
+            return Err(fmt::Error);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,116 @@
+//! Cross-connected duplexes for testing drivers on the host.
+//!
+//! A [`Loopback`] stands in for the wire between two [`Duplex`](crate::duplex::Duplex)es:
+//! each [`pump`](Loopback::pump) moves what one side's application sent into the other
+//! side's receive channel, and the other way around. A protocol driver under test then
+//! talks to a peer through the same channels it uses on the target, and limiting how many
+//! elements a pump moves with [`with_chunk`](Loopback::with_chunk) delivers messages in
+//! fragments, as a slow link would.
+//!
+//! ```rust
+//! use staticbip::{duplex::Duplex, loopback::Loopback};
+//!
+//! let mut a = Duplex::<u8, 8, 8>::default();
+//! let mut b = Duplex::<u8, 8, 8>::default();
+//! let (mut a_app, mut a_driver) = a.split();
+//! let (mut b_app, mut b_driver) = b.split();
+//! let mut wire = Loopback::with_chunk(2);
+//!
+//! for &byte in b"ping" {
+//!     a_app.sender().try_send(byte).unwrap();
+//! }
+//! assert_eq!(wire.pump(&mut a_driver, &mut b_driver), 2);
+//! assert_eq!(b_app.receiver().try_recv(), Ok(b'p'));
+//! assert_eq!(b_app.receiver().try_recv(), Ok(b'i'));
+//! assert!(b_app.receiver().try_recv().is_err());
+//! ```
+
+use crate::{
+    channel::{Receiver, Sender, TrySendError},
+    duplex::Port,
+};
+
+/// The wire between the driver ports of two duplexes.
+///
+/// An element that finds the receiving channel full stays on the wire until the next pump.
+#[derive(Debug)]
+pub struct Loopback<T> {
+    /// Largest number of elements moved in each direction per pump
+    chunk: usize,
+    /// Element on its way from the first port to the second
+    a_to_b: Option<T>,
+    /// Element on its way from the second port to the first
+    b_to_a: Option<T>,
+}
+
+impl<T> Default for Loopback<T> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Loopback<T> {
+    /// Creates a wire that moves everything it can on each pump.
+    #[inline]
+    pub const fn new() -> Self {
+        Self::with_chunk(usize::MAX)
+    }
+
+    /// Creates a wire that moves at most `chunk` elements in each direction per pump.
+    #[inline]
+    pub const fn with_chunk(chunk: usize) -> Self {
+        Self {
+            chunk,
+            a_to_b: None,
+            b_to_a: None,
+        }
+    }
+
+    /// Number of elements held on the wire because their receiving channel was full.
+    #[inline]
+    pub fn in_flight(&self) -> usize {
+        usize::from(self.a_to_b.is_some()) + usize::from(self.b_to_a.is_some())
+    }
+}
+
+impl<T: Copy> Loopback<T> {
+    /// Moves elements between the driver ports `a` and `b` in both directions.
+    ///
+    /// Returns the number of elements delivered, which is zero once neither side has anything
+    /// left to send or room to receive it.
+    pub fn pump<const A_OUT: usize, const A_IN: usize, const B_OUT: usize, const B_IN: usize>(
+        &mut self,
+        a: &mut Port<'_, T, A_OUT, A_IN>,
+        b: &mut Port<'_, T, B_OUT, B_IN>,
+    ) -> usize {
+        let forward = transfer(&mut self.a_to_b, self.chunk, a.receiver(), b.sender());
+        let backward = transfer(&mut self.b_to_a, self.chunk, b.receiver(), a.sender());
+        forward + backward
+    }
+}
+
+/// Moves up to `chunk` elements from `from` to `to`, starting with the one held in `wire`.
+fn transfer<T: Copy, const FROM: usize, const TO: usize>(
+    wire: &mut Option<T>,
+    chunk: usize,
+    from: &mut Receiver<'_, T, FROM>,
+    to: &mut Sender<'_, T, TO>,
+) -> usize {
+    let mut moved = 0;
+    while moved < chunk {
+        let Some(item) = wire.take().or_else(|| from.try_recv().ok()) else {
+            break;
+        };
+        match to.try_send(item) {
+            Ok(()) => moved += 1,
+            Err(TrySendError::Full(item)) => {
+                *wire = Some(item);
+                break;
+            }
+            // Nobody is listening on the other end, as with a disconnected cable
+            Err(TrySendError::Closed(_)) => {}
+        }
+    }
+    moved
+}
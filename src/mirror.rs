@@ -0,0 +1,226 @@
+//! Byte buffer mapped twice in a row, so committed data and free space are always contiguous.
+//!
+//! The backing memory of a [`MirrorBip`] is mapped at two consecutive virtual addresses.
+//! A block that runs past the end of the first mapping continues into the second one, which
+//! shows the start of the same memory, so no block ever has to be split at the wrap point.
+//!
+//! ```rust
+//! use staticbip::mirror::MirrorBip;
+//!
+//! let mut buffer = MirrorBip::new(4096).unwrap();
+//! let capacity = buffer.capacity();
+//!
+//! buffer.reserve(capacity - 2);
+//! buffer.commit(capacity - 2);
+//! buffer.decommit(capacity - 4);
+//!
+//! // Runs across the end of the backing memory
+//! buffer.reserve(4).copy_from_slice(&[1, 2, 3, 4]);
+//! buffer.commit(4);
+//! assert_eq!(&buffer.read()[2..], &[1, 2, 3, 4]);
+//! ```
+
+use crate::BipBuf;
+use std::{
+    io,
+    os::raw::{c_char, c_int, c_long, c_uint, c_void},
+    ptr, slice,
+};
+
+extern "C" {
+    fn memfd_create(name: *const c_char, flags: c_uint) -> c_int;
+    fn ftruncate(fd: c_int, length: i64) -> c_int;
+    fn mmap(
+        addr: *mut c_void,
+        len: usize,
+        prot: c_int,
+        flags: c_int,
+        fd: c_int,
+        offset: i64,
+    ) -> *mut c_void;
+    fn munmap(addr: *mut c_void, len: usize) -> c_int;
+    fn close(fd: c_int) -> c_int;
+    fn sysconf(name: c_int) -> c_long;
+}
+
+const MFD_CLOEXEC: c_uint = 1;
+const PROT_NONE: c_int = 0;
+const PROT_READ: c_int = 1;
+const PROT_WRITE: c_int = 2;
+const MAP_SHARED: c_int = 0x01;
+const MAP_PRIVATE: c_int = 0x02;
+const MAP_FIXED: c_int = 0x10;
+const MAP_ANONYMOUS: c_int = 0x20;
+const MAP_FAILED: *mut c_void = !0 as *mut c_void;
+const SC_PAGESIZE: c_int = 30;
+
+/// A byte buffer whose backing memory is mapped twice back-to-back.
+#[derive(Debug)]
+pub struct MirrorBip {
+    /// Start of the first mapping
+    ptr: *mut u8,
+    /// Size of the backing memory
+    capacity: usize,
+    /// Offset of the oldest committed byte
+    start: usize,
+    /// Number of committed bytes
+    committed: usize,
+    /// Number of reserved bytes
+    reserved: usize,
+}
+
+// SAFETY: the mappings are owned by the buffer and only accessed through it.
+unsafe impl Send for MirrorBip {}
+
+impl MirrorBip {
+    /// Maps a buffer of at least `capacity` bytes, rounded up to a whole number of pages.
+    pub fn new(capacity: usize) -> io::Result<Self> {
+        // SAFETY: `sysconf` has no preconditions.
+        let page = unsafe { sysconf(SC_PAGESIZE) } as usize;
+        let capacity = capacity.max(1).div_ceil(page) * page;
+
+        // SAFETY: the name is nul-terminated.
+        let fd = unsafe { memfd_create(b"staticbip\0".as_ptr().cast(), MFD_CLOEXEC) };
+        if fd < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        let result = Self::map(fd, capacity);
+        // SAFETY: `fd` is open, and the mappings keep the memory alive once it is closed.
+        unsafe { close(fd) };
+        let ptr = result?;
+        Ok(Self {
+            ptr,
+            capacity,
+            start: 0,
+            committed: 0,
+            reserved: 0,
+        })
+    }
+
+    /// Maps the memory of `fd` twice into a fresh address range.
+    fn map(fd: c_int, capacity: usize) -> io::Result<*mut u8> {
+        // SAFETY: `fd` is an open memory file, and the fixed mappings replace pages of the
+        // range that was just reserved for them.
+        unsafe {
+            if ftruncate(fd, capacity as i64) != 0 {
+                return Err(io::Error::last_os_error());
+            }
+            let base = mmap(
+                ptr::null_mut(),
+                2 * capacity,
+                PROT_NONE,
+                MAP_PRIVATE | MAP_ANONYMOUS,
+                -1,
+                0,
+            );
+            if base == MAP_FAILED {
+                return Err(io::Error::last_os_error());
+            }
+            for offset in [0, capacity] {
+                let addr = base.cast::<u8>().add(offset).cast();
+                let flags = MAP_SHARED | MAP_FIXED;
+                if mmap(addr, capacity, PROT_READ | PROT_WRITE, flags, fd, 0) == MAP_FAILED {
+                    let error = io::Error::last_os_error();
+                    munmap(base, 2 * capacity);
+                    return Err(error);
+                }
+            }
+            Ok(base.cast())
+        }
+    }
+
+    /// Size of the backing memory.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Number of committed bytes.
+    #[inline]
+    pub fn committed(&self) -> usize {
+        self.committed
+    }
+
+    /// Number of reserved bytes.
+    #[inline]
+    pub fn reserved(&self) -> usize {
+        self.reserved
+    }
+
+    /// Returns a mutable buffer containing up to `count` bytes for storing data.
+    ///
+    /// All free space is available as a single block.
+    #[inline]
+    pub fn reserve(&mut self, count: usize) -> &mut [u8] {
+        self.reserved = count.min(self.capacity - self.committed);
+        let offset = (self.start + self.committed) % self.capacity;
+        // SAFETY: the block starts in the first mapping and is no longer than the memory.
+        unsafe { slice::from_raw_parts_mut(self.ptr.add(offset), self.reserved) }
+    }
+
+    /// Commits the data in the reservation, allowing it to be read later.
+    #[inline]
+    pub fn commit(&mut self, len: usize) {
+        self.committed += len.min(self.reserved);
+        self.reserved = 0;
+    }
+
+    /// Retrieves all committed data as a single block.
+    #[inline]
+    pub fn read(&mut self) -> &mut [u8] {
+        // SAFETY: the block starts in the first mapping and is no longer than the memory.
+        unsafe { slice::from_raw_parts_mut(self.ptr.add(self.start), self.committed) }
+    }
+
+    /// Marks the first `len` bytes of the available data as seen.
+    #[inline]
+    pub fn decommit(&mut self, len: usize) {
+        let len = len.min(self.committed);
+        self.start = (self.start + len) % self.capacity;
+        self.committed -= len;
+    }
+}
+
+impl Drop for MirrorBip {
+    fn drop(&mut self) {
+        // SAFETY: both mappings were created by `new` and are not referenced any more.
+        unsafe { munmap(self.ptr.cast(), 2 * self.capacity) };
+    }
+}
+
+impl BipBuf<u8> for MirrorBip {
+    #[inline]
+    fn capacity(&self) -> usize {
+        self.capacity()
+    }
+
+    #[inline]
+    fn committed(&self) -> usize {
+        self.committed()
+    }
+
+    #[inline]
+    fn reserved(&self) -> usize {
+        self.reserved()
+    }
+
+    #[inline]
+    fn reserve(&mut self, count: usize) -> &mut [u8] {
+        self.reserve(count)
+    }
+
+    #[inline]
+    fn commit(&mut self, len: usize) {
+        self.commit(len)
+    }
+
+    #[inline]
+    fn read(&mut self) -> &mut [u8] {
+        self.read()
+    }
+
+    #[inline]
+    fn decommit(&mut self, len: usize) {
+        self.decommit(len)
+    }
+}
@@ -0,0 +1,247 @@
+//! Bip-Buffer feeding a pool of workers.
+//!
+//! Producers [`claim`](MpmcBip::claim) and [`publish`](MpmcBip::publish) records as with an
+//! [`MpscBip`](crate::mpsc::MpscBip), and each published record is handed to exactly one
+//! consumer, the one that [`take`](MpmcBip::take)s it, rather than to every reader as with a
+//! [`BroadcastBip`](crate::broadcast::BroadcastBip). Records are taken in claim order, but
+//! the jobs can be finished in any order. Space is released in claim order, so a job still in
+//! progress holds back the space of the jobs finished after it, but not the taking of later
+//! records.
+//!
+//! As with an `MpscBip`, each call only needs a short critical section around the buffer;
+//! the data of a claim or job is filled or processed between the calls.
+//!
+//! ```rust
+//! use staticbip::mpmc::MpmcBip;
+//!
+//! // Up to 4 outstanding records in 16 elements
+//! let mut queue = MpmcBip::<u8, 16, 4>::default();
+//!
+//! let request = queue.claim(2).unwrap();
+//! queue.claimed(&request).copy_from_slice(b"ab");
+//! queue.publish(request, 2);
+//! let request = queue.claim(3).unwrap();
+//! queue.claimed(&request).copy_from_slice(b"cde");
+//! queue.publish(request, 3);
+//!
+//! // Each record goes to one worker
+//! let first = queue.take().unwrap();
+//! let second = queue.take().unwrap();
+//! assert!(queue.take().is_none());
+//! assert_eq!(queue.job(&second), b"cde");
+//!
+//! queue.finish(second);
+//! assert!(!queue.is_empty());
+//! queue.finish(first);
+//! assert!(queue.is_empty());
+//! ```
+//!
+//! Claims carry their own type, so a claim from an [`MpscBip`](crate::mpsc::MpscBip) cannot
+//! be published here.
+//!
+//! ```rust,compile_fail
+//! use staticbip::{mpmc::MpmcBip, mpsc::MpscBip};
+//!
+//! let mut queue = MpmcBip::<u8, 16, 4>::default();
+//! let mut other = MpscBip::<u8, 16, 4>::default();
+//! let claim = other.claim(2).unwrap();
+//! queue.publish(claim, 2);
+//! ```
+
+use crate::StaticBip;
+use core::array;
+
+/// Placement of an outstanding record.
+#[derive(Debug, Clone, Copy)]
+struct Record {
+    /// Start of the claimed space
+    start: usize,
+    /// End of the claimed space
+    end: usize,
+    /// End of the published data, if published
+    published: Option<usize>,
+    /// Whether the consumer that took the record has finished with it
+    done: bool,
+}
+
+/// Space claimed by a producer, which must be passed back to [`publish`](MpmcBip::publish).
+#[derive(Debug)]
+#[must_use = "a claim holds back every later record until it is published"]
+pub struct Claim {
+    slot: usize,
+    len: usize,
+}
+
+impl Claim {
+    /// Number of claimed elements.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Whether the claim is empty, which never happens for a successful claim.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+/// A record taken by a consumer, which must be passed back to [`finish`](MpmcBip::finish).
+#[derive(Debug)]
+#[must_use = "a job holds back the space of every later record until it is finished"]
+pub struct Job {
+    slot: usize,
+    len: usize,
+}
+
+impl Job {
+    /// Number of elements in the record.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Whether the record is empty, which never happens for a taken job.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+/// A Bip-Buffer with a fixed capacity and up to `N` outstanding records, each consumed once.
+#[derive(Debug)]
+pub struct MpmcBip<T, const CAP: usize, const N: usize> {
+    /// Space taken by outstanding records
+    bip: StaticBip<T, CAP>,
+    /// Ring of outstanding records in claim order
+    records: [Record; N],
+    /// Index of the oldest record
+    head: usize,
+    /// Number of outstanding records
+    len: usize,
+    /// Number of outstanding records handed to consumers, which always come first
+    taken: usize,
+}
+
+impl<T: Default, const CAP: usize, const N: usize> Default for MpmcBip<T, CAP, N> {
+    #[inline]
+    fn default() -> Self {
+        Self::new(array::from_fn(|_| T::default()))
+    }
+}
+
+impl<T, const CAP: usize, const N: usize> MpmcBip<T, CAP, N> {
+    /// Creates and allocates a new buffer of `T` elements.
+    #[inline]
+    pub const fn new(buffer: [T; CAP]) -> Self {
+        Self {
+            bip: StaticBip::new(buffer),
+            records: [Record {
+                start: 0,
+                end: 0,
+                published: None,
+                done: false,
+            }; N],
+            head: 0,
+            len: 0,
+            taken: 0,
+        }
+    }
+
+    /// Size of the backing store.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.bip.capacity()
+    }
+
+    /// Whether no records are outstanding.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Claims up to `count` contiguous slots for a new record.
+    ///
+    /// Returns `None` if there is no free space or `N` records are already outstanding.
+    pub fn claim(&mut self, count: usize) -> Option<Claim> {
+        if self.len == N {
+            return None;
+        }
+        let len = self.bip.reserve(count).len();
+        if len == 0 {
+            return None;
+        }
+
+        let start = self.bip.regions.reservation().start;
+        self.bip.commit(len);
+        let slot = (self.head + self.len) % N;
+        self.records[slot] = Record {
+            start,
+            end: start + len,
+            published: None,
+            done: false,
+        };
+        self.len += 1;
+        Some(Claim { slot, len })
+    }
+
+    /// Returns the space reserved by `claim` for writing.
+    #[inline]
+    pub fn claimed(&mut self, claim: &Claim) -> &mut [T] {
+        let record = self.records[claim.slot];
+        self.bip.slice_mut(record.start..record.end)
+    }
+
+    /// Publishes the first `len` elements of `claim` for a consumer to take.
+    ///
+    /// A `len` of `0` discards the record.
+    #[inline]
+    pub fn publish(&mut self, claim: Claim, len: usize) {
+        let record = &mut self.records[claim.slot];
+        record.published = Some(record.start + len.min(claim.len));
+    }
+
+    /// Hands the oldest record nobody has taken yet to the caller.
+    ///
+    /// Returns `None` if every published record has been taken, or the next one in claim
+    /// order is not published yet.
+    pub fn take(&mut self) -> Option<Job> {
+        while self.taken < self.len {
+            let slot = (self.head + self.taken) % N;
+            let record = self.records[slot];
+            let len = record.published? - record.start;
+            self.taken += 1;
+            if len > 0 {
+                return Some(Job { slot, len });
+            }
+            self.records[slot].done = true;
+            self.release();
+        }
+        None
+    }
+
+    /// Returns the data of `job`.
+    #[inline]
+    pub fn job(&mut self, job: &Job) -> &mut [T] {
+        let start = self.records[job.slot].start;
+        self.bip.slice_mut(start..start + job.len)
+    }
+
+    /// Marks `job` as processed, releasing its space once every earlier record is released.
+    #[inline]
+    pub fn finish(&mut self, job: Job) {
+        self.records[job.slot].done = true;
+        self.release();
+    }
+
+    /// Frees leading records that are finished.
+    fn release(&mut self) {
+        while self.len > 0 && self.records[self.head].done {
+            let record = self.records[self.head];
+            self.bip.decommit(record.end - record.start);
+            self.head = (self.head + 1) % N;
+            self.len -= 1;
+            self.taken -= 1;
+        }
+    }
+}
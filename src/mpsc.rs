@@ -0,0 +1,238 @@
+//! Bip-Buffer fed by several producers.
+//!
+//! Producers [`claim`](MpscBip::claim) space, fill it, and [`publish`](MpscBip::publish) it
+//! independently of each other, in any order. The consumer reads published records in claim
+//! order, one record at a time, and a record that is still being written holds back the ones
+//! claimed after it.
+//!
+//! When producers run in different interrupt priorities, each call only needs a short critical
+//! section: a preempting producer can claim and publish its own record while an earlier claim
+//! is still being filled.
+//!
+//! ```rust
+//! use staticbip::mpsc::MpscBip;
+//!
+//! // Up to 4 outstanding records in 16 elements
+//! let mut buffer = MpscBip::<u8, 16, 4>::default();
+//!
+//! let first = buffer.claim(3).unwrap();
+//! let second = buffer.claim(2).unwrap();
+//!
+//! buffer.claimed(&second).copy_from_slice(&[4, 5]);
+//! buffer.publish(second, 2);
+//! assert!(buffer.read().is_empty());
+//!
+//! buffer.claimed(&first).copy_from_slice(&[1, 2, 3]);
+//! buffer.publish(first, 3);
+//! assert_eq!(buffer.read(), &[1, 2, 3]);
+//!
+//! buffer.decommit(3);
+//! assert_eq!(buffer.read(), &[4, 5]);
+//! ```
+//!
+//! # Pipelining
+//!
+//! A single producer can use the same claims to keep several reservations outstanding,
+//! such as preparing the next frame while DMA is still filling the previous one. Records are
+//! read in claim order regardless of when they are published. A DMA engine writes a claim
+//! through [`claimed_ptr`](MpscBip::claimed_ptr).
+//!
+//! ```rust
+//! use staticbip::mpsc::MpscBip;
+//!
+//! let mut buffer = MpscBip::<u8, 16, 2>::default();
+//!
+//! let dma = buffer.claim(4).unwrap();
+//! let ptr = buffer.claimed_ptr(&dma);
+//! // Start DMA into `ptr`, then prepare the next frame meanwhile
+//! let next = buffer.claim(2).unwrap();
+//! buffer.claimed(&next).copy_from_slice(&[5, 6]);
+//! buffer.publish(next, 2);
+//!
+//! // DMA completes
+//! unsafe { core::ptr::copy_nonoverlapping([1, 2, 3, 4].as_ptr(), ptr, 4) };
+//! buffer.publish(dma, 4);
+//!
+//! assert_eq!(buffer.read(), &[1, 2, 3, 4]);
+//! ```
+
+use crate::StaticBip;
+use core::array;
+
+/// Placement of an outstanding record.
+#[derive(Debug, Clone, Copy)]
+struct Record {
+    /// First unread element
+    start: usize,
+    /// End of the claimed space
+    end: usize,
+    /// End of the published data, if published
+    published: Option<usize>,
+}
+
+/// Space claimed by a producer, which must be passed back to [`publish`](MpscBip::publish).
+#[derive(Debug)]
+#[must_use = "a claim holds back every later record until it is published"]
+pub struct Claim {
+    slot: usize,
+    len: usize,
+}
+
+impl Claim {
+    /// Number of claimed elements.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Whether the claim is empty, which never happens for a successful claim.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+/// A Bip-Buffer with a fixed capacity and up to `N` outstanding records.
+#[derive(Debug)]
+pub struct MpscBip<T, const CAP: usize, const N: usize> {
+    /// Space taken by outstanding records
+    bip: StaticBip<T, CAP>,
+    /// Ring of outstanding records in claim order
+    records: [Record; N],
+    /// Index of the oldest record
+    head: usize,
+    /// Number of outstanding records
+    len: usize,
+}
+
+impl<T: Default, const CAP: usize, const N: usize> Default for MpscBip<T, CAP, N> {
+    #[inline]
+    fn default() -> Self {
+        Self::new(array::from_fn(|_| T::default()))
+    }
+}
+
+impl<T, const CAP: usize, const N: usize> MpscBip<T, CAP, N> {
+    /// Creates and allocates a new buffer of `T` elements.
+    #[inline]
+    pub const fn new(buffer: [T; CAP]) -> Self {
+        Self {
+            bip: StaticBip::new(buffer),
+            records: [Record {
+                start: 0,
+                end: 0,
+                published: None,
+            }; N],
+            head: 0,
+            len: 0,
+        }
+    }
+
+    /// Size of the backing store.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.bip.capacity()
+    }
+
+    /// Claims up to `count` contiguous slots for a new record.
+    ///
+    /// Returns `None` if there is no free space or `N` records are already outstanding.
+    pub fn claim(&mut self, count: usize) -> Option<Claim> {
+        if self.len == N {
+            return None;
+        }
+        let len = self.bip.reserve(count).len();
+        if len == 0 {
+            return None;
+        }
+
+        let start = self.bip.regions.reservation().start;
+        self.bip.commit(len);
+        let slot = (self.head + self.len) % N;
+        self.records[slot] = Record {
+            start,
+            end: start + len,
+            published: None,
+        };
+        self.len += 1;
+        Some(Claim { slot, len })
+    }
+
+    /// Returns the space reserved by `claim` for writing.
+    #[inline]
+    pub fn claimed(&mut self, claim: &Claim) -> &mut [T] {
+        let record = self.records[claim.slot];
+        self.bip.slice_mut(record.start..record.end)
+    }
+
+    /// Returns a pointer to the space reserved by `claim`, for writes by hardware such as DMA.
+    ///
+    /// The pointer stays valid until the claim is published, as long as the buffer is not
+    /// moved. It must not be written through while [`claimed`](Self::claimed) returns a
+    /// slice of the same claim.
+    #[inline]
+    pub fn claimed_ptr(&mut self, claim: &Claim) -> *mut T {
+        self.claimed(claim).as_mut_ptr()
+    }
+
+    /// Publishes the first `len` elements of `claim`, releasing the rest.
+    ///
+    /// A `len` of `0` discards the record.
+    pub fn publish(&mut self, claim: Claim, len: usize) {
+        let record = &mut self.records[claim.slot];
+        record.published = Some(record.start + len.min(claim.len));
+        self.release();
+    }
+
+    /// Retrieves the unread part of the oldest record, if it has been published.
+    #[inline]
+    pub fn read(&mut self) -> &mut [T] {
+        match self.front() {
+            Some(Record {
+                start,
+                published: Some(end),
+                ..
+            }) => self.bip.slice_mut(start..end),
+            _ => &mut [],
+        }
+    }
+
+    /// Marks the first `len` elements of the oldest record as seen.
+    ///
+    /// The record is released once all of it has been seen.
+    pub fn decommit(&mut self, len: usize) {
+        if let Some(Record {
+            start,
+            published: Some(end),
+            ..
+        }) = self.front()
+        {
+            let len = len.min(end - start);
+            self.records[self.head].start += len;
+            self.bip.decommit(len);
+            self.release();
+        }
+    }
+
+    /// The oldest outstanding record.
+    #[inline]
+    fn front(&self) -> Option<Record> {
+        if self.len == 0 {
+            None
+        } else {
+            Some(self.records[self.head])
+        }
+    }
+
+    /// Frees leading records that are published and fully read.
+    fn release(&mut self) {
+        while let Some(record) = self.front() {
+            if record.published != Some(record.start) {
+                break;
+            }
+            self.bip.decommit(record.end - record.start);
+            self.head = (self.head + 1) % N;
+            self.len -= 1;
+        }
+    }
+}
@@ -0,0 +1,139 @@
+//! Parallel buffers kept in lockstep, such as the channels of deinterleaved audio.
+//!
+//! A [`MultiBip`] holds one buffer per channel and performs every reservation, commit and
+//! read on all of them at once. The regions of all channels are therefore always laid out the
+//! same way, so sample `i` of one channel is sample `i` of every other, including across the
+//! wrap to the start of the backing store.
+//!
+//! ```rust
+//! use staticbip::multi::MultiBip;
+//!
+//! // Stereo, 8 samples per channel
+//! let mut audio = MultiBip::<f32, 8, 2>::default();
+//!
+//! let [left, right] = audio.reserve(3);
+//! left.copy_from_slice(&[0.1, 0.2, 0.3]);
+//! right.copy_from_slice(&[-0.1, -0.2, -0.3]);
+//! audio.commit(3);
+//!
+//! // Interleaved input is split across the channels
+//! assert_eq!(audio.write_interleaved(&[0.4, -0.4, 0.5, -0.5]), 2);
+//!
+//! let [left, right] = audio.read();
+//! assert_eq!(left, &[0.1, 0.2, 0.3, 0.4, 0.5]);
+//! assert_eq!(right, &[-0.1, -0.2, -0.3, -0.4, -0.5]);
+//! audio.decommit(5);
+//! ```
+
+use crate::StaticBip;
+use core::array;
+
+/// `CH` buffers of `CAP` elements each, reserved, committed and read together.
+#[derive(Debug, Clone)]
+pub struct MultiBip<T, const CAP: usize, const CH: usize> {
+    channels: [StaticBip<T, CAP>; CH],
+}
+
+impl<T: Default, const CAP: usize, const CH: usize> Default for MultiBip<T, CAP, CH> {
+    #[inline]
+    fn default() -> Self {
+        Self::new(array::from_fn(|_| array::from_fn(|_| T::default())))
+    }
+}
+
+impl<T, const CAP: usize, const CH: usize> MultiBip<T, CAP, CH> {
+    /// Creates empty buffers over one backing store per channel.
+    #[inline]
+    pub fn new(buffers: [[T; CAP]; CH]) -> Self {
+        Self {
+            channels: buffers.map(StaticBip::new),
+        }
+    }
+
+    /// Size of the backing store of each channel.
+    #[inline]
+    pub const fn capacity(&self) -> usize {
+        CAP
+    }
+
+    /// Number of committed elements in each channel.
+    #[inline]
+    pub fn committed(&self) -> usize {
+        self.channels.first().map_or(0, StaticBip::committed)
+    }
+
+    /// Number of reserved elements in each channel.
+    #[inline]
+    pub fn reserved(&self) -> usize {
+        self.channels.first().map_or(0, StaticBip::reserved)
+    }
+
+    /// Reserves up to `count` slots in every channel, returning the same range of each.
+    #[inline]
+    pub fn reserve(&mut self, count: usize) -> [&mut [T]; CH] {
+        self.channels
+            .each_mut()
+            .map(|channel| channel.reserve(count))
+    }
+
+    /// Commits the first `len` reserved elements of every channel.
+    #[inline]
+    pub fn commit(&mut self, len: usize) {
+        for channel in &mut self.channels {
+            channel.commit(len);
+        }
+    }
+
+    /// Retrieves the first contiguous block of committed data of every channel.
+    #[inline]
+    pub fn read(&mut self) -> [&mut [T]; CH] {
+        self.channels.each_mut().map(StaticBip::read)
+    }
+
+    /// Marks the first `len` elements of every channel's block as seen.
+    #[inline]
+    pub fn decommit(&mut self, len: usize) {
+        for channel in &mut self.channels {
+            channel.decommit(len);
+        }
+    }
+
+    /// Discards the committed data and the reservation of every channel.
+    #[inline]
+    pub fn clear(&mut self) {
+        for channel in &mut self.channels {
+            channel.clear();
+        }
+    }
+
+    /// Returns the buffers of the channels.
+    #[inline]
+    pub fn channels(&self) -> &[StaticBip<T, CAP>; CH] {
+        &self.channels
+    }
+
+    /// Returns the buffers of the channels.
+    #[inline]
+    pub fn into_inner(self) -> [StaticBip<T, CAP>; CH] {
+        self.channels
+    }
+}
+
+impl<T: Copy, const CAP: usize, const CH: usize> MultiBip<T, CAP, CH> {
+    /// Commits whole frames of `CH` interleaved samples from `samples` into a single
+    /// reservation, one sample per channel per frame.
+    ///
+    /// Returns the number of frames committed. A trailing partial frame is ignored.
+    pub fn write_interleaved(&mut self, samples: &[T]) -> usize {
+        let frames = samples.len().checked_div(CH).unwrap_or(0);
+        let mut reserved = self.reserve(frames);
+        let len = reserved.first().map_or(0, |channel| channel.len());
+        for (index, channel) in reserved.iter_mut().enumerate() {
+            for (slot, frame) in channel.iter_mut().zip(samples.chunks_exact(CH)) {
+                *slot = frame[index];
+            }
+        }
+        self.commit(len);
+        len
+    }
+}
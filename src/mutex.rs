@@ -0,0 +1,248 @@
+//! Shared access behind a lock chosen by the caller.
+//!
+//! A [`MutexBip`] produces and consumes through `&self` like a [`SpinBip`](crate::spin::SpinBip),
+//! with the lock as a type parameter implementing [`RawMutex`]. The trait has the shape of the
+//! one in `embassy-sync`, so the same buffer type serves interrupt handlers behind a critical
+//! section, a single executor without any locking, or threads behind a `std` mutex.
+//!
+//! ```rust
+//! use staticbip::mutex::{MutexBip, NoopRawMutex, SpinRawMutex};
+//!
+//! static SHARED: MutexBip<SpinRawMutex, u8, 4> = MutexBip::new([0; 4]);
+//! std::thread::spawn(|| SHARED.push(1).unwrap()).join().unwrap();
+//! assert_eq!(SHARED.pop(), Some(1));
+//!
+//! // Tasks of one executor share the buffer without a lock
+//! let local = MutexBip::<NoopRawMutex, u8, 4>::new([0; 4]);
+//! local.with(|bip| bip.extend(&[2, 3]));
+//! assert_eq!(local.committed(), 2);
+//! ```
+//!
+//! A lock masking interrupts wraps the platform's critical section:
+//!
+//! ```rust
+//! use staticbip::mutex::{MutexBip, RawMutex};
+//!
+//! struct CriticalSectionRawMutex;
+//!
+//! unsafe impl RawMutex for CriticalSectionRawMutex {
+//!     const INIT: Self = Self;
+//!
+//!     fn lock<R>(&self, f: impl FnOnce() -> R) -> R {
+//!         // In firmware:
+//!         // critical_section::with(|_| f())
+//!         f()
+//!     }
+//! }
+//!
+//! static FROM_IRQ: MutexBip<CriticalSectionRawMutex, u8, 16> = MutexBip::new([0; 16]);
+//! FROM_IRQ.push(1).unwrap();
+//! ```
+
+#[cfg(target_has_atomic = "ptr")]
+use crate::spin::{Relax, Spin};
+use crate::StaticBip;
+#[cfg(target_has_atomic = "ptr")]
+use core::sync::atomic::{AtomicBool, Ordering};
+use core::{cell::RefCell, fmt, marker::PhantomData};
+
+/// A lock that runs closures one at a time.
+///
+/// This has the shape of `embassy_sync::blocking_mutex::raw::RawMutex`, so its
+/// implementations are adapted by forwarding `lock`.
+///
+/// # Safety
+///
+/// While a closure passed to [`lock`](Self::lock) runs, no other call to `lock` on the same
+/// mutex may run a closure, from any thread or interrupt that can reach the mutex.
+pub unsafe trait RawMutex {
+    /// An unlocked mutex, usable in a `static`.
+    const INIT: Self;
+
+    /// Runs `f` while holding the lock.
+    fn lock<R>(&self, f: impl FnOnce() -> R) -> R;
+}
+
+/// A mutex for a single thread, which needs no locking.
+///
+/// It is not `Sync`, so a buffer using it cannot be shared between threads.
+#[derive(Debug, Default)]
+pub struct NoopRawMutex {
+    _not_sync: PhantomData<*mut ()>,
+}
+
+// SAFETY: the mutex is not `Sync`, so every call runs on the thread that owns it, and closures
+// do not overlap.
+unsafe impl RawMutex for NoopRawMutex {
+    const INIT: Self = Self {
+        _not_sync: PhantomData,
+    };
+
+    #[inline]
+    fn lock<R>(&self, f: impl FnOnce() -> R) -> R {
+        f()
+    }
+}
+
+/// A spinlock, running a [`Relax`] strategy while waiting.
+///
+/// Locking it again from inside `lock` never returns.
+#[cfg(target_has_atomic = "ptr")]
+pub struct SpinRawMutex<S = Spin> {
+    locked: AtomicBool,
+    relax: PhantomData<fn() -> S>,
+}
+
+#[cfg(target_has_atomic = "ptr")]
+impl<S> fmt::Debug for SpinRawMutex<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SpinRawMutex")
+            .field("locked", &self.locked.load(Ordering::Relaxed))
+            .finish()
+    }
+}
+
+/// Releases a [`SpinRawMutex`] when dropped, including on unwinding.
+#[cfg(target_has_atomic = "ptr")]
+struct Unlock<'a>(&'a AtomicBool);
+
+#[cfg(target_has_atomic = "ptr")]
+impl Drop for Unlock<'_> {
+    #[inline]
+    fn drop(&mut self) {
+        self.0.store(false, Ordering::Release);
+    }
+}
+
+// SAFETY: `locked` is set by one caller at a time and cleared once its closure returns.
+#[cfg(target_has_atomic = "ptr")]
+unsafe impl<S: Relax> RawMutex for SpinRawMutex<S> {
+    const INIT: Self = Self {
+        locked: AtomicBool::new(false),
+        relax: PhantomData,
+    };
+
+    fn lock<R>(&self, f: impl FnOnce() -> R) -> R {
+        while self
+            .locked
+            .compare_exchange_weak(false, true, Ordering::Acquire, Ordering::Relaxed)
+            .is_err()
+        {
+            while self.locked.load(Ordering::Relaxed) {
+                S::relax();
+            }
+        }
+        let _unlock = Unlock(&self.locked);
+        f()
+    }
+}
+
+/// A `std` mutex, parking waiting threads.
+///
+/// A panic inside `lock` does not poison it.
+#[cfg(feature = "std")]
+#[derive(Debug)]
+pub struct StdRawMutex(std::sync::Mutex<()>);
+
+// SAFETY: the closure runs while the guard of the inner mutex is held.
+#[cfg(feature = "std")]
+unsafe impl RawMutex for StdRawMutex {
+    const INIT: Self = Self(std::sync::Mutex::new(()));
+
+    #[inline]
+    fn lock<R>(&self, f: impl FnOnce() -> R) -> R {
+        let _guard = self
+            .0
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        f()
+    }
+}
+
+/// A Bip-Buffer shared through a [`RawMutex`].
+pub struct MutexBip<M, T, const CAP: usize> {
+    mutex: M,
+    /// Only borrowed while the mutex is held, which turns a nested `with` into a panic
+    bip: RefCell<StaticBip<T, CAP>>,
+}
+
+// SAFETY: the buffer and its borrow flag are only accessed while holding the mutex, and the
+// mutex being `Sync` is what allows callers on other threads.
+unsafe impl<M: RawMutex + Sync, T: Send, const CAP: usize> Sync for MutexBip<M, T, CAP> {}
+
+impl<M: RawMutex, T, const CAP: usize> MutexBip<M, T, CAP> {
+    /// Creates a new buffer of `T` elements, usable in a `static`.
+    #[inline]
+    pub const fn new(buffer: [T; CAP]) -> Self {
+        Self {
+            mutex: M::INIT,
+            bip: RefCell::new(StaticBip::new(buffer)),
+        }
+    }
+
+    /// Calls `f` with the buffer while holding the mutex.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called from inside another `with` on the same buffer, unless the mutex
+    /// itself blocks first.
+    #[inline]
+    pub fn with<R>(&self, f: impl FnOnce(&mut StaticBip<T, CAP>) -> R) -> R {
+        self.mutex.lock(|| f(&mut self.bip.borrow_mut()))
+    }
+
+    /// Appends `item`, handing it back if the buffer is full.
+    #[inline]
+    pub fn push(&self, item: T) -> Result<(), T> {
+        self.with(|bip| bip.enqueue(item))
+    }
+
+    /// Number of committed elements.
+    #[inline]
+    pub fn committed(&self) -> usize {
+        self.with(|bip| bip.committed())
+    }
+
+    /// Whether there are no committed elements.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.committed() == 0
+    }
+
+    /// Returns the wrapped buffer.
+    #[inline]
+    pub fn into_inner(self) -> StaticBip<T, CAP> {
+        self.bip.into_inner()
+    }
+}
+
+impl<M: RawMutex, T: Copy, const CAP: usize> MutexBip<M, T, CAP> {
+    /// Removes the oldest element.
+    #[inline]
+    pub fn pop(&self) -> Option<T> {
+        self.with(|bip| bip.dequeue())
+    }
+}
+
+impl<M: RawMutex, T: Default, const CAP: usize> Default for MutexBip<M, T, CAP> {
+    #[inline]
+    fn default() -> Self {
+        Self::from(StaticBip::default())
+    }
+}
+
+impl<M: RawMutex, T, const CAP: usize> From<StaticBip<T, CAP>> for MutexBip<M, T, CAP> {
+    #[inline]
+    fn from(bip: StaticBip<T, CAP>) -> Self {
+        Self {
+            mutex: M::INIT,
+            bip: RefCell::new(bip),
+        }
+    }
+}
+
+impl<M, T, const CAP: usize> fmt::Debug for MutexBip<M, T, CAP> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MutexBip").finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,74 @@
+//! Non-blocking operations that report an empty or full buffer as [`WouldBlock`].
+//!
+//! These follow the convention of the `nb` crate used by embedded-hal driver loops: an
+//! operation that cannot make progress yet fails with `WouldBlock` instead of succeeding with
+//! nothing, so it can be retried until it completes.
+//!
+//! ```rust
+//! use staticbip::{nb::WouldBlock, StaticBip};
+//!
+//! let mut buffer = StaticBip::<u8, 2>::default();
+//!
+//! assert_eq!(buffer.try_pop(), Err(WouldBlock));
+//! assert_eq!(buffer.try_write(&[1, 2, 3]), Ok(2));
+//! assert_eq!(buffer.try_push(4), Err(WouldBlock));
+//!
+//! assert_eq!(buffer.try_pop(), Ok(1));
+//! ```
+
+use crate::StaticBip;
+use core::fmt;
+
+/// The buffer is empty or full, so the operation has to be retried later.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WouldBlock;
+
+impl fmt::Display for WouldBlock {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("operation would block")
+    }
+}
+
+impl<T: Copy, const CAP: usize> StaticBip<T, CAP> {
+    /// Appends a single element.
+    #[inline]
+    pub fn try_push(&mut self, item: T) -> Result<(), WouldBlock> {
+        self.enqueue(item).map_err(|_| WouldBlock)
+    }
+
+    /// Removes the oldest element.
+    #[inline]
+    pub fn try_pop(&mut self) -> Result<T, WouldBlock> {
+        self.pop().map(|item| *item).ok_or(WouldBlock)
+    }
+
+    /// Appends as many elements of `data` as fit in one contiguous block.
+    ///
+    /// Returns the number of elements written, failing only if none fit.
+    pub fn try_write(&mut self, data: &[T]) -> Result<usize, WouldBlock> {
+        let reserved = self.reserve(data.len());
+        let len = reserved.len();
+        reserved.copy_from_slice(&data[..len]);
+        self.commit(len);
+        if len == 0 && !data.is_empty() {
+            Err(WouldBlock)
+        } else {
+            Ok(len)
+        }
+    }
+
+    /// Moves as many elements of the first committed block as fit into `out`.
+    ///
+    /// Returns the number of elements read, failing only if none are available.
+    pub fn try_read(&mut self, out: &mut [T]) -> Result<usize, WouldBlock> {
+        let block = self.read();
+        let len = block.len().min(out.len());
+        out[..len].copy_from_slice(&block[..len]);
+        self.decommit(len);
+        if len == 0 && !out.is_empty() {
+            Err(WouldBlock)
+        } else {
+            Ok(len)
+        }
+    }
+}
@@ -0,0 +1,145 @@
+//! Policies for writes that do not fit.
+//!
+//! [`StaticBip::write_slice`] takes the policy as a type parameter, so the handling of a full
+//! buffer is visible at each call site and only the selected branch is compiled in.
+//!
+//! ```rust
+//! use staticbip::{
+//!     overflow::{Overwrite, Reject, Truncate},
+//!     StaticBip,
+//! };
+//!
+//! let mut buffer = StaticBip::<u8, 4>::default();
+//! assert_eq!(buffer.write_slice::<Truncate>(&[1, 2, 3]), Ok(3));
+//! assert!(buffer.write_slice::<Reject>(&[4, 5]).is_err());
+//! assert_eq!(buffer.write_slice::<Overwrite>(&[4, 5]), Ok(2));
+//!
+//! assert_eq!(buffer.iter().copied().collect::<Vec<_>>(), [2, 3, 4, 5]);
+//! ```
+
+use crate::{nb::WouldBlock, StaticBip};
+
+/// What happens when a write does not fit.
+pub trait Overflow {
+    /// Makes room for a write of `len` elements, of which `fits` fit in the free space.
+    ///
+    /// Returns the number of elements to write, or `None` to reject the write.
+    fn admit<T, const CAP: usize>(
+        bip: &mut StaticBip<T, CAP>,
+        len: usize,
+        fits: usize,
+    ) -> Option<usize>;
+}
+
+/// Rejects writes that do not fit completely.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Reject {}
+
+/// Writes what fits and drops the rest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Truncate {}
+
+/// Drops the oldest committed elements to make room.
+///
+/// Of a write longer than the buffer, only the newest elements are kept.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Overwrite {}
+
+/// Panics on writes that do not fit in debug builds, and rejects them otherwise.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DebugPanic {}
+
+impl Overflow for Reject {
+    #[inline]
+    fn admit<T, const CAP: usize>(
+        _: &mut StaticBip<T, CAP>,
+        len: usize,
+        fits: usize,
+    ) -> Option<usize> {
+        if fits < len {
+            None
+        } else {
+            Some(len)
+        }
+    }
+}
+
+impl Overflow for Truncate {
+    #[inline]
+    fn admit<T, const CAP: usize>(
+        _: &mut StaticBip<T, CAP>,
+        len: usize,
+        fits: usize,
+    ) -> Option<usize> {
+        Some(len.min(fits))
+    }
+}
+
+impl Overflow for Overwrite {
+    fn admit<T, const CAP: usize>(
+        bip: &mut StaticBip<T, CAP>,
+        len: usize,
+        mut fits: usize,
+    ) -> Option<usize> {
+        let len = len.min(CAP);
+        while fits < len && bip.committed() != 0 {
+            bip.regions.consume(len - fits);
+            fits = bip.writable(len);
+        }
+        Some(len.min(fits))
+    }
+}
+
+impl Overflow for DebugPanic {
+    #[inline]
+    fn admit<T, const CAP: usize>(
+        bip: &mut StaticBip<T, CAP>,
+        len: usize,
+        fits: usize,
+    ) -> Option<usize> {
+        debug_assert!(fits >= len, "write of {} elements does not fit", len);
+        Reject::admit(bip, len, fits)
+    }
+}
+
+impl<T: Copy, const CAP: usize> StaticBip<T, CAP> {
+    /// Appends `data`, handling a lack of space as selected by `P`.
+    ///
+    /// The data may be split across both regions. Returns the number of elements written, or
+    /// [`WouldBlock`] if `P` rejects the write.
+    pub fn write_slice<P: Overflow>(&mut self, data: &[T]) -> Result<usize, WouldBlock> {
+        let fits = self.writable(data.len());
+        let len = P::admit(self, data.len(), fits).ok_or(WouldBlock)?;
+        let data = &data[data.len() - len..];
+
+        let mut written = 0;
+        while written < len {
+            let reserved = self.reserve(len - written);
+            let n = reserved.len();
+            if n == 0 {
+                break;
+            }
+            reserved.copy_from_slice(&data[written..written + n]);
+            self.commit(n);
+            written += n;
+        }
+        Ok(written)
+    }
+}
+
+impl<T, const CAP: usize> StaticBip<T, CAP> {
+    /// Number of elements of a `len` element write that fit in the free space.
+    fn writable(&self, len: usize) -> usize {
+        let mut regions = self.regions.clone();
+        let mut fits = 0;
+        while fits < len {
+            let reserved = regions.reserve(CAP, len - fits).len();
+            if reserved == 0 {
+                break;
+            }
+            regions.commit(reserved);
+            fits += reserved;
+        }
+        fits
+    }
+}
@@ -0,0 +1,86 @@
+//! Splitting large writes into MTU-sized frames.
+//!
+//! [`Packetizer`] takes writes of any length and splits them into [`Framed`] frames that are
+//! at most `mtu` bytes long including the frame overhead. The first payload byte of every
+//! frame holds flags: [`MORE`] marks a fragment that is followed by another fragment of the
+//! same write. Each fragment is written straight into a reservation of the buffer.
+//!
+//! ```rust
+//! use staticbip::{frame::Framed, packet::{Packetizer, MORE}, StaticBip};
+//!
+//! let mut buffer = StaticBip::<u8, 64>::default();
+//! let mut packetizer = Packetizer::<(), 64>::new(&mut buffer, 9);
+//! assert_eq!(packetizer.write(b"hello world"), 11);
+//!
+//! let mut framed = Framed::<_>::new(&mut buffer);
+//! assert_eq!(framed.read().unwrap().unwrap(), b"\x01hello ");
+//! framed.decommit();
+//! assert_eq!(framed.read().unwrap().unwrap(), b"\x00world");
+//! ```
+
+use crate::{
+    frame::{Checksum, Framed, MAX_PAYLOAD},
+    StaticBip,
+};
+
+/// Flag set on every fragment except the last one of a write.
+pub const MORE: u8 = 0x01;
+
+/// Size of the flags byte at the start of each payload.
+pub const FLAGS_LEN: usize = 1;
+
+/// Producer-side adapter splitting writes into frames no larger than an MTU.
+pub struct Packetizer<'a, C, const CAP: usize> {
+    framed: Framed<'a, StaticBip<u8, CAP>, C>,
+    /// Largest fragment payload, excluding the flags byte
+    max_fragment: usize,
+}
+
+impl<'a, C: Checksum, const CAP: usize> Packetizer<'a, C, CAP> {
+    /// Wraps a byte buffer, producing frames of at most `mtu` bytes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `mtu` leaves no room for at least one payload byte after the frame overhead
+    /// and the flags byte.
+    pub fn new(bip: &'a mut StaticBip<u8, CAP>, mtu: usize) -> Self {
+        let overhead = Framed::<StaticBip<u8, CAP>, C>::OVERHEAD + FLAGS_LEN;
+        assert!(mtu > overhead, "MTU too small for the frame overhead");
+        Self {
+            framed: Framed::new(bip),
+            max_fragment: (mtu - overhead).min(MAX_PAYLOAD - FLAGS_LEN),
+        }
+    }
+
+    /// Largest number of bytes of a write carried by one frame.
+    #[inline]
+    pub fn max_fragment(&self) -> usize {
+        self.max_fragment
+    }
+
+    /// Writes `data` as a sequence of fragments and returns how many bytes were accepted.
+    ///
+    /// Fragments are written until `data` is exhausted or the buffer runs out of space. If
+    /// fewer bytes than `data.len()` are accepted, the last fragment written carries [`MORE`]
+    /// and the rest of `data` should be passed to the next call.
+    pub fn write(&mut self, data: &[u8]) -> usize {
+        let mut written = 0;
+        while written < data.len() {
+            let rest = &data[written..];
+            let payload = self
+                .framed
+                .reserve(FLAGS_LEN + rest.len().min(self.max_fragment));
+            if payload.len() <= FLAGS_LEN {
+                self.framed.commit(0);
+                break;
+            }
+
+            let len = payload.len() - FLAGS_LEN;
+            payload[0] = if len < rest.len() { MORE } else { 0 };
+            payload[FLAGS_LEN..].copy_from_slice(&rest[..len]);
+            self.framed.commit(FLAGS_LEN + len);
+            written += len;
+        }
+        written
+    }
+}
@@ -0,0 +1,238 @@
+//! Power-loss tolerant queue journaled to NOR flash.
+//!
+//! A [`PersistentBip`] collects data in RAM and [`flush`](PersistentBip::flush)es it to
+//! flash as records, so it survives a reset and is handed to the consumer again after
+//! [`open`](PersistentBip::open). The RAM buffer combines small writes into one record;
+//! data that has not been flushed is lost on power loss.
+//!
+//! Flash is accessed through [`NorFlash`], which mirrors the read, write and erase methods of
+//! the `embedded-storage` traits so a driver is adapted in a few lines. The flash must allow
+//! writing single bytes, and writes may only clear bits, as in NOR flash.
+//!
+//! Each record is a state byte, a little-endian `u16` payload length and the payload. The
+//! state is written last, so a record torn by a reset is skipped. Records are marked as
+//! consumed in place, and the flash is erased once every record has been consumed and the
+//! next one does not fit.
+//!
+//! ```rust
+//! use staticbip::persist::{NorFlash, PersistentBip};
+//! # use core::convert::Infallible;
+//! # struct Flash([u8; 64]);
+//! # impl NorFlash for Flash {
+//! #     type Error = Infallible;
+//! #     fn capacity(&self) -> usize { self.0.len() }
+//! #     fn read(&mut self, offset: u32, bytes: &mut [u8]) -> Result<(), Infallible> {
+//! #         let offset = offset as usize;
+//! #         bytes.copy_from_slice(&self.0[offset..offset + bytes.len()]);
+//! #         Ok(())
+//! #     }
+//! #     fn write(&mut self, offset: u32, bytes: &[u8]) -> Result<(), Infallible> {
+//! #         for (cell, byte) in self.0[offset as usize..].iter_mut().zip(bytes) { *cell &= byte; }
+//! #         Ok(())
+//! #     }
+//! #     fn erase(&mut self, from: u32, to: u32) -> Result<(), Infallible> {
+//! #         self.0[from as usize..to as usize].fill(0xFF);
+//! #         Ok(())
+//! #     }
+//! # }
+//!
+//! let mut queue = PersistentBip::<_, 16>::open(Flash([0xFF; 64])).unwrap();
+//! queue.get_mut().extend(b"boot");
+//! queue.flush().unwrap();
+//!
+//! // After a reset
+//! let mut queue = PersistentBip::<_, 16>::open(queue.into_flash()).unwrap();
+//! let mut record = [0; 16];
+//! assert_eq!(queue.pop(&mut record), Ok(Some(4)));
+//! assert_eq!(&record[..4], b"boot");
+//! ```
+
+use crate::StaticBip;
+use core::fmt;
+
+/// Size of a record header in bytes.
+pub const HEADER_LEN: usize = 3;
+
+/// State of a record whose payload is complete.
+const VALID: u8 = 0x7F;
+/// State of a record that has been consumed.
+const CONSUMED: u8 = 0x00;
+/// Value of erased flash.
+const ERASED: u8 = 0xFF;
+
+/// NOR flash holding a journal.
+///
+/// Offsets are relative to the start of the journal.
+pub trait NorFlash {
+    /// Error reported by the flash driver.
+    type Error;
+
+    /// Size of the journal in bytes. It must be a whole number of erase blocks.
+    fn capacity(&self) -> usize;
+
+    /// Reads `bytes.len()` bytes starting at `offset`.
+    fn read(&mut self, offset: u32, bytes: &mut [u8]) -> Result<(), Self::Error>;
+
+    /// Writes `bytes` starting at `offset`, clearing bits only.
+    fn write(&mut self, offset: u32, bytes: &[u8]) -> Result<(), Self::Error>;
+
+    /// Erases the range `from..to`, setting every byte to `0xFF`.
+    fn erase(&mut self, from: u32, to: u32) -> Result<(), Self::Error>;
+}
+
+/// Reasons a journal operation failed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PersistError<E> {
+    /// The flash driver failed.
+    Flash(E),
+    /// The journal has no room until pending records are consumed.
+    Full,
+    /// The oldest record is longer than the output buffer.
+    Overflow,
+}
+
+impl<E> fmt::Display for PersistError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Flash(_) => "flash access failed",
+            Self::Full => "journal full",
+            Self::Overflow => "record exceeds output buffer",
+        })
+    }
+}
+
+/// A byte queue in RAM journaled to flash.
+#[derive(Debug)]
+pub struct PersistentBip<F, const CAP: usize> {
+    bip: StaticBip<u8, CAP>,
+    flash: F,
+    /// Offset of the oldest record that may still be pending
+    read: usize,
+    /// Offset where the next record is written
+    write: usize,
+}
+
+impl<F: NorFlash, const CAP: usize> PersistentBip<F, CAP> {
+    /// Opens the journal on `flash`, finding the records that have not been consumed.
+    pub fn open(mut flash: F) -> Result<Self, F::Error> {
+        let capacity = flash.capacity();
+        let mut read = None;
+        let mut pos = 0;
+        while pos + HEADER_LEN <= capacity {
+            let (state, len) = header(&mut flash, pos)?;
+            if state == ERASED && len == usize::from(u16::MAX) {
+                break;
+            }
+            if pos + HEADER_LEN + len > capacity {
+                // Corrupt length: nothing more can be appended until the journal is erased
+                pos = capacity;
+                break;
+            }
+            if state == VALID && read.is_none() {
+                read = Some(pos);
+            }
+            pos += HEADER_LEN + len;
+        }
+        Ok(Self {
+            bip: StaticBip::new([0; CAP]),
+            flash,
+            read: read.unwrap_or(pos),
+            write: pos,
+        })
+    }
+
+    /// Returns the RAM buffer.
+    #[inline]
+    pub fn get_ref(&self) -> &StaticBip<u8, CAP> {
+        &self.bip
+    }
+
+    /// Returns the RAM buffer, for producing data.
+    #[inline]
+    pub fn get_mut(&mut self) -> &mut StaticBip<u8, CAP> {
+        &mut self.bip
+    }
+
+    /// Returns the flash, dropping data that has not been flushed.
+    #[inline]
+    pub fn into_flash(self) -> F {
+        self.flash
+    }
+
+    /// Writes the committed data of the RAM buffer to flash and decommits it.
+    ///
+    /// Returns the number of bytes written. Fails with [`PersistError::Full`] if nothing
+    /// could be written; data that does not fit stays in RAM.
+    pub fn flush(&mut self) -> Result<usize, PersistError<F::Error>> {
+        let capacity = self.flash.capacity();
+        let mut total = 0;
+        while !self.bip.is_empty() {
+            let mut room = capacity.saturating_sub(self.write + HEADER_LEN);
+            if self.read == self.write && self.write != 0 && self.bip.read().len() > room {
+                self.flash
+                    .erase(0, capacity as u32)
+                    .map_err(PersistError::Flash)?;
+                self.read = 0;
+                self.write = 0;
+                room = capacity.saturating_sub(HEADER_LEN);
+            }
+            let block = self.bip.read();
+            let len = block.len().min(room).min(usize::from(u16::MAX - 1));
+            if len == 0 {
+                break;
+            }
+
+            let [lo, hi] = (len as u16).to_le_bytes();
+            let pos = self.write as u32;
+            let flash = &mut self.flash;
+            flash
+                .write(pos + 1, &[lo, hi])
+                .map_err(PersistError::Flash)?;
+            flash
+                .write(pos + HEADER_LEN as u32, &block[..len])
+                .map_err(PersistError::Flash)?;
+            flash.write(pos, &[VALID]).map_err(PersistError::Flash)?;
+            self.write += HEADER_LEN + len;
+            self.bip.decommit(len);
+            total += len;
+        }
+        if total == 0 && !self.bip.is_empty() {
+            return Err(PersistError::Full);
+        }
+        Ok(total)
+    }
+
+    /// Moves the oldest pending record into `out` and marks it as consumed.
+    ///
+    /// Returns `None` if no record is pending. A record longer than `out` stays pending.
+    pub fn pop(&mut self, out: &mut [u8]) -> Result<Option<usize>, PersistError<F::Error>> {
+        while self.read < self.write {
+            let (state, len) = header(&mut self.flash, self.read).map_err(PersistError::Flash)?;
+            if state != VALID {
+                self.read += HEADER_LEN + len;
+                continue;
+            }
+            let out = out.get_mut(..len).ok_or(PersistError::Overflow)?;
+            let pos = self.read as u32;
+            self.flash
+                .read(pos + HEADER_LEN as u32, out)
+                .map_err(PersistError::Flash)?;
+            self.flash
+                .write(pos, &[CONSUMED])
+                .map_err(PersistError::Flash)?;
+            self.read += HEADER_LEN + len;
+            return Ok(Some(len));
+        }
+        Ok(None)
+    }
+}
+
+/// Reads the state and payload length of the record at `pos`.
+fn header<F: NorFlash>(flash: &mut F, pos: usize) -> Result<(u8, usize), F::Error> {
+    let mut header = [0; HEADER_LEN];
+    flash.read(pos as u32, &mut header)?;
+    Ok((
+        header[0],
+        usize::from(u16::from_le_bytes([header[1], header[2]])),
+    ))
+}
@@ -0,0 +1,92 @@
+//! Circular DMA into the two halves of a buffer.
+//!
+//! DMA in circular mode writes the whole backing store over and over, raising one interrupt
+//! when the first half is full and another when the second half is. Passing each half to
+//! [`commit_dma_half`](StaticBip::commit_dma_half) from those interrupts commits it, and the
+//! consumer drains the data through the normal read path. The DMA transfer is set up once,
+//! over [`as_raw_parts`](StaticBip::as_raw_parts), and covers `2 * (CAP / 2)` elements.
+//!
+//! DMA does not wait for the consumer. If a half is completed while older data in it has not
+//! been read, that data is gone; the commit reports an [`Overrun`] and discards everything
+//! that was left unread before committing the new half.
+//!
+//! ```rust
+//! use staticbip::{pingpong::Half, StaticBip};
+//!
+//! let mut buffer = StaticBip::<u8, 8>::default();
+//!
+//! // Half-transfer interrupt
+//! buffer.commit_dma_half(Half::First).unwrap();
+//! assert_eq!(buffer.read().len(), 4);
+//! buffer.decommit(4);
+//!
+//! // Transfer-complete interrupt, then the first half again
+//! buffer.commit_dma_half(Half::Second).unwrap();
+//! buffer.commit_dma_half(Half::First).unwrap();
+//! assert_eq!(buffer.committed(), 8);
+//! assert!(buffer.commit_dma_half(Half::Second).is_err());
+//! ```
+
+use crate::StaticBip;
+use core::fmt;
+
+/// A half of the backing store.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Half {
+    /// Elements `0..CAP / 2`.
+    First,
+    /// Elements `CAP / 2..2 * (CAP / 2)`.
+    Second,
+}
+
+/// DMA completed a half that still held unread data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Overrun;
+
+impl fmt::Display for Overrun {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("DMA overran unread data")
+    }
+}
+
+impl<T, const CAP: usize> StaticBip<T, CAP> {
+    /// Commits `half`, which DMA has just filled.
+    ///
+    /// Any reservation is cleared. On an [`Overrun`] the unread data is discarded and `half`
+    /// is committed on its own.
+    pub fn commit_dma_half(&mut self, half: Half) -> Result<(), Overrun> {
+        let len = CAP / 2;
+        let span = match half {
+            Half::First => 0..len,
+            Half::Second => len..2 * len,
+        };
+        let committed = self.committed();
+        let regions = &mut self.regions;
+        regions.clear_reservation();
+        let (a, b) = (regions.a(), regions.b());
+
+        let result = if committed == 0 {
+            regions.set_a(span);
+            Ok(())
+        } else if b.is_empty() && a.end == span.start {
+            regions.set_a(a.start..span.end);
+            Ok(())
+        } else if b.is_empty() && span.start == 0 && a.start >= span.end {
+            regions.set_b_end(span.end);
+            Ok(())
+        } else {
+            #[cfg(feature = "stats")]
+            {
+                regions.read += committed as u64;
+            }
+            regions.set_a(span);
+            regions.set_b_end(0);
+            Err(Overrun)
+        };
+        #[cfg(feature = "stats")]
+        {
+            self.regions.written += len as u64;
+        }
+        result
+    }
+}
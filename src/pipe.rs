@@ -0,0 +1,127 @@
+//! Moving data between a buffer and asynchronous I/O.
+//!
+//! [`copy_from`](StaticBip::copy_from) and [`copy_into`](StaticBip::copy_into) drive the
+//! reservation and commit cycle against poll functions shaped like `AsyncRead::poll_read`
+//! and `AsyncWrite::poll_write`, so an I/O object is plugged in with a closure such as
+//! `|cx, buf| Pin::new(&mut socket).poll_read(cx, buf)`. Data moves straight between the
+//! buffer and the I/O object.
+//!
+//! ```rust
+//! use core::{convert::Infallible, task::Poll};
+//! use staticbip::StaticBip;
+//!
+//! let mut buffer = StaticBip::<u8, 8>::default();
+//! let mut input = &b"hello"[..];
+//! let mut output = Vec::new();
+//!
+//! let task = async {
+//!     let received = buffer
+//!         .copy_from(|_, buf| {
+//!             let len = buf.len().min(input.len());
+//!             buf[..len].copy_from_slice(&input[..len]);
+//!             input = &input[len..];
+//!             Poll::Ready(Ok::<_, Infallible>(len))
+//!         })
+//!         .await;
+//!     assert_eq!(received, Ok(5));
+//!
+//!     let sent = buffer
+//!         .copy_into(|_, buf| {
+//!             output.extend_from_slice(buf);
+//!             Poll::Ready(Ok::<_, Infallible>(buf.len()))
+//!         })
+//!         .await;
+//!     assert_eq!(sent, Ok(5));
+//! };
+//! # use core::future::Future;
+//! # let mut task = Box::pin(task);
+//! # let mut cx = core::task::Context::from_waker(core::task::Waker::noop());
+//! # assert!(task.as_mut().poll(&mut cx).is_ready());
+//! # drop(task);
+//! # assert_eq!(output, b"hello");
+//! ```
+//!
+//! # Cancellation
+//!
+//! Both futures can be dropped at any await point, as in a `select!`, without losing data or
+//! leaving the buffer reserved. Each read or write is committed or decommitted as soon as it
+//! completes, and a reservation only lasts for a single call of `poll_read`, so dropping a
+//! future only abandons the call that was pending. The counts returned are then lost, but the
+//! data is in the buffer or has been written.
+
+use crate::StaticBip;
+use core::{
+    future::poll_fn,
+    task::{Context, Poll},
+};
+
+impl<T, const CAP: usize> StaticBip<T, CAP> {
+    /// Reads into reservations with `poll_read` until the buffer is full or a read returns
+    /// `0`, committing what arrives.
+    ///
+    /// Returns the number of elements committed. An error is only returned if nothing was
+    /// committed; otherwise it ends the copy. No reservation is held while waiting.
+    pub async fn copy_from<E>(
+        &mut self,
+        mut poll_read: impl FnMut(&mut Context<'_>, &mut [T]) -> Poll<Result<usize, E>>,
+    ) -> Result<usize, E> {
+        let mut total = 0;
+        loop {
+            let read = poll_fn(|cx| match self.reserve_max() {
+                [] => Poll::Ready(Ok(0)),
+                reserved => {
+                    let poll = poll_read(cx, reserved);
+                    if poll.is_pending() {
+                        // Nothing to keep, and the future may be dropped before the next poll
+                        self.commit(0);
+                    }
+                    poll
+                }
+            })
+            .await;
+            match read {
+                Ok(0) => break,
+                Ok(len) => {
+                    self.commit(len);
+                    total += len;
+                }
+                Err(err) if total == 0 => {
+                    self.commit(0);
+                    return Err(err);
+                }
+                Err(_) => break,
+            }
+        }
+        self.commit(0);
+        Ok(total)
+    }
+
+    /// Writes committed data with `poll_write` until the buffer is empty or a write returns
+    /// `0`, decommitting what was written.
+    ///
+    /// Returns the number of elements decommitted. An error is only returned if nothing was
+    /// written; otherwise it ends the copy.
+    pub async fn copy_into<E>(
+        &mut self,
+        mut poll_write: impl FnMut(&mut Context<'_>, &[T]) -> Poll<Result<usize, E>>,
+    ) -> Result<usize, E> {
+        let mut total = 0;
+        loop {
+            let written = poll_fn(|cx| match self.read() {
+                [] => Poll::Ready(Ok(0)),
+                block => poll_write(cx, block),
+            })
+            .await;
+            match written {
+                Ok(0) => break,
+                Ok(len) => {
+                    self.decommit(len);
+                    total += len;
+                }
+                Err(err) if total == 0 => return Err(err),
+                Err(_) => break,
+            }
+        }
+        Ok(total)
+    }
+}
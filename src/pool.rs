@@ -0,0 +1,113 @@
+//! Pool of Bip-Buffers handed out one at a time.
+//!
+//! A buffer is taken with [`acquire`](BipPool::acquire) and goes back to the pool, cleared,
+//! when its [`PoolBip`] guard is dropped. Guards can be moved between pipeline stages, so a
+//! filled buffer changes hands without copying its contents.
+//!
+//! ```rust
+//! use staticbip::pool::BipPool;
+//!
+//! let pool = BipPool::<u8, 4, 2>::default();
+//!
+//! let mut first = pool.acquire().unwrap();
+//! let second = pool.acquire().unwrap();
+//! assert!(pool.acquire().is_none());
+//!
+//! first.reserve(2).copy_from_slice(&[1, 2]);
+//! first.commit(2);
+//!
+//! drop(second);
+//! assert!(pool.acquire().is_some());
+//! ```
+
+use crate::StaticBip;
+use core::{
+    array,
+    cell::UnsafeCell,
+    ops::{Deref, DerefMut},
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+/// A pool of `N` Bip-Buffers with a fixed capacity.
+pub struct BipPool<T, const CAP: usize, const N: usize> {
+    /// Buffers, each owned by at most one guard
+    buffers: [UnsafeCell<StaticBip<T, CAP>>; N],
+    /// Whether each buffer is handed out
+    taken: [AtomicBool; N],
+}
+
+// SAFETY: a buffer is only reachable through the guard that set its `taken` flag.
+unsafe impl<T: Send, const CAP: usize, const N: usize> Sync for BipPool<T, CAP, N> {}
+
+impl<T: Default, const CAP: usize, const N: usize> Default for BipPool<T, CAP, N> {
+    #[inline]
+    fn default() -> Self {
+        Self::new(array::from_fn(|_| array::from_fn(|_| T::default())))
+    }
+}
+
+impl<T, const CAP: usize, const N: usize> BipPool<T, CAP, N> {
+    /// Creates a pool over `N` backing stores.
+    pub fn new(buffers: [[T; CAP]; N]) -> Self {
+        Self {
+            buffers: buffers.map(|buffer| UnsafeCell::new(StaticBip::new(buffer))),
+            taken: [const { AtomicBool::new(false) }; N],
+        }
+    }
+
+    /// Number of buffers that can currently be acquired.
+    pub fn available(&self) -> usize {
+        self.taken
+            .iter()
+            .filter(|taken| !taken.load(Ordering::Relaxed))
+            .count()
+    }
+
+    /// Takes an empty buffer out of the pool.
+    ///
+    /// Returns `None` if every buffer is in use.
+    pub fn acquire(&self) -> Option<PoolBip<'_, T, CAP>> {
+        let (buffer, taken) = self
+            .buffers
+            .iter()
+            .zip(&self.taken)
+            .find(|(_, taken)| !taken.swap(true, Ordering::Acquire))?;
+        Some(PoolBip {
+            // SAFETY: the `taken` flag was clear, so no other guard refers to this buffer.
+            bip: unsafe { &mut *buffer.get() },
+            taken,
+        })
+    }
+}
+
+/// A buffer borrowed from a [`BipPool`].
+///
+/// The buffer is cleared and returned to the pool on drop.
+pub struct PoolBip<'a, T, const CAP: usize> {
+    bip: &'a mut StaticBip<T, CAP>,
+    taken: &'a AtomicBool,
+}
+
+impl<T, const CAP: usize> Deref for PoolBip<'_, T, CAP> {
+    type Target = StaticBip<T, CAP>;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        self.bip
+    }
+}
+
+impl<T, const CAP: usize> DerefMut for PoolBip<'_, T, CAP> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.bip
+    }
+}
+
+impl<T, const CAP: usize> Drop for PoolBip<'_, T, CAP> {
+    #[inline]
+    fn drop(&mut self) {
+        self.bip.clear();
+        self.taken.store(false, Ordering::Release);
+    }
+}
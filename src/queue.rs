@@ -0,0 +1,76 @@
+//! Element queue operations in the style of `heapless::spsc::Queue`.
+//!
+//! [`enqueue`](StaticBip::enqueue) and [`dequeue`](StaticBip::dequeue) follow the queue's
+//! signatures, so code written against it switches over unchanged.
+//! [`fill_from`](StaticBip::fill_from) and [`drain_into`](StaticBip::drain_into) move
+//! elements between a buffer and a queue through its `dequeue` and `enqueue` methods.
+//!
+//! ```rust
+//! use staticbip::StaticBip;
+//! # struct Queue(Vec<u8>);
+//! # impl Queue {
+//! #     fn enqueue(&mut self, item: u8) -> Result<(), u8> {
+//! #         if self.0.len() < 2 { self.0.push(item); Ok(()) } else { Err(item) }
+//! #     }
+//! # }
+//! # let mut queue = Queue(Vec::new());
+//!
+//! let mut buffer = StaticBip::<u8, 4>::default();
+//! assert_eq!(buffer.enqueue(1), Ok(()));
+//! buffer.extend(&[2, 3, 4]);
+//! assert_eq!(buffer.enqueue(5), Err(5));
+//!
+//! // Queue with room for two elements
+//! assert_eq!(buffer.drain_into(|item| queue.enqueue(item)), 2);
+//! assert_eq!(buffer.dequeue(), Some(3));
+//! ```
+
+use crate::StaticBip;
+use core::iter;
+
+impl<T, const CAP: usize> StaticBip<T, CAP> {
+    /// Appends `item`, handing it back if the buffer is full.
+    pub fn enqueue(&mut self, item: T) -> Result<(), T> {
+        match self.reserve(1).first_mut() {
+            Some(slot) => {
+                *slot = item;
+                self.commit(1);
+                Ok(())
+            }
+            None => Err(item),
+        }
+    }
+
+    /// Appends elements returned by `dequeue` until it returns `None` or the buffer is full.
+    ///
+    /// Returns the number of elements moved.
+    #[inline]
+    pub fn fill_from(&mut self, dequeue: impl FnMut() -> Option<T>) -> usize {
+        self.push_iter(iter::from_fn(dequeue))
+    }
+}
+
+impl<T: Copy, const CAP: usize> StaticBip<T, CAP> {
+    /// Removes the oldest element.
+    #[inline]
+    pub fn dequeue(&mut self) -> Option<T> {
+        self.pop().copied()
+    }
+
+    /// Passes the oldest elements to `enqueue` until it hands one back or the buffer is
+    /// empty.
+    ///
+    /// An element that is handed back stays in the buffer. Returns the number of elements
+    /// moved.
+    pub fn drain_into(&mut self, mut enqueue: impl FnMut(T) -> Result<(), T>) -> usize {
+        let mut count = 0;
+        while let Some(&item) = self.front() {
+            if enqueue(item).is_err() {
+                break;
+            }
+            self.pop();
+            count += 1;
+        }
+        count
+    }
+}
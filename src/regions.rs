@@ -0,0 +1,414 @@
+//! Region bookkeeping shared by the buffer variants.
+
+use core::{ops::Range, ptr};
+
+/// Positions of the regions within a backing store.
+///
+/// Region `B` always starts at the beginning of the backing store, so only its end is kept.
+#[derive(Debug, Clone)]
+pub(crate) struct Regions {
+    /// Start of region `A`
+    a_start: usize,
+    /// End of region `A`
+    a_end: usize,
+    /// End of region `B`, which is empty if this is `0`
+    b_end: usize,
+    /// Start of the reservation
+    ///
+    /// This is not derived from the other cursors, since a reservation does not always start
+    /// where its free block does: aligned reservations skip slots, and decommitting region `A`
+    /// completely moves `A` away from the block a reservation was taken from.
+    reserve_start: usize,
+    /// End of the reservation
+    reserve_end: usize,
+    /// Choice between the space after `A` and the space before it
+    pub(crate) policy: WrapPolicy,
+    /// Number of elements ever committed
+    #[cfg(feature = "stats")]
+    pub(crate) written: u64,
+    /// Number of elements ever decommitted
+    #[cfg(feature = "stats")]
+    pub(crate) read: u64,
+    /// Number of reservations replaced by another before being committed
+    #[cfg(feature = "stats")]
+    pub(crate) abandoned: u64,
+}
+
+/// How a reservation chooses between the free space after region `A` and the space at the
+/// start of the backing store, while region `B` is empty.
+///
+/// Once a reservation wraps to the start, the space after `A` stays unused until `A` has been
+/// read completely.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum WrapPolicy {
+    /// Use the larger of the two blocks, preferring the space after `A` on a tie.
+    ///
+    /// With the `chaos` feature, the choice between two non-empty blocks is pseudo-random
+    /// instead, so consumers that assume committed data is contiguous fail early in testing.
+    #[default]
+    Largest,
+    /// Keep using the space after `A` until it is exhausted.
+    NoWrap,
+    /// Use the first block that fits the whole request, or else the larger one.
+    Fit,
+}
+
+impl Regions {
+    /// Empty regions with no reservation.
+    #[inline]
+    pub(crate) const fn new() -> Self {
+        Self {
+            a_start: 0,
+            a_end: 0,
+            b_end: 0,
+            reserve_start: 0,
+            reserve_end: 0,
+            policy: WrapPolicy::Largest,
+            #[cfg(feature = "stats")]
+            written: 0,
+            #[cfg(feature = "stats")]
+            read: 0,
+            #[cfg(feature = "stats")]
+            abandoned: 0,
+        }
+    }
+
+    /// Region `A` covering the first `len` elements.
+    #[inline]
+    pub(crate) const fn full(len: usize) -> Self {
+        Self {
+            a_start: 0,
+            a_end: len,
+            b_end: 0,
+            reserve_start: 0,
+            reserve_end: 0,
+            policy: WrapPolicy::Largest,
+            #[cfg(feature = "stats")]
+            written: len as u64,
+            #[cfg(feature = "stats")]
+            read: 0,
+            #[cfg(feature = "stats")]
+            abandoned: 0,
+        }
+    }
+
+    /// Reads regions `A` and `B` and the reservation from memory that may not hold valid
+    /// regions, leaving out the policy.
+    ///
+    /// # Safety
+    ///
+    /// `regions` must be readable as plain bytes.
+    pub(crate) unsafe fn read_raw(regions: *const Self) -> [Range<usize>; 3] {
+        let read = |field: *const usize| field.read();
+        [
+            read(ptr::addr_of!((*regions).a_start))..read(ptr::addr_of!((*regions).a_end)),
+            0..read(ptr::addr_of!((*regions).b_end)),
+            read(ptr::addr_of!((*regions).reserve_start))
+                ..read(ptr::addr_of!((*regions).reserve_end)),
+        ]
+    }
+
+    /// Region `A`.
+    #[inline]
+    pub(crate) const fn a(&self) -> Range<usize> {
+        self.a_start..self.a_end
+    }
+
+    /// Region `B`.
+    #[inline]
+    pub(crate) const fn b(&self) -> Range<usize> {
+        0..self.b_end
+    }
+
+    /// Reserved region.
+    #[inline]
+    pub(crate) const fn reservation(&self) -> Range<usize> {
+        self.reserve_start..self.reserve_end
+    }
+
+    /// Moves region `A`.
+    #[inline]
+    pub(crate) fn set_a(&mut self, a: Range<usize>) {
+        self.a_start = a.start;
+        self.a_end = a.end;
+    }
+
+    /// Moves the end of region `B`.
+    #[inline]
+    pub(crate) fn set_b_end(&mut self, end: usize) {
+        self.b_end = end;
+    }
+
+    /// Moves the reservation.
+    #[inline]
+    pub(crate) fn set_reservation(&mut self, reserve: Range<usize>) {
+        self.reserve_start = reserve.start;
+        self.reserve_end = reserve.end;
+    }
+
+    /// Clears the reservation.
+    #[inline]
+    pub(crate) fn clear_reservation(&mut self) {
+        self.set_reservation(0..0);
+    }
+
+    /// Number of elements in region `A`.
+    ///
+    /// Like the rest of the bookkeeping, this saturates rather than panicking on a corrupted
+    /// layout, such as one restored from retained memory.
+    #[inline]
+    fn a_len(&self) -> usize {
+        self.a_end.saturating_sub(self.a_start)
+    }
+
+    /// Number of committed elements.
+    #[inline]
+    pub(crate) fn committed(&self) -> usize {
+        self.a_len().saturating_add(self.b_end)
+    }
+
+    /// Number of reserved elements.
+    #[inline]
+    pub(crate) fn reserved(&self) -> usize {
+        self.reserve_end.saturating_sub(self.reserve_start)
+    }
+
+    /// Clears all regions and reservations, counting the committed elements as read.
+    #[inline]
+    pub(crate) fn clear(&mut self) {
+        #[cfg(feature = "stats")]
+        {
+            self.read += self.committed() as u64;
+        }
+        self.set_a(0..0);
+        self.b_end = 0;
+        self.clear_reservation();
+    }
+
+    /// Free space following the most recent data, where the next reservation starts.
+    #[inline]
+    pub(crate) fn free_space(&self, capacity: usize) -> Range<usize> {
+        self.free_space_for(capacity, usize::MAX)
+    }
+
+    /// Free space where a reservation of `count` slots starts.
+    ///
+    /// The block before `A` starts at the end of `B`, which is `0` while `B` is empty, so the
+    /// choice is a single selection between two precomputed blocks.
+    #[inline]
+    pub(crate) fn free_space_for(&self, capacity: usize, count: usize) -> Range<usize> {
+        let after_end = capacity.max(self.a_end);
+        let after = after_end - self.a_end;
+        let before = self.a_start.saturating_sub(self.b_end);
+        let after_first = self.b_end == 0
+            && match self.policy {
+                WrapPolicy::Largest => after >= before,
+                WrapPolicy::NoWrap => after != 0 || before == 0,
+                WrapPolicy::Fit => after >= count || (before < count && after >= before),
+            };
+        #[cfg(feature = "chaos")]
+        let after_first =
+            if self.policy == WrapPolicy::Largest && self.b_end == 0 && after != 0 && before != 0 {
+                self.coin()
+            } else {
+                after_first
+            };
+        if after_first {
+            self.a_end..after_end
+        } else {
+            self.b_end..self.a_start
+        }
+    }
+
+    /// Pseudo-random choice derived from the stream positions.
+    ///
+    /// It depends only on the regions, so a copy of them makes the same choice.
+    #[cfg(feature = "chaos")]
+    #[inline]
+    fn coin(&self) -> bool {
+        let mut x = self.written ^ self.read.rotate_left(21) ^ self.abandoned.rotate_left(42);
+        x = (x ^ (x >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        x = (x ^ (x >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        (x ^ (x >> 31)) & 1 != 0
+    }
+
+    /// Reserves up to `count` slots of a backing store of `capacity` elements.
+    #[inline]
+    pub(crate) fn reserve(&mut self, capacity: usize, count: usize) -> Range<usize> {
+        #[cfg(feature = "strict")]
+        debug_assert!(
+            self.reserved() == 0,
+            "reserve called while {} slots are still reserved",
+            self.reserved()
+        );
+        #[cfg(feature = "stats")]
+        if self.reserved() != 0 {
+            self.abandoned += 1;
+        }
+        let free = self.free_space_for(capacity, count);
+        self.reserve_start = free.start;
+        self.reserve_end = free.start + count.min(free.len());
+        self.reservation()
+    }
+
+    /// Grows the reservation by up to `additional` slots of the free space it starts in.
+    #[inline]
+    pub(crate) fn extend_reserve(&mut self, capacity: usize, additional: usize) -> Range<usize> {
+        let limit = if self.reserve_start >= self.a_end && self.b_end == 0 {
+            capacity
+        } else {
+            self.a_start
+        };
+        self.reserve_end = self
+            .reserve_end
+            .saturating_add(additional)
+            .min(limit.max(self.reserve_end));
+        self.reservation()
+    }
+
+    /// Commits up to `len` reserved slots and clears the reservation.
+    ///
+    /// A reservation that starts past the end of a region extends the region over the skipped
+    /// slots, including a reservation that wraps to the start of the backing store, since
+    /// region `B` always starts there. Only a reservation in an empty buffer leaves them out.
+    #[inline]
+    pub(crate) fn commit(&mut self, len: usize) {
+        #[cfg(feature = "strict")]
+        debug_assert!(
+            len <= self.reserved(),
+            "commit of {} elements exceeds the reservation of {}",
+            len,
+            self.reserved()
+        );
+        let committed = self.committed();
+        let start = self.reserve_start;
+        let end = start + len.min(self.reserved());
+        // Every cursor is selected rather than branched on, so the outcome of the commit does
+        // not have to be predicted
+        let grown = end != start;
+        let after = committed == 0 || start >= self.a_end;
+        self.a_start = if grown && committed == 0 {
+            start
+        } else {
+            self.a_start
+        };
+        self.a_end = if grown && after { end } else { self.a_end };
+        self.b_end = if grown && !after { end } else { self.b_end };
+        self.clear_reservation();
+        #[cfg(feature = "stats")]
+        {
+            self.written += self.committed().saturating_sub(committed) as u64;
+        }
+    }
+
+    /// Marks the first `len` elements of region `A` as seen.
+    #[inline]
+    pub(crate) fn decommit(&mut self, len: usize) {
+        let a_len = self.a_len();
+        #[cfg(feature = "strict")]
+        debug_assert!(
+            len <= a_len,
+            "decommit of {} elements exceeds the {} readable ones",
+            len,
+            a_len
+        );
+        #[cfg(feature = "stats")]
+        {
+            self.read += len.min(a_len) as u64;
+        }
+        // Region `B` becomes region `A` once `A` is exhausted, selected as in `commit`
+        let drained = len >= a_len;
+        self.a_start = if drained { 0 } else { self.a_start + len };
+        self.a_end = if drained { self.b_end } else { self.a_end };
+        self.b_end = if drained { 0 } else { self.b_end };
+    }
+
+    /// Marks the first `len` committed elements as seen, continuing into region `B`.
+    #[inline]
+    pub(crate) fn consume(&mut self, len: usize) {
+        let a_len = self.a_len();
+        if len >= a_len {
+            self.decommit(a_len);
+            self.decommit(len - a_len);
+        } else {
+            self.decommit(len);
+        }
+    }
+
+    /// Removes the newest `len` committed elements, continuing into region `A`, and clears
+    /// the reservation.
+    #[inline]
+    pub(crate) fn truncate_back(&mut self, len: usize) {
+        #[cfg(feature = "stats")]
+        {
+            self.written = self
+                .written
+                .saturating_sub(len.min(self.committed()) as u64);
+        }
+        let from_b = len.min(self.b_end);
+        self.b_end -= from_b;
+        self.a_end -= (len - from_b).min(self.a_len());
+        self.clear_reservation();
+    }
+
+    /// Committed elements from logical position `pos` up to the end of their region.
+    #[inline]
+    pub(crate) fn block_at(&self, pos: usize) -> Range<usize> {
+        let a_len = self.a_len();
+        if pos < a_len {
+            self.a_start + pos..self.a_end
+        } else {
+            (pos - a_len).min(self.b_end)..self.b_end
+        }
+    }
+
+    /// Index of the oldest committed element.
+    #[inline]
+    pub(crate) fn front(&self) -> Option<usize> {
+        if self.a_len() != 0 {
+            Some(self.a_start)
+        } else if self.b_end != 0 {
+            Some(0)
+        } else {
+            None
+        }
+    }
+
+    /// Index of the newest committed element.
+    #[inline]
+    pub(crate) fn back(&self) -> Option<usize> {
+        if self.b_end != 0 {
+            Some(self.b_end - 1)
+        } else if self.a_len() != 0 {
+            Some(self.a_end - 1)
+        } else {
+            None
+        }
+    }
+
+    /// Positions in the backing store of the committed elements at logical positions `range`,
+    /// if they are committed and contiguous.
+    #[inline]
+    pub(crate) fn logical(&self, range: Range<usize>) -> Option<Range<usize>> {
+        let a_len = self.a_len();
+        if range.start > range.end || range.end > self.committed() {
+            None
+        } else if range.end <= a_len {
+            Some(self.a_start + range.start..self.a_start + range.end)
+        } else if range.start >= a_len {
+            Some(range.start - a_len..range.end - a_len)
+        } else {
+            None
+        }
+    }
+
+    /// Removes the oldest committed element and returns its index.
+    #[inline]
+    pub(crate) fn pop(&mut self) -> Option<usize> {
+        let index = self.front()?;
+        // Region `B` becomes region `A` once `A` is exhausted, since `B` must not hold data
+        // while `A` is empty
+        self.decommit(1);
+        Some(index)
+    }
+}
@@ -0,0 +1,101 @@
+//! Acknowledged consumption with replay.
+//!
+//! A [`Replay`] hands out committed data with [`peek`](Replay::peek) without releasing it.
+//! [`ack`](Replay::ack) releases delivered data, while [`nack`](Replay::nack) rewinds so
+//! that everything not acknowledged is handed out again, as an unreliable transport needs
+//! for retransmits.
+//!
+//! ```rust
+//! use staticbip::{replay::Replay, StaticBip};
+//!
+//! let mut replay = Replay::new(StaticBip::<u8, 8>::default());
+//! replay.get_mut().reserve(4).copy_from_slice(&[1, 2, 3, 4]);
+//! replay.get_mut().commit(4);
+//!
+//! assert_eq!(replay.peek(2), &[1, 2]);
+//! assert_eq!(replay.peek(2), &[3, 4]);
+//!
+//! // Only the first packet was delivered
+//! replay.ack(2);
+//! replay.nack();
+//! assert_eq!(replay.peek(2), &[3, 4]);
+//! ```
+
+use crate::StaticBip;
+
+/// A buffer whose consumed data stays available until it is acknowledged.
+#[derive(Debug)]
+pub struct Replay<T, const CAP: usize> {
+    bip: StaticBip<T, CAP>,
+    /// Number of committed elements handed out and not acknowledged
+    in_flight: usize,
+}
+
+impl<T: Default, const CAP: usize> Default for Replay<T, CAP> {
+    #[inline]
+    fn default() -> Self {
+        Self::new(StaticBip::default())
+    }
+}
+
+impl<T, const CAP: usize> Replay<T, CAP> {
+    /// Wraps a buffer, with nothing in flight.
+    #[inline]
+    pub fn new(bip: StaticBip<T, CAP>) -> Self {
+        Self { bip, in_flight: 0 }
+    }
+
+    /// Returns the wrapped buffer.
+    #[inline]
+    pub fn get_ref(&self) -> &StaticBip<T, CAP> {
+        &self.bip
+    }
+
+    /// Returns the wrapped buffer, for producing data.
+    ///
+    /// Release data with [`ack`](Self::ack) rather than through the buffer, which does not
+    /// know what is in flight.
+    #[inline]
+    pub fn get_mut(&mut self) -> &mut StaticBip<T, CAP> {
+        &mut self.bip
+    }
+
+    /// Unwraps the buffer, leaving in-flight data committed.
+    #[inline]
+    pub fn into_inner(self) -> StaticBip<T, CAP> {
+        self.bip
+    }
+
+    /// Number of elements handed out and not yet acknowledged.
+    #[inline]
+    pub fn in_flight(&self) -> usize {
+        self.in_flight.min(self.bip.committed())
+    }
+
+    /// Hands out up to `max` contiguous elements following the in-flight data.
+    ///
+    /// The elements stay committed until they are acknowledged.
+    pub fn peek(&mut self, max: usize) -> &[T] {
+        let start = self.in_flight();
+        let block = self.bip.regions.block_at(start);
+        let block = block.start..block.start + block.len().min(max);
+        self.in_flight = start + block.len();
+        self.bip.slice(block)
+    }
+
+    /// Releases the oldest `len` in-flight elements.
+    ///
+    /// A `len` above [`in_flight`](Self::in_flight) releases all of them.
+    #[inline]
+    pub fn ack(&mut self, len: usize) {
+        let len = len.min(self.in_flight());
+        self.bip.regions.consume(len);
+        self.in_flight -= len;
+    }
+
+    /// Returns all in-flight elements to the buffer, to be handed out again.
+    #[inline]
+    pub fn nack(&mut self) {
+        self.in_flight = 0;
+    }
+}
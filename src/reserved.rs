@@ -0,0 +1,203 @@
+//! Reservations as values.
+//!
+//! [`StaticBip::reservation`] returns a [`Reserved`] token that borrows the buffer until it is
+//! committed. Committing consumes the token, so committing twice or without a reservation
+//! does not compile. Dropping the token clears the reservation.
+//!
+//! ```rust
+//! use staticbip::StaticBip;
+//!
+//! let mut buffer = StaticBip::<u8, 8>::default();
+//!
+//! let mut reserved = buffer.reservation(4);
+//! reserved.copy_from_slice(&[1, 2, 3, 4]);
+//! reserved.commit(3);
+//!
+//! assert_eq!(buffer.read(), &[1, 2, 3]);
+//! ```
+//!
+//! A [`ReservationWriter`] fills a reservation piece by piece, keeping track of the write
+//! position, and commits exactly what was written when finished.
+//!
+//! ```rust
+//! use staticbip::StaticBip;
+//!
+//! let mut buffer = StaticBip::<u8, 8>::default();
+//!
+//! let mut writer = buffer.reservation_writer(6);
+//! writer.write(&[0xAA, 3]);
+//! writer.write(b"abc");
+//! assert_eq!((writer.written(), writer.remaining()), (5, 1));
+//! writer.finish();
+//!
+//! assert_eq!(buffer.read(), &[0xAA, 3, b'a', b'b', b'c']);
+//! ```
+//!
+//! The writer can move back to fill in a field once the data after it is known, such as a
+//! leading length. With the `std` feature, byte writers implement [`std::io::Write`] and
+//! [`std::io::Seek`] for serializers that do this themselves.
+//!
+//! ```rust
+//! use staticbip::StaticBip;
+//!
+//! let mut buffer = StaticBip::<u8, 8>::default();
+//!
+//! let mut writer = buffer.reservation_writer(8);
+//! writer.set_position(1);
+//! let len = writer.write(b"body");
+//! writer.set_position(0);
+//! writer.write(&[len as u8]);
+//! writer.finish();
+//!
+//! assert_eq!(buffer.read(), b"\x04body");
+//! ```
+//!
+//! ```rust,compile_fail
+//! use staticbip::StaticBip;
+//!
+//! let mut buffer = StaticBip::<u8, 8>::default();
+//! let reserved = buffer.reservation(4);
+//! reserved.commit(4);
+//! reserved.commit(4);
+//! ```
+
+use crate::StaticBip;
+use core::ops::{Deref, DerefMut};
+
+/// A pending reservation of a buffer.
+///
+/// Created by [`StaticBip::reservation`]. Dereferences to the reserved slots.
+#[derive(Debug)]
+pub struct Reserved<'a, T, const CAP: usize> {
+    bip: &'a mut StaticBip<T, CAP>,
+}
+
+/// A reservation filled from a movable position.
+///
+/// Created by [`StaticBip::reservation_writer`]. Dropping the writer without calling
+/// [`finish`](Self::finish) clears the reservation, committing nothing.
+#[derive(Debug)]
+pub struct ReservationWriter<'a, T, const CAP: usize> {
+    reserved: Reserved<'a, T, CAP>,
+    /// Slot the next write starts at
+    pos: usize,
+    /// Number of slots up to the end of the furthest write
+    written: usize,
+}
+
+impl<T, const CAP: usize> StaticBip<T, CAP> {
+    /// Reserves up to `count` slots and returns them as a token that must be committed.
+    #[inline]
+    pub fn reservation(&mut self, count: usize) -> Reserved<'_, T, CAP> {
+        self.reserve(count);
+        Reserved { bip: self }
+    }
+
+    /// Reserves up to `count` slots and returns a writer filling them in order.
+    #[inline]
+    pub fn reservation_writer(&mut self, count: usize) -> ReservationWriter<'_, T, CAP> {
+        ReservationWriter {
+            reserved: self.reservation(count),
+            pos: 0,
+            written: 0,
+        }
+    }
+}
+
+impl<T, const CAP: usize> Reserved<'_, T, CAP> {
+    /// Commits the first `len` reserved slots.
+    ///
+    /// A `len` longer than the reservation commits all of it.
+    #[inline]
+    pub fn commit(self, len: usize) {
+        self.bip.commit(len);
+        core::mem::forget(self);
+    }
+
+    /// Commits every reserved slot.
+    #[inline]
+    pub fn commit_all(self) {
+        let len = self.len();
+        self.commit(len);
+    }
+}
+
+impl<T, const CAP: usize> Deref for Reserved<'_, T, CAP> {
+    type Target = [T];
+
+    #[inline]
+    fn deref(&self) -> &[T] {
+        let reserve = self.bip.regions.reservation();
+        self.bip.slice(reserve)
+    }
+}
+
+impl<T, const CAP: usize> DerefMut for Reserved<'_, T, CAP> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut [T] {
+        let reserve = self.bip.regions.reservation();
+        self.bip.slice_mut(reserve)
+    }
+}
+
+impl<T, const CAP: usize> Drop for Reserved<'_, T, CAP> {
+    #[inline]
+    fn drop(&mut self) {
+        self.bip.commit(0);
+    }
+}
+
+impl<T, const CAP: usize> ReservationWriter<'_, T, CAP> {
+    /// Number of slots up to the end of the furthest write, which [`finish`](Self::finish)
+    /// commits.
+    #[inline]
+    pub fn written(&self) -> usize {
+        self.written
+    }
+
+    /// Number of reserved slots after the position.
+    #[inline]
+    pub fn remaining(&self) -> usize {
+        self.reserved.len().saturating_sub(self.pos)
+    }
+
+    /// Slot the next write starts at.
+    #[inline]
+    pub fn position(&self) -> usize {
+        self.pos
+    }
+
+    /// Moves the position to `pos`.
+    ///
+    /// Writes past the end of the reservation write nothing. Slots skipped by moving past
+    /// the written ones keep their previous contents and are committed along with the data
+    /// after them.
+    #[inline]
+    pub fn set_position(&mut self, pos: usize) {
+        self.pos = pos;
+    }
+
+    /// Commits the written slots and returns their number.
+    #[inline]
+    pub fn finish(self) -> usize {
+        let written = self.written;
+        self.reserved.commit(written);
+        written
+    }
+}
+
+impl<T: Copy, const CAP: usize> ReservationWriter<'_, T, CAP> {
+    /// Writes as much of `data` as fits at the position and moves past it.
+    ///
+    /// Returns the number of elements written.
+    pub fn write(&mut self, data: &[T]) -> usize {
+        let start = self.pos;
+        let len = data.len().min(self.remaining());
+        if len != 0 {
+            self.reserved[start..start + len].copy_from_slice(&data[..len]);
+            self.pos += len;
+            self.written = self.written.max(self.pos);
+        }
+        len
+    }
+}
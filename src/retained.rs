@@ -0,0 +1,125 @@
+//! Buffers that survive a reset in RAM that is not initialized at startup.
+//!
+//! A [`Retained`] buffer lives in a `.noinit` or battery-backed RAM section and keeps a magic
+//! value and a checksum of its region positions next to the data. After a reset,
+//! [`recover`](Retained::recover) keeps the contents if both match, and starts an empty buffer
+//! otherwise, such as after power-up. This turns the buffer into a log that survives
+//! watchdog resets.
+//!
+//! Changes are made through [`with`](Retained::with), which updates the checksum afterwards;
+//! a reset in the middle of it discards the contents. A reservation pending at the reset is
+//! dropped, the wrap policy returns to the default and the stream positions restart.
+//!
+//! ```rust
+//! use core::mem::MaybeUninit;
+//! use staticbip::retained::Retained;
+//!
+//! // In firmware:
+//! // #[link_section = ".noinit"]
+//! // static mut LOG: MaybeUninit<Retained<u8, 64>> = MaybeUninit::uninit();
+//! let mut slot = MaybeUninit::<Retained<u8, 64>>::zeroed();
+//!
+//! let (log, recovered) = unsafe { Retained::recover(&mut slot, 0) };
+//! assert!(!recovered);
+//! log.with(|bip| bip.extend(b"panic"));
+//!
+//! // After a watchdog reset
+//! let (log, recovered) = unsafe { Retained::recover(&mut slot, 0) };
+//! assert!(recovered);
+//! assert_eq!(log.get_ref().as_slices().0, b"panic");
+//! ```
+
+use crate::{regions::Regions, BipState, StaticBip, WrapPolicy};
+use core::{mem::MaybeUninit, ptr};
+
+/// Marks a [`Retained`] buffer that has been initialized.
+pub const MAGIC: u32 = 0xB1B0_5EED;
+
+/// A buffer whose contents can be recovered after a reset.
+#[derive(Debug)]
+#[repr(C)]
+pub struct Retained<T, const CAP: usize> {
+    magic: u32,
+    checksum: u32,
+    bip: StaticBip<T, CAP>,
+}
+
+impl<T: Copy, const CAP: usize> Retained<T, CAP> {
+    /// Recovers the buffer in `slot`, or initializes it with elements of `fill` if its
+    /// contents are not trustworthy.
+    ///
+    /// Returns the buffer and whether its contents were recovered.
+    ///
+    /// # Safety
+    ///
+    /// `slot` must be readable as plain bytes, as RAM is after a reset even if it has not been
+    /// initialized, and `T` must be valid for any bit pattern.
+    pub unsafe fn recover(slot: &mut MaybeUninit<Self>, fill: T) -> (&mut Self, bool) {
+        let this = slot.as_mut_ptr();
+        let regions = ptr::addr_of_mut!((*this).bip.regions);
+        // The policy is not read, since an arbitrary value may not be a valid variant
+        let [a, b, reserve] = Regions::read_raw(regions);
+        let state = BipState {
+            a: a.into(),
+            b: b.into(),
+            reserve: reserve.into(),
+        };
+        let recovered = ptr::addr_of!((*this).magic).read() == MAGIC
+            && ptr::addr_of!((*this).checksum).read() == checksum(&state)
+            && state.is_valid(CAP);
+
+        if recovered {
+            ptr::addr_of_mut!((*regions).policy).write(WrapPolicy::default());
+            #[cfg(feature = "stats")]
+            {
+                ptr::addr_of_mut!((*regions).written).write((state.a.len + state.b.len) as u64);
+                ptr::addr_of_mut!((*regions).read).write(0);
+                ptr::addr_of_mut!((*regions).abandoned).write(0);
+            }
+        } else {
+            this.write(Self {
+                magic: MAGIC,
+                checksum: 0,
+                bip: StaticBip::new([fill; CAP]),
+            });
+        }
+        let this = &mut *this;
+        this.with(|bip| bip.commit(0));
+        (this, recovered)
+    }
+}
+
+impl<T, const CAP: usize> Retained<T, CAP> {
+    /// Returns the buffer.
+    #[inline]
+    pub fn get_ref(&self) -> &StaticBip<T, CAP> {
+        &self.bip
+    }
+
+    /// Calls `f` with the buffer and records the resulting region positions.
+    #[inline]
+    pub fn with<R>(&mut self, f: impl FnOnce(&mut StaticBip<T, CAP>) -> R) -> R {
+        let result = f(&mut self.bip);
+        self.checksum = checksum(&self.bip.state());
+        result
+    }
+}
+
+/// FNV-1a hash of the region positions.
+fn checksum(state: &BipState) -> u32 {
+    let words = [
+        state.a.offset,
+        state.a.len,
+        state.b.offset,
+        state.b.len,
+        state.reserve.offset,
+        state.reserve.len,
+    ];
+    let mut hash = 0x811C_9DC5_u32;
+    for word in words.iter() {
+        for byte in (*word as u64).to_le_bytes().iter() {
+            hash = (hash ^ u32::from(*byte)).wrapping_mul(0x0100_0193);
+        }
+    }
+    hash
+}
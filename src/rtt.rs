@@ -0,0 +1,239 @@
+//! Debug output readable by an RTT host.
+//!
+//! A [`ControlBlock`] has the memory layout of a SEGGER RTT control block with a single up
+//! channel, so debug probes and tools such as probe-rs find it by its `"SEGGER RTT"` id and
+//! drain the channel over the debug port while the target keeps running. The target only
+//! writes; the host reads and advances the read offset itself.
+//!
+//! ```rust
+//! use core::{ffi::CStr, fmt::Write};
+//! use staticbip::rtt::{ControlBlock, Mode};
+//!
+//! // In firmware:
+//! // static mut RTT: ControlBlock<1024> = ControlBlock::new();
+//! let mut block = ControlBlock::<64>::new();
+//!
+//! let name = CStr::from_bytes_with_nul(b"Terminal\0").unwrap();
+//! let mut channel = block.init(name);
+//! channel.set_mode(Mode::NoBlockTrim);
+//! writeln!(channel, "boot").unwrap();
+//! assert_eq!(channel.pending(), 5);
+//! ```
+//!
+//! Hosts wrap the channel at its size and know nothing about the watermark a Bip-Buffer keeps
+//! when it wraps early, so the channel is a plain ring: writes are split at the end of the
+//! buffer, and one byte stays free to tell a full channel from an empty one.
+
+use core::{
+    cell::UnsafeCell,
+    ffi::CStr,
+    fmt, ptr,
+    sync::atomic::{compiler_fence, AtomicU32, Ordering},
+};
+
+/// Id the host searches memory for.
+const ID: &[u8; 16] = b"SEGGER RTT\0\0\0\0\0\0";
+
+/// What a write does when the channel does not have room for all of it.
+///
+/// The values are the ones of the RTT `Flags` field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u32)]
+pub enum Mode {
+    /// Writes nothing.
+    NoBlockSkip = 0,
+    /// Writes as much as fits.
+    NoBlockTrim = 1,
+    /// Waits for the host to read.
+    BlockIfFull = 2,
+}
+
+/// Description of an up channel, laid out as `SEGGER_RTT_BUFFER_UP`.
+#[repr(C)]
+struct UpChannel {
+    name: *const u8,
+    buffer: *mut u8,
+    size: u32,
+    /// Written by the target
+    write: AtomicU32,
+    /// Written by the host
+    read: AtomicU32,
+    flags: AtomicU32,
+}
+
+/// An RTT control block with one up channel of `N` bytes.
+///
+/// `N` must be at least 2 and fit in a `u32`.
+#[repr(C)]
+pub struct ControlBlock<const N: usize> {
+    /// Zero until [`init`](Self::init), so the host does not find a half-initialized block
+    id: UnsafeCell<[u8; 16]>,
+    max_up: u32,
+    max_down: u32,
+    up: UnsafeCell<UpChannel>,
+    buffer: UnsafeCell<[u8; N]>,
+}
+
+// SAFETY: the block is only changed through the `&mut` borrow held by a `Channel`; shared
+// access is limited to the host's reads and its atomic read offset.
+unsafe impl<const N: usize> Sync for ControlBlock<N> {}
+// SAFETY: the raw pointers point into the block itself.
+unsafe impl<const N: usize> Send for ControlBlock<N> {}
+
+impl<const N: usize> Default for ControlBlock<N> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> fmt::Debug for ControlBlock<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ControlBlock").field("size", &N).finish()
+    }
+}
+
+impl<const N: usize> ControlBlock<N> {
+    /// Creates a block that is not visible to the host until [`init`](Self::init).
+    pub const fn new() -> Self {
+        assert!(
+            N >= 2 && N <= u32::MAX as usize,
+            "unsupported RTT buffer size"
+        );
+        Self {
+            id: UnsafeCell::new([0; 16]),
+            max_up: 1,
+            max_down: 0,
+            up: UnsafeCell::new(UpChannel {
+                name: ptr::null(),
+                buffer: ptr::null_mut(),
+                size: 0,
+                write: AtomicU32::new(0),
+                read: AtomicU32::new(0),
+                flags: AtomicU32::new(Mode::NoBlockSkip as u32),
+            }),
+            buffer: UnsafeCell::new([0; N]),
+        }
+    }
+
+    /// Publishes the block to the host and returns its up channel, named `name`.
+    ///
+    /// The channel starts empty. The block must not move while the host may read it, which
+    /// the borrow ensures for the lifetime of the channel; in firmware it is a `static`.
+    pub fn init<'a>(&'a mut self, name: &'static CStr) -> Channel<'a, N> {
+        let buffer = self.buffer.get_mut().as_mut_ptr();
+        let up = self.up.get_mut();
+        up.name = name.as_ptr().cast();
+        up.buffer = buffer;
+        up.size = N as u32;
+        up.write.store(0, Ordering::Relaxed);
+        up.read.store(0, Ordering::Relaxed);
+        // The id is written last, so a host scanning memory only finds a complete block
+        compiler_fence(Ordering::SeqCst);
+        let id = self.id.get();
+        for (i, &byte) in ID.iter().enumerate() {
+            // SAFETY: `i` is within the id, which only this exclusive borrow writes.
+            unsafe { ptr::write_volatile(id.cast::<u8>().add(i), byte) };
+        }
+        compiler_fence(Ordering::SeqCst);
+        Channel { block: self }
+    }
+}
+
+/// The up channel of an initialized [`ControlBlock`], written by the target.
+#[derive(Debug)]
+pub struct Channel<'a, const N: usize> {
+    block: &'a mut ControlBlock<N>,
+}
+
+impl<const N: usize> Channel<'_, N> {
+    #[inline]
+    fn up(&self) -> &UpChannel {
+        // SAFETY: the host only reads the channel description and stores the read offset
+        // atomically.
+        unsafe { &*self.block.up.get() }
+    }
+
+    /// Sets what writes do when the channel is full.
+    #[inline]
+    pub fn set_mode(&mut self, mode: Mode) {
+        self.up().flags.store(mode as u32, Ordering::Relaxed);
+    }
+
+    /// Returns what writes do when the channel is full.
+    ///
+    /// Unknown values set by the host read as [`Mode::NoBlockSkip`].
+    #[inline]
+    pub fn mode(&self) -> Mode {
+        match self.up().flags.load(Ordering::Relaxed) & 3 {
+            1 => Mode::NoBlockTrim,
+            2 => Mode::BlockIfFull,
+            _ => Mode::NoBlockSkip,
+        }
+    }
+
+    /// Number of bytes written and not yet read by the host.
+    #[inline]
+    pub fn pending(&self) -> usize {
+        let up = self.up();
+        let write = up.write.load(Ordering::Relaxed) as usize;
+        let read = up.read.load(Ordering::Acquire) as usize;
+        if write >= read {
+            write - read
+        } else {
+            N - read + write
+        }
+    }
+
+    /// Number of bytes that can be written before the host reads.
+    #[inline]
+    pub fn free(&self) -> usize {
+        N - 1 - self.pending()
+    }
+
+    /// Writes as much of `data` as fits, without regard to the mode.
+    fn write_some(&mut self, data: &[u8]) -> usize {
+        let len = data.len().min(self.free());
+        let write = self.up().write.load(Ordering::Relaxed) as usize;
+        let first = len.min(N - write);
+        let buffer = self.block.buffer.get().cast::<u8>();
+        // SAFETY: the ranges are free space, which the host does not read.
+        unsafe {
+            ptr::copy_nonoverlapping(data.as_ptr(), buffer.add(write), first);
+            ptr::copy_nonoverlapping(data[first..].as_ptr(), buffer, len - first);
+        }
+        let write = (write + len) % N;
+        self.up().write.store(write as u32, Ordering::Release);
+        len
+    }
+
+    /// Writes `data` according to the [`mode`](Self::mode).
+    ///
+    /// Returns the number of bytes written. With [`Mode::BlockIfFull`] this spins until the
+    /// host has read enough to write all of `data`.
+    pub fn write(&mut self, data: &[u8]) -> usize {
+        match self.mode() {
+            Mode::NoBlockSkip if data.len() > self.free() => 0,
+            Mode::NoBlockSkip | Mode::NoBlockTrim => self.write_some(data),
+            Mode::BlockIfFull => {
+                let mut written = 0;
+                while written < data.len() {
+                    written += self.write_some(&data[written..]);
+                    core::hint::spin_loop();
+                }
+                written
+            }
+        }
+    }
+}
+
+impl<const N: usize> fmt::Write for Channel<'_, N> {
+    /// Writes `s`, failing if the mode dropped any of it.
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        if self.write(s.as_bytes()) == s.len() {
+            Ok(())
+        } else {
+            Err(fmt::Error)
+        }
+    }
+}
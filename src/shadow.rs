@@ -0,0 +1,147 @@
+//! Differential checking against a reference model.
+//!
+//! [`Shadowed`] wraps a [`StaticBip`] and, in builds with debug assertions, keeps a copy of
+//! the committed elements in a `VecDeque`. After every operation the buffer's contents, read
+//! in order across both regions, must equal the model and its regions must be valid;
+//! otherwise it panics, naming the operation and showing the layout. A mistake in the region
+//! bookkeeping surfaces at the operation that caused it instead of as corrupted data later.
+//! Without debug assertions no model is kept and every call goes straight to the buffer.
+//!
+//! A downstream crate switches its tests over with a type alias, such as
+//! `type Buffer = Shadowed<u8, 64>` in place of `StaticBip<u8, 64>` behind one of its own
+//! features, as long as it uses the buffer through [`BipBuf`].
+//!
+//! ```rust
+//! use staticbip::{shadow::Shadowed, BipBuf, StaticBip};
+//!
+//! let mut buffer = Shadowed::new(StaticBip::<u8, 4>::default());
+//! buffer.reserve(3).copy_from_slice(&[1, 2, 3]);
+//! buffer.commit(3);
+//! buffer.decommit(2);
+//! assert_eq!(buffer.get_ref(), &[3]);
+//! ```
+//!
+//! The model records elements as they are committed. Changing committed elements through
+//! [`read`](BipBuf::read) therefore fails the next check, so shadowing suits code that
+//! treats committed data as read-only.
+
+use crate::{BipBuf, StaticBip};
+#[cfg(debug_assertions)]
+use alloc::collections::VecDeque;
+use core::fmt;
+
+/// A buffer checked against a model of its contents after every operation.
+pub struct Shadowed<T, const CAP: usize> {
+    bip: StaticBip<T, CAP>,
+    /// Committed elements, oldest first
+    #[cfg(debug_assertions)]
+    model: VecDeque<T>,
+}
+
+impl<T: Clone + PartialEq + fmt::Debug, const CAP: usize> Shadowed<T, CAP> {
+    /// Starts shadowing `bip`, taking its committed elements as the model.
+    #[inline]
+    pub fn new(bip: StaticBip<T, CAP>) -> Self {
+        Self {
+            #[cfg(debug_assertions)]
+            model: bip.iter().cloned().collect(),
+            bip,
+        }
+    }
+
+    /// Returns the wrapped buffer.
+    #[inline]
+    pub fn get_ref(&self) -> &StaticBip<T, CAP> {
+        &self.bip
+    }
+
+    /// Returns the wrapped buffer.
+    #[inline]
+    pub fn into_inner(self) -> StaticBip<T, CAP> {
+        self.bip
+    }
+
+    /// Panics if the buffer no longer matches the model after `op`.
+    #[inline]
+    fn check(&self, op: &str) {
+        #[cfg(debug_assertions)]
+        {
+            let state = self.bip.state();
+            assert!(
+                state.is_valid(CAP),
+                "{} left invalid regions {}",
+                op,
+                state.layout(CAP)
+            );
+            assert!(
+                self.bip.committed() == self.model.len() && self.bip.iter().eq(&self.model),
+                "{} diverged from the model: buffer {:?} in {}, model {:?}",
+                op,
+                self.bip.iter().collect::<alloc::vec::Vec<_>>(),
+                state.layout(CAP),
+                self.model
+            );
+        }
+        #[cfg(not(debug_assertions))]
+        let _ = op;
+    }
+}
+
+impl<T: Clone + PartialEq + fmt::Debug, const CAP: usize> BipBuf<T> for Shadowed<T, CAP> {
+    #[inline]
+    fn capacity(&self) -> usize {
+        self.bip.capacity()
+    }
+
+    #[inline]
+    fn committed(&self) -> usize {
+        self.bip.committed()
+    }
+
+    #[inline]
+    fn reserved(&self) -> usize {
+        self.bip.reserved()
+    }
+
+    #[inline]
+    fn reserve(&mut self, count: usize) -> &mut [T] {
+        self.check("reserve");
+        self.bip.reserve(count)
+    }
+
+    #[inline]
+    fn commit(&mut self, len: usize) {
+        #[cfg(debug_assertions)]
+        {
+            let start = self.bip.regions.reservation().start;
+            let end = start + len.min(self.bip.reserved());
+            self.model
+                .extend(self.bip.slice(start..end).iter().cloned());
+        }
+        self.bip.commit(len);
+        self.check("commit");
+    }
+
+    #[inline]
+    fn read(&mut self) -> &mut [T] {
+        self.check("read");
+        self.bip.read()
+    }
+
+    #[inline]
+    fn decommit(&mut self, len: usize) {
+        #[cfg(debug_assertions)]
+        {
+            let len = len.min(self.bip.regions.a().len());
+            self.model.drain(..len);
+        }
+        self.bip.decommit(len);
+        self.check("decommit");
+    }
+}
+
+impl<T: fmt::Debug, const CAP: usize> fmt::Debug for Shadowed<T, CAP> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Shadowed").field(&self.bip).finish()
+    }
+}
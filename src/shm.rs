@@ -0,0 +1,262 @@
+//! Byte queues shared between processes.
+//!
+//! A [`SharedQueue`] places a [`BufStorage`] in a file mapped with `MAP_SHARED`, so a
+//! producer in one process and a consumer in another exchange bytes through the same memory
+//! without copying them through the kernel. One process [`create`](SharedQueue::create)s the
+//! file, typically under `/dev/shm`, and the other [`open`](SharedQueue::open)s it; each
+//! then claims its half.
+//!
+//! ```rust
+//! use staticbip::shm::SharedQueue;
+//!
+//! let path = std::env::temp_dir().join(format!("staticbip-doc-{}", std::process::id()));
+//!
+//! // Process A
+//! let queue = SharedQueue::<64>::create(&path).unwrap();
+//! let mut producer = queue.producer().unwrap();
+//! producer.write_all(b"hello", || std::thread::yield_now());
+//!
+//! // Process B
+//! let queue = SharedQueue::<64>::open(&path).unwrap();
+//! let mut consumer = queue.consumer().unwrap();
+//! let grant = consumer.read().unwrap();
+//! assert_eq!(&grant[..], b"hello");
+//! grant.release(5);
+//! # std::fs::remove_file(&path).unwrap();
+//! ```
+//!
+//! Both processes must agree on `N` and on the `cache-padding` feature. The file holds a
+//! `#[repr(C)]` header of a magic number and `N` as `size_t`, and one byte each for the
+//! producer and consumer claims, followed by the [`BufStorage`] laid out as documented there,
+//! so the peer does not have to be written in Rust. Every position the halves load from the
+//! file is checked against `N` before it is used, so a faulty peer makes them fail with
+//! [`Error::Corrupted`] instead of reaching outside the storage. Wakers cannot be shared
+//! between processes, so the halves have no waker registration; a process waiting for its
+//! peer polls, as with the `idle` hook of [`write_all`](SharedProducer::write_all).
+
+use crate::bbqueue::{BufStorage, Consumer, Error, GrantR, GrantW, Producer};
+use std::{
+    fs::{File, OpenOptions},
+    io,
+    mem::size_of,
+    os::{
+        raw::{c_int, c_void},
+        unix::io::AsRawFd,
+    },
+    path::Path,
+    ptr,
+    sync::atomic::{AtomicBool, AtomicUsize, Ordering},
+};
+
+extern "C" {
+    fn mmap(
+        addr: *mut c_void,
+        len: usize,
+        prot: c_int,
+        flags: c_int,
+        fd: c_int,
+        offset: i64,
+    ) -> *mut c_void;
+    fn munmap(addr: *mut c_void, len: usize) -> c_int;
+}
+
+const PROT_READ: c_int = 1;
+const PROT_WRITE: c_int = 2;
+const MAP_SHARED: c_int = 0x01;
+const MAP_FAILED: *mut c_void = !0 as *mut c_void;
+
+/// Marks a file whose storage has been initialized.
+const MAGIC: usize = 0x5354_4250;
+
+/// Contents of the shared file.
+#[repr(C)]
+struct Shared<const N: usize> {
+    /// [`MAGIC`] once the storage is initialized
+    magic: AtomicUsize,
+    /// Size of the storage, checked when opening
+    size: AtomicUsize,
+    /// Whether a process has claimed the producer
+    producer: AtomicBool,
+    /// Whether a process has claimed the consumer
+    consumer: AtomicBool,
+    storage: BufStorage<N>,
+}
+
+/// A [`BufStorage`] of `N` bytes in memory shared with other processes.
+#[derive(Debug)]
+pub struct SharedQueue<const N: usize> {
+    shared: *mut Shared<N>,
+}
+
+// SAFETY: the mapping is owned by the queue, and the shared state is only accessed through
+// atomics and the halves.
+unsafe impl<const N: usize> Send for SharedQueue<N> {}
+// SAFETY: see above.
+unsafe impl<const N: usize> Sync for SharedQueue<N> {}
+
+impl<const N: usize> SharedQueue<N> {
+    /// Creates the file at `path` with empty storage.
+    ///
+    /// Fails if the file already exists.
+    pub fn create(path: impl AsRef<Path>) -> io::Result<Self> {
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create_new(true)
+            .open(path)?;
+        file.set_len(size_of::<Shared<N>>() as u64)?;
+        let queue = Self::map(&file)?;
+        // SAFETY: the file was just created, so no other process uses the storage before the
+        // magic number is published.
+        unsafe {
+            ptr::addr_of_mut!((*queue.shared).storage).write(BufStorage::new());
+        }
+        let shared = queue.shared();
+        shared.size.store(N, Ordering::Relaxed);
+        shared.magic.store(MAGIC, Ordering::Release);
+        Ok(queue)
+    }
+
+    /// Opens storage created by [`create`](Self::create) at `path`.
+    ///
+    /// Fails with [`io::ErrorKind::InvalidData`] if the file does not hold initialized storage
+    /// of `N` bytes.
+    pub fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        let file = OpenOptions::new().read(true).write(true).open(path)?;
+        if file.metadata()?.len() != size_of::<Shared<N>>() as u64 {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "size mismatch"));
+        }
+        let queue = Self::map(&file)?;
+        let shared = queue.shared();
+        if shared.magic.load(Ordering::Acquire) != MAGIC || shared.size.load(Ordering::Relaxed) != N
+        {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "not an initialized queue",
+            ));
+        }
+        Ok(queue)
+    }
+
+    /// Maps the contents of `file`.
+    fn map(file: &File) -> io::Result<Self> {
+        // SAFETY: the file is open for reading and writing and has the size of the mapping.
+        let ptr = unsafe {
+            mmap(
+                ptr::null_mut(),
+                size_of::<Shared<N>>(),
+                PROT_READ | PROT_WRITE,
+                MAP_SHARED,
+                file.as_raw_fd(),
+                0,
+            )
+        };
+        if ptr == MAP_FAILED {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(Self { shared: ptr.cast() })
+    }
+
+    #[inline]
+    fn shared(&self) -> &Shared<N> {
+        // SAFETY: the mapping lives as long as the queue.
+        unsafe { &*self.shared }
+    }
+
+    /// Claims the producer half.
+    ///
+    /// Fails with [`Error::AlreadySplit`] if any process has claimed it before.
+    pub fn producer(&self) -> Result<SharedProducer<'_, N>, Error> {
+        let shared = self.shared();
+        if shared.producer.swap(true, Ordering::AcqRel) {
+            return Err(Error::AlreadySplit);
+        }
+        // SAFETY: the flag hands out the producer once, and the storage stays mapped while
+        // the half borrows the queue.
+        let producer = unsafe { Producer::from_raw(&shared.storage) };
+        Ok(SharedProducer { producer })
+    }
+
+    /// Claims the consumer half.
+    ///
+    /// Fails with [`Error::AlreadySplit`] if any process has claimed it before.
+    pub fn consumer(&self) -> Result<SharedConsumer<'_, N>, Error> {
+        let shared = self.shared();
+        if shared.consumer.swap(true, Ordering::AcqRel) {
+            return Err(Error::AlreadySplit);
+        }
+        // SAFETY: as in `producer`.
+        let consumer = unsafe { Consumer::from_raw(&shared.storage) };
+        Ok(SharedConsumer { consumer })
+    }
+}
+
+impl<const N: usize> Drop for SharedQueue<N> {
+    fn drop(&mut self) {
+        // SAFETY: the mapping was created in `map` with this size, and the halves borrowing
+        // it are gone.
+        unsafe { munmap(self.shared.cast(), size_of::<Shared<N>>()) };
+    }
+}
+
+/// Writing half of a [`SharedQueue`].
+pub struct SharedProducer<'a, const N: usize> {
+    producer: Producer<'a, N>,
+}
+
+impl<const N: usize> SharedProducer<'_, N> {
+    /// Grants exactly `sz` contiguous bytes for writing.
+    #[inline]
+    pub fn grant_exact(&mut self, sz: usize) -> Result<GrantW<'_, N>, Error> {
+        self.producer.grant_exact(sz)
+    }
+
+    /// Grants up to `max_sz` contiguous bytes for writing.
+    #[inline]
+    pub fn grant_max_remaining(&mut self, max_sz: usize) -> Result<GrantW<'_, N>, Error> {
+        self.producer.grant_max_remaining(max_sz)
+    }
+
+    /// Commits all of `src`, calling `idle` whenever the storage has no room left.
+    #[inline]
+    pub fn write_all(&mut self, src: &[u8], idle: impl FnMut()) {
+        self.producer.write_all(src, idle)
+    }
+
+    /// Total number of bytes ever committed, wrapping on overflow.
+    #[inline]
+    pub fn sequence(&self) -> usize {
+        self.producer.sequence()
+    }
+
+    /// Number of bytes committed and not yet released.
+    #[inline]
+    pub fn pending(&self) -> usize {
+        self.producer.pending()
+    }
+}
+
+/// Reading half of a [`SharedQueue`].
+pub struct SharedConsumer<'a, const N: usize> {
+    consumer: Consumer<'a, N>,
+}
+
+impl<const N: usize> SharedConsumer<'_, N> {
+    /// Grants the next contiguous block of committed bytes for reading.
+    #[inline]
+    pub fn read(&mut self) -> Result<GrantR<'_, N>, Error> {
+        self.consumer.read()
+    }
+
+    /// Total number of bytes ever released, wrapping on overflow.
+    #[inline]
+    pub fn sequence(&self) -> usize {
+        self.consumer.sequence()
+    }
+
+    /// Number of bytes committed and not yet released.
+    #[inline]
+    pub fn pending(&self) -> usize {
+        self.consumer.pending()
+    }
+}
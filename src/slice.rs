@@ -0,0 +1,238 @@
+//! Bip-Buffer over borrowed storage.
+
+use crate::{range_mut, regions::Regions, BipBuf, BipState, RawParts};
+use core::{mem, ops::Range, ptr};
+
+/// A Bip-Buffer over a borrowed slice.
+///
+/// The backing store can be any mutable slice, including memory obtained through
+/// [`from_raw_parts`](Self::from_raw_parts).
+///
+/// ```rust
+/// use staticbip::SliceBip;
+///
+/// let mut storage = [0u8; 4];
+/// let mut buffer = SliceBip::new(&mut storage);
+///
+/// buffer.reserve(2).copy_from_slice(&[1, 2]);
+/// buffer.commit(2);
+/// assert_eq!(buffer.read(), &[1, 2]);
+/// ```
+#[derive(Debug)]
+pub struct SliceBip<'a, T> {
+    /// Region bookkeeping
+    regions: Regions,
+    /// Backing store
+    buffer: &'a mut [T],
+}
+
+impl<'a, T> SliceBip<'a, T> {
+    /// Creates a new buffer over `buffer`.
+    #[inline]
+    pub const fn new(buffer: &'a mut [T]) -> Self {
+        Self {
+            regions: Regions::new(),
+            buffer,
+        }
+    }
+
+    /// Creates a new buffer over `len` elements starting at `ptr`.
+    ///
+    /// This allows the buffer to live in memory-mapped regions, memory handed over by a
+    /// bootloader, or storage owned by foreign code.
+    ///
+    /// # Safety
+    ///
+    /// The same rules as for [`core::slice::from_raw_parts_mut`] apply:
+    ///
+    /// * `ptr` must be non-null, aligned for `T`, and valid for reads and writes of `len`
+    ///   elements for the whole lifetime `'a`.
+    /// * All `len` elements must be initialized values of `T`.
+    /// * The memory must not be accessed through any other pointer while the buffer exists.
+    /// * `len * size_of::<T>()` must not exceed `isize::MAX`.
+    #[inline]
+    pub unsafe fn from_raw_parts(ptr: *mut T, len: usize) -> Self {
+        Self::new(core::slice::from_raw_parts_mut(ptr, len))
+    }
+
+    /// Splits `storage` into independent buffers of the lengths in `lens`, in order.
+    ///
+    /// This backs several buffers, such as the receive and transmit buffers of one
+    /// peripheral, with a single named array. Elements past the sum of `lens` are unused.
+    ///
+    /// ```rust
+    /// use staticbip::SliceBip;
+    ///
+    /// // In firmware:
+    /// // static mut UART0_BUFFER: [u8; 384] = [0; 384];
+    /// let mut uart0_buffer = [0u8; 384];
+    /// let [mut rx, mut tx] = SliceBip::partition(&mut uart0_buffer, [128, 256]);
+    ///
+    /// assert_eq!((rx.capacity(), tx.capacity()), (128, 256));
+    /// tx.reserve(2).copy_from_slice(b"at");
+    /// tx.commit(2);
+    /// assert!(rx.is_empty());
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the lengths add up to more than the length of `storage`.
+    pub fn partition<const N: usize>(storage: &'a mut [T], lens: [usize; N]) -> [Self; N] {
+        let total = lens
+            .iter()
+            .try_fold(0usize, |total, &len| total.checked_add(len));
+        assert!(
+            total.is_some_and(|total| total <= storage.len()),
+            "partition lengths exceed the storage"
+        );
+        let mut rest = storage;
+        lens.map(|len| {
+            let (head, tail) = mem::take(&mut rest).split_at_mut(len);
+            rest = tail;
+            Self::new(head)
+        })
+    }
+
+    /// Size of the backing store.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        ptr::addr_of!(*self.buffer).len()
+    }
+
+    /// Returns the region layout, without the data.
+    #[inline]
+    pub fn state(&self) -> BipState {
+        BipState::from(&self.regions)
+    }
+
+    /// Returns the base pointer of the backing store along with the region layout.
+    #[inline]
+    pub fn as_raw_parts(&mut self) -> RawParts<T> {
+        RawParts {
+            ptr: ptr::addr_of_mut!(*self.buffer).cast(),
+            capacity: self.capacity(),
+            state: BipState::from(&self.regions),
+        }
+    }
+
+    /// Number of committed elements.
+    #[inline]
+    pub fn committed(&self) -> usize {
+        self.regions.committed()
+    }
+
+    /// Number of reserved elements.
+    #[inline]
+    pub fn reserved(&self) -> usize {
+        self.regions.reserved()
+    }
+
+    /// Whether any space has been reserved or committed in the buffer.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.reserved() == 0 && self.committed() == 0
+    }
+
+    /// Clears all regions and reservations.
+    ///
+    /// Data in the underlying buffer is unchanged.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.regions.clear();
+    }
+
+    /// Returns a mutable buffer containing up to maximum slots for storing data.
+    #[inline]
+    pub fn reserve_max(&mut self) -> &mut [T] {
+        self.reserve(self.capacity())
+    }
+
+    /// Returns a mutable buffer containing up to `count` slots for storing data.
+    #[inline]
+    pub fn reserve(&mut self, count: usize) -> &mut [T] {
+        let reserve = self.regions.reserve(self.capacity(), count);
+        self.slice_mut(reserve)
+    }
+
+    /// Commits the data in the reservation, allowing it to be read later.
+    ///
+    /// If a `len` of `0` is passed in, the reservation will be cleared without making any other changes.
+    #[inline]
+    pub fn commit(&mut self, len: usize) {
+        self.regions.commit(len);
+    }
+
+    /// Retrieves available (committed) data as a contiguous block.
+    #[inline]
+    pub fn read(&mut self) -> &mut [T] {
+        self.slice_mut(self.regions.a())
+    }
+
+    /// Marks the first `len` elements of the available data as seen.
+    #[inline]
+    pub fn decommit(&mut self, len: usize) {
+        self.regions.decommit(len);
+    }
+
+    /// Removes the oldest committed element and returns it.
+    #[inline]
+    pub fn pop(&mut self) -> Option<&mut T> {
+        let index = self.regions.pop()?;
+        self.slice_mut(index..index + 1).first_mut()
+    }
+
+    /// Returns the backing store.
+    #[inline]
+    pub fn into_inner(self) -> &'a mut [T] {
+        self.buffer
+    }
+
+    /// Returns the elements in `range` of the backing store mutably, or an empty slice if
+    /// `range` does not fit it.
+    ///
+    /// Only the elements in `range` are borrowed, as for
+    /// [`StaticBip`](crate::StaticBip).
+    #[inline]
+    fn slice_mut(&mut self, range: Range<usize>) -> &mut [T] {
+        let store = ptr::addr_of_mut!(*self.buffer);
+        // SAFETY: `self` borrows the backing store mutably for the returned lifetime.
+        unsafe { range_mut(store.cast::<T>(), store.len(), range) }
+    }
+}
+
+impl<T> BipBuf<T> for SliceBip<'_, T> {
+    #[inline]
+    fn capacity(&self) -> usize {
+        self.capacity()
+    }
+
+    #[inline]
+    fn committed(&self) -> usize {
+        self.committed()
+    }
+
+    #[inline]
+    fn reserved(&self) -> usize {
+        self.reserved()
+    }
+
+    #[inline]
+    fn reserve(&mut self, count: usize) -> &mut [T] {
+        self.reserve(count)
+    }
+
+    #[inline]
+    fn commit(&mut self, len: usize) {
+        self.commit(len)
+    }
+
+    #[inline]
+    fn read(&mut self) -> &mut [T] {
+        self.read()
+    }
+
+    #[inline]
+    fn decommit(&mut self, len: usize) {
+        self.decommit(len)
+    }
+}
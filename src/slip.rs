@@ -0,0 +1,143 @@
+//! SLIP framing over byte buffers (RFC 1055).
+//!
+//! Each packet is written escaped and terminated by an `END` byte through a single
+//! reservation. Reading decodes the oldest complete packet from the committed data, which
+//! may span both regions.
+//!
+//! ```rust
+//! use staticbip::{slip::Slip, StaticBip};
+//!
+//! let mut buffer = StaticBip::<u8, 16>::default();
+//! let mut slip = Slip::new(&mut buffer);
+//!
+//! slip.write(&[1, 0xC0, 2]).unwrap();
+//!
+//! let mut packet = [0; 8];
+//! assert_eq!(slip.read(&mut packet), Some(Ok(3)));
+//! assert_eq!(packet[..3], [1, 0xC0, 2]);
+//! assert_eq!(slip.read(&mut packet), None);
+//! ```
+
+use crate::{nb::WouldBlock, StaticBip};
+use core::fmt;
+
+/// Ends a packet.
+pub const END: u8 = 0xC0;
+/// Starts an escape sequence.
+pub const ESC: u8 = 0xDB;
+/// Escaped [`END`].
+pub const ESC_END: u8 = 0xDC;
+/// Escaped [`ESC`].
+pub const ESC_ESC: u8 = 0xDD;
+
+/// Reasons a committed packet could not be decoded.
+///
+/// The packet is removed from the buffer either way.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SlipError {
+    /// The decoded packet is longer than the output buffer.
+    Overflow,
+    /// An escape byte is followed by something other than an escaped byte.
+    Escape,
+}
+
+impl fmt::Display for SlipError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Overflow => "SLIP packet exceeds output buffer",
+            Self::Escape => "invalid SLIP escape sequence",
+        })
+    }
+}
+
+/// Number of bytes `data` occupies once encoded, including the trailing [`END`].
+pub fn encoded_len(data: &[u8]) -> usize {
+    data.len() + data.iter().filter(|&&b| b == END || b == ESC).count() + 1
+}
+
+/// SLIP view of a byte buffer.
+pub struct Slip<'a, const CAP: usize> {
+    bip: &'a mut StaticBip<u8, CAP>,
+}
+
+impl<'a, const CAP: usize> Slip<'a, CAP> {
+    /// Wraps a byte buffer.
+    #[inline]
+    pub fn new(bip: &'a mut StaticBip<u8, CAP>) -> Self {
+        Self { bip }
+    }
+
+    /// Encodes `data` as one packet.
+    ///
+    /// Fails without writing anything if the encoded packet does not fit in one reservation.
+    pub fn write(&mut self, data: &[u8]) -> Result<(), WouldBlock> {
+        let len = encoded_len(data);
+        let reserved = self.bip.reserve(len);
+        if reserved.len() < len {
+            self.bip.commit(0);
+            return Err(WouldBlock);
+        }
+
+        let mut out = reserved.iter_mut();
+        let mut put = |byte| {
+            if let Some(slot) = out.next() {
+                *slot = byte;
+            }
+        };
+        for &byte in data {
+            match byte {
+                END => {
+                    put(ESC);
+                    put(ESC_END);
+                }
+                ESC => {
+                    put(ESC);
+                    put(ESC_ESC);
+                }
+                byte => put(byte),
+            }
+        }
+        put(END);
+        self.bip.commit(len);
+        Ok(())
+    }
+
+    /// Decodes the oldest complete packet into `out` and removes it.
+    ///
+    /// Returns `None` if no complete packet is committed. Empty packets are skipped.
+    pub fn read(&mut self, out: &mut [u8]) -> Option<Result<usize, SlipError>> {
+        loop {
+            let end = self.bip.position(|&byte| byte == END)?;
+            let result = decode(self.bip.iter().take(end).copied(), out);
+            self.bip.regions.consume(end + 1);
+            if end != 0 {
+                return Some(result);
+            }
+        }
+    }
+}
+
+/// Decodes the bytes of one packet, without its [`END`], into `out`.
+fn decode(bytes: impl Iterator<Item = u8>, out: &mut [u8]) -> Result<usize, SlipError> {
+    let mut len = 0;
+    let mut escaped = false;
+    for byte in bytes {
+        let byte = match (escaped, byte) {
+            (false, ESC) => {
+                escaped = true;
+                continue;
+            }
+            (false, byte) => byte,
+            (true, ESC_END) => END,
+            (true, ESC_ESC) => ESC,
+            (true, _) => return Err(SlipError::Escape),
+        };
+        escaped = false;
+        *out.get_mut(len).ok_or(SlipError::Overflow)? = byte;
+        len += 1;
+    }
+    if escaped {
+        return Err(SlipError::Escape);
+    }
+    Ok(len)
+}
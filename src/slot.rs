@@ -0,0 +1,162 @@
+//! Buffer of fixed-size slots.
+//!
+//! [`SlotBip`] hands out and takes back whole slots of `SLOT` elements, so every reservation
+//! and every read is contiguous by construction and no length bookkeeping is needed for
+//! fixed-size records such as camera lines or CAN-FD frames.
+//!
+//! ```rust
+//! use staticbip::slot::SlotBip;
+//!
+//! let mut lines = SlotBip::<u8, 4, 2>::default();
+//!
+//! lines.reserve_slot().unwrap().copy_from_slice(&[1, 2, 3, 4]);
+//! lines.commit_slot();
+//! lines.push_slot([5, 6, 7, 8]).unwrap();
+//! assert!(lines.push_slot([0; 4]).is_err());
+//!
+//! assert_eq!(lines.pop_slot(), Some(&mut [1, 2, 3, 4]));
+//! assert_eq!(lines.read_slot(), Some(&[5, 6, 7, 8]));
+//! ```
+
+use core::{array, mem};
+
+/// A ring of `N` slots of `SLOT` elements each.
+#[derive(Debug)]
+pub struct SlotBip<T, const SLOT: usize, const N: usize> {
+    /// Backing store
+    slots: [[T; SLOT]; N],
+    /// Index of the oldest committed slot
+    head: usize,
+    /// Number of committed slots
+    len: usize,
+    /// Whether the slot after the committed ones is reserved
+    reserved: bool,
+}
+
+impl<T: Default, const SLOT: usize, const N: usize> Default for SlotBip<T, SLOT, N> {
+    #[inline]
+    fn default() -> Self {
+        Self::new(array::from_fn(|_| array::from_fn(|_| T::default())))
+    }
+}
+
+impl<T, const SLOT: usize, const N: usize> SlotBip<T, SLOT, N> {
+    /// Creates an empty buffer over `slots`.
+    #[inline]
+    pub const fn new(slots: [[T; SLOT]; N]) -> Self {
+        Self {
+            slots,
+            head: 0,
+            len: 0,
+            reserved: false,
+        }
+    }
+
+    /// Number of slots in the buffer.
+    #[inline]
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Number of committed slots.
+    #[inline]
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Whether no slot is committed.
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Whether every slot is committed.
+    #[inline]
+    pub const fn is_full(&self) -> bool {
+        self.len == N
+    }
+
+    /// Index of the slot `offset` places after the oldest one.
+    #[inline]
+    fn index(&self, offset: usize) -> usize {
+        (self.head + offset) % N
+    }
+
+    /// Reserves the next free slot.
+    ///
+    /// Returns `None` if every slot is committed. The slot keeps whatever it held before.
+    pub fn reserve_slot(&mut self) -> Option<&mut [T; SLOT]> {
+        if self.is_full() {
+            self.reserved = false;
+            return None;
+        }
+        self.reserved = true;
+        let index = self.index(self.len);
+        Some(&mut self.slots[index])
+    }
+
+    /// Commits the reserved slot.
+    ///
+    /// Does nothing if no slot is reserved.
+    #[inline]
+    pub fn commit_slot(&mut self) {
+        if mem::take(&mut self.reserved) {
+            self.len += 1;
+        }
+    }
+
+    /// Stores `slot` in the next free slot and commits it.
+    ///
+    /// Returns `slot` back if every slot is committed, and otherwise clears the reservation.
+    pub fn push_slot(&mut self, slot: [T; SLOT]) -> Result<(), [T; SLOT]> {
+        match self.reserve_slot() {
+            Some(free) => {
+                *free = slot;
+                self.commit_slot();
+                Ok(())
+            }
+            None => Err(slot),
+        }
+    }
+
+    /// Returns the oldest committed slot.
+    #[inline]
+    pub fn read_slot(&self) -> Option<&[T; SLOT]> {
+        (!self.is_empty()).then(|| &self.slots[self.head])
+    }
+
+    /// Releases the oldest committed slot.
+    ///
+    /// Does nothing if no slot is committed.
+    #[inline]
+    pub fn decommit_slot(&mut self) {
+        self.pop_slot();
+    }
+
+    /// Releases the oldest committed slot and returns it.
+    ///
+    /// The slot stays valid until the next reservation reuses it.
+    pub fn pop_slot(&mut self) -> Option<&mut [T; SLOT]> {
+        if self.is_empty() {
+            return None;
+        }
+        let index = self.head;
+        self.head = self.index(1);
+        self.len -= 1;
+        Some(&mut self.slots[index])
+    }
+
+    /// Releases every committed slot and clears the reservation.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.head = 0;
+        self.len = 0;
+        self.reserved = false;
+    }
+
+    /// Returns the backing store.
+    #[inline]
+    pub fn into_inner(self) -> [[T; SLOT]; N] {
+        self.slots
+    }
+}
@@ -0,0 +1,145 @@
+//! Buffering for network sockets.
+//!
+//! [`BufferedSocket`] collects small writes in a transmit buffer and hands them to the network
+//! stack in contiguous blocks with [`flush`](BufferedSocket::flush), and fills a receive
+//! buffer from the stack with [`fill`](BufferedSocket::fill). Both take closures around the
+//! stack's calls, such as `send` and `receive` of an `embedded-nal` TCP stack, so a modem that
+//! charges per command sees a few large transfers instead of many small ones.
+//!
+//! Errors follow [`read_from`](crate::StaticBip::read_from): a call fails only if nothing was
+//! transferred, so with `nb` results an idle stack reports `WouldBlock` as usual.
+//!
+//! ```rust
+//! use staticbip::{socket::BufferedSocket, StaticBip};
+//!
+//! let mut socket = BufferedSocket::new(StaticBip::<u8, 64>::default(), StaticBip::<u8, 64>::default());
+//!
+//! socket.write(b"GET / HTTP/1.0\r\n").unwrap();
+//! socket.write(b"\r\n").unwrap();
+//!
+//! // Both writes go to the stack in one command
+//! let mut commands = Vec::new();
+//! let len = socket
+//!     .flush(|block| {
+//!         commands.push(block.to_vec());
+//!         Ok::<_, ()>(block.len())
+//!     })
+//!     .unwrap();
+//! assert_eq!(len, 18);
+//! assert_eq!(commands, [b"GET / HTTP/1.0\r\n\r\n"]);
+//!
+//! let mut response = &b"HTTP/1.0 200 OK\r\n"[..];
+//! socket.fill(|buf| std::io::Read::read(&mut response, buf)).unwrap();
+//! let mut data = [0; 32];
+//! assert_eq!(socket.read(&mut data), Ok(17));
+//! ```
+
+use crate::{nb::WouldBlock, StaticBip};
+
+/// Receive and transmit buffers of a network socket.
+#[derive(Debug)]
+pub struct BufferedSocket<const RX: usize, const TX: usize> {
+    /// Data received from the stack
+    rx: StaticBip<u8, RX>,
+    /// Data to send to the stack
+    tx: StaticBip<u8, TX>,
+}
+
+impl<const RX: usize, const TX: usize> BufferedSocket<RX, TX> {
+    /// Creates buffering over a receive and a transmit buffer.
+    #[inline]
+    pub fn new(rx: StaticBip<u8, RX>, tx: StaticBip<u8, TX>) -> Self {
+        Self { rx, tx }
+    }
+
+    /// Passes queued data to `send` in contiguous blocks until it takes less than a whole
+    /// block or fails.
+    ///
+    /// `send` returns the number of bytes the stack accepted. Returns the number of bytes
+    /// sent; an error is only returned if nothing was sent.
+    pub fn flush<E>(
+        &mut self,
+        mut send: impl FnMut(&[u8]) -> Result<usize, E>,
+    ) -> Result<usize, E> {
+        let mut total = 0;
+        loop {
+            let block = self.tx.read();
+            let len = block.len();
+            if len == 0 {
+                break;
+            }
+            let sent = match send(block) {
+                Ok(sent) => sent.min(len),
+                Err(err) if total == 0 => return Err(err),
+                Err(_) => 0,
+            };
+            self.tx.decommit(sent);
+            total += sent;
+            if sent < len {
+                break;
+            }
+        }
+        Ok(total)
+    }
+
+    /// Receives into the free space with `receive` until it returns less than it was offered
+    /// or fails.
+    ///
+    /// `receive` returns the number of bytes written. Returns the number of bytes received;
+    /// an error is only returned if nothing was received.
+    pub fn fill<E>(
+        &mut self,
+        mut receive: impl FnMut(&mut [u8]) -> Result<usize, E>,
+    ) -> Result<usize, E> {
+        let mut total = 0;
+        loop {
+            let free = self.rx.reserve_max();
+            let len = free.len();
+            if len == 0 {
+                break;
+            }
+            let received = match receive(free) {
+                Ok(received) => received,
+                Err(err) if total == 0 => {
+                    self.rx.commit(0);
+                    return Err(err);
+                }
+                Err(_) => 0,
+            };
+            self.rx.commit(received);
+            total += received;
+            if received < len {
+                break;
+            }
+        }
+        Ok(total)
+    }
+
+    /// Number of bytes queued and not yet sent.
+    #[inline]
+    pub fn unsent(&self) -> usize {
+        self.tx.committed()
+    }
+
+    /// Moves received data into `out`.
+    ///
+    /// Returns the number of bytes read, or [`WouldBlock`] if nothing has been received.
+    #[inline]
+    pub fn read(&mut self, out: &mut [u8]) -> Result<usize, WouldBlock> {
+        self.rx.try_read(out)
+    }
+
+    /// Queues `data` for sending.
+    ///
+    /// Returns the number of bytes queued, or [`WouldBlock`] if the buffer is full.
+    #[inline]
+    pub fn write(&mut self, data: &[u8]) -> Result<usize, WouldBlock> {
+        self.tx.try_write(data)
+    }
+
+    /// Returns the receive and transmit buffers.
+    #[inline]
+    pub fn into_inner(self) -> (StaticBip<u8, RX>, StaticBip<u8, TX>) {
+        (self.rx, self.tx)
+    }
+}
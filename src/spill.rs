@@ -0,0 +1,128 @@
+//! Heap spillover for bursts that outgrow the static buffer.
+//!
+//! A [`Spillover`] writes into its [`StaticBip`] while there is room and appends the rest to
+//! a growable queue on the heap. As the consumer frees space, spilled elements move back into
+//! the static buffer, oldest first, so elements are always read in the order they were
+//! written. While nothing is spilled, writes and reads take the static fast path and never
+//! allocate.
+//!
+//! ```rust
+//! use staticbip::{spill::Spillover, StaticBip};
+//!
+//! let mut buffer = Spillover::new(StaticBip::<u8, 4>::default());
+//! assert_eq!(buffer.write(&[1, 2, 3, 4, 5, 6]), 2);
+//! assert_eq!(buffer.spilled(), 2);
+//!
+//! assert_eq!(buffer.read(), &[1, 2, 3, 4]);
+//! buffer.decommit(3);
+//! assert_eq!(buffer.spilled(), 0);
+//! assert_eq!(buffer.pop(), Some(4));
+//! assert_eq!(buffer.pop(), Some(5));
+//! ```
+
+use crate::StaticBip;
+use alloc::collections::VecDeque;
+
+/// A [`StaticBip`] that spills writes which do not fit into a queue on the heap.
+#[derive(Debug)]
+pub struct Spillover<T, const CAP: usize> {
+    bip: StaticBip<T, CAP>,
+    /// Elements written after the buffer filled up, oldest first
+    spill: VecDeque<T>,
+}
+
+impl<T: Default, const CAP: usize> Default for Spillover<T, CAP> {
+    #[inline]
+    fn default() -> Self {
+        Self::new(StaticBip::default())
+    }
+}
+
+impl<T, const CAP: usize> Spillover<T, CAP> {
+    /// Wraps `bip`, keeping its committed elements.
+    #[inline]
+    pub fn new(bip: StaticBip<T, CAP>) -> Self {
+        Self {
+            bip,
+            spill: VecDeque::new(),
+        }
+    }
+
+    /// Returns the wrapped buffer.
+    #[inline]
+    pub fn get_ref(&self) -> &StaticBip<T, CAP> {
+        &self.bip
+    }
+
+    /// Returns the wrapped buffer and the spilled elements, oldest first.
+    #[inline]
+    pub fn into_parts(self) -> (StaticBip<T, CAP>, VecDeque<T>) {
+        (self.bip, self.spill)
+    }
+
+    /// Number of elements waiting on the heap for room in the buffer.
+    #[inline]
+    pub fn spilled(&self) -> usize {
+        self.spill.len()
+    }
+
+    /// Number of elements written and not yet read, including spilled ones.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.bip.committed() + self.spill.len()
+    }
+
+    /// Whether there is nothing to read.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Retrieves the oldest elements as a contiguous block of the buffer.
+    #[inline]
+    pub fn read(&mut self) -> &mut [T] {
+        self.bip.read()
+    }
+}
+
+impl<T: Copy, const CAP: usize> Spillover<T, CAP> {
+    /// Appends `data`, spilling what does not fit in the buffer.
+    ///
+    /// Once anything has spilled, later writes go to the heap as well until it drains, which
+    /// keeps the elements in order. Returns the number of elements spilled.
+    pub fn write(&mut self, data: &[T]) -> usize {
+        let direct = if self.spill.is_empty() {
+            self.bip.push_iter(data.iter().copied())
+        } else {
+            0
+        };
+        self.spill.extend(&data[direct..]);
+        data.len() - direct
+    }
+
+    /// Appends `item`, spilling it if it does not fit in the buffer.
+    #[inline]
+    pub fn push(&mut self, item: T) {
+        self.write(&[item]);
+    }
+
+    /// Marks the first `len` elements of [`read`](Self::read) as seen and moves spilled
+    /// elements into the freed space.
+    pub fn decommit(&mut self, len: usize) {
+        self.bip.decommit(len);
+        self.refill();
+    }
+
+    /// Removes the oldest element.
+    pub fn pop(&mut self) -> Option<T> {
+        let item = self.bip.dequeue()?;
+        self.refill();
+        Some(item)
+    }
+
+    /// Moves spilled elements into the buffer while there is room.
+    fn refill(&mut self) {
+        let moved = self.bip.push_iter(self.spill.iter().copied());
+        self.spill.drain(..moved);
+    }
+}
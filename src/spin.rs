@@ -0,0 +1,188 @@
+//! Shared access across cores behind a spinlock.
+//!
+//! A [`SpinBip`] produces and consumes through `&self` like a [`LocalBip`](crate::local::LocalBip),
+//! but can be shared between cores and threads. Each call holds a spinlock instead of
+//! disabling interrupts, which suits multi-core systems where masking interrupts on one core
+//! does not keep the others out.
+//!
+//! While the lock is taken, waiting callers run a [`Relax`] strategy between attempts:
+//! [`Spin`] hints the processor, [`Loop`] retries immediately, and an implementation of the
+//! trait can yield to a scheduler or wait for an event.
+//!
+//! ```rust
+//! use staticbip::spin::SpinBip;
+//!
+//! static BUFFER: SpinBip<u8, 4> = SpinBip::new([0; 4]);
+//!
+//! std::thread::spawn(|| BUFFER.push(1).unwrap()).join().unwrap();
+//! BUFFER.with(|bip| {
+//!     bip.reserve(2).copy_from_slice(&[2, 3]);
+//!     bip.commit(2);
+//! });
+//!
+//! assert_eq!(BUFFER.pop(), Some(1));
+//! assert_eq!(BUFFER.with(|bip| bip.read().len()), 2);
+//! ```
+//!
+//! A lock held by an interrupted caller is never released to an interrupt handler spinning on
+//! the same core, so a buffer shared with a handler needs interrupts masked around each call
+//! on that core, or [`try_with`](SpinBip::try_with) in the handler.
+
+use crate::StaticBip;
+use core::{
+    cell::UnsafeCell,
+    fmt, hint,
+    marker::PhantomData,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+/// What a caller does between attempts to take a [`SpinBip`] lock.
+pub trait Relax {
+    /// Waits a little before the next attempt.
+    fn relax();
+}
+
+/// Hints the processor that the caller is spinning.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Spin;
+
+impl Relax for Spin {
+    #[inline]
+    fn relax() {
+        hint::spin_loop();
+    }
+}
+
+/// Retries right away.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Loop;
+
+impl Relax for Loop {
+    #[inline]
+    fn relax() {}
+}
+
+/// A Bip-Buffer shared between cores through a spinlock.
+pub struct SpinBip<T, const CAP: usize, S = Spin> {
+    bip: UnsafeCell<StaticBip<T, CAP>>,
+    locked: AtomicBool,
+    relax: PhantomData<fn() -> S>,
+}
+
+// SAFETY: the buffer is only accessed while holding `locked`.
+unsafe impl<T: Send, const CAP: usize, S> Sync for SpinBip<T, CAP, S> {}
+
+/// Releases the lock when dropped, including on unwinding.
+struct Unlock<'a>(&'a AtomicBool);
+
+impl Drop for Unlock<'_> {
+    #[inline]
+    fn drop(&mut self) {
+        self.0.store(false, Ordering::Release);
+    }
+}
+
+impl<T, const CAP: usize, S> SpinBip<T, CAP, S> {
+    /// Creates a new buffer of `T` elements, usable in a `static`.
+    #[inline]
+    pub const fn new(buffer: [T; CAP]) -> Self {
+        Self {
+            bip: UnsafeCell::new(StaticBip::new(buffer)),
+            locked: AtomicBool::new(false),
+            relax: PhantomData,
+        }
+    }
+
+    /// Calls `f` with the buffer if the lock is free, without waiting.
+    ///
+    /// Returns `None` if the lock is held, including from inside another `with` on the same
+    /// buffer.
+    #[inline]
+    pub fn try_with<R>(&self, f: impl FnOnce(&mut StaticBip<T, CAP>) -> R) -> Option<R> {
+        self.locked
+            .compare_exchange(false, true, Ordering::Acquire, Ordering::Relaxed)
+            .ok()?;
+        let _unlock = Unlock(&self.locked);
+        // SAFETY: the lock was free, so nothing else refers to the buffer until it is released.
+        Some(f(unsafe { &mut *self.bip.get() }))
+    }
+
+    /// Returns the wrapped buffer.
+    #[inline]
+    pub fn into_inner(self) -> StaticBip<T, CAP> {
+        self.bip.into_inner()
+    }
+}
+
+impl<T, const CAP: usize, S: Relax> SpinBip<T, CAP, S> {
+    /// Calls `f` with the buffer, spinning until the lock is free.
+    ///
+    /// Calling `with` from inside another `with` on the same buffer never returns.
+    #[inline]
+    pub fn with<R>(&self, f: impl FnOnce(&mut StaticBip<T, CAP>) -> R) -> R {
+        while self
+            .locked
+            .compare_exchange_weak(false, true, Ordering::Acquire, Ordering::Relaxed)
+            .is_err()
+        {
+            while self.locked.load(Ordering::Relaxed) {
+                S::relax();
+            }
+        }
+        let _unlock = Unlock(&self.locked);
+        // SAFETY: the lock was free, so nothing else refers to the buffer until it is released.
+        f(unsafe { &mut *self.bip.get() })
+    }
+
+    /// Appends `item`, handing it back if the buffer is full.
+    #[inline]
+    pub fn push(&self, item: T) -> Result<(), T> {
+        self.with(|bip| bip.enqueue(item))
+    }
+
+    /// Number of committed elements.
+    #[inline]
+    pub fn committed(&self) -> usize {
+        self.with(|bip| bip.committed())
+    }
+
+    /// Whether there are no committed elements.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.committed() == 0
+    }
+}
+
+impl<T: Copy, const CAP: usize, S: Relax> SpinBip<T, CAP, S> {
+    /// Removes the oldest element.
+    #[inline]
+    pub fn pop(&self) -> Option<T> {
+        self.with(|bip| bip.dequeue())
+    }
+}
+
+impl<T: Default, const CAP: usize, S> Default for SpinBip<T, CAP, S> {
+    #[inline]
+    fn default() -> Self {
+        Self::from(StaticBip::default())
+    }
+}
+
+impl<T, const CAP: usize, S> From<StaticBip<T, CAP>> for SpinBip<T, CAP, S> {
+    #[inline]
+    fn from(bip: StaticBip<T, CAP>) -> Self {
+        Self {
+            bip: UnsafeCell::new(bip),
+            locked: AtomicBool::new(false),
+            relax: PhantomData,
+        }
+    }
+}
+
+impl<T, const CAP: usize, S> fmt::Debug for SpinBip<T, CAP, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SpinBip")
+            .field("locked", &self.locked.load(Ordering::Relaxed))
+            .finish_non_exhaustive()
+    }
+}
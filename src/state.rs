@@ -0,0 +1,171 @@
+//! Region layout exported from a buffer.
+
+use crate::regions::Regions;
+use core::{fmt, ops::Range};
+
+/// A run of elements in the backing store.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(C)]
+pub struct Span {
+    /// Index of the first element
+    pub offset: usize,
+    /// Number of elements
+    pub len: usize,
+}
+
+impl Span {
+    /// Index one past the last element.
+    #[inline]
+    pub const fn end(&self) -> usize {
+        self.offset + self.len
+    }
+}
+
+impl From<Range<usize>> for Span {
+    #[inline]
+    fn from(range: Range<usize>) -> Self {
+        Self {
+            offset: range.start,
+            len: range.len(),
+        }
+    }
+}
+
+impl From<Span> for Range<usize> {
+    #[inline]
+    fn from(span: Span) -> Self {
+        span.offset..span.end()
+    }
+}
+
+/// Positions of the regions of a buffer, without its data.
+///
+/// Region `A` holds the oldest committed data and region `B`, which always precedes it in
+/// the backing store, the newest. Region `B` starts at the beginning of the backing store.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(C)]
+pub struct BipState {
+    /// `A` region
+    pub a: Span,
+    /// `B` region
+    pub b: Span,
+    /// Reserved region
+    pub reserve: Span,
+}
+
+impl BipState {
+    /// Whether the regions fit a backing store of `capacity` elements as a buffer can leave
+    /// them: `B` starts the backing store, ends before `A` and only holds data while `A` does,
+    /// and a non-empty reservation lies within the free space.
+    pub fn is_valid(&self, capacity: usize) -> bool {
+        let Self { a, b, reserve } = *self;
+        // Ends are checked so that a corrupted offset near `usize::MAX` fails validation
+        // instead of wrapping around into range
+        let (Some(a_end), Some(b_end), Some(reserve_end)) = (
+            a.offset.checked_add(a.len),
+            b.offset.checked_add(b.len),
+            reserve.offset.checked_add(reserve.len),
+        ) else {
+            return false;
+        };
+        // While `B` holds data the only free space is between it and `A`, otherwise there
+        // are the blocks after and before `A`
+        let reserve_free = if b.len != 0 {
+            b_end <= reserve.offset && reserve_end <= a.offset
+        } else {
+            (a_end <= reserve.offset && reserve_end <= capacity) || reserve_end <= a.offset
+        };
+        a_end <= capacity
+            && reserve_end <= capacity
+            && (b.len == 0 || (a.len != 0 && b.offset == 0 && b_end <= a.offset))
+            && (reserve.len == 0 || reserve_free)
+    }
+
+    /// Returns a map of the regions in a backing store of `capacity` elements, for display.
+    #[inline]
+    pub fn layout(&self, capacity: usize) -> LayoutMap {
+        LayoutMap {
+            state: *self,
+            capacity,
+        }
+    }
+}
+
+/// One character per element of a backing store: `A` and `B` for the regions, `R` for the
+/// reservation and `.` for free space, such as `[BB.RRAAAA..]`.
+///
+/// Created by [`BipState::layout`]. An element claimed by more than one region, which only
+/// happens in an invalid state, is shown as `!`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LayoutMap {
+    state: BipState,
+    capacity: usize,
+}
+
+impl fmt::Display for LayoutMap {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use fmt::Write;
+
+        let BipState { a, b, reserve } = self.state;
+        let contains =
+            |span: Span, index: usize| span.offset <= index && index - span.offset < span.len;
+        f.write_char('[')?;
+        for index in 0..self.capacity {
+            let c = match (
+                contains(a, index),
+                contains(b, index),
+                contains(reserve, index),
+            ) {
+                (false, false, false) => '.',
+                (true, false, false) => 'A',
+                (false, true, false) => 'B',
+                (false, false, true) => 'R',
+                _ => '!',
+            };
+            f.write_char(c)?;
+        }
+        f.write_char(']')
+    }
+}
+
+impl From<&Regions> for BipState {
+    #[inline]
+    fn from(regions: &Regions) -> Self {
+        Self {
+            a: regions.a().into(),
+            b: regions.b().into(),
+            reserve: regions.reservation().into(),
+        }
+    }
+}
+
+impl From<BipState> for Regions {
+    #[inline]
+    fn from(state: BipState) -> Self {
+        let mut regions = Self::new();
+        regions.set_a(state.a.into());
+        regions.set_b_end(state.b.len);
+        regions.set_reservation(state.reserve.into());
+        #[cfg(feature = "stats")]
+        {
+            regions.written = (state.a.len + state.b.len) as u64;
+        }
+        regions
+    }
+}
+
+/// Base pointer and region layout of a buffer, for foreign code and DMA descriptors.
+///
+/// Created by [`StaticBip::as_raw_parts`](crate::StaticBip::as_raw_parts). The pointer is only
+/// valid while the buffer is neither moved nor accessed through Rust references, and
+/// the layout only describes the buffer until its next operation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(C)]
+pub struct RawParts<T> {
+    /// First element of the backing store
+    pub ptr: *mut T,
+    /// Number of elements in the backing store
+    pub capacity: usize,
+    /// Positions of the regions
+    pub state: BipState,
+}
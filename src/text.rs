@@ -0,0 +1,105 @@
+//! UTF-8 views of committed bytes.
+//!
+//! [`as_strs`](StaticBip::as_strs) validates the committed data of both regions, decoding a
+//! character whose bytes are split between the end of the first region and the start of the
+//! second one. Validation stops at the first invalid or incomplete character;
+//! [`Utf8Strs::valid_up_to`] tells how many bytes the views cover.
+//!
+//! ```rust
+//! use staticbip::StaticBip;
+//!
+//! let mut buffer = StaticBip::<u8, 8>::default();
+//! buffer.extend(b"xxxxx");
+//! buffer.decommit(3);
+//! buffer.extend("é€".as_bytes());
+//!
+//! let strs = buffer.as_strs();
+//! assert_eq!(strs.head, "xxé");
+//! assert_eq!(strs.boundary, Some('€'));
+//! assert_eq!(strs.valid_up_to, 7);
+//! ```
+
+use crate::StaticBip;
+use core::str;
+
+/// UTF-8 views of the committed data of a byte buffer, in order.
+///
+/// Created by [`StaticBip::as_strs`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Utf8Strs<'a> {
+    /// Valid prefix of the first region.
+    pub head: &'a str,
+    /// Character split between the first and second region.
+    pub boundary: Option<char>,
+    /// Valid prefix of the second region following the boundary character.
+    pub tail: &'a str,
+    /// Number of committed bytes covered by the views. Any bytes after them are invalid or
+    /// an incomplete character.
+    pub valid_up_to: usize,
+}
+
+impl<const CAP: usize> StaticBip<u8, CAP> {
+    /// Returns the valid UTF-8 prefix of the first contiguous block of committed data, and
+    /// the bytes of the block after it.
+    #[inline]
+    pub fn as_str(&self) -> (&str, &[u8]) {
+        valid_prefix(self.as_slices().0)
+    }
+
+    /// Returns UTF-8 views of the committed data of both regions.
+    pub fn as_strs(&self) -> Utf8Strs<'_> {
+        let (a, b) = self.as_slices();
+        let (head, rest) = valid_prefix(a);
+        let mut strs = Utf8Strs {
+            head,
+            boundary: None,
+            tail: "",
+            valid_up_to: head.len(),
+        };
+
+        let b = if rest.is_empty() {
+            b
+        } else {
+            let (boundary, len) = match boundary_char(rest, b) {
+                Some(boundary) => boundary,
+                None => return strs,
+            };
+            strs.boundary = Some(boundary);
+            strs.valid_up_to += len;
+            &b[len - rest.len()..]
+        };
+        strs.tail = valid_prefix(b).0;
+        strs.valid_up_to += strs.tail.len();
+        strs
+    }
+}
+
+/// Splits `bytes` into its longest valid UTF-8 prefix and the rest.
+fn valid_prefix(bytes: &[u8]) -> (&str, &[u8]) {
+    let valid = match str::from_utf8(bytes) {
+        Ok(_) => bytes.len(),
+        Err(err) => err.valid_up_to(),
+    };
+    let (valid, rest) = bytes.split_at(valid);
+    // SAFETY: `from_utf8` validated the prefix.
+    (unsafe { str::from_utf8_unchecked(valid) }, rest)
+}
+
+/// Decodes the character that starts with the incomplete bytes `start` and continues in
+/// `next`, returning it and its encoded length.
+fn boundary_char(start: &[u8], next: &[u8]) -> Option<(char, usize)> {
+    let mut bytes = [0; 4];
+    let start_len = start.len();
+    if start_len >= bytes.len() {
+        return None;
+    }
+    let next_len = next.len().min(bytes.len() - start_len);
+    bytes[..start_len].copy_from_slice(start);
+    bytes[start_len..start_len + next_len].copy_from_slice(&next[..next_len]);
+
+    let c = valid_prefix(&bytes[..start_len + next_len])
+        .0
+        .chars()
+        .next()?;
+    Some((c, c.len_utf8()))
+}
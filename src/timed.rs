@@ -0,0 +1,162 @@
+//! Bip-Buffer of timestamped records.
+//!
+//! Each commit to a [`TimedBip`] stores one record along with the time it was committed,
+//! read back with the record, so consumers can tell how stale buffered data is. Time comes
+//! from the caller as any [`Instant`], such as a tick counter or `std::time::Instant`.
+//!
+//! ```rust
+//! use staticbip::timed::TimedBip;
+//!
+//! // Up to 4 records in 16 elements, timestamped with a millisecond tick counter
+//! let mut buffer = TimedBip::<u8, u32, 16, 4>::default();
+//!
+//! buffer.reserve(2).copy_from_slice(&[1, 2]);
+//! buffer.commit(2, 100);
+//! buffer.reserve(1).copy_from_slice(&[3]);
+//! buffer.commit(1, 250);
+//!
+//! assert_eq!(buffer.age(300), Some(200));
+//! assert_eq!(buffer.read(), Some((100, &mut [1, 2][..])));
+//!
+//! buffer.decommit();
+//! assert_eq!(buffer.age(300), Some(50));
+//! ```
+
+use crate::StaticBip;
+use core::array;
+
+/// A point in time.
+pub trait Instant: Copy {
+    /// Time elapsed between two instants.
+    type Duration;
+
+    /// Time elapsed from `earlier` to `self`.
+    fn duration_since(&self, earlier: Self) -> Self::Duration;
+}
+
+macro_rules! tick_instant {
+    ($($ty:ty),*) => {
+        $(
+            /// A wrapping tick counter.
+            impl Instant for $ty {
+                type Duration = $ty;
+
+                #[inline]
+                fn duration_since(&self, earlier: Self) -> Self::Duration {
+                    self.wrapping_sub(earlier)
+                }
+            }
+        )*
+    };
+}
+
+tick_instant!(u16, u32, u64);
+
+#[cfg(feature = "std")]
+impl Instant for std::time::Instant {
+    type Duration = std::time::Duration;
+
+    #[inline]
+    fn duration_since(&self, earlier: Self) -> Self::Duration {
+        self.saturating_duration_since(earlier)
+    }
+}
+
+/// A Bip-Buffer with a fixed capacity and up to `N` timestamped records.
+#[derive(Debug)]
+pub struct TimedBip<T, I, const CAP: usize, const N: usize> {
+    bip: StaticBip<T, CAP>,
+    /// Ring of record lengths and commit times, oldest first
+    records: [Option<(usize, I)>; N],
+    /// Index of the oldest record
+    head: usize,
+    /// Number of records
+    len: usize,
+}
+
+impl<T: Default, I: Instant, const CAP: usize, const N: usize> Default for TimedBip<T, I, CAP, N> {
+    #[inline]
+    fn default() -> Self {
+        Self::new(array::from_fn(|_| T::default()))
+    }
+}
+
+impl<T, I: Instant, const CAP: usize, const N: usize> TimedBip<T, I, CAP, N> {
+    /// Creates and allocates a new buffer of `T` elements.
+    #[inline]
+    pub fn new(buffer: [T; CAP]) -> Self {
+        Self {
+            bip: StaticBip::new(buffer),
+            records: [None; N],
+            head: 0,
+            len: 0,
+        }
+    }
+
+    /// Number of committed records.
+    #[inline]
+    pub fn records(&self) -> usize {
+        self.len
+    }
+
+    /// Returns a mutable buffer containing up to `count` slots for the next record.
+    ///
+    /// The buffer is empty if `N` records are already committed.
+    #[inline]
+    pub fn reserve(&mut self, count: usize) -> &mut [T] {
+        if self.len == N {
+            return self.bip.reserve(0);
+        }
+        self.bip.reserve(count)
+    }
+
+    /// Commits the first `len` reserved elements as a record taken at `now`.
+    ///
+    /// If a `len` of `0` is passed in, the reservation will be cleared without adding a record.
+    pub fn commit(&mut self, len: usize, now: I) {
+        let len = len.min(self.bip.reserved());
+        self.bip.commit(len);
+        if len != 0 {
+            self.records[(self.head + self.len) % N] = Some((len, now));
+            self.len += 1;
+        }
+    }
+
+    /// Returns the oldest record along with its commit time.
+    pub fn read(&mut self) -> Option<(I, &mut [T])> {
+        let (len, time) = self.front()?;
+        Some((time, self.bip.read_exact(len)?))
+    }
+
+    /// Removes the oldest record.
+    pub fn decommit(&mut self) {
+        if let Some((len, _)) = self.front() {
+            self.bip.decommit(len);
+            self.records[self.head] = None;
+            self.head = (self.head + 1) % N;
+            self.len -= 1;
+        }
+    }
+
+    /// Commit time of the oldest record.
+    #[inline]
+    pub fn oldest(&self) -> Option<I> {
+        self.front().map(|(_, time)| time)
+    }
+
+    /// Time elapsed from the commit of the oldest record to `now`.
+    #[inline]
+    pub fn age(&self, now: I) -> Option<I::Duration> {
+        self.oldest().map(|time| now.duration_since(time))
+    }
+
+    /// The oldest record.
+    #[inline]
+    fn front(&self) -> Option<(usize, I)> {
+        if self.len == 0 {
+            None
+        } else {
+            self.records[self.head]
+        }
+    }
+}
@@ -0,0 +1,385 @@
+//! Interrupt-driven UART buffering.
+//!
+//! [`BufferedUart`] keeps a receive and a transmit buffer for a serial port. The interrupt
+//! handler drains and feeds the hardware with [`on_rx_irq`](BufferedUart::on_rx_irq) and
+//! [`on_tx_irq`](BufferedUart::on_tx_irq), passing closures around the embedded-hal serial
+//! `read` and `write` calls, while the application uses [`read`](BufferedUart::read) and
+//! [`write`](BufferedUart::write).
+//!
+//! ```rust
+//! use staticbip::{uart::BufferedUart, StaticBip};
+//!
+//! let mut uart = BufferedUart::new(StaticBip::<u8, 16>::default(), StaticBip::<u8, 16>::default());
+//!
+//! // Receive interrupt: the data register holds two bytes
+//! let mut fifo = b"ok".iter().copied();
+//! uart.on_rx_irq(|| fifo.next().ok_or(()));
+//!
+//! // Application: echo the data
+//! let mut data = [0; 4];
+//! let len = uart.read(&mut data).unwrap();
+//! uart.write(&data[..len]).unwrap();
+//!
+//! // Transmit interrupt: the data register accepts bytes
+//! let mut sent = Vec::new();
+//! let pending = uart.on_tx_irq(|byte| {
+//!     sent.push(byte);
+//!     Ok::<_, ()>(())
+//! });
+//! assert!(!pending);
+//! assert_eq!(sent, b"ok");
+//! ```
+//!
+//! [`AsyncUart`] is the counterpart for async drivers. Its buffers are [`bbqueue`](crate::bbqueue)
+//! storages shared with the interrupt handler, which keeps the other halves in a [`UartIrq`]
+//! and feeds and drains the hardware the same way. Reads wait for received data and writes
+//! for free room, woken by the interrupt handler; writes call a hook that starts the
+//! transmission, such as enabling the transmit interrupt.
+//!
+//! ```rust
+//! use staticbip::{
+//!     bbqueue::BufStorage,
+//!     uart::{AsyncUart, UartIrq},
+//! };
+//! # use core::{future::Future, pin::pin, task::{Context, Poll, Waker}};
+//!
+//! let rx = BufStorage::<16>::new();
+//! let tx = BufStorage::<16>::new();
+//! let (rx_producer, rx_consumer) = rx.try_split().unwrap();
+//! let (tx_producer, tx_consumer) = tx.try_split().unwrap();
+//! let mut irq = UartIrq::new(rx_producer, tx_consumer);
+//! let mut uart = AsyncUart::new(rx_consumer, tx_producer, || {});
+//!
+//! let mut fifo = b"ok".iter().copied();
+//! irq.on_rx_irq(|| fifo.next().ok_or(()));
+//!
+//! let task = async {
+//!     let mut data = [0; 4];
+//!     let len = uart.read(&mut data).await;
+//!     uart.write(&data[..len]).await
+//! };
+//! let mut cx = Context::from_waker(Waker::noop());
+//! assert_eq!(pin!(task).poll(&mut cx), Poll::Ready(2));
+//!
+//! let mut sent = Vec::new();
+//! irq.on_tx_irq(|byte| {
+//!     sent.push(byte);
+//!     Ok::<_, ()>(())
+//! });
+//! assert_eq!(sent, b"ok");
+//! ```
+
+#[cfg(target_has_atomic = "ptr")]
+use crate::bbqueue::{Consumer, Producer};
+use crate::{nb::WouldBlock, StaticBip};
+#[cfg(target_has_atomic = "ptr")]
+use core::{
+    fmt,
+    future::{poll_fn, Future},
+    pin::pin,
+    task::Poll,
+};
+
+/// Receive and transmit buffers of a serial port.
+#[derive(Debug)]
+pub struct BufferedUart<const RX: usize, const TX: usize> {
+    /// Data received from the port
+    rx: StaticBip<u8, RX>,
+    /// Data to send to the port
+    tx: StaticBip<u8, TX>,
+    /// Number of received bytes dropped because the receive buffer was full
+    overruns: usize,
+}
+
+impl<const RX: usize, const TX: usize> BufferedUart<RX, TX> {
+    /// Creates a port over a receive and a transmit buffer.
+    #[inline]
+    pub fn new(rx: StaticBip<u8, RX>, tx: StaticBip<u8, TX>) -> Self {
+        Self {
+            rx,
+            tx,
+            overruns: 0,
+        }
+    }
+
+    /// Number of received bytes dropped because the receive buffer was full.
+    #[inline]
+    pub fn overruns(&self) -> usize {
+        self.overruns
+    }
+
+    /// Stores bytes returned by `read` until it fails.
+    ///
+    /// Bytes that do not fit are dropped and counted as [`overruns`](Self::overruns).
+    /// Returns the number of bytes stored.
+    pub fn on_rx_irq<E>(&mut self, mut read: impl FnMut() -> Result<u8, E>) -> usize {
+        let mut stored = 0;
+        while let Ok(byte) = read() {
+            match self.rx.try_push(byte) {
+                Ok(()) => stored += 1,
+                Err(WouldBlock) => self.overruns += 1,
+            }
+        }
+        stored
+    }
+
+    /// Passes pending bytes to `write` until it fails.
+    ///
+    /// Returns whether bytes are still pending, so the transmit interrupt can be disabled
+    /// once everything has been sent.
+    pub fn on_tx_irq<E>(&mut self, mut write: impl FnMut(u8) -> Result<(), E>) -> bool {
+        while let Some(&byte) = self.tx.front() {
+            if write(byte).is_err() {
+                break;
+            }
+            self.tx.pop();
+        }
+        !self.tx.is_empty()
+    }
+
+    /// Moves received data into `out`.
+    ///
+    /// Returns the number of bytes read, or [`WouldBlock`] if nothing has been received.
+    #[inline]
+    pub fn read(&mut self, out: &mut [u8]) -> Result<usize, WouldBlock> {
+        self.rx.try_read(out)
+    }
+
+    /// Queues `data` for sending.
+    ///
+    /// Returns the number of bytes queued, or [`WouldBlock`] if the buffer is full.
+    #[inline]
+    pub fn write(&mut self, data: &[u8]) -> Result<usize, WouldBlock> {
+        self.tx.try_write(data)
+    }
+}
+
+/// Nothing was received before the deadline.
+#[cfg(target_has_atomic = "ptr")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimedOut;
+
+#[cfg(target_has_atomic = "ptr")]
+impl fmt::Display for TimedOut {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("timed out")
+    }
+}
+
+/// Task side of an interrupt-driven serial port for async code.
+///
+/// The method signatures follow `embedded_io_async::{Read, Write}`. The interrupt side is a
+/// [`UartIrq`] over the other halves of the same storages.
+#[cfg(target_has_atomic = "ptr")]
+pub struct AsyncUart<'a, const RX: usize, const TX: usize, F> {
+    /// Data received from the port
+    rx: Consumer<'a, RX>,
+    /// Data to send to the port
+    tx: Producer<'a, TX>,
+    /// Called after queueing data to send
+    start_tx: F,
+}
+
+#[cfg(target_has_atomic = "ptr")]
+impl<'a, const RX: usize, const TX: usize, F: FnMut()> AsyncUart<'a, RX, TX, F> {
+    /// Creates a port reading from `rx` and writing to `tx`.
+    ///
+    /// `start_tx` is called whenever data has been queued, typically to enable the transmit
+    /// interrupt.
+    #[inline]
+    pub fn new(rx: Consumer<'a, RX>, tx: Producer<'a, TX>, start_tx: F) -> Self {
+        Self { rx, tx, start_tx }
+    }
+
+    /// Moves received data into `out`, waiting until some has been received.
+    ///
+    /// Returns the number of bytes read, `0` only if `out` is empty.
+    pub async fn read(&mut self, out: &mut [u8]) -> usize {
+        if out.is_empty() {
+            return 0;
+        }
+        let rx = &mut self.rx;
+        poll_fn(|cx| {
+            // Registered first, so a commit between the check and the return still wakes
+            rx.register_read_waker(cx.waker());
+            match rx.read() {
+                Ok(grant) => {
+                    let len = grant.len().min(out.len());
+                    out[..len].copy_from_slice(&grant[..len]);
+                    grant.release(len);
+                    Poll::Ready(len)
+                }
+                Err(_) => Poll::Pending,
+            }
+        })
+        .await
+    }
+
+    /// Moves received data into `out` until `n` bytes have been read or `deadline` completes.
+    ///
+    /// `deadline` is any future that completes when time is up, such as
+    /// `delay.delay_ms(20)` on an `embedded_hal_async::delay::DelayNs`. It is polled in the
+    /// same future as the receive buffer, so bytes that arrive together with the deadline are
+    /// still read. An `n` above the length of `out` waits for `out` to fill.
+    ///
+    /// Returns the number of bytes read, or [`TimedOut`] if none arrived before the deadline.
+    pub async fn read_timeout(
+        &mut self,
+        out: &mut [u8],
+        n: usize,
+        deadline: impl Future<Output = ()>,
+    ) -> Result<usize, TimedOut> {
+        let n = n.min(out.len());
+        let mut deadline = pin!(deadline);
+        let mut read = 0;
+        let rx = &mut self.rx;
+        poll_fn(|cx| {
+            rx.register_read_waker(cx.waker());
+            while read < n {
+                let Ok(grant) = rx.read() else { break };
+                let len = grant.len().min(out.len() - read);
+                out[read..read + len].copy_from_slice(&grant[..len]);
+                grant.release(len);
+                read += len;
+            }
+            if read >= n {
+                return Poll::Ready(Ok(read));
+            }
+            match deadline.as_mut().poll(cx) {
+                Poll::Ready(()) if read == 0 => Poll::Ready(Err(TimedOut)),
+                Poll::Ready(()) => Poll::Ready(Ok(read)),
+                Poll::Pending => Poll::Pending,
+            }
+        })
+        .await
+    }
+
+    /// Queues `data` for sending, waiting until there is room for some of it.
+    ///
+    /// Returns the number of bytes queued, `0` only if `data` is empty.
+    pub async fn write(&mut self, data: &[u8]) -> usize {
+        if data.is_empty() {
+            return 0;
+        }
+        let tx = &mut self.tx;
+        let start_tx = &mut self.start_tx;
+        poll_fn(|cx| {
+            tx.register_write_waker(cx.waker());
+            match tx.grant_max_remaining(data.len()) {
+                Ok(mut grant) => {
+                    let len = grant.len();
+                    grant.copy_from_slice(&data[..len]);
+                    grant.commit(len);
+                    start_tx();
+                    Poll::Ready(len)
+                }
+                Err(_) => Poll::Pending,
+            }
+        })
+        .await
+    }
+
+    /// Queues all of `data` for sending.
+    pub async fn write_all(&mut self, mut data: &[u8]) {
+        while !data.is_empty() {
+            let len = self.write(data).await;
+            data = &data[len..];
+        }
+    }
+
+    /// Waits until the interrupt handler has taken every queued byte.
+    ///
+    /// The last bytes may still be in the hardware.
+    pub async fn flush(&mut self) {
+        let tx = &self.tx;
+        poll_fn(|cx| {
+            tx.register_write_waker(cx.waker());
+            if tx.pending() == 0 {
+                Poll::Ready(())
+            } else {
+                Poll::Pending
+            }
+        })
+        .await
+    }
+
+    /// Returns the receive and transmit halves and the transmit hook.
+    #[inline]
+    pub fn into_inner(self) -> (Consumer<'a, RX>, Producer<'a, TX>, F) {
+        (self.rx, self.tx, self.start_tx)
+    }
+}
+
+/// Interrupt side of an [`AsyncUart`].
+#[cfg(target_has_atomic = "ptr")]
+pub struct UartIrq<'a, const RX: usize, const TX: usize> {
+    /// Data received from the port
+    rx: Producer<'a, RX>,
+    /// Data to send to the port
+    tx: Consumer<'a, TX>,
+    /// Number of received bytes dropped because the receive buffer was full
+    overruns: usize,
+}
+
+#[cfg(target_has_atomic = "ptr")]
+impl<'a, const RX: usize, const TX: usize> UartIrq<'a, RX, TX> {
+    /// Creates the interrupt side writing to `rx` and reading from `tx`.
+    #[inline]
+    pub fn new(rx: Producer<'a, RX>, tx: Consumer<'a, TX>) -> Self {
+        Self {
+            rx,
+            tx,
+            overruns: 0,
+        }
+    }
+
+    /// Number of received bytes dropped because the receive buffer was full.
+    #[inline]
+    pub fn overruns(&self) -> usize {
+        self.overruns
+    }
+
+    /// Stores bytes returned by `read` until it fails, waking a waiting reader.
+    ///
+    /// Bytes that do not fit are dropped and counted as [`overruns`](Self::overruns).
+    /// Returns the number of bytes stored.
+    pub fn on_rx_irq<E>(&mut self, mut read: impl FnMut() -> Result<u8, E>) -> usize {
+        let mut stored = 0;
+        while let Ok(byte) = read() {
+            match self.rx.grant_exact(1) {
+                Ok(mut grant) => {
+                    grant[0] = byte;
+                    grant.commit(1);
+                    stored += 1;
+                }
+                Err(_) => self.overruns += 1,
+            }
+        }
+        stored
+    }
+
+    /// Passes pending bytes to `write` until it fails, waking a waiting writer.
+    ///
+    /// Returns whether bytes are still pending, so the transmit interrupt can be disabled
+    /// once everything has been sent.
+    pub fn on_tx_irq<E>(&mut self, mut write: impl FnMut(u8) -> Result<(), E>) -> bool {
+        while let Ok(grant) = self.tx.read() {
+            let sent = grant
+                .iter()
+                .take_while(|&&byte| write(byte).is_ok())
+                .count();
+            let blocked = sent < grant.len();
+            grant.release(sent);
+            if blocked {
+                break;
+            }
+        }
+        self.tx.pending() != 0
+    }
+
+    /// Returns the receive and transmit halves.
+    #[inline]
+    pub fn into_inner(self) -> (Producer<'a, RX>, Consumer<'a, TX>) {
+        (self.rx, self.tx)
+    }
+}
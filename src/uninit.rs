@@ -0,0 +1,92 @@
+//! Filling a buffer from readers that write into uninitialized memory.
+//!
+//! [`ReadUninit`] has the shape of the unstable `Read::read_buf`, reduced to a slice of
+//! [`MaybeUninit`] bytes, so sources such as DMA transfers, `recv` through FFI or
+//! decompressors write straight into the free space of a buffer without the reservation
+//! being handed out as initialized `&mut [u8]` first.
+//!
+//! ```rust
+//! use staticbip::StaticBip;
+//!
+//! let mut buffer = StaticBip::<u8, 8>::default();
+//! let mut source = &b"0123456789"[..];
+//!
+//! assert_eq!(buffer.read_uninit_from(&mut source), Ok(8));
+//! buffer.decommit(5);
+//! // Continues into the free space at the start
+//! assert_eq!(buffer.read_uninit_from(&mut source), Ok(2));
+//! assert_eq!(buffer.as_slices(), (&b"567"[..], &b"89"[..]));
+//! ```
+
+use crate::StaticBip;
+use core::{convert::Infallible, mem::MaybeUninit};
+
+/// A source of bytes written into uninitialized memory.
+///
+/// # Safety
+///
+/// [`read_uninit`](Self::read_uninit) must initialize at least as many bytes at the start of
+/// `buf` as it reports, and must not write uninitialized values into any part of `buf`.
+pub unsafe trait ReadUninit {
+    /// Error returned by the source.
+    type Error;
+
+    /// Writes up to `buf.len()` bytes to the start of `buf`.
+    ///
+    /// Returns the number of bytes written, `0` once the source is exhausted.
+    fn read_uninit(&mut self, buf: &mut [MaybeUninit<u8>]) -> Result<usize, Self::Error>;
+}
+
+unsafe impl<R: ReadUninit + ?Sized> ReadUninit for &mut R {
+    type Error = R::Error;
+
+    #[inline]
+    fn read_uninit(&mut self, buf: &mut [MaybeUninit<u8>]) -> Result<usize, R::Error> {
+        (**self).read_uninit(buf)
+    }
+}
+
+unsafe impl ReadUninit for &[u8] {
+    type Error = Infallible;
+
+    fn read_uninit(&mut self, buf: &mut [MaybeUninit<u8>]) -> Result<usize, Infallible> {
+        let len = buf.len().min(self.len());
+        let (data, rest) = self.split_at(len);
+        for (slot, &byte) in buf.iter_mut().zip(data) {
+            slot.write(byte);
+        }
+        *self = rest;
+        Ok(len)
+    }
+}
+
+impl<const CAP: usize> StaticBip<u8, CAP> {
+    /// Commits bytes from `reader` into the free space until a read comes up short or the
+    /// buffer is full.
+    ///
+    /// Returns the number of bytes committed. An error is only returned if nothing was
+    /// committed; otherwise it ends the read. Any reservation is replaced.
+    pub fn read_uninit_from<R: ReadUninit>(&mut self, mut reader: R) -> Result<usize, R::Error> {
+        let mut total = 0;
+        loop {
+            // SAFETY: the reader only writes initialized bytes, as its trait requires.
+            let spare = unsafe { self.spare_capacity_mut() };
+            let capacity = spare.len();
+            if capacity == 0 {
+                break;
+            }
+            let len = match reader.read_uninit(spare) {
+                Ok(len) => len.min(capacity),
+                Err(err) if total == 0 => return Err(err),
+                Err(_) => 0,
+            };
+            // SAFETY: the reader initialized the first `len` spare bytes.
+            unsafe { self.set_committed(len) };
+            total += len;
+            if len < capacity {
+                break;
+            }
+        }
+        Ok(total)
+    }
+}
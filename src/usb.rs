@@ -0,0 +1,119 @@
+//! Packet buffering between a USB endpoint and application code.
+//!
+//! [`UsbBuffer`] moves data between a pair of buffers and the bulk endpoints of a serial
+//! class such as CDC-ACM. The interrupt handler calls [`read_packet`](UsbBuffer::read_packet)
+//! and [`write_packet`](UsbBuffer::write_packet) with closures that access the endpoints,
+//! and the application calls [`read`](UsbBuffer::read) and [`write`](UsbBuffer::write).
+//!
+//! A packet is only read from the endpoint once a whole packet fits, so the host is held
+//! off rather than losing data, and a transfer that ends on a packet boundary is terminated
+//! with a zero-length packet.
+//!
+//! ```rust
+//! use staticbip::{usb::UsbBuffer, StaticBip};
+//!
+//! let mut buffer = UsbBuffer::new(StaticBip::<u8, 128>::default(), StaticBip::<u8, 128>::default(), 64);
+//!
+//! // Interrupt: OUT packet from the host
+//! buffer.read_packet(|packet| {
+//!     packet[..5].copy_from_slice(b"hello");
+//!     Ok::<_, ()>(5)
+//! }).unwrap();
+//!
+//! // Application: echo it back
+//! let mut data = [0; 8];
+//! let len = buffer.read(&mut data).unwrap();
+//! buffer.write(&data[..len]).unwrap();
+//!
+//! // Interrupt: IN endpoint ready
+//! let sent = buffer.write_packet(|packet| Ok::<_, ()>(packet.len())).unwrap();
+//! assert_eq!(sent, 5);
+//! ```
+
+use crate::{nb::WouldBlock, StaticBip};
+
+/// Receive and transmit buffers of a USB serial function.
+#[derive(Debug)]
+pub struct UsbBuffer<const RX: usize, const TX: usize> {
+    /// Data received from the host
+    rx: StaticBip<u8, RX>,
+    /// Data to send to the host
+    tx: StaticBip<u8, TX>,
+    /// Maximum packet size of the endpoints
+    max_packet: usize,
+    /// Whether the last packet sent was full
+    full_packet: bool,
+}
+
+impl<const RX: usize, const TX: usize> UsbBuffer<RX, TX> {
+    /// Creates buffering for endpoints with a `max_packet` byte maximum packet size.
+    #[inline]
+    pub fn new(rx: StaticBip<u8, RX>, tx: StaticBip<u8, TX>, max_packet: usize) -> Self {
+        Self {
+            rx,
+            tx,
+            max_packet,
+            full_packet: false,
+        }
+    }
+
+    /// Maximum packet size of the endpoints.
+    #[inline]
+    pub fn max_packet(&self) -> usize {
+        self.max_packet
+    }
+
+    /// Reads a packet from the host with `f`, which returns the packet length.
+    ///
+    /// `f` is only called when a whole packet fits, otherwise `Ok(0)` is returned and the
+    /// packet stays in the endpoint.
+    pub fn read_packet<E>(
+        &mut self,
+        f: impl FnOnce(&mut [u8]) -> Result<usize, E>,
+    ) -> Result<usize, E> {
+        let packet = self.rx.reserve(self.max_packet);
+        if packet.len() < self.max_packet {
+            self.rx.commit(0);
+            return Ok(0);
+        }
+        let result = f(packet);
+        self.rx.commit(*result.as_ref().unwrap_or(&0));
+        result
+    }
+
+    /// Sends the next packet to the host with `f`, which returns the number of bytes sent.
+    ///
+    /// Returns `Ok(0)` without calling `f` if there is nothing to send. After a full packet,
+    /// `f` is called with an empty packet to end the transfer.
+    pub fn write_packet<E>(
+        &mut self,
+        f: impl FnOnce(&[u8]) -> Result<usize, E>,
+    ) -> Result<usize, E> {
+        let block = self.tx.read();
+        let packet = &block[..block.len().min(self.max_packet)];
+        if packet.is_empty() && !self.full_packet {
+            return Ok(0);
+        }
+
+        let len = f(packet)?;
+        self.full_packet = len == self.max_packet;
+        self.tx.decommit(len);
+        Ok(len)
+    }
+
+    /// Moves received data into `out`.
+    ///
+    /// Returns the number of bytes read, or [`WouldBlock`] if nothing has been received.
+    #[inline]
+    pub fn read(&mut self, out: &mut [u8]) -> Result<usize, WouldBlock> {
+        self.rx.try_read(out)
+    }
+
+    /// Queues `data` for sending.
+    ///
+    /// Returns the number of bytes queued, or [`WouldBlock`] if the buffer is full.
+    #[inline]
+    pub fn write(&mut self, data: &[u8]) -> Result<usize, WouldBlock> {
+        self.tx.try_write(data)
+    }
+}
@@ -0,0 +1,96 @@
+//! Volatile element access for storage shared with hardware.
+//!
+//! A [`Volatile`] view reads and writes elements with [`read_volatile`](core::ptr::read_volatile)
+//! and [`write_volatile`](core::ptr::write_volatile) through raw pointers, and never hands out
+//! slices of the storage. Use it for buffers placed in memory that a peripheral or another
+//! core modifies behind the compiler's back. The region bookkeeping stays in ordinary memory.
+//!
+//! ```rust
+//! use staticbip::StaticBip;
+//!
+//! let mut buffer = StaticBip::<u8, 4>::default();
+//! let mut volatile = buffer.volatile();
+//!
+//! assert_eq!(volatile.write(&[1, 2, 3]), 3);
+//!
+//! let mut out = [0; 4];
+//! assert_eq!(volatile.read(&mut out), 3);
+//! assert_eq!(out, [1, 2, 3, 0]);
+//! ```
+
+use crate::StaticBip;
+use core::ptr;
+
+/// A view of a buffer that accesses elements through volatile operations.
+///
+/// Created by [`StaticBip::volatile`].
+#[derive(Debug)]
+pub struct Volatile<'a, T, const CAP: usize> {
+    bip: &'a mut StaticBip<T, CAP>,
+}
+
+impl<T, const CAP: usize> StaticBip<T, CAP> {
+    /// Returns a view that reads and writes elements through volatile operations.
+    #[inline]
+    pub fn volatile(&mut self) -> Volatile<'_, T, CAP> {
+        Volatile { bip: self }
+    }
+}
+
+impl<T: Copy, const CAP: usize> Volatile<'_, T, CAP> {
+    /// Number of committed elements.
+    #[inline]
+    pub fn committed(&self) -> usize {
+        self.bip.committed()
+    }
+
+    /// Appends as many elements of `data` as fit in one reservation.
+    ///
+    /// Returns the number of elements written.
+    pub fn write(&mut self, data: &[T]) -> usize {
+        let reserve = self.bip.regions.reserve(CAP, data.len());
+        let base = self.base();
+        for (i, &value) in reserve.clone().zip(data) {
+            // SAFETY: the index lies in the reservation, which is within the storage.
+            unsafe { base.add(i).write_volatile(value) };
+        }
+        self.bip.regions.commit(reserve.len());
+        reserve.len()
+    }
+
+    /// Moves the oldest committed elements into `out`, continuing across regions.
+    ///
+    /// Returns the number of elements read.
+    pub fn read(&mut self, out: &mut [T]) -> usize {
+        let regions = &self.bip.regions;
+        let committed = regions.a().chain(regions.b());
+        let base = self.base();
+        let mut len = 0;
+        for (slot, i) in out.iter_mut().zip(committed) {
+            // SAFETY: the index lies in a committed region, which is within the storage.
+            *slot = unsafe { base.add(i).read_volatile() };
+            len += 1;
+        }
+        self.bip.regions.consume(len);
+        len
+    }
+
+    /// Returns the oldest committed element without removing it.
+    #[inline]
+    pub fn front(&self) -> Option<T> {
+        let i = self.bip.regions.front()?;
+        // SAFETY: the index lies in a committed region, which is within the storage.
+        Some(unsafe {
+            ptr::addr_of!(self.bip.buffer)
+                .cast::<T>()
+                .add(i)
+                .read_volatile()
+        })
+    }
+
+    /// Pointer to the first element, taken without creating a reference to the storage.
+    #[inline]
+    fn base(&mut self) -> *mut T {
+        ptr::addr_of_mut!(self.bip.buffer).cast()
+    }
+}
@@ -0,0 +1,135 @@
+//! Lock-free waker slot for notifying tasks from another context.
+//!
+//! An [`AtomicWaker`] holds the waker of the one task waiting on a condition. The waiting
+//! side [`register`](AtomicWaker::register)s before checking the condition and the other side
+//! [`wake`](AtomicWaker::wake)s after changing it, so a wake-up is never lost between the two.
+//! It has the API of `AtomicWaker` in the `futures` crate, without depending on an executor.
+//!
+//! The halves of a [`BufStorage`](crate::bbqueue::BufStorage) use it to wake each other on
+//! commit and release.
+//!
+//! ```rust
+//! use core::task::{Poll, Waker};
+//! use staticbip::waker::AtomicWaker;
+//!
+//! static READY: AtomicWaker = AtomicWaker::new();
+//!
+//! let poll = |waker: &Waker, done: bool| {
+//!     READY.register(waker);
+//!     if done { Poll::Ready(()) } else { Poll::Pending }
+//! };
+//! assert_eq!(poll(Waker::noop(), false), Poll::Pending);
+//!
+//! // From an interrupt, once the condition holds
+//! READY.wake();
+//! assert!(READY.take().is_none());
+//! ```
+
+use core::{
+    cell::UnsafeCell,
+    fmt, hint,
+    sync::atomic::{AtomicUsize, Ordering},
+    task::Waker,
+};
+
+/// No registration or wake-up is in progress.
+const WAITING: usize = 0;
+/// A waker is being stored.
+const REGISTERING: usize = 0b01;
+/// The stored waker is being taken.
+const WAKING: usize = 0b10;
+
+/// A slot for one [`Waker`], registered and woken from different contexts.
+pub struct AtomicWaker {
+    state: AtomicUsize,
+    waker: UnsafeCell<Option<Waker>>,
+}
+
+// SAFETY: the waker is only accessed by the side that moved `state` out of `WAITING`.
+unsafe impl Send for AtomicWaker {}
+unsafe impl Sync for AtomicWaker {}
+
+impl Default for AtomicWaker {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for AtomicWaker {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("AtomicWaker")
+    }
+}
+
+impl AtomicWaker {
+    /// Creates an empty slot, usable in a `static`.
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            state: AtomicUsize::new(WAITING),
+            waker: UnsafeCell::new(None),
+        }
+    }
+
+    /// Stores `waker` to be woken by the next [`wake`](Self::wake), replacing an earlier one.
+    ///
+    /// If a wake-up runs concurrently, `waker` is woken right away instead. Registering from
+    /// two contexts at once is not supported; one of the wakers is dropped.
+    pub fn register(&self, waker: &Waker) {
+        match self
+            .state
+            .compare_exchange(WAITING, REGISTERING, Ordering::Acquire, Ordering::Acquire)
+            .unwrap_or_else(|state| state)
+        {
+            WAITING => {
+                // SAFETY: `REGISTERING` is set, so `take` leaves the slot alone.
+                let slot = unsafe { &mut *self.waker.get() };
+                match slot {
+                    Some(old) if old.will_wake(waker) => {}
+                    _ => *slot = Some(waker.clone()),
+                }
+                if self
+                    .state
+                    .compare_exchange(REGISTERING, WAITING, Ordering::AcqRel, Ordering::Acquire)
+                    .is_err()
+                {
+                    // A wake-up arrived while storing; it found nothing to take, so wake here
+                    let waker = slot.take();
+                    self.state.swap(WAITING, Ordering::AcqRel);
+                    if let Some(waker) = waker {
+                        waker.wake();
+                    }
+                }
+            }
+            WAKING => {
+                // The waker being taken may be an older one
+                waker.wake_by_ref();
+                hint::spin_loop();
+            }
+            _ => {}
+        }
+    }
+
+    /// Wakes the registered waker, if any, and clears the slot.
+    #[inline]
+    pub fn wake(&self) {
+        if let Some(waker) = self.take() {
+            waker.wake();
+        }
+    }
+
+    /// Takes the registered waker out of the slot, if any.
+    pub fn take(&self) -> Option<Waker> {
+        match self.state.fetch_or(WAKING, Ordering::AcqRel) {
+            WAITING => {
+                // SAFETY: `WAKING` is set, so `register` leaves the slot alone.
+                let waker = unsafe { (*self.waker.get()).take() };
+                self.state.fetch_and(!WAKING, Ordering::Release);
+                waker
+            }
+            // A registration in progress sees `WAKING` and wakes its waker itself
+            _ => None,
+        }
+    }
+}
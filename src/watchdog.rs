@@ -0,0 +1,113 @@
+//! Detection of a consumer that has stopped reading.
+//!
+//! Occupancy alone does not tell a busy consumer from a stuck one. A [`Watchdog`] is fed the
+//! consumer's position from supervisory code, such as a periodic timer task, and measures how
+//! long data has been waiting without the consumer moving. Once that exceeds a threshold the
+//! pipeline can be reset.
+//!
+//! Time is any [`Instant`]: a tick counter, `std::time::Instant`, or
+//! [`total_written`](StaticBip::total_written) to measure in elements committed meanwhile.
+//! Observing a [`StaticBip`] directly takes its stream positions, which are only kept with the
+//! `stats` feature; [`observe_position`](Watchdog::observe_position) works without them.
+//!
+//! ```rust
+//! # #[cfg(feature = "stats")]
+//! # {
+//! use staticbip::{watchdog::Watchdog, StaticBip};
+//!
+//! let mut buffer = StaticBip::<u8, 16>::default();
+//! let mut watchdog = Watchdog::<u32>::new();
+//!
+//! buffer.extend(&[1, 2, 3]);
+//! watchdog.observe(&buffer, 100);
+//! buffer.decommit(1);
+//! watchdog.observe(&buffer, 200);
+//! assert_eq!(watchdog.stalled_for(250), Some(50));
+//!
+//! // No progress since 200
+//! watchdog.observe(&buffer, 900);
+//! assert!(watchdog.is_stalled(900, 500));
+//!
+//! buffer.clear();
+//! watchdog.observe(&buffer, 1000);
+//! assert_eq!(watchdog.stalled_for(1000), None);
+//! # }
+//! ```
+//!
+//! When records carry their commit time, [`TimedBip::age`](crate::timed::TimedBip::age) gives
+//! the exact age of the oldest record instead.
+
+use crate::timed::Instant;
+#[cfg(feature = "stats")]
+use crate::StaticBip;
+
+/// Tracks how long committed data has waited without the consumer making progress.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Watchdog<I> {
+    /// Consumer position at the last observed progress
+    read: u64,
+    /// When data was first seen waiting at that position
+    since: Option<I>,
+}
+
+impl<I: Instant> Default for Watchdog<I> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<I: Instant> Watchdog<I> {
+    /// Creates a watchdog that has seen no data yet.
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            read: 0,
+            since: None,
+        }
+    }
+
+    /// Observes the consumer of `bip` at `now`.
+    #[cfg(feature = "stats")]
+    #[inline]
+    pub fn observe<T, const CAP: usize>(&mut self, bip: &StaticBip<T, CAP>, now: I) {
+        self.observe_position(bip.total_read(), bip.committed(), now);
+    }
+
+    /// Observes a consumer that has read `read` elements in total and has `pending` more
+    /// waiting, at `now`.
+    ///
+    /// This fits buffers other than [`StaticBip`], such as a
+    /// [`bbqueue`](crate::bbqueue) consumer with its sequence and pending counts.
+    pub fn observe_position(&mut self, read: u64, pending: usize, now: I) {
+        if pending == 0 {
+            self.since = None;
+        } else if self.since.is_none() || read != self.read {
+            self.since = Some(now);
+        }
+        self.read = read;
+    }
+
+    /// Time elapsed up to `now` since the consumer last made progress while data was
+    /// waiting, or `None` if no data was waiting at the last observation.
+    #[inline]
+    pub fn stalled_for(&self, now: I) -> Option<I::Duration> {
+        self.since.map(|since| now.duration_since(since))
+    }
+
+    /// Whether data has waited without progress for longer than `threshold` at `now`.
+    #[inline]
+    pub fn is_stalled(&self, now: I, threshold: I::Duration) -> bool
+    where
+        I::Duration: PartialOrd,
+    {
+        self.stalled_for(now)
+            .is_some_and(|stalled| stalled > threshold)
+    }
+
+    /// Forgets the observed progress, as after the pipeline has been reset.
+    #[inline]
+    pub fn reset(&mut self) {
+        self.since = None;
+    }
+}
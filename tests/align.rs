@@ -0,0 +1,31 @@
+use staticbip::{
+    align::{Aligned, A16, A4, A64},
+    SliceBip, StaticBip,
+};
+
+#[test]
+fn static_storage_is_aligned() {
+    struct Driver {
+        _state: u8,
+        buffer: Aligned<A64, StaticBip<u8, 128>>,
+    }
+
+    let mut driver = Driver {
+        _state: 0,
+        buffer: Aligned::default(),
+    };
+    assert_eq!(driver.buffer.reserve_max().as_ptr() as usize % 64, 0);
+}
+
+#[test]
+fn slice_storage_is_aligned() {
+    let mut storage = Aligned::<A16, [u8; 32]>::new([0; 32]);
+    let mut buffer = SliceBip::new(&mut *storage);
+    assert_eq!(buffer.reserve(1).as_ptr() as usize % 16, 0);
+}
+
+#[test]
+fn wrapper_size() {
+    assert_eq!(core::mem::align_of::<Aligned<A4, u8>>(), 4);
+    assert_eq!(core::mem::size_of::<Aligned<A4, [u8; 5]>>(), 8);
+}
@@ -0,0 +1,472 @@
+use staticbip::bbqueue::{BufStorage, Consumer, Error, GrantR, GrantW, Producer};
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    task::{Wake, Waker},
+    thread,
+};
+
+#[test]
+fn split_once() {
+    let storage = BufStorage::<4>::new();
+    assert!(storage.try_split().is_ok());
+    assert_eq!(storage.try_split().err(), Some(Error::AlreadySplit));
+}
+
+#[test]
+fn grants_wrap() {
+    let storage = BufStorage::<8>::new();
+    let (mut producer, mut consumer) = storage.try_split().unwrap();
+    assert_eq!(consumer.read().err(), Some(Error::InsufficientSize));
+
+    producer.grant_exact(6).unwrap().commit(6);
+    consumer.read().unwrap().release(4);
+
+    // Does not fit after the data, so wraps to the start
+    let mut grant = producer.grant_exact(3).unwrap();
+    grant.copy_from_slice(&[1, 2, 3]);
+    grant.commit(3);
+    assert_eq!(producer.grant_exact(1).err(), Some(Error::InsufficientSize));
+    assert_eq!(
+        producer.grant_max_remaining(8).err(),
+        Some(Error::InsufficientSize)
+    );
+
+    assert_eq!(consumer.read().unwrap().len(), 2);
+    consumer.read().unwrap().release(2);
+    assert_eq!(&*consumer.read().unwrap(), &[1, 2, 3]);
+
+    // Dropped without committing
+    drop(producer.grant_max_remaining(8).unwrap());
+    consumer.read().unwrap().release(3);
+    assert_eq!(consumer.read().err(), Some(Error::InsufficientSize));
+}
+
+#[test]
+fn oversized_and_empty_grants_leave_data_alone() {
+    let storage = BufStorage::<8>::new();
+    let (mut producer, mut consumer) = storage.try_split().unwrap();
+
+    producer.grant_exact(2).unwrap().commit(2);
+    assert_eq!(
+        producer.grant_exact(usize::MAX).err(),
+        Some(Error::InsufficientSize)
+    );
+    consumer.read().unwrap().release(1);
+    producer.grant_exact(6).unwrap().commit(6);
+    assert_eq!(
+        producer.grant_exact(usize::MAX).err(),
+        Some(Error::InsufficientSize)
+    );
+
+    // Full, with the reader at the start
+    let storage = BufStorage::<8>::new();
+    let (mut producer, mut consumer) = storage.try_split().unwrap();
+    producer.grant_exact(8).unwrap().commit(8);
+    assert_eq!(
+        producer.grant_max_remaining(0).err(),
+        Some(Error::InsufficientSize)
+    );
+    assert_eq!(producer.pending(), 8);
+    assert_eq!(consumer.read().unwrap().len(), 8);
+}
+
+#[test]
+fn threads_see_ordered_stream() {
+    static STORAGE: BufStorage<64> = BufStorage::new();
+    let (mut producer, mut consumer) = STORAGE.try_split().unwrap();
+    const TOTAL: usize = 20_000;
+
+    let writer = thread::spawn(move || {
+        let mut next = 0usize;
+        while next < TOTAL {
+            if let Ok(mut grant) = producer.grant_max_remaining(7) {
+                let len = grant.len().min(TOTAL - next);
+                for byte in &mut grant[..len] {
+                    *byte = next as u8;
+                    next += 1;
+                }
+                grant.commit(len);
+            }
+        }
+    });
+
+    let mut expected = 0usize;
+    while expected < TOTAL {
+        if let Ok(grant) = consumer.read() {
+            for &byte in grant.iter() {
+                assert_eq!(byte, expected as u8);
+                expected += 1;
+            }
+            let len = grant.len();
+            grant.release(len);
+        }
+    }
+    writer.join().unwrap();
+}
+
+/// Xorshift step, for test threads that need varied but reproducible choices.
+fn next_random(state: &mut u32) -> usize {
+    *state ^= *state << 13;
+    *state ^= *state >> 17;
+    *state ^= *state << 5;
+    *state as usize
+}
+
+/// Writes the next bytes of the stream into up to `want` bytes of `grant` and commits them.
+fn fill(mut grant: GrantW<'_, 7>, want: usize, next: &mut usize) {
+    let len = grant.len().min(want);
+    for byte in &mut grant[..len] {
+        *byte = *next as u8;
+        *next += 1;
+    }
+    grant.commit(len);
+}
+
+/// Checks up to `want` bytes of `grant` against the stream and releases them.
+fn drain(grant: GrantR<'_, 7>, want: usize, expected: &mut usize) {
+    let len = grant.len().min(want);
+    for &byte in &grant[..len] {
+        assert_eq!(byte, *expected as u8);
+        *expected += 1;
+    }
+    grant.release(len);
+}
+
+// Stands in for a loom model of the handoff, which this crate cannot depend on: a queue of
+// odd size with partial commits, partial releases and batches on both sides, so that wraps
+// and the watermark are crossed from either thread in every interleaving the scheduler finds
+#[test]
+fn handoff_survives_wraps_and_batches() {
+    static STORAGE: BufStorage<7> = BufStorage::new();
+    let (mut producer, mut consumer) = STORAGE.try_split().unwrap();
+    const TOTAL: usize = 100_000;
+
+    let writer = thread::spawn(move || {
+        let (mut next, mut random) = (0, 0x2545_f491);
+        while next < TOTAL {
+            let choice = next_random(&mut random);
+            let want = (1 + choice % 7).min(TOTAL - next);
+            match choice >> 3 & 3 {
+                0 => match producer.grant_exact(want) {
+                    Ok(grant) => fill(grant, want, &mut next),
+                    Err(_) => thread::yield_now(),
+                },
+                1 => {
+                    let mut batch = producer.batch();
+                    for _ in 0..2 {
+                        let want = want.min(TOTAL - next);
+                        if let Ok(grant) = batch.grant_max_remaining(want) {
+                            fill(grant, want, &mut next);
+                        }
+                    }
+                    batch.commit_batch();
+                }
+                _ => match producer.grant_max_remaining(7) {
+                    Ok(grant) => fill(grant, want, &mut next),
+                    Err(_) => thread::yield_now(),
+                },
+            }
+        }
+        producer.sequence()
+    });
+
+    let (mut expected, mut random) = (0, 0x9e37_79b9);
+    while expected < TOTAL {
+        let choice = next_random(&mut random);
+        let want = 1 + choice % 7;
+        if choice >> 3 & 1 == 0 {
+            match consumer.read() {
+                Ok(grant) => drain(grant, want, &mut expected),
+                Err(_) => thread::yield_now(),
+            }
+        } else {
+            let mut batch = consumer.batch();
+            for _ in 0..2 {
+                if let Ok(grant) = batch.read() {
+                    drain(grant, want, &mut expected);
+                }
+            }
+            batch.release_batch();
+        }
+    }
+    assert_eq!(writer.join().unwrap(), TOTAL);
+    assert_eq!(consumer.sequence(), TOTAL);
+    assert_eq!(consumer.read().err(), Some(Error::InsufficientSize));
+}
+
+#[test]
+fn static_halves_are_send() {
+    fn assert_send<T: Send>(_: &T) {}
+
+    static STORAGE: BufStorage<4> = BufStorage::new();
+    let (producer, consumer) = STORAGE.try_split().unwrap();
+    assert_send(&producer);
+    assert_send(&consumer);
+
+    let mut storage = BufStorage::<4>::new();
+    assert!(storage.try_split().is_ok());
+    let (mut producer, mut consumer) = storage.split_mut();
+    producer.grant_exact(2).unwrap().commit(2);
+    assert_eq!(consumer.read().unwrap().len(), 2);
+}
+
+#[test]
+fn sequence_counters_span_wrap_arounds() {
+    let storage = BufStorage::<4>::new();
+    let (mut producer, mut consumer) = storage.try_split().unwrap();
+
+    for round in 1..=5 {
+        producer.grant_exact(2).unwrap().commit(2);
+        assert_eq!(producer.pending(), 2);
+        consumer.read().unwrap().release(2);
+        assert_eq!(consumer.pending(), 0);
+        assert_eq!(producer.sequence(), round * 2);
+        assert_eq!(consumer.sequence(), round * 2);
+    }
+}
+
+#[cfg(feature = "cache-padding")]
+#[test]
+fn positions_on_separate_cache_lines() {
+    assert_eq!(std::mem::align_of::<BufStorage<4>>(), 64);
+    assert!(std::mem::size_of::<BufStorage<4>>() >= 3 * 64);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn owned_halves_outlive_the_scope() {
+    use std::sync::Arc;
+
+    let storage = Arc::new(BufStorage::<8>::new());
+    let (mut producer, mut consumer) = storage.clone().split_owned().unwrap();
+    assert_eq!(
+        storage.clone().split_owned().err(),
+        Some(Error::AlreadySplit)
+    );
+    drop(storage);
+
+    let writer = std::thread::spawn(move || {
+        for byte in 0..32 {
+            loop {
+                if let Ok(mut grant) = producer.grant_exact(1) {
+                    grant[0] = byte;
+                    grant.commit(1);
+                    break;
+                }
+            }
+        }
+    });
+    let mut expected = 0;
+    while expected < 32 {
+        if let Ok(grant) = consumer.read() {
+            for &byte in grant.iter() {
+                assert_eq!(byte, expected);
+                expected += 1;
+            }
+            let len = grant.len();
+            grant.release(len);
+        }
+    }
+    writer.join().unwrap();
+    assert_eq!(consumer.pending(), 0);
+}
+
+#[derive(Default)]
+struct Counter(AtomicUsize);
+
+impl Wake for Counter {
+    fn wake(self: Arc<Self>) {
+        self.0.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+#[test]
+fn halves_wake_each_other() {
+    let counter = Arc::new(Counter::default());
+    let waker = Waker::from(counter.clone());
+    let storage = BufStorage::<8>::new();
+    let (mut producer, mut consumer) = storage.try_split().unwrap();
+
+    consumer.register_read_waker(&waker);
+    producer.grant_exact(2).unwrap().commit(0);
+    assert_eq!(counter.0.load(Ordering::Relaxed), 0);
+    producer.grant_exact(2).unwrap().commit(2);
+    assert_eq!(counter.0.load(Ordering::Relaxed), 1);
+
+    // Woken once per registration
+    producer.grant_exact(2).unwrap().commit(2);
+    assert_eq!(counter.0.load(Ordering::Relaxed), 1);
+
+    producer.register_write_waker(&waker);
+    consumer.read().unwrap().release(3);
+    assert_eq!(counter.0.load(Ordering::Relaxed), 2);
+}
+
+#[test]
+fn halves_cross_threads_as_raw_pointers() {
+    static STORAGE: BufStorage<8> = BufStorage::new();
+    let (producer, consumer) = STORAGE.try_split().unwrap();
+    let (producer, consumer) = (producer.into_raw() as usize, consumer.into_raw() as usize);
+
+    thread::spawn(move || {
+        let mut producer = unsafe { Producer::<8>::from_raw(producer as *const BufStorage<8>) };
+        let mut grant = producer.grant_exact(3).unwrap();
+        grant.copy_from_slice(&[1, 2, 3]);
+        grant.commit(3);
+    })
+    .join()
+    .unwrap();
+
+    let mut consumer = unsafe { Consumer::<8>::from_raw(consumer as *const BufStorage<8>) };
+    assert_eq!(&*consumer.read().unwrap(), &[1, 2, 3]);
+}
+
+#[test]
+fn write_all_idles_until_the_consumer_makes_room() {
+    let mut storage = BufStorage::<8>::new();
+    let (mut producer, mut consumer) = storage.split_mut();
+    let data: Vec<u8> = (0..30).collect();
+
+    let mut received = Vec::new();
+    let mut idles = 0;
+    producer.write_all(&data, || {
+        idles += 1;
+        let grant = consumer.read().unwrap();
+        let len = grant.len().min(3);
+        received.extend_from_slice(&grant[..len]);
+        grant.release(len);
+    });
+    while let Ok(grant) = consumer.read() {
+        let len = grant.len();
+        received.extend_from_slice(&grant);
+        grant.release(len);
+    }
+
+    assert_eq!(received, data);
+    assert!(idles >= 8);
+}
+
+#[test]
+#[cfg(not(feature = "cache-padding"))]
+fn layout_is_readable_through_offsets() {
+    type Storage = BufStorage<8>;
+    let word = std::mem::size_of::<usize>();
+    assert_eq!(
+        [
+            Storage::WRITE_OFFSET,
+            Storage::LAST_OFFSET,
+            Storage::COMMITTED_OFFSET,
+            Storage::READ_OFFSET,
+            Storage::RELEASED_OFFSET,
+            Storage::SPLIT_OFFSET,
+            Storage::BUFFER_OFFSET,
+        ],
+        [
+            0,
+            word,
+            2 * word,
+            3 * word,
+            4 * word,
+            5 * word,
+            5 * word + 1
+        ]
+    );
+
+    // Another core reads the storage as raw memory
+    let mut storage = Storage::new();
+    let base = &storage as *const Storage as *const u8;
+    let (mut producer, _consumer) = storage.split_mut();
+    producer.grant_exact(3).unwrap().commit(2);
+    unsafe {
+        let word_at = |offset| std::ptr::read_volatile(base.add(offset).cast::<usize>());
+        assert_eq!(word_at(Storage::WRITE_OFFSET), 2);
+        assert_eq!(word_at(Storage::COMMITTED_OFFSET), 2);
+        assert_eq!(word_at(Storage::READ_OFFSET), 0);
+        assert_eq!(*base.add(Storage::SPLIT_OFFSET), 1);
+    }
+}
+
+#[test]
+fn positions_outside_the_storage_are_refused() {
+    type Storage = BufStorage<8>;
+    let storage = Storage::new();
+    let base = &storage as *const Storage as *const u8;
+    // A faulty peer stores positions directly, as through the offsets
+    let position = |offset| unsafe { &*base.add(offset).cast::<AtomicUsize>() };
+    let (mut producer, mut consumer) = storage.try_split().unwrap();
+    producer.grant_exact(2).unwrap().commit(2);
+
+    position(Storage::WRITE_OFFSET).store(100, Ordering::Release);
+    assert_eq!(consumer.read().err(), Some(Error::Corrupted));
+    position(Storage::WRITE_OFFSET).store(2, Ordering::Release);
+    position(Storage::LAST_OFFSET).store(usize::MAX, Ordering::Release);
+    assert_eq!(consumer.read().err(), Some(Error::Corrupted));
+    position(Storage::LAST_OFFSET).store(8, Ordering::Release);
+
+    position(Storage::READ_OFFSET).store(9, Ordering::Release);
+    assert_eq!(producer.grant_exact(1).err(), Some(Error::Corrupted));
+    assert_eq!(
+        producer.grant_max_remaining(1).err(),
+        Some(Error::Corrupted)
+    );
+    position(Storage::READ_OFFSET).store(0, Ordering::Release);
+    assert_eq!(consumer.read().unwrap().len(), 2);
+}
+
+#[test]
+fn batch_publishes_once() {
+    let mut storage = BufStorage::<8>::new();
+    let (mut producer, mut consumer) = storage.split_mut();
+    producer.grant_exact(6).unwrap().commit(6);
+    consumer.read().unwrap().release(5);
+
+    let mut batch = producer.batch();
+    batch.grant_exact(2).unwrap().commit(2);
+    // Wraps to the start, behind the unpublished commit
+    let mut grant = batch.grant_max_remaining(8).unwrap();
+    assert_eq!(grant.len(), 4);
+    grant.copy_from_slice(&[1, 2, 3, 4]);
+    grant.commit(3);
+    assert_eq!(batch.staged(), 5);
+    assert_eq!(consumer.pending(), 1);
+    assert_eq!(consumer.read().unwrap().len(), 1);
+
+    batch.commit_batch();
+    assert_eq!(producer.sequence(), 11);
+    let grant = consumer.read().unwrap();
+    assert_eq!(grant.len(), 3);
+    grant.release(3);
+    assert_eq!(&*consumer.read().unwrap(), &[1, 2, 3]);
+}
+
+#[test]
+fn release_batch_frees_space_once() {
+    let mut storage = BufStorage::<8>::new();
+    let (mut producer, mut consumer) = storage.split_mut();
+    producer.grant_exact(6).unwrap().commit(6);
+
+    let mut batch = consumer.batch();
+    for _ in 0..6 {
+        let grant = batch.read().unwrap();
+        grant.release(1);
+    }
+    assert!(batch.read().is_err());
+    assert_eq!(batch.staged(), 6);
+    // The bytes at the start are not free until the batch is published
+    assert_eq!(producer.pending(), 6);
+    assert!(producer.grant_exact(4).is_err());
+
+    batch.release_batch();
+    assert_eq!(consumer.sequence(), 6);
+    producer.grant_exact(4).unwrap().commit(4);
+
+    // Continues after the wrap inside a batch
+    let mut batch = consumer.batch();
+    assert_eq!(batch.read().unwrap().len(), 4);
+    batch.read().unwrap().release(3);
+    drop(batch);
+    assert_eq!(consumer.read().unwrap().len(), 1);
+}
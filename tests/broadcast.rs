@@ -0,0 +1,52 @@
+use staticbip::broadcast::BroadcastBip;
+
+#[test]
+fn readers_are_independent() {
+    let mut buffer = BroadcastBip::<u8, 4, 2>::default();
+    buffer.reserve(4).copy_from_slice(&[1, 2, 3, 4]);
+    buffer.commit(4);
+
+    buffer.decommit(0, 3);
+    assert_eq!(buffer.read(0), &[4]);
+    assert_eq!(buffer.read(1), &[1, 2, 3, 4]);
+    assert_eq!(buffer.committed(0), 1);
+    assert_eq!(buffer.committed(1), 4);
+}
+
+#[test]
+fn space_is_reclaimed_by_slowest_reader() {
+    let mut buffer = BroadcastBip::<u8, 4, 2>::default();
+    buffer.reserve(4).copy_from_slice(&[1, 2, 3, 4]);
+    buffer.commit(4);
+
+    buffer.decommit(0, 4);
+    assert!(buffer.reserve(1).is_empty());
+
+    buffer.decommit(1, 2);
+    buffer.reserve(2).copy_from_slice(&[5, 6]);
+    buffer.commit(2);
+
+    assert_eq!(buffer.read(0), &[5, 6]);
+    assert_eq!(buffer.read(1), &[3, 4]);
+    buffer.decommit(1, 2);
+    assert_eq!(buffer.read(1), &[5, 6]);
+}
+
+#[test]
+fn reader_ahead_in_second_region() {
+    let mut buffer = BroadcastBip::<u8, 4, 2>::default();
+    buffer.reserve(4).copy_from_slice(&[1, 2, 3, 4]);
+    buffer.commit(4);
+    buffer.decommit(0, 4);
+    buffer.decommit(1, 2);
+
+    buffer.reserve(2).copy_from_slice(&[5, 6]);
+    buffer.commit(2);
+    buffer.decommit(0, 1);
+    assert_eq!(buffer.read(0), &[6]);
+
+    buffer.decommit(1, 2);
+    assert_eq!(buffer.read(0), &[6]);
+    assert_eq!(buffer.read(1), &[5, 6]);
+    assert_eq!(buffer.reserve(4).len(), 2);
+}
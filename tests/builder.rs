@@ -0,0 +1,49 @@
+use staticbip::{align::A64, builder::BipBuilder, hook::Hooks, BipBuf, StaticBip, WrapPolicy};
+
+#[test]
+fn defaults_match_new() {
+    let built = BipBuilder::default().build([0u8; 4]);
+    assert!(built.is_empty());
+    assert_eq!(built.wrap_policy(), StaticBip::new([0u8; 4]).wrap_policy());
+}
+
+#[test]
+fn options_apply_to_every_build() {
+    const FULL: BipBuilder = BipBuilder::new()
+        .committed()
+        .wrap_policy(WrapPolicy::NoWrap);
+
+    let mut buffer = FULL.build([1u8, 2, 3]);
+    assert_eq!(buffer.read(), &[1, 2, 3]);
+    assert_eq!(buffer.wrap_policy(), WrapPolicy::NoWrap);
+
+    let aligned = FULL.build_aligned::<A64, _, 2>([0u16; 2]);
+    assert_eq!(&*aligned as *const _ as usize % 64, 0);
+    assert_eq!(aligned.committed(), 2);
+    assert_eq!(aligned.wrap_policy(), WrapPolicy::NoWrap);
+}
+
+#[test]
+fn hooked_build() {
+    #[derive(Default)]
+    struct Count(usize);
+
+    impl Hooks for Count {
+        fn on_commit(&mut self, len: usize) {
+            self.0 += len;
+        }
+    }
+
+    let mut buffer = BipBuilder::new().build_hooked([0u8; 8], Count::default());
+    buffer.reserve(3);
+    buffer.commit(2);
+    assert_eq!(buffer.hooks().0, 2);
+}
+
+#[cfg(feature = "latency")]
+#[test]
+fn instrumented_build() {
+    let mut buffer = BipBuilder::new().build_instrumented([0u8; 8], || 0);
+    buffer.reserve(1);
+    assert_eq!(buffer.latency().reserve.count(), 1);
+}
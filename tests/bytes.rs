@@ -0,0 +1,29 @@
+use staticbip::{nb::WouldBlock, StaticBip};
+
+// Relies on the deterministic wrap choice, which the `chaos` feature randomizes
+#[cfg(not(feature = "chaos"))]
+#[test]
+fn reads_across_regions() {
+    let mut buffer = StaticBip::<u8, 8>::default();
+    buffer.write_u32_le(0xdead_beef).unwrap();
+    buffer.write_u16_be(0x1234).unwrap();
+    assert_eq!(buffer.read_u32_le(), Some(0xdead_beef));
+    buffer.write_i32_be(-2).unwrap();
+
+    assert_eq!(buffer.as_slices().0, &[0x12, 0x34]);
+    assert_eq!(buffer.read_u64_be(), None);
+    assert_eq!(buffer.committed(), 6);
+    assert_eq!(buffer.read_u16_be(), Some(0x1234));
+    assert_eq!(buffer.read_i32_be(), Some(-2));
+    assert!(buffer.is_empty());
+}
+
+#[test]
+fn writes_need_contiguous_space() {
+    let mut buffer = StaticBip::<u8, 8>::default();
+    buffer.write_u32_le(0).unwrap();
+    buffer.write_u16_le(0).unwrap();
+    assert_eq!(buffer.write_u32_le(0), Err(WouldBlock));
+    assert_eq!(buffer.reserved(), 0);
+    assert_eq!(buffer.committed(), 6);
+}
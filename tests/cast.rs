@@ -0,0 +1,56 @@
+use staticbip::{
+    align::{Aligned, A4, A8},
+    cast::{CastError, Pod},
+    StaticBip,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[repr(C)]
+struct Frame {
+    left: i16,
+    right: i16,
+}
+
+unsafe impl Pod for Frame {}
+
+#[test]
+fn committed_bytes_as_samples() {
+    let mut buffer = Aligned::<A4, StaticBip<u8, 16>>::default();
+    buffer.extend(&[1, 0, 2, 0, 0xff, 0xff, 3, 0]);
+
+    assert_eq!(
+        buffer.read_as::<Frame>(),
+        Ok(&mut [Frame { left: 1, right: 2 }, Frame { left: -1, right: 3 }][..])
+    );
+    buffer.decommit_as::<Frame>(1);
+    assert_eq!(buffer.read_as::<i16>(), Ok(&mut [-1, 3][..]));
+}
+
+#[test]
+fn errors() {
+    let mut buffer = Aligned::<A4, StaticBip<u8, 16>>::default();
+    assert_eq!(buffer.read_as::<u32>(), Ok(&mut [][..]));
+
+    buffer.extend(&[1, 2, 3, 4, 5, 6]);
+    assert_eq!(buffer.reserve_as::<u16>(2), Ok(&mut [0, 0][..]));
+    buffer.commit(0);
+    assert_eq!(buffer.reserve_as::<u32>(2), Err(CastError::Alignment));
+    buffer.commit(0);
+
+    assert_eq!(buffer.read_as::<u32>(), Err(CastError::Size));
+    buffer.decommit(1);
+    assert_eq!(buffer.read_as::<u8>(), Ok(&mut [2, 3, 4, 5, 6][..]));
+    assert_eq!(buffer.read_as::<u16>(), Err(CastError::Alignment));
+}
+
+#[test]
+fn reservations_hold_whole_elements() {
+    let mut buffer = Aligned::<A8, StaticBip<u8, 20>>::default();
+    assert_eq!(buffer.reserve_as::<u64>(4).unwrap().len(), 2);
+    buffer.commit_as::<u64>(2);
+    buffer.commit(0);
+
+    assert_eq!(buffer.reserve_as::<u32>(4).unwrap().len(), 1);
+    buffer.commit_as::<u32>(1);
+    assert_eq!(buffer.committed(), 20);
+}
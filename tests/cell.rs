@@ -0,0 +1,26 @@
+use staticbip::cell::StaticBipCell;
+
+static BUFFER: StaticBipCell<u8, 4> = StaticBipCell::new([0; 4]);
+
+#[test]
+fn take_once() {
+    let buffer = BUFFER.take().unwrap();
+    buffer.reserve(2).copy_from_slice(&[1, 2]);
+    buffer.commit(2);
+    assert_eq!(buffer.read(), &[1, 2]);
+
+    assert!(BUFFER.take().is_none());
+}
+
+staticbip::static_bip!(SECTIONED, u16, 8, section = ".data.staticbip");
+staticbip::static_bip!(INITIALIZED, char, 2, init = '-');
+
+#[test]
+fn declared_by_macro() {
+    let sectioned = SECTIONED.take().unwrap();
+    assert_eq!(sectioned.capacity(), 8);
+    assert!(SECTIONED.take().is_none());
+
+    let initialized = INITIALIZED.take().unwrap();
+    assert_eq!(initialized.reserve(2), &['-', '-']);
+}
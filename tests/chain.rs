@@ -0,0 +1,58 @@
+use staticbip::{chain::Chain, BipBuf, StaticBip};
+
+#[test]
+fn reads_first_then_second() {
+    let mut first = StaticBip::<u8, 4>::default();
+    let mut second = StaticBip::<u8, 4>::default();
+    first.reserve(4).copy_from_slice(&[1, 2, 3, 4]);
+    first.commit(4);
+    second.reserve(2).copy_from_slice(&[5, 6]);
+    second.commit(2);
+
+    let mut chain = Chain::new(&mut first, &mut second);
+    assert_eq!(chain.committed(), 6);
+
+    chain.decommit(3);
+    assert_eq!(chain.read(), &[4]);
+    chain.decommit(1);
+    assert_eq!(chain.read(), &[5, 6]);
+    chain.decommit(2);
+    assert!(chain.read().is_empty());
+    assert_eq!(chain.committed(), 0);
+}
+
+#[test]
+fn refilled_first_buffer_is_read_first() {
+    let mut first = StaticBip::<u8, 4>::default();
+    let mut second = StaticBip::<u8, 4>::default();
+    second.reserve(1).copy_from_slice(&[2]);
+    second.commit(1);
+
+    let (first, second) = Chain::new(&mut first, &mut second).into_inner();
+    first.reserve(1).copy_from_slice(&[1]);
+    first.commit(1);
+
+    let mut chain = Chain::new(first, second);
+    assert_eq!(chain.read(), &[1]);
+}
+
+#[test]
+fn nested_chain() {
+    let mut first = StaticBip::<u8, 2>::default();
+    let mut second = StaticBip::<u8, 2>::default();
+    let mut third = StaticBip::<u8, 2>::default();
+    first.reserve(1).copy_from_slice(&[0]);
+    first.commit(1);
+    second.reserve(1).copy_from_slice(&[1]);
+    second.commit(1);
+
+    let mut chain = Chain::new(Chain::new(&mut first, &mut second), &mut third);
+    chain.reserve(1).copy_from_slice(&[2]);
+    chain.commit(1);
+
+    for i in 0..3 {
+        assert_eq!(chain.read(), &[i]);
+        chain.decommit(1);
+    }
+    assert_eq!(chain.capacity(), 6);
+}
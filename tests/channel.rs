@@ -0,0 +1,166 @@
+use staticbip::channel::{Channel, Closed, Receiver, TryRecvError, TrySendError};
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+    task::{Context, Poll, Wake, Waker},
+};
+
+#[derive(Default)]
+struct Counter(AtomicUsize);
+
+impl Wake for Counter {
+    fn wake(self: Arc<Self>) {
+        self.0.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+#[test]
+fn wakes_on_send_and_receive() {
+    let counter = Arc::new(Counter::default());
+    let waker = Waker::from(counter.clone());
+    let mut cx = Context::from_waker(&waker);
+
+    let mut channel = Channel::<u8, 1>::default();
+    let (mut sender, mut receiver) = channel.split();
+
+    assert_eq!(receiver.poll_next(&mut cx), Poll::Pending);
+    assert_eq!(sender.poll_ready(&mut cx), Poll::Ready(Ok(())));
+    sender.start_send(7).unwrap();
+    assert_eq!(counter.0.load(Ordering::Relaxed), 1);
+
+    assert_eq!(sender.poll_ready(&mut cx), Poll::Pending);
+    assert_eq!(receiver.poll_next(&mut cx), Poll::Ready(Some(7)));
+    assert_eq!(counter.0.load(Ordering::Relaxed), 2);
+    assert_eq!(sender.poll_ready(&mut cx), Poll::Ready(Ok(())));
+}
+
+#[test]
+fn dropping_a_half_closes() {
+    let mut cx = Context::from_waker(Waker::noop());
+    let mut channel = Channel::<u8, 4>::default();
+
+    let (mut sender, receiver) = channel.split();
+    drop(receiver);
+    assert_eq!(sender.poll_ready(&mut cx), Poll::Ready(Err(Closed)));
+    assert_eq!(sender.start_send(1), Err(Closed));
+    drop(sender);
+
+    let (mut sender, mut receiver) = channel.split();
+    sender.start_send(1).unwrap();
+    drop(sender);
+    assert_eq!(receiver.poll_next(&mut cx), Poll::Ready(Some(1)));
+    assert_eq!(receiver.poll_next(&mut cx), Poll::Ready(None));
+}
+
+#[test]
+fn receiver_wakes_at_watermark() {
+    let counter = Arc::new(Counter::default());
+    let waker = Waker::from(counter.clone());
+    let mut cx = Context::from_waker(&waker);
+
+    let mut channel = Channel::<u8, 4>::default();
+    let (mut sender, mut receiver) = channel.split();
+
+    assert_eq!(receiver.poll_at_least(3, &mut cx), Poll::Pending);
+    sender.start_send(1).unwrap();
+    sender.start_send(2).unwrap();
+    assert_eq!(counter.0.load(Ordering::Relaxed), 0);
+    sender.start_send(3).unwrap();
+    assert_eq!(counter.0.load(Ordering::Relaxed), 1);
+    assert_eq!(receiver.poll_at_least(3, &mut cx), Poll::Ready(Ok(())));
+
+    assert_eq!(receiver.poll_at_least(4, &mut cx), Poll::Pending);
+    drop(sender);
+    assert_eq!(receiver.poll_at_least(4, &mut cx), Poll::Ready(Err(Closed)));
+}
+
+#[test]
+fn sender_wakes_when_space_frees() {
+    let counter = Arc::new(Counter::default());
+    let waker = Waker::from(counter.clone());
+    let mut cx = Context::from_waker(&waker);
+
+    let mut channel = Channel::<u8, 4>::default();
+    let (mut sender, mut receiver) = channel.split();
+    for item in 0..4 {
+        sender.start_send(item).unwrap();
+    }
+
+    assert_eq!(sender.poll_free(3, &mut cx), Poll::Pending);
+    assert_eq!(receiver.poll_next(&mut cx), Poll::Ready(Some(0)));
+    assert_eq!(receiver.poll_next(&mut cx), Poll::Ready(Some(1)));
+    assert_eq!(counter.0.load(Ordering::Relaxed), 0);
+    assert_eq!(receiver.poll_next(&mut cx), Poll::Ready(Some(2)));
+    assert_eq!(counter.0.load(Ordering::Relaxed), 1);
+    assert_eq!(sender.poll_free(3, &mut cx), Poll::Ready(Ok(())));
+
+    drop(receiver);
+    assert_eq!(sender.poll_free(1, &mut cx), Poll::Ready(Err(Closed)));
+}
+
+#[test]
+fn try_variants_never_wait() {
+    let mut channel = Channel::<u8, 1>::default();
+    let (mut sender, mut receiver) = channel.split();
+
+    assert_eq!(receiver.try_recv(), Err(TryRecvError::Empty));
+    assert_eq!(sender.try_send(1), Ok(()));
+    assert_eq!(sender.try_send(2), Err(TrySendError::Full(2)));
+    assert_eq!(receiver.try_recv(), Ok(1));
+    drop(sender);
+    assert_eq!(receiver.try_recv(), Err(TryRecvError::Closed));
+}
+
+/// Task that receives as soon as it is woken, as an executor polling it inline would.
+#[derive(Default)]
+struct InlineReceive {
+    receiver: Mutex<Option<Receiver<'static, u8, 2>>>,
+    received: Mutex<Vec<u8>>,
+}
+
+impl Wake for InlineReceive {
+    fn wake(self: Arc<Self>) {
+        if let Some(receiver) = self.receiver.lock().unwrap().as_mut() {
+            while let Ok(item) = receiver.try_recv() {
+                self.received.lock().unwrap().push(item);
+            }
+        }
+    }
+}
+
+#[test]
+fn wakers_run_after_the_lock_is_released() {
+    let channel = Box::leak(Box::new(Channel::<u8, 2>::default()));
+    let (mut sender, mut receiver) = channel.split();
+    let task = Arc::new(InlineReceive::default());
+    let waker = Waker::from(task.clone());
+
+    assert_eq!(
+        receiver.poll_next(&mut Context::from_waker(&waker)),
+        Poll::Pending
+    );
+    *task.receiver.lock().unwrap() = Some(receiver);
+    sender.try_send(1).unwrap();
+    assert_eq!(*task.received.lock().unwrap(), [1]);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn blocking_halves_across_threads() {
+    let mut channel = Channel::<u32, 4>::default();
+    let (mut sender, mut receiver) = channel.split();
+
+    std::thread::scope(|scope| {
+        scope.spawn(move || {
+            for item in 0..1000 {
+                sender.send_blocking(item).unwrap();
+            }
+        });
+        for item in 0..1000 {
+            assert_eq!(receiver.recv_blocking(), Some(item));
+        }
+        assert_eq!(receiver.recv_blocking(), None);
+    });
+}
@@ -0,0 +1,51 @@
+#![cfg(feature = "chaos")]
+
+use staticbip::StaticBip;
+use std::collections::VecDeque;
+
+#[test]
+fn varies_the_wrap_and_keeps_order() {
+    let mut buffer = StaticBip::<u32, 16>::default();
+    let mut model = VecDeque::new();
+    let (mut wrapped, mut stayed) = (0, 0);
+    for step in 0..1000u32 {
+        let before = buffer.state();
+        let choice = before.b.len == 0 && before.a.offset != 0 && before.a.end() != 16;
+        let reserved = buffer.reserve(3);
+        let len = reserved.len();
+        for (slot, value) in reserved.iter_mut().zip(step * 3..) {
+            *slot = value;
+        }
+        if choice {
+            if buffer.state().reserve.offset == 0 {
+                wrapped += 1;
+            } else {
+                stayed += 1;
+            }
+        }
+        buffer.commit(len);
+        model.extend(step * 3..step * 3 + len as u32);
+
+        let taken = (step as usize * 7 % 5).min(model.len());
+        buffer.consume(taken);
+        model.drain(..taken);
+        assert!(buffer.iter().eq(model.iter()));
+    }
+    assert!(wrapped > 0 && stayed > 0, "{} {}", wrapped, stayed);
+}
+
+#[test]
+fn frames_replacing_a_reservation_stay_whole() {
+    for fill in 0..=12 {
+        for read in 0..=fill {
+            for frame_len in 1..6 {
+                let mut buffer = StaticBip::<u8, 12>::default();
+                buffer.extend(&[1; 12][..fill]);
+                buffer.decommit(read);
+                buffer.reserve(1);
+                let len = buffer.reserve_frames(frame_len, 4).len();
+                assert_eq!(len % frame_len, 0, "{} {} {}", fill, read, frame_len);
+            }
+        }
+    }
+}
@@ -0,0 +1,112 @@
+use staticbip::{
+    codec::{Decoder, EncodeError, Encoder, Step},
+    StaticBip,
+};
+
+/// Frames of a length byte followed by that many bytes.
+struct Prefixed;
+
+impl Decoder for Prefixed {
+    type Item = Vec<u8>;
+    type Error = ();
+
+    fn decode(&mut self, src: &[u8]) -> Result<Option<(Vec<u8>, usize)>, ()> {
+        match src.split_first() {
+            Some((&0, _)) => Err(()),
+            Some((&len, rest)) if rest.len() >= usize::from(len) => Ok(Some((
+                rest[..usize::from(len)].to_vec(),
+                1 + usize::from(len),
+            ))),
+            _ => Ok(None),
+        }
+    }
+}
+
+impl Encoder<&[u8]> for Prefixed {
+    type Error = ();
+
+    fn encoded_len(&self, item: &&[u8]) -> usize {
+        1 + item.len()
+    }
+
+    fn encode(&mut self, item: &[u8], dst: &mut [u8]) -> Result<(), ()> {
+        if item.is_empty() {
+            return Err(());
+        }
+        dst[0] = item.len() as u8;
+        dst[1..].copy_from_slice(item);
+        Ok(())
+    }
+}
+
+#[test]
+fn frames_round_trip() {
+    let mut buffer = StaticBip::<u8, 8>::default();
+    buffer.encode_with(&mut Prefixed, &b"abc"[..]).unwrap();
+    buffer.encode_with(&mut Prefixed, &b"de"[..]).unwrap();
+    assert_eq!(
+        buffer.encode_with(&mut Prefixed, &b"fg"[..]),
+        Err(EncodeError::Full(&b"fg"[..]))
+    );
+    assert_eq!(
+        buffer.encode_with(&mut Prefixed, &b""[..]),
+        Err(EncodeError::Codec(()))
+    );
+    assert_eq!(buffer.committed(), 7);
+
+    assert_eq!(buffer.decode_with(&mut Prefixed), Ok(Some(b"abc".to_vec())));
+    assert_eq!(buffer.decode_with(&mut Prefixed), Ok(Some(b"de".to_vec())));
+    assert_eq!(buffer.decode_with(&mut Prefixed), Ok(None));
+}
+
+#[test]
+fn frame_across_the_wrap_is_decoded() {
+    let mut buffer = StaticBip::<u8, 8>::default();
+    buffer.reserve(8)[6..].copy_from_slice(&[3, b'a']);
+    buffer.commit(8);
+    buffer.decommit(6);
+    buffer.reserve(2).copy_from_slice(b"bc");
+    buffer.commit(2);
+    assert_eq!(buffer.read().len(), 2);
+
+    assert_eq!(buffer.decode_with(&mut Prefixed), Ok(Some(b"abc".to_vec())));
+    assert!(buffer.is_empty());
+}
+
+#[test]
+fn parse_with_across_the_wrap() {
+    let mut buffer = StaticBip::<u8, 8>::default();
+    buffer.extend(&[9, 9, 9, 9, 9, 9, 2, 7]);
+    buffer.decommit(6);
+    buffer.extend(&[8, 0, 1]);
+    assert_eq!(buffer.read(), &[2, 7]);
+
+    // Length-prefixed records, with a zero length skipping the byte
+    let parser = |src: &[u8]| -> Result<Step<Vec<u8>>, ()> {
+        Ok(match src.split_first() {
+            Some((&0, _)) => Step::Consumed(1, None),
+            Some((&0xFF, _)) => return Err(()),
+            Some((&len, rest)) if rest.len() >= usize::from(len) => Step::Consumed(
+                1 + usize::from(len),
+                Some(rest[..usize::from(len)].to_vec()),
+            ),
+            _ => Step::Incomplete,
+        })
+    };
+
+    let mut records = Vec::new();
+    assert_eq!(
+        buffer.parse_with(parser, |record| records.push(record)),
+        Ok(1)
+    );
+    assert_eq!(records, [vec![7, 8]]);
+    assert_eq!(buffer.read(), &[1]);
+
+    buffer.extend(&[5, 0xFF]);
+    assert_eq!(
+        buffer.parse_with(parser, |record| records.push(record)),
+        Err(())
+    );
+    assert_eq!(records, [vec![7, 8], vec![5]]);
+    assert_eq!(buffer.read(), &[0xFF]);
+}
@@ -0,0 +1,48 @@
+use core::{fmt::Write, mem::MaybeUninit};
+use staticbip::crash::CrashLog;
+
+#[test]
+fn keeps_the_newest_bytes_across_a_reset() {
+    let mut slot = MaybeUninit::<CrashLog<16>>::zeroed();
+    let (log, recovered) = unsafe { CrashLog::recover(&mut slot) };
+    assert!(!recovered);
+
+    for tick in 0..5 {
+        write!(log, "tick {};", tick).unwrap();
+    }
+    log.capture_panic(&"boom");
+
+    let (log, recovered) = unsafe { CrashLog::recover(&mut slot) };
+    assert!(recovered);
+    let (a, b) = log.contents();
+    let kept = [a, b].concat();
+    assert!(kept.len() > "tick 4;boom\n".len());
+    assert!(b"tick 0;tick 1;tick 2;tick 3;tick 4;boom\n".ends_with(&kept));
+
+    log.clear();
+    let (log, recovered) = unsafe { CrashLog::recover(&mut slot) };
+    assert!(recovered);
+    assert!(log.get_ref().is_empty());
+}
+
+#[test]
+fn captures_std_panic_messages() {
+    let mut slot = MaybeUninit::<CrashLog<128>>::zeroed();
+    let (log, _) = unsafe { CrashLog::recover(&mut slot) };
+    let log = log as *mut CrashLog<128> as usize;
+
+    let hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        // SAFETY: the log outlives the hook and nothing else touches it meanwhile.
+        unsafe { &mut *(log as *mut CrashLog<128>) }.capture_panic(info);
+    }));
+    let _ = std::panic::catch_unwind(|| panic!("out of range"));
+    std::panic::set_hook(hook);
+
+    let (log, recovered) = unsafe { CrashLog::recover(&mut slot) };
+    assert!(recovered);
+    let (a, b) = log.contents();
+    let text = String::from_utf8([a, b].concat()).unwrap();
+    assert!(text.starts_with("panicked at tests/crash.rs:"), "{}", text);
+    assert!(text.ends_with("out of range\n"), "{}", text);
+}
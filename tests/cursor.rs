@@ -0,0 +1,47 @@
+use staticbip::StaticBip;
+
+fn wrapped() -> StaticBip<u8, 4> {
+    let mut buffer = StaticBip::default();
+    buffer.reserve(4).copy_from_slice(&[1, 2, 3, 4]);
+    buffer.commit(4);
+    buffer.decommit(2);
+    buffer.reserve(2).copy_from_slice(&[5, 6]);
+    buffer.commit(2);
+    buffer
+}
+
+#[test]
+fn reads_across_regions() {
+    let mut buffer = wrapped();
+    let mut cursor = buffer.cursor();
+    assert_eq!(cursor.remaining(), 4);
+    assert_eq!(cursor.read(3), &[3, 4]);
+    assert_eq!(cursor.read(3), &[5, 6]);
+    assert!(cursor.read(3).is_empty());
+    assert_eq!(cursor.position(), 4);
+}
+
+#[test]
+fn seek_backtracks_without_consuming() {
+    let mut buffer = wrapped();
+    let mut cursor = buffer.cursor();
+    cursor.seek(3);
+    assert_eq!(cursor.read(1), &[6]);
+    cursor.seek(1);
+    assert_eq!(cursor.read(1), &[4]);
+    cursor.seek(10);
+    assert_eq!(cursor.remaining(), 0);
+
+    assert_eq!(buffer.committed(), 4);
+    assert_eq!(buffer.read(), &[3, 4]);
+}
+
+#[test]
+fn consume_to_position() {
+    let mut buffer = wrapped();
+    let mut cursor = buffer.cursor();
+    cursor.seek(3);
+    cursor.consume_to_position();
+    assert_eq!(buffer.read(), &[6]);
+    assert_eq!(buffer.committed(), 1);
+}
@@ -0,0 +1,50 @@
+use staticbip::defmt::DefmtBuffer;
+
+fn frame<const CAP: usize>(buffer: &mut DefmtBuffer<CAP>, parts: &[&[u8]]) -> bool {
+    buffer.start_frame();
+    for part in parts {
+        buffer.write(part);
+    }
+    buffer.end_frame()
+}
+
+fn drain_all<const CAP: usize>(buffer: &mut DefmtBuffer<CAP>) -> Vec<u8> {
+    let mut sent = Vec::new();
+    buffer.drain(|bytes| {
+        sent.extend_from_slice(bytes);
+        bytes.len()
+    });
+    sent
+}
+
+#[test]
+fn drops_frames_that_do_not_fit() {
+    let mut buffer = DefmtBuffer::<8>::new();
+    assert!(frame(&mut buffer, &[&[1, 2], &[3, 0]]));
+    assert!(!frame(&mut buffer, &[&[4, 5, 6], &[7, 8, 0]]));
+    assert_eq!(buffer.dropped(), 1);
+
+    // The stream only holds whole frames
+    assert_eq!(drain_all(&mut buffer), [1, 2, 3, 0]);
+
+    assert!(frame(&mut buffer, &[&[4, 5, 6], &[7, 8, 0]]));
+    assert_eq!(drain_all(&mut buffer), [4, 5, 6, 7, 8, 0]);
+}
+
+#[test]
+fn unfinished_frame_is_discarded() {
+    let mut buffer = DefmtBuffer::<8>::new();
+    buffer.start_frame();
+    buffer.write(&[9, 9]);
+    assert!(frame(&mut buffer, &[&[1, 0]]));
+    assert_eq!(drain_all(&mut buffer), [1, 0]);
+}
+
+#[test]
+fn drain_stops_at_short_transfer() {
+    let mut buffer = DefmtBuffer::<8>::new();
+    assert!(frame(&mut buffer, &[&[1, 2, 3, 0]]));
+    assert_eq!(buffer.drain(|bytes| bytes.len() - 1), 3);
+    assert_eq!(drain_all(&mut buffer), [0]);
+    assert_eq!(buffer.drain(|_| unreachable!()), 0);
+}
@@ -0,0 +1,34 @@
+use staticbip::StaticBip;
+use std::collections::VecDeque;
+
+#[test]
+fn matches_vec_deque() {
+    let mut buffer = StaticBip::<u32, 5>::default();
+    let mut model = VecDeque::new();
+    for step in 0..50u32 {
+        if step % 3 == 2 {
+            assert_eq!(buffer.pop_front(), model.pop_front());
+        } else if buffer.push_back(step).is_ok() {
+            model.push_back(step);
+        } else {
+            assert_eq!(model.len(), buffer.capacity());
+            assert_eq!(buffer.pop_front(), model.pop_front());
+        }
+
+        assert_eq!(buffer.len(), model.len());
+        assert!(buffer.iter().eq(model.iter()));
+        let (a, b) = buffer.as_slices();
+        assert!(a.iter().chain(b).eq(&model));
+        assert_eq!(buffer.front(), model.front());
+        assert_eq!(buffer.back(), model.back());
+    }
+}
+
+#[test]
+fn push_back_hands_back_when_full() {
+    let mut buffer = StaticBip::new(<[String; 1]>::default());
+    assert_eq!(buffer.push_back("a".into()), Ok(()));
+    assert_eq!(buffer.push_back("b".into()), Err("b".to_string()));
+    assert_eq!(buffer.pop_front().as_deref(), Some("a"));
+    assert!(buffer.is_empty());
+}
@@ -0,0 +1,37 @@
+use staticbip::{device::FrameDevice, StaticBip};
+
+#[test]
+fn no_tokens_without_room() {
+    let mut rx = StaticBip::<u8, 16>::default();
+    let mut tx = StaticBip::<u8, 8>::default();
+    let mut device = FrameDevice::new(&mut rx, &mut tx, 6);
+    assert!(device.receive().is_none());
+
+    device.transmit().unwrap().consume(4, |frame| frame.fill(1));
+    assert!(device.transmit().is_none());
+
+    device.rx().reserve(2);
+    device.rx().commit(2);
+    assert!(device.receive().is_none());
+
+    device.tx().decommit();
+    assert!(device.receive().is_some());
+}
+
+#[test]
+fn frames_keep_their_order() {
+    let mut rx = StaticBip::<u8, 16>::default();
+    let mut tx = StaticBip::<u8, 16>::default();
+    let mut device = FrameDevice::new(&mut rx, &mut tx, 4);
+    for frame in [&b"ab"[..], b"cde"].iter() {
+        let mut frames = device.rx();
+        frames.reserve(frame.len()).copy_from_slice(frame);
+        frames.commit(frame.len());
+    }
+
+    let (first, _) = device.receive().unwrap();
+    assert_eq!(first.consume(|frame| frame.to_vec()), b"ab");
+    let (second, _) = device.receive().unwrap();
+    assert_eq!(second.consume(|frame| frame.to_vec()), b"cde");
+    assert!(device.receive().is_none());
+}
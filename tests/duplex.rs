@@ -0,0 +1,64 @@
+use staticbip::{channel::TryRecvError, duplex::Duplex};
+
+#[test]
+fn ports_face_each_other() {
+    let mut duplex = Duplex::<u32, 2, 4>::default();
+    let (mut app, driver) = duplex.split();
+    let (mut driver_tx, mut driver_rx) = driver.into_parts();
+
+    app.sender().try_send(1).unwrap();
+    app.sender().try_send(2).unwrap();
+    assert!(app.sender().try_send(3).is_err());
+    for n in 0..4 {
+        driver_tx.try_send(n).unwrap();
+    }
+
+    assert_eq!(driver_rx.try_recv(), Ok(1));
+    assert_eq!(driver_rx.try_recv(), Ok(2));
+    assert_eq!(driver_rx.try_recv(), Err(TryRecvError::Empty));
+    for n in 0..4 {
+        assert_eq!(app.receiver().try_recv(), Ok(n));
+    }
+
+    drop(driver_tx);
+    assert_eq!(app.receiver().try_recv(), Err(TryRecvError::Closed));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn io_between_threads() {
+    use std::io::{ErrorKind, Read, Write};
+
+    // Room for the whole echo, so the driver never waits for the application
+    let mut duplex = Duplex::<u8, 4, 16>::default();
+    let (mut app, mut driver) = duplex.split();
+
+    std::thread::scope(|scope| {
+        scope.spawn(move || {
+            // Echo until the application hangs up
+            let mut data = [0; 8];
+            loop {
+                let len = driver.read(&mut data).unwrap();
+                if len == 0 {
+                    break;
+                }
+                driver.write_all(&data[..len]).unwrap();
+            }
+        });
+
+        let mut echo = [0; 10];
+        app.write_all(b"0123456789")
+            .unwrap_or_else(|_| unreachable!());
+        app.read_exact(&mut echo).unwrap();
+        assert_eq!(&echo, b"0123456789");
+
+        let (sender, mut receiver) = app.into_parts();
+        drop(sender);
+        assert_eq!(receiver.recv_blocking(), None);
+    });
+
+    let (mut app, driver) = duplex.split();
+    drop(driver);
+    assert_eq!(app.write(b"x").unwrap_err().kind(), ErrorKind::BrokenPipe);
+    assert_eq!(app.read(&mut [0]).unwrap(), 0);
+}
@@ -0,0 +1,33 @@
+#![cfg(feature = "ffi")]
+
+use core::{mem::MaybeUninit, slice};
+use staticbip::ffi::*;
+
+#[test]
+fn wraps_around() {
+    let mut storage = [0u8; 4];
+    let mut handle = MaybeUninit::<BipHandle>::uninit();
+    unsafe {
+        staticbip_init(handle.as_mut_ptr(), storage.as_mut_ptr(), storage.len());
+        let handle = handle.as_mut_ptr();
+        let mut len = 0;
+
+        let ptr = staticbip_reserve(handle, 4, &mut len);
+        assert_eq!(len, 4);
+        slice::from_raw_parts_mut(ptr, len).copy_from_slice(&[1, 2, 3, 4]);
+        staticbip_commit(handle, 4);
+        staticbip_decommit(handle, 3);
+
+        let ptr = staticbip_reserve(handle, 4, &mut len);
+        assert_eq!(len, 3);
+        slice::from_raw_parts_mut(ptr, 2).copy_from_slice(&[5, 6]);
+        staticbip_commit(handle, 2);
+
+        let ptr = staticbip_read(handle, &mut len);
+        assert_eq!(slice::from_raw_parts(ptr, len), &[4]);
+        staticbip_decommit(handle, len);
+
+        let ptr = staticbip_read(handle, &mut len);
+        assert_eq!(slice::from_raw_parts(ptr, len), &[5, 6]);
+    }
+}
@@ -0,0 +1,30 @@
+use staticbip::flow::{FlowEvent, Watermarks};
+
+#[test]
+fn events_follow_hysteresis() {
+    let mut watermarks = Watermarks::new(1, 3);
+    let events: Vec<_> = [0, 2, 3, 4, 2, 1, 0, 3]
+        .iter()
+        .map(|&occupancy| watermarks.update(occupancy))
+        .collect();
+    assert_eq!(
+        events,
+        [
+            None,
+            None,
+            Some(FlowEvent::Stop),
+            None,
+            None,
+            Some(FlowEvent::Start),
+            None,
+            Some(FlowEvent::Stop),
+        ]
+    );
+    assert!(watermarks.is_stopped());
+}
+
+#[test]
+#[should_panic]
+fn inverted_watermarks() {
+    Watermarks::new(3, 3);
+}
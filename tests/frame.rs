@@ -0,0 +1,118 @@
+use staticbip::{
+    frame::{Ccitt, Crc16, FrameError, Framed},
+    StaticBip,
+};
+
+#[test]
+fn frames_round_trip() {
+    let mut buffer = StaticBip::<u8, 16>::default();
+    let mut framed = Framed::<_>::new(&mut buffer);
+
+    framed.reserve(3).copy_from_slice(&[1, 2, 3]);
+    framed.commit(3);
+    framed.reserve(2).copy_from_slice(&[4, 5]);
+    framed.commit(2);
+
+    assert_eq!(framed.read(), Some(Ok(&mut [1, 2, 3][..])));
+    framed.decommit();
+    assert_eq!(framed.read(), Some(Ok(&mut [4, 5][..])));
+    framed.decommit();
+    assert_eq!(framed.read(), None);
+}
+
+#[test]
+fn reserve_without_room_for_overhead() {
+    let mut buffer = StaticBip::<u8, 5>::default();
+    let mut framed = Framed::<_, Crc16<Ccitt>>::new(&mut buffer);
+
+    assert_eq!(framed.reserve(8).len(), 1);
+    framed.commit(1);
+    assert!(framed.reserve(1).is_empty());
+    framed.commit(1);
+    assert_eq!(buffer.committed(), 5);
+}
+
+#[test]
+fn crc_check_value() {
+    assert_eq!(Crc16::<Ccitt>::checksum(b"123456789"), 0x29B1);
+}
+
+#[test]
+fn corrupt_frame_is_reported() {
+    let mut buffer = StaticBip::<u8, 16>::default();
+    let mut framed = Framed::<_, Crc16<Ccitt>>::new(&mut buffer);
+    framed.reserve(4).copy_from_slice(b"ping");
+    framed.commit(4);
+    framed.reserve(4).copy_from_slice(b"pong");
+    framed.commit(4);
+
+    buffer.read()[3] ^= 0xFF;
+
+    let mut framed = Framed::<_, Crc16<Ccitt>>::new(&mut buffer);
+    assert_eq!(framed.read(), Some(Err(FrameError::Checksum)));
+    framed.decommit();
+    assert_eq!(framed.read().unwrap().unwrap(), b"pong");
+}
+
+#[test]
+fn corrupt_length_is_reported() {
+    let mut buffer = StaticBip::<u8, 16>::default();
+    let mut framed = Framed::<_>::new(&mut buffer);
+    framed.reserve(4).copy_from_slice(b"ping");
+    framed.commit(4);
+
+    buffer.read()[0] = 0xFF;
+
+    let mut framed = Framed::<_>::new(&mut buffer);
+    assert_eq!(framed.read(), Some(Err(FrameError::Length)));
+    framed.decommit();
+    assert_eq!(framed.read(), None);
+}
+
+#[test]
+fn next_frame_leaves_partial_frame_buffered() {
+    let mut buffer = StaticBip::<u8, 16>::default();
+    buffer.extend(&[3, 0, b'y']);
+    assert!(Framed::<_>::new(&mut buffer).next_frame().is_none());
+
+    buffer.extend(&[b'o', b'!', 2, 0, b'h']);
+    let mut framed = Framed::<_>::new(&mut buffer);
+    let mut frame = framed.next_frame().unwrap().unwrap();
+    frame[2] = b'?';
+    assert_eq!(&*frame, b"yo?");
+    drop(frame);
+    assert!(framed.next_frame().is_none());
+    assert_eq!(buffer.as_slices(), (&[2, 0, b'h'][..], &[][..]));
+}
+
+#[test]
+fn next_frame_removes_corrupt_frames() {
+    let mut buffer = StaticBip::<u8, 32>::default();
+    let mut framed = Framed::<_, Crc16<Ccitt>>::new(&mut buffer);
+    for payload in [b"ping", b"pong"] {
+        framed.reserve(4).copy_from_slice(payload);
+        framed.commit(4);
+    }
+    buffer.read()[3] ^= 0xFF;
+
+    let mut framed = Framed::<_, Crc16<Ccitt>>::new(&mut buffer);
+    assert_eq!(
+        framed.next_frame().unwrap().unwrap_err(),
+        FrameError::Checksum
+    );
+    assert_eq!(&*framed.next_frame().unwrap().unwrap(), b"pong");
+    assert!(framed.next_frame().is_none());
+}
+
+#[test]
+fn next_frame_rejects_length_beyond_capacity() {
+    let mut buffer = StaticBip::<u8, 8>::default();
+    buffer.extend(&[0xFF, 0, 1, 2]);
+
+    let mut framed = Framed::<_>::new(&mut buffer);
+    assert_eq!(
+        framed.next_frame().unwrap().unwrap_err(),
+        FrameError::Length
+    );
+    assert!(buffer.is_empty());
+}
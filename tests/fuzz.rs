@@ -0,0 +1,55 @@
+#![cfg(feature = "fuzzing")]
+
+use staticbip::{
+    fuzz::{self, Op},
+    BipState, StaticBip,
+};
+
+#[test]
+fn states_are_valid_and_varied() {
+    let mut wrapped = false;
+    let mut reserved = false;
+    for seed in 0..2048u32 {
+        let data = seed.wrapping_mul(0x9e37_79b9).to_le_bytes().repeat(3);
+        for capacity in [0, 1, 7] {
+            let state = BipState::from_fuzz(capacity, &data);
+            assert!(state.is_valid(capacity), "{:?}", state);
+            wrapped |= state.b.len > 0 && state.a.len > 0;
+            reserved |= state.reserve.len > 0;
+        }
+    }
+    assert!(wrapped && reserved);
+}
+
+#[test]
+fn buffer_from_fuzz_input() {
+    let mut buffer = StaticBip::from_fuzz([1u8; 8], &[2, 0, 3, 0, 2, 0, 2, 0]);
+    assert_eq!(buffer.as_slices(), (&[1; 2][..], &[1; 3][..]));
+    buffer.extend([2; 4].iter());
+    assert_eq!(buffer.committed(), 6);
+}
+
+#[test]
+fn operation_sequences_keep_state_valid() {
+    for seed in 0..512u32 {
+        let data: Vec<u8> = (0..60u32)
+            .map(|i| (seed.wrapping_mul(31).wrapping_add(i * 7919) >> 3) as u8)
+            .collect();
+        let mut buffer = StaticBip::from_fuzz([0u8; 8], &data);
+        let mut model: usize = buffer.committed();
+        for op in fuzz::ops(8, &data) {
+            let reserved = buffer.reserved();
+            buffer.apply(op);
+            match op {
+                Op::Commit(len) => model += len.min(reserved),
+                Op::Pop => model = model.saturating_sub(1),
+                _ => {}
+            }
+            if let Op::Decommit(_) = op {
+                model = buffer.committed();
+            }
+            assert_eq!(buffer.committed(), model);
+        }
+        assert!(buffer.into_parts().1.is_valid(8));
+    }
+}
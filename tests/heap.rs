@@ -0,0 +1,101 @@
+#![cfg(feature = "alloc")]
+
+use staticbip::heap::{AllocError, Allocator, Global, HeapBip};
+use std::{alloc::Layout, cell::Cell, ptr::NonNull, rc::Rc};
+
+/// Global allocator with a byte budget, counting live blocks.
+struct Budget {
+    remaining: Cell<usize>,
+    live: Cell<usize>,
+}
+
+unsafe impl Allocator for Budget {
+    fn allocate(&self, layout: Layout) -> Option<NonNull<u8>> {
+        let remaining = self.remaining.get().checked_sub(layout.size())?;
+        self.remaining.set(remaining);
+        self.live.set(self.live.get() + 1);
+        Global.allocate(layout)
+    }
+
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        self.live.set(self.live.get() - 1);
+        Global.deallocate(ptr, layout)
+    }
+}
+
+#[test]
+fn storage_comes_from_the_allocator() {
+    let budget = Budget {
+        remaining: Cell::new(16),
+        live: Cell::new(0),
+    };
+    let mut buffer = HeapBip::<u32, _>::try_with_capacity_in(4, &budget).unwrap();
+    assert_eq!(budget.live.get(), 1);
+    assert_eq!(
+        HeapBip::<u32, _>::try_with_capacity_in(1, &budget).err(),
+        Some(AllocError)
+    );
+
+    buffer.reserve(4).copy_from_slice(&[1, 2, 3, 4]);
+    buffer.commit(4);
+    buffer.decommit(3);
+    buffer.reserve(2).copy_from_slice(&[5, 6]);
+    buffer.commit(2);
+    assert_eq!(buffer.read(), &[4]);
+    buffer.decommit(1);
+    assert_eq!(buffer.read(), &[5, 6]);
+
+    drop(buffer);
+    assert_eq!(budget.live.get(), 0);
+}
+
+#[test]
+fn elements_are_dropped() {
+    let marker = Rc::new(());
+    let mut buffer = HeapBip::<Option<Rc<()>>>::with_capacity(3);
+    buffer.reserve(3).fill(Some(marker.clone()));
+    assert_eq!(Rc::strong_count(&marker), 4);
+    drop(buffer);
+    assert_eq!(Rc::strong_count(&marker), 1);
+
+    let empty = HeapBip::<()>::with_capacity(8);
+    assert_eq!(empty.capacity(), 8);
+}
+
+thread_local! {
+    static CREATED: Cell<usize> = const { Cell::new(0) };
+    static DROPPED: Cell<usize> = const { Cell::new(0) };
+}
+
+/// Element whose third default value panics.
+struct Fragile {
+    _storage: u8,
+}
+
+impl Default for Fragile {
+    fn default() -> Self {
+        let created = CREATED.with(|created| created.replace(created.get() + 1));
+        assert!(created < 2, "out of fragile elements");
+        Fragile { _storage: 0 }
+    }
+}
+
+impl Drop for Fragile {
+    fn drop(&mut self) {
+        DROPPED.with(|dropped| dropped.set(dropped.get() + 1));
+    }
+}
+
+#[test]
+fn panicking_default_releases_storage() {
+    let budget = Budget {
+        remaining: Cell::new(64),
+        live: Cell::new(0),
+    };
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        HeapBip::<Fragile, _>::try_with_capacity_in(4, &budget)
+    }));
+    assert!(result.is_err());
+    assert_eq!(DROPPED.with(Cell::get), 2);
+    assert_eq!(budget.live.get(), 0);
+}
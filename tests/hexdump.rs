@@ -0,0 +1,25 @@
+#![cfg(feature = "hexdump")]
+
+use staticbip::StaticBip;
+
+#[test]
+fn offsets_continue_across_the_wrap() {
+    let mut buffer = StaticBip::<u8, 20>::default();
+    buffer.extend(&[0; 20]);
+    buffer.decommit(16);
+    buffer.extend(b"\r\nHello, world");
+    assert_eq!(buffer.as_slices().0.len(), 4);
+    assert_eq!(buffer.committed(), 18);
+
+    assert_eq!(
+        buffer.hexdump().to_string(),
+        "00000000  00 00 00 00 0d 0a 48 65  6c 6c 6f 2c 20 77 6f 72  |......Hello, wor|\n\
+         00000010  6c 64                                             |ld|\n"
+    );
+}
+
+#[test]
+fn empty_buffer_prints_nothing() {
+    let buffer = StaticBip::<u8, 4>::default();
+    assert_eq!(buffer.hexdump().to_string(), "");
+}
@@ -0,0 +1,45 @@
+// Relies on clamping, which the `strict` feature turns into panics
+#![cfg(not(feature = "strict"))]
+
+use staticbip::{
+    hook::{Hooked, Hooks},
+    BipBuf, SliceBip,
+};
+
+#[derive(Default)]
+struct Log(Vec<(&'static str, usize)>);
+
+impl Hooks for Log {
+    fn on_commit(&mut self, len: usize) {
+        self.0.push(("commit", len));
+    }
+
+    fn on_decommit(&mut self, len: usize) {
+        self.0.push(("decommit", len));
+    }
+
+    fn on_reject(&mut self, len: usize) {
+        self.0.push(("reject", len));
+    }
+}
+
+#[test]
+fn reports_actual_lengths() {
+    let mut storage = [0u8; 4];
+    let mut buffer = Hooked::new(SliceBip::new(&mut storage), Log::default());
+    buffer.reserve(3);
+    buffer.commit(5);
+    buffer.reserve(2);
+    buffer.commit(0);
+    buffer.decommit(8);
+    buffer.decommit(1);
+    buffer.reserve(0);
+    buffer.reserve(4);
+    buffer.commit(4);
+    buffer.reserve(1);
+
+    assert_eq!(
+        buffer.hooks().0,
+        [("commit", 3), ("decommit", 3), ("commit", 4), ("reject", 1)]
+    );
+}
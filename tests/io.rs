@@ -0,0 +1,114 @@
+#![cfg(feature = "std")]
+
+use staticbip::StaticBip;
+use std::io::{self, BufRead, Read};
+
+#[test]
+fn read_line_across_regions() {
+    let mut buffer = StaticBip::<u8, 8>::default();
+    buffer.extend(b"xxxxxxab");
+    buffer.decommit(6);
+    buffer.extend(b"c\nd");
+    assert_eq!(buffer.as_slices(), (&b"ab"[..], &b"c\nd"[..]));
+
+    let mut line = String::new();
+    assert_eq!(buffer.read_line(&mut line).unwrap(), 4);
+    assert_eq!(line, "abc\n");
+
+    let mut rest = Vec::new();
+    buffer.read_to_end(&mut rest).unwrap();
+    assert_eq!(rest, b"d");
+}
+
+#[test]
+fn vectored_io_wraps() {
+    let mut buffer = StaticBip::<u8, 8>::default();
+    buffer.extend(b"xxxxxx");
+    buffer.decommit(4);
+
+    let [tail, head] = buffer.as_io_slices_mut();
+    assert_eq!((tail.len(), head.len()), (2, 4));
+    assert_eq!(buffer.read_vectored_from(&mut &b"abcd"[..]).unwrap(), 4);
+    assert_eq!(buffer.as_slices(), (&b"xxab"[..], &b"cd"[..]));
+
+    let [free, empty] = buffer.as_io_slices_mut();
+    assert_eq!((free.len(), empty.len()), (2, 0));
+
+    let [a, b] = buffer.as_io_slices();
+    assert_eq!((&*a, &*b), (&b"xxab"[..], &b"cd"[..]));
+}
+
+#[test]
+fn vectored_read_after_draining_is_readable() {
+    let mut buffer = StaticBip::<u8, 4>::default();
+    buffer.extend(&[1, 2, 3, 4]);
+    while buffer.pop().is_some() {}
+
+    assert_eq!(buffer.read_vectored_from(&mut &b"xy"[..]).unwrap(), 2);
+    assert_eq!(buffer.committed(), 2);
+    assert_eq!(buffer.read(), b"xy");
+    assert_eq!(buffer.as_slices(), (&b"xy"[..], &b""[..]));
+}
+
+#[test]
+fn read_from_continues_into_wrapped_space() {
+    let mut buffer = StaticBip::<u8, 8>::default();
+    let mut input = &b"0123456789"[..];
+    assert_eq!(buffer.read_from(&mut input).unwrap(), 8);
+    buffer.decommit(5);
+
+    // The three free slots at the start are larger than the empty space after the data
+    assert_eq!(buffer.read_from(&mut input).unwrap(), 2);
+    assert_eq!(buffer.as_slices(), (&b"567"[..], &b"89"[..]));
+    assert_eq!(buffer.read_from(&mut input).unwrap(), 0);
+}
+
+#[test]
+fn read_from_retries_interrupts_and_keeps_data_before_errors() {
+    struct Flaky(Vec<io::Result<&'static [u8]>>);
+
+    impl Read for Flaky {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            let data = self.0.remove(0)?;
+            buf[..data.len()].copy_from_slice(data);
+            Ok(data.len())
+        }
+    }
+
+    let mut buffer = StaticBip::<u8, 4>::default();
+    let mut reader = Flaky(vec![
+        Err(io::ErrorKind::Interrupted.into()),
+        Ok(b"abcd"),
+        Err(io::ErrorKind::Other.into()),
+    ]);
+    assert_eq!(buffer.read_from(&mut reader).unwrap(), 4);
+    buffer.decommit(4);
+    assert!(buffer.read_from(&mut reader).is_err());
+    assert_eq!(buffer.reserved(), 0);
+}
+
+#[test]
+fn reservation_writer_seeks_to_length_field() {
+    use std::io::{ErrorKind, Seek, SeekFrom, Write};
+
+    let mut buffer = StaticBip::<u8, 16>::default();
+    let mut writer = buffer.reservation_writer(16);
+    writer.seek(SeekFrom::Start(2)).unwrap();
+    write!(writer, "{}-{}", 12, 34).unwrap();
+    let end = writer.stream_position().unwrap();
+    writer.rewind().unwrap();
+    writer.write_all(&((end - 2) as u16).to_le_bytes()).unwrap();
+
+    assert_eq!(writer.seek(SeekFrom::End(-1)).unwrap(), 6);
+    assert_eq!(
+        writer.seek(SeekFrom::Current(-7)).unwrap_err().kind(),
+        ErrorKind::InvalidInput
+    );
+    writer.seek(SeekFrom::End(10)).unwrap();
+    assert_eq!(
+        writer.write_all(b"x").unwrap_err().kind(),
+        ErrorKind::WriteZero
+    );
+    assert_eq!(writer.finish(), 7);
+    assert_eq!(buffer.read(), b"\x05\x0012-34");
+}
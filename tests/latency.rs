@@ -0,0 +1,54 @@
+#![cfg(feature = "latency")]
+
+use core::cell::Cell;
+use staticbip::{latency::Instrumented, BipBuf, StaticBip};
+
+#[test]
+fn min_max_and_average_per_operation() {
+    // Each operation takes the next cost from the list
+    let costs = [5u32, 9, 1, 4];
+    let step = Cell::new(0);
+    let now = Cell::new(u32::MAX - 6);
+    let counter = || {
+        let step_count = step.get();
+        step.set(step_count + 1);
+        if step_count % 2 == 1 {
+            now.set(now.get().wrapping_add(costs[step_count / 2 % costs.len()]));
+        }
+        now.get()
+    };
+
+    let mut buffer = Instrumented::new(StaticBip::<u8, 16>::default(), counter);
+    assert_eq!(buffer.latency().reserve.min(), None);
+
+    buffer.reserve(4);
+    buffer.commit(4);
+    buffer.reserve(2);
+    buffer.commit(2);
+    assert_eq!(buffer.read().len(), 6);
+    buffer.decommit(6);
+
+    let latency = *buffer.latency();
+    assert_eq!(latency.reserve.count(), 2);
+    assert_eq!(
+        (
+            latency.reserve.min(),
+            latency.reserve.max(),
+            latency.reserve.avg()
+        ),
+        (Some(1), Some(5), Some(3))
+    );
+    assert_eq!(
+        (
+            latency.commit.min(),
+            latency.commit.max(),
+            latency.commit.avg()
+        ),
+        (Some(4), Some(9), Some(6))
+    );
+    assert_eq!(latency.decommit.max(), Some(5));
+    assert_eq!(buffer.get_ref().committed(), 0);
+
+    buffer.reset_latency();
+    assert_eq!(buffer.latency().commit.count(), 0);
+}
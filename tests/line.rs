@@ -0,0 +1,29 @@
+use staticbip::StaticBip;
+
+#[test]
+fn chunks_across_the_region_boundary() {
+    let mut buffer = StaticBip::<u8, 8>::default();
+    buffer.extend(b"xxxx$GP");
+    buffer.decommit(4);
+    buffer.extend(b"A*\n$");
+    assert_eq!(buffer.as_slices(), (&b"$GP"[..], &b"A*\n$"[..]));
+
+    let parts = buffer.read_until_with(b'\n', |a, b| (a.to_vec(), b.to_vec()));
+    assert_eq!(parts, Some((b"$GP".to_vec(), b"A*\n".to_vec())));
+    assert_eq!(buffer.as_slices(), (&b"$"[..], &b""[..]));
+    assert_eq!(buffer.read_line_with(|_, _| ()), None);
+}
+
+#[test]
+fn delimiter_at_end_of_first_region() {
+    let mut buffer = StaticBip::<u8, 8>::default();
+    buffer.extend(b"xxxxab\n");
+    buffer.decommit(4);
+    buffer.extend(b"cd\n");
+
+    let line = buffer.read_line_with(|a, b| (a.len(), b.len()));
+    assert_eq!(line, Some((3, 0)));
+    let line = buffer.read_line_with(|a, b| [a, b].concat());
+    assert_eq!(line.as_deref(), Some(&b"cd\n"[..]));
+    assert!(buffer.is_empty());
+}
@@ -0,0 +1,24 @@
+use staticbip::{local::LocalBip, StaticBip};
+
+#[test]
+fn produce_and_consume_through_shared_references() {
+    let buffer = LocalBip::<u8, 2>::new([0; 2]);
+    let producer = &buffer;
+    let consumer = &buffer;
+
+    assert_eq!(producer.push(1), Ok(()));
+    assert_eq!(producer.push(2), Ok(()));
+    assert_eq!(producer.push(3), Err(3));
+    assert_eq!(consumer.committed(), 2);
+    assert_eq!(consumer.pop(), Some(1));
+    assert_eq!(consumer.pop(), Some(2));
+    assert!(consumer.is_empty());
+}
+
+#[test]
+fn nested_access_is_refused() {
+    let buffer = LocalBip::from(StaticBip::<u8, 2>::default());
+    let nested = buffer.with(|_| buffer.try_with(|bip| bip.committed()));
+    assert_eq!(nested, None);
+    assert_eq!(buffer.try_with(|bip| bip.committed()), Some(0));
+}
@@ -0,0 +1,44 @@
+use staticbip::logger::{LogBuffer, Truncation};
+
+#[test]
+fn drops_records_that_do_not_fit() {
+    let mut log = LogBuffer::<16>::new();
+    write!(log, "0123456789").unwrap();
+    assert!(write!(log, "abcdef").is_err());
+    assert_eq!(log.dropped(), 1);
+
+    let mut records = Vec::new();
+    assert_eq!(log.drain(|record| records.push(record.to_vec())), 1);
+    assert_eq!(records, [b"0123456789"]);
+
+    write!(log, "abcdef").unwrap();
+    assert_eq!(log.drain(|record| assert_eq!(record, b"abcdef")), 1);
+}
+
+#[test]
+fn marker_ends_truncated_records() {
+    let mut log = LogBuffer::<15>::with_truncation(Truncation::Marker("~"));
+    assert_eq!(log.truncation(), Truncation::Marker("~"));
+    // Twelve bytes fit before the marker, so the cut falls inside the two-byte 'é'
+    let tail = "é and more";
+    write!(log, "abcdefghijk{}", tail).unwrap();
+    assert_eq!(log.truncated(), 1);
+    assert_eq!(
+        log.drain(|record| assert_eq!(record, "abcdefghijk~".as_bytes())),
+        1
+    );
+
+    // A failing Display impl is not a lack of space
+    struct Fails;
+    impl core::fmt::Display for Fails {
+        fn fmt(&self, _: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+            Err(core::fmt::Error)
+        }
+    }
+    assert!(write!(log, "{}", Fails).is_err());
+    assert_eq!((log.dropped(), log.truncated()), (1, 1));
+
+    log.set_truncation(Truncation::Drop);
+    assert!(write!(log, "{:20}", 1).is_err());
+    assert_eq!(log.dropped(), 2);
+}
@@ -0,0 +1,68 @@
+use staticbip::{channel::TryRecvError, duplex::Duplex, loopback::Loopback};
+
+#[test]
+fn full_receiver_holds_element_on_wire() {
+    let mut a = Duplex::<u8, 8, 8>::default();
+    let mut b = Duplex::<u8, 8, 2>::default();
+    let (mut a_app, mut a_driver) = a.split();
+    let (mut b_app, mut b_driver) = b.split();
+    let mut wire = Loopback::new();
+
+    for &byte in b"hello" {
+        a_app.sender().try_send(byte).unwrap();
+    }
+    b_app.sender().try_send(b'!').unwrap();
+
+    assert_eq!(wire.pump(&mut a_driver, &mut b_driver), 3);
+    assert_eq!(wire.in_flight(), 1);
+    assert_eq!(a_app.receiver().try_recv(), Ok(b'!'));
+
+    let mut received = Vec::new();
+    while received.len() < 5 {
+        wire.pump(&mut a_driver, &mut b_driver);
+        while let Ok(byte) = b_app.receiver().try_recv() {
+            received.push(byte);
+        }
+    }
+    assert_eq!(received, b"hello");
+    assert_eq!(wire.in_flight(), 0);
+    assert_eq!(wire.pump(&mut a_driver, &mut b_driver), 0);
+}
+
+#[test]
+fn chunks_fragment_across_wraps() {
+    let mut a = Duplex::<u32, 5, 5>::default();
+    let mut b = Duplex::<u32, 5, 5>::default();
+    let (mut a_app, mut a_driver) = a.split();
+    let (mut b_app, mut b_driver) = b.split();
+    let mut wire = Loopback::with_chunk(3);
+
+    let mut next = 0;
+    let mut expected = 0;
+    while expected < 40 {
+        while next < 40 && a_app.sender().try_send(next).is_ok() {
+            next += 1;
+        }
+        assert!(wire.pump(&mut a_driver, &mut b_driver) <= 3);
+        while let Ok(item) = b_app.receiver().try_recv() {
+            assert_eq!(item, expected);
+            expected += 1;
+        }
+    }
+}
+
+#[test]
+fn closed_receiver_drops_elements() {
+    let mut a = Duplex::<u8, 4, 4>::default();
+    let mut b = Duplex::<u8, 4, 4>::default();
+    let (mut a_app, mut a_driver) = a.split();
+    let (b_app, mut b_driver) = b.split();
+    let (_, b_rx) = b_app.into_parts();
+    drop(b_rx);
+
+    a_app.sender().try_send(1).unwrap();
+    let mut wire = Loopback::new();
+    assert_eq!(wire.pump(&mut a_driver, &mut b_driver), 0);
+    assert_eq!(wire.in_flight(), 0);
+    assert_eq!(a_driver.receiver().try_recv(), Err(TryRecvError::Empty));
+}
@@ -0,0 +1,39 @@
+#![cfg(all(
+    feature = "std",
+    target_os = "linux",
+    any(target_arch = "x86_64", target_arch = "aarch64")
+))]
+
+use staticbip::mirror::MirrorBip;
+
+#[test]
+fn blocks_wrap_contiguously() {
+    let mut buffer = MirrorBip::new(1).unwrap();
+    let capacity = buffer.capacity();
+    assert!(capacity >= 1 && capacity.is_power_of_two());
+
+    for round in 0..3u8 {
+        let len = buffer.reserve(capacity).len();
+        assert_eq!(len, capacity);
+        buffer.reserve(capacity - 1).fill(round);
+        buffer.commit(capacity - 1);
+        assert!(buffer.read().iter().all(|&byte| byte == round));
+        assert_eq!(buffer.reserve(capacity).len(), 1);
+        buffer.decommit(capacity - 1);
+    }
+    assert_eq!(buffer.committed(), 0);
+}
+
+#[test]
+fn second_mapping_aliases_first() {
+    let mut buffer = MirrorBip::new(4096).unwrap();
+    let capacity = buffer.capacity();
+    buffer.reserve(capacity - 1);
+    buffer.commit(capacity - 1);
+    buffer.decommit(capacity - 1);
+
+    buffer.reserve(3).copy_from_slice(&[7, 8, 9]);
+    buffer.commit(3);
+    buffer.decommit(1);
+    assert_eq!(buffer.read(), &[8, 9]);
+}
@@ -0,0 +1,73 @@
+use staticbip::mpmc::MpmcBip;
+
+#[test]
+fn each_record_is_taken_once() {
+    let mut queue = MpmcBip::<u8, 8, 4>::default();
+    let first = queue.claim(2).unwrap();
+    let second = queue.claim(2).unwrap();
+
+    queue.claimed(&second).copy_from_slice(&[3, 4]);
+    queue.publish(second, 2);
+    assert!(queue.take().is_none());
+
+    queue.claimed(&first).copy_from_slice(&[1, 2]);
+    queue.publish(first, 2);
+    let a = queue.take().unwrap();
+    let b = queue.take().unwrap();
+    assert!(queue.take().is_none());
+    assert_eq!(queue.job(&a), &[1, 2]);
+    assert_eq!(queue.job(&b), &[3, 4]);
+
+    queue.finish(b);
+    queue.finish(a);
+    assert!(queue.is_empty());
+    assert_eq!(queue.claim(8).unwrap().len(), 8);
+}
+
+#[test]
+fn space_is_released_in_claim_order() {
+    let mut queue = MpmcBip::<u8, 8, 4>::default();
+    for len in [3, 3] {
+        let claim = queue.claim(len).unwrap();
+        queue.publish(claim, len);
+    }
+    let slow = queue.take().unwrap();
+    let fast = queue.take().unwrap();
+
+    queue.finish(fast);
+    assert_eq!(queue.claim(8).unwrap().len(), 2);
+    queue.finish(slow);
+    // Only the claim made above remains
+    assert!(queue.take().is_none());
+    assert!(!queue.is_empty());
+}
+
+#[test]
+fn empty_records_are_skipped() {
+    let mut queue = MpmcBip::<u8, 8, 4>::default();
+    let discarded = queue.claim(4).unwrap();
+    let kept = queue.claim(2).unwrap();
+    queue.publish(discarded, 0);
+    queue.claimed(&kept).copy_from_slice(&[5, 6]);
+    queue.publish(kept, 1);
+
+    let job = queue.take().unwrap();
+    assert_eq!(queue.job(&job), &[5]);
+    queue.finish(job);
+    assert!(queue.is_empty());
+}
+
+#[test]
+fn record_limit() {
+    let mut queue = MpmcBip::<u8, 8, 2>::default();
+    let first = queue.claim(1).unwrap();
+    let second = queue.claim(1).unwrap();
+    assert!(queue.claim(1).is_none());
+
+    queue.publish(first, 1);
+    queue.publish(second, 1);
+    let job = queue.take().unwrap();
+    assert!(queue.claim(1).is_none());
+    queue.finish(job);
+    assert!(queue.claim(1).is_some());
+}
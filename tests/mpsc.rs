@@ -0,0 +1,74 @@
+use staticbip::mpsc::MpscBip;
+
+#[test]
+fn records_are_read_in_claim_order() {
+    let mut buffer = MpscBip::<u8, 8, 2>::default();
+    let first = buffer.claim(2).unwrap();
+    let second = buffer.claim(2).unwrap();
+
+    buffer.claimed(&second).copy_from_slice(&[3, 4]);
+    buffer.publish(second, 2);
+    assert!(buffer.read().is_empty());
+
+    buffer.claimed(&first).copy_from_slice(&[1, 2]);
+    buffer.publish(first, 2);
+    assert_eq!(buffer.read(), &[1, 2]);
+
+    buffer.decommit(1);
+    assert_eq!(buffer.read(), &[2]);
+    buffer.decommit(1);
+    assert_eq!(buffer.read(), &[3, 4]);
+}
+
+#[test]
+fn claim_limits() {
+    let mut buffer = MpscBip::<u8, 4, 2>::default();
+    let first = buffer.claim(1).unwrap();
+    let second = buffer.claim(8).unwrap();
+    assert_eq!(second.len(), 3);
+    assert!(buffer.claim(1).is_none());
+
+    buffer.publish(first, 1);
+    buffer.decommit(1);
+    assert!(buffer.claim(1).is_some());
+}
+
+#[test]
+fn unused_space_is_skipped() {
+    let mut buffer = MpscBip::<u8, 8, 4>::default();
+    let first = buffer.claim(4).unwrap();
+    let second = buffer.claim(2).unwrap();
+    let third = buffer.claim(2).unwrap();
+
+    buffer.claimed(&first)[0] = 1;
+    buffer.publish(first, 1);
+    buffer.publish(second, 0);
+    buffer.claimed(&third).copy_from_slice(&[2, 3]);
+    buffer.publish(third, 2);
+
+    assert_eq!(buffer.read(), &[1]);
+    buffer.decommit(1);
+    assert_eq!(buffer.read(), &[2, 3]);
+    buffer.decommit(2);
+    assert!(buffer.read().is_empty());
+    assert_eq!(buffer.claim(8).unwrap().len(), 8);
+}
+
+#[test]
+fn pipelined_claims_read_in_claim_order() {
+    let mut buffer = MpscBip::<u8, 8, 3>::default();
+    let first = buffer.claim(3).unwrap();
+    let second = buffer.claim(3).unwrap();
+    let first_ptr = buffer.claimed_ptr(&first);
+    let second_ptr = buffer.claimed_ptr(&second);
+    assert_eq!(second_ptr as usize - first_ptr as usize, 3);
+
+    buffer.claimed(&second).copy_from_slice(&[4, 5, 6]);
+    buffer.publish(second, 3);
+    buffer.claimed(&first).copy_from_slice(&[1, 2, 3]);
+    buffer.publish(first, 2);
+
+    assert_eq!(buffer.read(), &[1, 2]);
+    buffer.decommit(2);
+    assert_eq!(buffer.read(), &[4, 5, 6]);
+}
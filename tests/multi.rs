@@ -0,0 +1,41 @@
+use staticbip::multi::MultiBip;
+
+// Relies on the deterministic wrap choice, which the `chaos` feature randomizes
+#[cfg(not(feature = "chaos"))]
+#[test]
+fn channels_stay_aligned_across_the_wrap() {
+    let mut audio = MultiBip::<i16, 6, 3>::default();
+    audio.write_interleaved(&[1, 10, 100, 2, 20, 200, 3, 30, 300, 4, 40, 400]);
+    audio.decommit(3);
+
+    // Wraps to the start, in every channel
+    assert_eq!(audio.write_interleaved(&[5, 50, 500, 6, 60, 600, 7, 70]), 2);
+    for (index, channel) in audio.channels().iter().enumerate() {
+        let scale = 10i16.pow(index as u32);
+        assert_eq!(
+            channel.as_slices(),
+            (&[4 * scale][..], &[5 * scale, 6 * scale][..])
+        );
+    }
+
+    audio.decommit(1);
+    let [a, b, c] = audio.read();
+    assert_eq!(
+        (&a[..], &b[..], &c[..]),
+        (&[5, 6][..], &[50, 60][..], &[500, 600][..])
+    );
+}
+
+#[test]
+fn reservations_match_in_every_channel() {
+    let mut audio = MultiBip::<u8, 4, 2>::default();
+    let [left, right] = audio.reserve(8);
+    assert_eq!((left.len(), right.len()), (4, 4));
+    audio.commit(4);
+    assert_eq!((audio.committed(), audio.reserved()), (4, 0));
+    assert_eq!(audio.write_interleaved(&[1, 2]), 0);
+
+    audio.clear();
+    assert_eq!(audio.committed(), 0);
+    assert_eq!(MultiBip::<u8, 4, 0>::default().write_interleaved(&[1]), 0);
+}
@@ -0,0 +1,74 @@
+use staticbip::mutex::{MutexBip, NoopRawMutex, RawMutex, SpinRawMutex};
+use std::{
+    panic::{self, AssertUnwindSafe},
+    sync::atomic::{AtomicUsize, Ordering},
+    thread,
+};
+
+/// Counts how often the lock is taken.
+struct Counting(SpinRawMutex);
+
+static LOCKS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl RawMutex for Counting {
+    const INIT: Self = Self(SpinRawMutex::INIT);
+
+    fn lock<R>(&self, f: impl FnOnce() -> R) -> R {
+        LOCKS.fetch_add(1, Ordering::Relaxed);
+        self.0.lock(f)
+    }
+}
+
+#[test]
+fn threads_share_the_buffer_through_any_mutex() {
+    static BUFFER: MutexBip<Counting, u32, 8> = MutexBip::new([0; 8]);
+
+    let producers: Vec<_> = (0..2)
+        .map(|_| {
+            thread::spawn(|| {
+                for item in 1..=50 {
+                    while BUFFER.push(item).is_err() {
+                        thread::yield_now();
+                    }
+                }
+            })
+        })
+        .collect();
+    let mut sum = 0;
+    let mut received = 0;
+    while received < 100 {
+        match BUFFER.pop() {
+            Some(item) => {
+                sum += item;
+                received += 1;
+            }
+            None => thread::yield_now(),
+        }
+    }
+    for producer in producers {
+        producer.join().unwrap();
+    }
+    assert_eq!(sum, 2 * 1275);
+    assert!(LOCKS.load(Ordering::Relaxed) >= 200);
+}
+
+#[test]
+fn nested_access_panics_instead_of_aliasing() {
+    let buffer = MutexBip::<NoopRawMutex, u8, 4>::default();
+    let nested = panic::catch_unwind(AssertUnwindSafe(|| buffer.with(|_| buffer.push(1))));
+    assert!(nested.is_err());
+    buffer.push(2).unwrap();
+    assert_eq!(buffer.into_inner().read(), &[2]);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn std_mutex_survives_a_panic() {
+    use staticbip::mutex::StdRawMutex;
+
+    let buffer = MutexBip::<StdRawMutex, u8, 4>::default();
+    let panicked = panic::catch_unwind(AssertUnwindSafe(|| buffer.with(|_| panic!("oops"))));
+    assert!(panicked.is_err());
+    buffer.push(1).unwrap();
+    assert_eq!(buffer.pop(), Some(1));
+}
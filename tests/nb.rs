@@ -0,0 +1,26 @@
+use staticbip::{nb::WouldBlock, StaticBip};
+
+#[test]
+fn empty_and_full_would_block() {
+    let mut buffer = StaticBip::<u8, 4>::default();
+    let mut out = [0; 4];
+    assert_eq!(buffer.try_read(&mut out), Err(WouldBlock));
+    assert_eq!(buffer.try_read(&mut []), Ok(0));
+
+    assert_eq!(buffer.try_write(&[1, 2, 3, 4]), Ok(4));
+    assert_eq!(buffer.try_write(&[5]), Err(WouldBlock));
+    assert_eq!(buffer.try_write(&[]), Ok(0));
+
+    assert_eq!(buffer.try_read(&mut out[..3]), Ok(3));
+    assert_eq!(&out[..3], &[1, 2, 3]);
+}
+
+#[test]
+fn push_pop_wraps() {
+    let mut buffer = StaticBip::<u8, 2>::default();
+    for round in 0..4 {
+        assert_eq!(buffer.try_push(round), Ok(()));
+        assert_eq!(buffer.try_pop(), Ok(round));
+    }
+    assert_eq!(buffer.try_pop(), Err(WouldBlock));
+}
@@ -0,0 +1,55 @@
+#![cfg_attr(feature = "chaos", allow(unused_imports))]
+
+use staticbip::{
+    nb::WouldBlock,
+    overflow::{DebugPanic, Overwrite, Reject, Truncate},
+    StaticBip,
+};
+
+fn wrapped() -> StaticBip<u8, 8> {
+    let mut buffer = StaticBip::default();
+    buffer.extend(&[0, 0, 0, 0, 1, 2]);
+    buffer.decommit(4);
+    buffer
+}
+
+// Relies on the deterministic wrap choice, which the `chaos` feature randomizes
+#[cfg(not(feature = "chaos"))]
+#[test]
+fn writes_span_both_blocks() {
+    // Two slots after A, four before it
+    let mut buffer = wrapped();
+    assert_eq!(
+        buffer.write_slice::<Reject>(&[3, 4, 5, 6, 7]),
+        Err(WouldBlock)
+    );
+    assert_eq!(buffer.write_slice::<Reject>(&[3, 4, 5, 6]), Ok(4));
+    assert_eq!(
+        buffer.iter().copied().collect::<Vec<_>>(),
+        [1, 2, 3, 4, 5, 6]
+    );
+    assert_eq!(buffer.write_slice::<Truncate>(&[7, 8, 9]), Ok(0));
+}
+
+// Relies on the deterministic wrap choice, which the `chaos` feature randomizes
+#[cfg(not(feature = "chaos"))]
+#[test]
+fn overwrite_keeps_newest() {
+    let mut buffer = wrapped();
+    assert_eq!(buffer.write_slice::<Overwrite>(&[3, 4, 5, 6, 7, 8]), Ok(6));
+    assert_eq!(
+        buffer.iter().copied().collect::<Vec<_>>(),
+        [3, 4, 5, 6, 7, 8]
+    );
+
+    let data: Vec<u8> = (10..30).collect();
+    assert_eq!(buffer.write_slice::<Overwrite>(&data), Ok(8));
+    assert_eq!(buffer.iter().copied().collect::<Vec<_>>(), &data[12..]);
+}
+
+#[test]
+#[cfg_attr(debug_assertions, should_panic(expected = "does not fit"))]
+fn debug_panic() {
+    let mut buffer = wrapped();
+    assert!(buffer.write_slice::<DebugPanic>(&[0; 7]).is_err());
+}
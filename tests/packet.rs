@@ -0,0 +1,40 @@
+use staticbip::{
+    frame::{Ccitt, Crc16, Framed},
+    packet::{Packetizer, MORE},
+    StaticBip,
+};
+
+#[test]
+fn fragments_respect_the_mtu() {
+    let mut buffer = StaticBip::<u8, 64>::default();
+    let mut packetizer = Packetizer::<Crc16<Ccitt>, 64>::new(&mut buffer, 8);
+    assert_eq!(packetizer.max_fragment(), 3);
+    assert_eq!(packetizer.write(b"abcdefg"), 7);
+
+    let mut framed = Framed::<_, Crc16<Ccitt>>::new(&mut buffer);
+    for expected in [&[MORE, b'a', b'b', b'c'][..], b"\x01def", b"\x00g"] {
+        assert_eq!(framed.read().unwrap().unwrap(), expected);
+        framed.decommit();
+    }
+    assert_eq!(framed.read(), None);
+}
+
+#[test]
+fn partial_write_leaves_more_set() {
+    let mut buffer = StaticBip::<u8, 10>::default();
+    let mut packetizer = Packetizer::<(), 10>::new(&mut buffer, 6);
+    assert_eq!(packetizer.write(b"abcdefghij"), 4);
+    assert_eq!(packetizer.write(b""), 0);
+
+    let mut framed = Framed::<_>::new(&mut buffer);
+    assert_eq!(framed.read().unwrap().unwrap(), b"\x01abc");
+    framed.decommit();
+    assert_eq!(framed.read().unwrap().unwrap(), b"\x01d");
+}
+
+#[test]
+#[should_panic]
+fn mtu_must_fit_a_byte() {
+    let mut buffer = StaticBip::<u8, 8>::default();
+    Packetizer::<(), 8>::new(&mut buffer, 3);
+}
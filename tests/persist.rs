@@ -0,0 +1,85 @@
+use staticbip::persist::{NorFlash, PersistError, PersistentBip};
+
+/// NOR flash in RAM that counts erases.
+struct Flash {
+    bytes: [u8; 16],
+    erases: usize,
+}
+
+impl Flash {
+    fn new() -> Self {
+        Self {
+            bytes: [0xFF; 16],
+            erases: 0,
+        }
+    }
+}
+
+impl NorFlash for Flash {
+    type Error = ();
+
+    fn capacity(&self) -> usize {
+        self.bytes.len()
+    }
+
+    fn read(&mut self, offset: u32, bytes: &mut [u8]) -> Result<(), ()> {
+        let offset = offset as usize;
+        bytes.copy_from_slice(self.bytes.get(offset..offset + bytes.len()).ok_or(())?);
+        Ok(())
+    }
+
+    fn write(&mut self, offset: u32, bytes: &[u8]) -> Result<(), ()> {
+        let offset = offset as usize;
+        let cells = self.bytes.get_mut(offset..offset + bytes.len()).ok_or(())?;
+        for (cell, byte) in cells.iter_mut().zip(bytes) {
+            *cell &= byte;
+        }
+        Ok(())
+    }
+
+    fn erase(&mut self, from: u32, to: u32) -> Result<(), ()> {
+        self.bytes[from as usize..to as usize].fill(0xFF);
+        self.erases += 1;
+        Ok(())
+    }
+}
+
+#[test]
+fn pending_records_survive_reopen() {
+    let mut queue = PersistentBip::<_, 8>::open(Flash::new()).unwrap();
+    queue.get_mut().extend(b"ab");
+    assert_eq!(queue.flush(), Ok(2));
+    queue.get_mut().extend(b"cde");
+    assert_eq!(queue.flush(), Ok(3));
+
+    let mut out = [0; 4];
+    assert_eq!(queue.pop(&mut out), Ok(Some(2)));
+    queue.get_mut().extend(b"lost");
+
+    let mut queue = PersistentBip::<_, 8>::open(queue.into_flash()).unwrap();
+    assert_eq!(queue.pop(&mut out[..2]), Err(PersistError::Overflow));
+    assert_eq!(queue.pop(&mut out), Ok(Some(3)));
+    assert_eq!(&out[..3], b"cde");
+    assert_eq!(queue.pop(&mut out), Ok(None));
+}
+
+#[test]
+fn erases_once_everything_is_consumed() {
+    let mut queue = PersistentBip::<_, 16>::open(Flash::new()).unwrap();
+    queue.get_mut().extend(b"0123456789");
+    assert_eq!(queue.flush(), Ok(10));
+
+    // Only room for a header is left, and the first record is still pending
+    queue.get_mut().extend(b"abcdef");
+    assert_eq!(queue.flush(), Err(PersistError::Full));
+    assert_eq!(queue.get_ref().committed(), 6);
+
+    let mut out = [0; 16];
+    assert_eq!(queue.pop(&mut out), Ok(Some(10)));
+    assert_eq!(queue.flush(), Ok(6));
+    assert_eq!(queue.pop(&mut out), Ok(Some(6)));
+    assert_eq!(&out[..6], b"abcdef");
+
+    let flash = queue.into_flash();
+    assert_eq!(flash.erases, 1);
+}
@@ -0,0 +1,44 @@
+use staticbip::{
+    pingpong::{Half, Overrun},
+    StaticBip,
+};
+
+#[test]
+fn halves_alternate() {
+    let mut buffer = StaticBip::<u8, 8>::default();
+    buffer.reserve(8).copy_from_slice(&[1, 2, 3, 4, 5, 6, 7, 8]);
+    buffer.commit(8);
+    buffer.decommit(8);
+
+    buffer.commit_dma_half(Half::First).unwrap();
+    assert_eq!(buffer.read(), &[1, 2, 3, 4]);
+    buffer.commit_dma_half(Half::Second).unwrap();
+    assert_eq!(buffer.read(), &[1, 2, 3, 4, 5, 6, 7, 8]);
+    buffer.decommit(6);
+
+    buffer.commit_dma_half(Half::First).unwrap();
+    assert_eq!(buffer.read(), &[7, 8]);
+    buffer.decommit(2);
+    assert_eq!(buffer.read(), &[1, 2, 3, 4]);
+    buffer.decommit(4);
+
+    buffer.commit_dma_half(Half::Second).unwrap();
+    assert_eq!(buffer.read(), &[5, 6, 7, 8]);
+    #[cfg(feature = "stats")]
+    assert_eq!(buffer.total_written(), 24);
+}
+
+#[test]
+fn overrun_discards_unread_data() {
+    let mut buffer = StaticBip::<u8, 8>::default();
+    buffer.commit_dma_half(Half::First).unwrap();
+    buffer.commit_dma_half(Half::Second).unwrap();
+    buffer.decommit(2);
+
+    assert_eq!(buffer.commit_dma_half(Half::First), Err(Overrun));
+    assert_eq!(buffer.committed(), 4);
+    #[cfg(feature = "stats")]
+    assert_eq!(buffer.total_read(), 8);
+    buffer.commit_dma_half(Half::Second).unwrap();
+    assert_eq!(buffer.committed(), 8);
+}
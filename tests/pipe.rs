@@ -0,0 +1,70 @@
+use staticbip::StaticBip;
+use std::{
+    future::Future,
+    pin::pin,
+    task::{Context, Poll, Waker},
+};
+
+#[test]
+fn copies_across_regions_and_waits_for_io() {
+    let mut buffer = StaticBip::<u8, 4>::default();
+    buffer.extend(&[1, 2, 3]);
+    buffer.decommit(2);
+
+    let mut polls = 0;
+    let mut output = Vec::new();
+    {
+        let task = buffer.copy_into(|_, block| {
+            polls += 1;
+            // Every other poll is not ready
+            if polls % 2 == 1 {
+                return Poll::Pending;
+            }
+            output.extend_from_slice(block);
+            Poll::Ready(Ok::<_, ()>(block.len()))
+        });
+        let mut task = pin!(task);
+        let mut cx = Context::from_waker(Waker::noop());
+        assert_eq!(task.as_mut().poll(&mut cx), Poll::Pending);
+        assert_eq!(task.as_mut().poll(&mut cx), Poll::Ready(Ok(1)));
+    }
+    assert_eq!(output, [3]);
+
+    buffer.extend(&[4, 5, 6]);
+    buffer.decommit(1);
+    let mut input = [7, 8, 9].iter();
+    let task = buffer.copy_from(|_, reserved| {
+        Poll::Ready(match input.next() {
+            Some(&byte) => {
+                reserved[0] = byte;
+                Ok(1)
+            }
+            None => Err(()),
+        })
+    });
+    let mut cx = Context::from_waker(Waker::noop());
+    assert_eq!(pin!(task).poll(&mut cx), Poll::Ready(Ok(2)));
+    assert_eq!(buffer.as_slices(), (&[5, 6, 7][..], &[8][..]));
+}
+
+#[test]
+fn dropping_a_pending_copy_keeps_data_and_releases_the_reservation() {
+    let mut buffer = StaticBip::<u8, 8>::default();
+    let chunks: [&[u8]; 2] = [&[1, 2], &[3]];
+    let mut chunks = chunks.iter();
+    {
+        let task = buffer.copy_from(|_, reserved| match chunks.next() {
+            Some(chunk) => {
+                reserved[..chunk.len()].copy_from_slice(chunk);
+                Poll::Ready(Ok::<_, ()>(chunk.len()))
+            }
+            None => Poll::Pending,
+        });
+        let mut cx = Context::from_waker(Waker::noop());
+        assert_eq!(pin!(task).poll(&mut cx), Poll::Pending);
+    }
+    assert_eq!(buffer.reserved(), 0);
+    #[cfg(feature = "stats")]
+    assert_eq!(buffer.abandoned_reservations(), 0);
+    assert_eq!(buffer.read(), &[1, 2, 3]);
+}
@@ -0,0 +1,31 @@
+use staticbip::pool::BipPool;
+
+#[test]
+fn exhaustion_is_reported() {
+    let pool = BipPool::<u8, 4, 2>::default();
+    let first = pool.acquire().unwrap();
+    let _second = pool.acquire().unwrap();
+    assert_eq!(pool.available(), 0);
+    assert!(pool.acquire().is_none());
+
+    drop(first);
+    assert_eq!(pool.available(), 1);
+    assert!(pool.acquire().is_some());
+}
+
+#[test]
+fn buffers_are_handed_between_stages() {
+    let pool = BipPool::<u8, 4, 1>::default();
+
+    let mut filled = pool.acquire().unwrap();
+    filled.reserve(3).copy_from_slice(&[1, 2, 3]);
+    filled.commit(3);
+
+    let mut processing = filled;
+    assert_eq!(processing.read(), &[1, 2, 3]);
+    drop(processing);
+
+    let mut reused = pool.acquire().unwrap();
+    assert!(reused.is_empty());
+    assert!(reused.read().is_empty());
+}
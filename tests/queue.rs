@@ -0,0 +1,27 @@
+use staticbip::StaticBip;
+
+#[test]
+fn enqueue_non_copy() {
+    let mut buffer = StaticBip::new(<[String; 2]>::default());
+    assert_eq!(buffer.enqueue("a".into()), Ok(()));
+    assert_eq!(buffer.enqueue("b".into()), Ok(()));
+    assert_eq!(buffer.enqueue("c".into()), Err("c".to_string()));
+}
+
+#[test]
+fn fill_from_stops_when_full() {
+    let mut source = vec![1, 2, 3, 4, 5];
+    source.reverse();
+    let mut buffer = StaticBip::<u8, 3>::default();
+    assert_eq!(buffer.fill_from(|| source.pop()), 3);
+    assert_eq!(source, [5, 4]);
+
+    let mut sink = Vec::new();
+    let moved = buffer.drain_into(|item| {
+        sink.push(item);
+        Ok(())
+    });
+    assert_eq!(moved, 3);
+    assert_eq!(sink, [1, 2, 3]);
+    assert_eq!(buffer.dequeue(), None);
+}
@@ -0,0 +1,36 @@
+use staticbip::{replay::Replay, StaticBip};
+
+#[test]
+fn replays_unacknowledged_data_across_regions() {
+    let mut replay = Replay::<u8, 4>::default();
+    let bip = replay.get_mut();
+    bip.reserve(4).copy_from_slice(&[1, 2, 3, 4]);
+    bip.commit(4);
+    bip.decommit(2);
+    bip.reserve(2).copy_from_slice(&[5, 6]);
+    bip.commit(2);
+
+    assert_eq!(replay.peek(4), &[3, 4]);
+    assert_eq!(replay.peek(4), &[5, 6]);
+    assert_eq!(replay.peek(4), &[]);
+    assert_eq!(replay.in_flight(), 4);
+
+    replay.ack(3);
+    assert_eq!(replay.in_flight(), 1);
+    replay.nack();
+    assert_eq!(replay.peek(4), &[6]);
+    replay.ack(usize::MAX);
+    assert!(replay.get_ref().is_empty());
+}
+
+#[test]
+fn decommit_through_buffer_shrinks_in_flight() {
+    let mut replay = Replay::new(StaticBip::<u8, 4>::default());
+    replay.get_mut().reserve(3).copy_from_slice(&[1, 2, 3]);
+    replay.get_mut().commit(3);
+    assert_eq!(replay.peek(2), &[1, 2]);
+
+    replay.get_mut().clear();
+    assert_eq!(replay.in_flight(), 0);
+    assert_eq!(replay.peek(2), &[]);
+}
@@ -0,0 +1,65 @@
+use staticbip::StaticBip;
+
+#[test]
+fn commit_consumes_reservation() {
+    let mut buffer = StaticBip::<u8, 4>::default();
+    let mut reserved = buffer.reservation(8);
+    assert_eq!(reserved.len(), 4);
+    reserved[..2].copy_from_slice(&[1, 2]);
+    reserved.commit(2);
+    assert_eq!(buffer.read(), &[1, 2]);
+    assert_eq!(buffer.reserved(), 0);
+
+    buffer.reservation(2).commit_all();
+    assert_eq!(buffer.committed(), 4);
+}
+
+#[test]
+fn drop_clears_reservation() {
+    let mut buffer = StaticBip::<u8, 4>::default();
+    {
+        let mut reserved = buffer.reservation(3);
+        reserved.copy_from_slice(&[1, 2, 3]);
+    }
+    assert_eq!(buffer.reserved(), 0);
+    assert!(buffer.is_empty());
+}
+
+#[test]
+fn writer_commits_what_was_written() {
+    let mut buffer = StaticBip::<u8, 8>::default();
+    let mut writer = buffer.reservation_writer(4);
+    assert_eq!(writer.write(&[1, 2]), 2);
+    assert_eq!(writer.write(&[3, 4, 5]), 2);
+    assert_eq!(writer.write(&[6]), 0);
+    assert_eq!((writer.written(), writer.remaining()), (4, 0));
+    assert_eq!(writer.finish(), 4);
+    assert_eq!(buffer.read(), &[1, 2, 3, 4]);
+
+    {
+        let mut writer = buffer.reservation_writer(2);
+        writer.write(&[7]);
+    }
+    assert_eq!(buffer.reserved(), 0);
+    assert_eq!(buffer.committed(), 4);
+
+    buffer.reservation_writer(4).finish();
+    assert_eq!(buffer.committed(), 4);
+}
+
+#[test]
+fn writer_backpatches_before_commit() {
+    let mut buffer = StaticBip::<u8, 8>::default();
+    let mut writer = buffer.reservation_writer(6);
+    writer.set_position(2);
+    assert_eq!(writer.write(b"abcdef"), 4);
+    assert_eq!((writer.written(), writer.remaining()), (6, 0));
+
+    writer.set_position(0);
+    assert_eq!(writer.write(&[4, 0]), 2);
+    assert_eq!((writer.position(), writer.written()), (2, 6));
+    writer.set_position(9);
+    assert_eq!(writer.write(&[1]), 0);
+    assert_eq!(writer.finish(), 6);
+    assert_eq!(buffer.read(), &[4, 0, b'a', b'b', b'c', b'd']);
+}
@@ -0,0 +1,28 @@
+use staticbip::retained::{Retained, MAGIC};
+use std::mem::MaybeUninit;
+
+#[test]
+fn recovers_only_matching_state() {
+    let mut slot = MaybeUninit::<Retained<u8, 8>>::zeroed();
+    let (log, recovered) = unsafe { Retained::recover(&mut slot, 0) };
+    assert!(!recovered);
+    log.with(|bip| {
+        bip.extend(b"abc");
+        bip.reserve(2);
+    });
+
+    let (log, recovered) = unsafe { Retained::recover(&mut slot, 0) };
+    assert!(recovered);
+    assert_eq!(log.get_ref().committed(), 3);
+    assert_eq!(log.get_ref().reserved(), 0);
+
+    // Corrupt the checksum that follows the magic value
+    unsafe {
+        let words = slot.as_mut_ptr().cast::<u32>();
+        assert_eq!(words.read(), MAGIC);
+        words.add(1).write(!words.add(1).read());
+    }
+    let (log, recovered) = unsafe { Retained::recover(&mut slot, 0) };
+    assert!(!recovered);
+    assert!(log.get_ref().is_empty());
+}
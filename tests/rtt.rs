@@ -0,0 +1,53 @@
+use core::{ffi::CStr, mem::size_of, ptr};
+use staticbip::rtt::{ControlBlock, Mode};
+
+/// Reads a `u32` field of the control block the way a debug probe does.
+unsafe fn field(base: *const u8, offset: usize) -> u32 {
+    ptr::read_volatile(base.add(offset).cast::<u32>())
+}
+
+#[test]
+fn host_drains_the_channel_through_the_rtt_layout() {
+    let mut block = Box::new(ControlBlock::<8>::new());
+    let base = &*block as *const ControlBlock<8> as *const u8;
+    let up = 24;
+    let size = up + 2 * size_of::<usize>();
+    let (write, read, flags) = (size + 4, size + 8, size + 12);
+    unsafe {
+        assert_eq!(ptr::read_volatile(base.cast::<[u8; 16]>()), [0; 16]);
+    }
+
+    let name = CStr::from_bytes_with_nul(b"Terminal\0").unwrap();
+    let mut channel = block.init(name);
+    channel.set_mode(Mode::NoBlockTrim);
+    assert_eq!(channel.write(b"abcdefghij"), 7);
+
+    unsafe {
+        assert_eq!(
+            &ptr::read_volatile(base.cast::<[u8; 16]>())[..11],
+            b"SEGGER RTT\0"
+        );
+        assert_eq!((field(base, 16), field(base, 20)), (1, 0));
+        let name_ptr = ptr::read_volatile(base.add(up).cast::<*const u8>());
+        assert_eq!(CStr::from_ptr(name_ptr.cast()), name);
+        assert_eq!(field(base, size), 8);
+        assert_eq!((field(base, write), field(base, read)), (7, 0));
+        assert_eq!(field(base, flags), 1);
+
+        // The host reads five bytes and advances the read offset
+        let buffer = ptr::read_volatile(base.add(up + size_of::<usize>()).cast::<*const u8>());
+        assert_eq!(std::slice::from_raw_parts(buffer, 5), b"abcde");
+        ptr::write_volatile(base.add(read) as *mut u32, 5);
+    }
+
+    channel.set_mode(Mode::NoBlockSkip);
+    assert_eq!(channel.write(b"klmnop"), 0);
+    assert_eq!(channel.write(b"klm"), 3);
+    assert_eq!(channel.pending(), 5);
+    unsafe {
+        // The write wrapped at the end of the buffer
+        assert_eq!(field(base, write), 2);
+        let buffer = ptr::read_volatile(base.add(up + size_of::<usize>()).cast::<*const u8>());
+        assert_eq!(std::slice::from_raw_parts(buffer, 2), b"lm");
+    }
+}
@@ -0,0 +1,31 @@
+#![cfg(all(feature = "shadow", debug_assertions))]
+
+use staticbip::{shadow::Shadowed, BipBuf, StaticBip};
+
+#[test]
+fn wrapping_traffic_matches_the_model() {
+    let mut buffer = Shadowed::new(StaticBip::<u16, 7>::default());
+    let mut next = 0;
+    for round in 0..200 {
+        let reserved = buffer.reserve(round % 5 + 1);
+        for slot in reserved.iter_mut() {
+            *slot = next;
+            next += 1;
+        }
+        let len = reserved.len();
+        buffer.commit(len - len / 3);
+        let read = buffer.read().len();
+        buffer.decommit(read.min(round % 4));
+    }
+    assert!(buffer.get_ref().committed() > 0);
+}
+
+#[test]
+#[should_panic(expected = "reserve diverged from the model")]
+fn changed_committed_data_is_detected() {
+    let mut buffer = Shadowed::new(StaticBip::<u8, 4>::default());
+    buffer.reserve(2).copy_from_slice(&[1, 2]);
+    buffer.commit(2);
+    buffer.read()[0] = 9;
+    buffer.reserve(1);
+}
@@ -0,0 +1,66 @@
+#![cfg(all(
+    feature = "std",
+    target_os = "linux",
+    any(target_arch = "x86_64", target_arch = "aarch64")
+))]
+
+use staticbip::{bbqueue::Error, shm::SharedQueue};
+use std::{io, path::PathBuf};
+
+fn path(name: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("staticbip-{}-{}", name, std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    path
+}
+
+#[test]
+fn halves_in_separate_mappings_share_data() {
+    let path = path("share");
+    let created = SharedQueue::<8>::create(&path).unwrap();
+    // A second mapping of the file stands in for another process
+    let opened = SharedQueue::<8>::open(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    let mut producer = created.producer().unwrap();
+    assert_eq!(opened.producer().err(), Some(Error::AlreadySplit));
+    let mut consumer = opened.consumer().unwrap();
+    assert!(created.consumer().is_err());
+
+    let data: Vec<u8> = (0..20).collect();
+    let received = std::thread::scope(|scope| {
+        let thread = scope.spawn(move || {
+            let mut received = Vec::new();
+            while received.len() < 20 {
+                match consumer.read() {
+                    Ok(grant) => {
+                        received.extend_from_slice(&grant);
+                        let len = grant.len();
+                        grant.release(len);
+                    }
+                    Err(_) => std::thread::yield_now(),
+                }
+            }
+            received
+        });
+        producer.write_all(&data, std::thread::yield_now);
+        thread.join().unwrap()
+    });
+    assert_eq!(received, data);
+    assert_eq!(producer.sequence(), 20);
+}
+
+#[test]
+fn open_checks_the_file() {
+    let path = path("check");
+    assert_eq!(
+        SharedQueue::<8>::open(&path).unwrap_err().kind(),
+        io::ErrorKind::NotFound
+    );
+    let _queue = SharedQueue::<8>::create(&path).unwrap();
+    assert!(SharedQueue::<8>::create(&path).is_err());
+    assert_eq!(
+        SharedQueue::<16>::open(&path).unwrap_err().kind(),
+        io::ErrorKind::InvalidData
+    );
+    std::fs::remove_file(&path).unwrap();
+}
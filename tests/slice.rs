@@ -0,0 +1,91 @@
+use core::ops::Range;
+use staticbip::{SliceBip, Span};
+
+#[test]
+fn wraps_like_static() {
+    let mut storage = [0u8; 4];
+    let mut buffer = SliceBip::new(&mut storage);
+    buffer.reserve(4).copy_from_slice(&[1, 2, 3, 4]);
+    buffer.commit(4);
+    buffer.decommit(2);
+
+    buffer.reserve(4).copy_from_slice(&[5, 6]);
+    buffer.commit(2);
+    assert_eq!(buffer.read(), &[3, 4]);
+
+    buffer.decommit(2);
+    assert_eq!(buffer.read(), &[5, 6]);
+    assert_eq!(buffer.into_inner(), &[5, 6, 3, 4]);
+}
+
+#[test]
+fn from_raw_parts() {
+    let mut storage = [0u32; 8];
+    let mut buffer = unsafe { SliceBip::from_raw_parts(storage.as_mut_ptr(), storage.len()) };
+    assert_eq!(buffer.capacity(), 8);
+    assert_eq!(buffer.reserve_max().len(), 8);
+    buffer.commit(3);
+    assert_eq!(buffer.committed(), 3);
+    assert_eq!(buffer.pop(), Some(&mut 0));
+}
+
+#[test]
+fn raw_parts_describe_regions() {
+    let mut storage = [0u8; 8];
+    let base = storage.as_mut_ptr();
+    let mut buffer = SliceBip::new(&mut storage);
+    buffer.reserve(8).copy_from_slice(&[1, 2, 3, 4, 5, 6, 7, 8]);
+    buffer.commit(8);
+    buffer.decommit(6);
+    buffer.reserve(3);
+
+    let parts = buffer.as_raw_parts();
+    assert_eq!(parts.ptr, base);
+    assert_eq!(parts.capacity, 8);
+    assert_eq!(parts.state.a, Span { offset: 6, len: 2 });
+    assert_eq!(parts.state.reserve, Span { offset: 0, len: 3 });
+
+    let a: Range<usize> = parts.state.a.into();
+    let committed = unsafe { core::slice::from_raw_parts(parts.ptr.add(a.start), a.len()) };
+    assert_eq!(committed, &[7, 8]);
+}
+
+#[test]
+fn partition_shares_one_array() {
+    let mut storage = [0u8; 10];
+    let [mut rx, mut tx, empty] = SliceBip::partition(&mut storage, [4, 4, 0]);
+    assert_eq!(empty.capacity(), 0);
+
+    rx.reserve_max().fill(1);
+    rx.commit(4);
+    tx.reserve(8).copy_from_slice(&[2, 3, 4, 5]);
+    tx.commit(4);
+    assert_eq!(rx.read(), &[1; 4]);
+    assert_eq!(tx.read(), &[2, 3, 4, 5]);
+
+    assert_eq!(storage, [1, 1, 1, 1, 2, 3, 4, 5, 0, 0]);
+}
+
+#[test]
+#[should_panic(expected = "partition lengths exceed the storage")]
+fn partition_checks_lengths() {
+    let mut storage = [0u8; 4];
+    SliceBip::partition(&mut storage, [2, usize::MAX]);
+}
+
+#[test]
+fn state_describes_regions() {
+    let mut storage = [0u8; 8];
+    let mut buffer = SliceBip::new(&mut storage);
+    buffer.reserve(8);
+    buffer.commit(8);
+    buffer.decommit(6);
+    buffer.reserve(3);
+    buffer.commit(3);
+    buffer.reserve(2);
+
+    let state = buffer.state();
+    assert_eq!(state.a, Span { offset: 6, len: 2 });
+    assert_eq!(state.b, Span { offset: 0, len: 3 });
+    assert_eq!(state.reserve, Span { offset: 3, len: 2 });
+}
@@ -0,0 +1,52 @@
+#![cfg_attr(feature = "chaos", allow(unused_imports))]
+
+use staticbip::{
+    nb::WouldBlock,
+    slip::{encoded_len, Slip, SlipError, END, ESC, ESC_END, ESC_ESC},
+    StaticBip,
+};
+
+#[test]
+fn escapes_special_bytes() {
+    let mut buffer = StaticBip::<u8, 16>::default();
+    let data = [END, 1, ESC];
+    assert_eq!(encoded_len(&data), 6);
+    Slip::new(&mut buffer).write(&data).unwrap();
+    assert_eq!(buffer.read(), &[ESC, ESC_END, 1, ESC, ESC_ESC, END]);
+
+    let mut out = [0; 4];
+    assert_eq!(Slip::new(&mut buffer).read(&mut out), Some(Ok(3)));
+    assert_eq!(out[..3], data);
+}
+
+// Relies on the deterministic wrap choice, which the `chaos` feature randomizes
+#[cfg(not(feature = "chaos"))]
+#[test]
+fn decodes_across_regions_and_reports_errors() {
+    let mut buffer = StaticBip::<u8, 8>::default();
+    buffer.reserve(7).copy_from_slice(&[9, 9, 9, 9, END, 1, 2]);
+    buffer.commit(7);
+    let mut out = [0; 3];
+    assert_eq!(
+        Slip::new(&mut buffer).read(&mut out),
+        Some(Err(SlipError::Overflow))
+    );
+
+    // The rest of the packet wraps to the start
+    buffer.reserve(2).copy_from_slice(&[3, END]);
+    buffer.commit(2);
+    assert_eq!(Slip::new(&mut buffer).read(&mut out), Some(Ok(3)));
+    assert_eq!(out, [1, 2, 3]);
+
+    let mut slip = Slip::new(&mut buffer);
+    assert_eq!(slip.write(&[0; 8]), Err(WouldBlock));
+    slip.write(&[ESC, 1]).unwrap();
+    assert_eq!(slip.read(&mut out), Some(Ok(2)));
+
+    buffer.reserve(3).copy_from_slice(&[ESC, 1, END]);
+    buffer.commit(3);
+    let mut slip = Slip::new(&mut buffer);
+    assert_eq!(slip.read(&mut out), Some(Err(SlipError::Escape)));
+    assert_eq!(slip.read(&mut out), None);
+    assert!(buffer.is_empty());
+}
@@ -0,0 +1,35 @@
+use staticbip::slot::SlotBip;
+
+#[test]
+fn slots_wrap_around() {
+    let mut slots = SlotBip::<u16, 3, 2>::default();
+    assert_eq!(slots.capacity(), 2);
+
+    slots.push_slot([1, 2, 3]).unwrap();
+    slots.push_slot([4, 5, 6]).unwrap();
+    assert!(slots.is_full());
+    assert!(slots.reserve_slot().is_none());
+
+    slots.decommit_slot();
+    slots.push_slot([7, 8, 9]).unwrap();
+    assert_eq!(slots.pop_slot(), Some(&mut [4, 5, 6]));
+    assert_eq!(slots.pop_slot(), Some(&mut [7, 8, 9]));
+    assert_eq!(slots.pop_slot(), None);
+    assert!(slots.is_empty());
+}
+
+#[test]
+fn commit_needs_a_reservation() {
+    let mut slots = SlotBip::<u8, 2, 2>::default();
+    slots.commit_slot();
+    assert_eq!(slots.len(), 0);
+
+    *slots.reserve_slot().unwrap() = [1, 2];
+    slots.commit_slot();
+    slots.commit_slot();
+    assert_eq!(slots.len(), 1);
+    assert_eq!(slots.read_slot(), Some(&[1, 2]));
+
+    slots.clear();
+    assert_eq!(slots.read_slot(), None);
+}
@@ -0,0 +1,52 @@
+use staticbip::{socket::BufferedSocket, StaticBip};
+
+#[derive(Debug, PartialEq)]
+enum NbError {
+    WouldBlock,
+}
+
+#[test]
+fn flush_sends_both_regions_and_reports_a_busy_stack() {
+    let mut socket =
+        BufferedSocket::new(StaticBip::<u8, 4>::default(), StaticBip::<u8, 8>::default());
+    assert_eq!(socket.write(b"abcdefgh"), Ok(8));
+    assert_eq!(
+        socket.flush(|block| Ok::<_, NbError>(block.len().min(5))),
+        Ok(5)
+    );
+    assert_eq!(socket.write(b"ijkl"), Ok(4));
+    assert_eq!(socket.unsent(), 7);
+
+    assert_eq!(
+        socket.flush(|_| Err(NbError::WouldBlock)),
+        Err(NbError::WouldBlock)
+    );
+
+    let mut blocks = Vec::new();
+    let sent = socket.flush(|block| {
+        blocks.push(block.to_vec());
+        Ok::<_, NbError>(block.len())
+    });
+    assert_eq!(sent, Ok(7));
+    assert_eq!(blocks, [b"fgh".to_vec(), b"ijkl".to_vec()]);
+    assert_eq!(socket.unsent(), 0);
+}
+
+#[test]
+fn fill_keeps_data_received_before_an_error() {
+    let mut socket =
+        BufferedSocket::new(StaticBip::<u8, 4>::default(), StaticBip::<u8, 4>::default());
+    let mut chunks = vec![Ok(&b"abcd"[..]), Err(NbError::WouldBlock)].into_iter();
+    let mut receive = |buf: &mut [u8]| {
+        let chunk = chunks.next().unwrap()?;
+        buf[..chunk.len()].copy_from_slice(chunk);
+        Ok(chunk.len())
+    };
+    assert_eq!(socket.fill(&mut receive), Ok(4));
+
+    let mut data = [0; 4];
+    assert_eq!(socket.read(&mut data[..2]), Ok(2));
+    assert_eq!(socket.fill(&mut receive), Err(NbError::WouldBlock));
+    assert_eq!(socket.read(&mut data), Ok(2));
+    assert_eq!(&data[..2], b"cd");
+}
@@ -0,0 +1,41 @@
+#![cfg(feature = "alloc")]
+
+use staticbip::{spill::Spillover, StaticBip};
+
+#[test]
+fn keeps_order_while_spilled() {
+    let mut buffer = Spillover::new(StaticBip::<u32, 4>::default());
+    assert_eq!(buffer.write(&[0, 1, 2]), 0);
+    assert_eq!(buffer.write(&[3, 4, 5]), 2);
+    buffer.decommit(1);
+
+    // Refilled from the heap, so later writes still queue behind the spilled element
+    assert_eq!(buffer.spilled(), 1);
+    assert_eq!(buffer.write(&[6]), 1);
+    assert_eq!(buffer.len(), 6);
+
+    let mut read = Vec::new();
+    while let Some(item) = buffer.pop() {
+        read.push(item);
+    }
+    assert_eq!(read, [1, 2, 3, 4, 5, 6]);
+    assert!(buffer.is_empty());
+
+    // Back on the static path
+    assert_eq!(buffer.write(&[7, 8]), 0);
+    let (bip, spill) = buffer.into_parts();
+    assert_eq!(bip.committed(), 2);
+    assert!(spill.is_empty());
+}
+
+#[test]
+fn refills_across_the_wrap() {
+    let mut buffer = Spillover::<u8, 6>::default();
+    buffer.write(&[0, 1, 2, 3, 4, 5, 6, 7, 8, 9]);
+    buffer.decommit(4);
+    assert_eq!(buffer.spilled(), 0);
+    assert_eq!(
+        buffer.get_ref().iter().copied().collect::<Vec<_>>(),
+        [4, 5, 6, 7, 8, 9]
+    );
+}
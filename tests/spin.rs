@@ -0,0 +1,79 @@
+use staticbip::spin::{Loop, Relax, SpinBip};
+use std::{
+    panic::{self, AssertUnwindSafe},
+    sync::atomic::{AtomicUsize, Ordering},
+    thread,
+};
+
+#[test]
+fn threads_share_the_buffer() {
+    static BUFFER: SpinBip<u32, 16> = SpinBip::new([0; 16]);
+
+    let producers: Vec<_> = (0..4)
+        .map(|_| {
+            thread::spawn(|| {
+                for item in 1..=100 {
+                    while BUFFER.push(item).is_err() {
+                        thread::yield_now();
+                    }
+                }
+            })
+        })
+        .collect();
+
+    let mut sum = 0;
+    let mut received = 0;
+    while received < 400 {
+        match BUFFER.pop() {
+            Some(item) => {
+                sum += item;
+                received += 1;
+            }
+            None => thread::yield_now(),
+        }
+    }
+    for producer in producers {
+        producer.join().unwrap();
+    }
+    assert_eq!(sum, 4 * 5050);
+    assert!(BUFFER.is_empty());
+}
+
+#[test]
+fn try_with_fails_while_locked() {
+    let buffer = SpinBip::<u8, 4, Loop>::default();
+    buffer.with(|_| assert!(buffer.try_with(|_| ()).is_none()));
+    assert_eq!(buffer.try_with(|bip| bip.enqueue(1)), Some(Ok(())));
+    assert_eq!(buffer.into_inner().read(), &[1]);
+}
+
+#[test]
+fn waiting_relaxes_and_panics_unlock() {
+    static RELAXED: AtomicUsize = AtomicUsize::new(0);
+
+    struct Count;
+
+    impl Relax for Count {
+        fn relax() {
+            RELAXED.fetch_add(1, Ordering::Relaxed);
+            thread::yield_now();
+        }
+    }
+
+    let buffer = SpinBip::<u8, 4, Count>::default();
+    thread::scope(|scope| {
+        buffer.with(|bip| {
+            let waiting = scope.spawn(|| buffer.push(2));
+            while RELAXED.load(Ordering::Relaxed) == 0 {
+                thread::yield_now();
+            }
+            bip.enqueue(1).unwrap();
+            waiting
+        });
+    });
+    assert_eq!(buffer.committed(), 2);
+
+    let result = panic::catch_unwind(AssertUnwindSafe(|| buffer.with(|_| panic!("in with"))));
+    assert!(result.is_err());
+    assert_eq!(buffer.pop(), Some(1));
+}
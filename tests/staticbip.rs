@@ -1,4 +1,10 @@
-use staticbip::StaticBip;
+#![cfg_attr(feature = "chaos", allow(unused_imports))]
+
+use core::mem::MaybeUninit;
+use staticbip::{
+    align::{Aligned, A16},
+    copy_between, BipBuf, BipBytes, BipState, CommitError, Span, StaticBip, WrapPolicy,
+};
 
 #[test]
 fn read_empty() {
@@ -59,6 +65,50 @@ fn decommit() {
     assert_eq!(buffer.read(), &[4]);
 }
 
+#[test]
+fn consume_continues_into_second_region() {
+    let mut buffer = StaticBip::<u8, 4>::default();
+    buffer.reserve(4).copy_from_slice(&[1, 2, 3, 4]);
+    buffer.commit(4);
+    buffer.decommit(2);
+    buffer.reserve(2).copy_from_slice(&[5, 6]);
+    buffer.commit(2);
+
+    buffer.consume(3);
+    assert_eq!(buffer.read(), &[6]);
+    #[cfg(feature = "stats")]
+    assert_eq!(buffer.total_read(), 5);
+    buffer.consume(1);
+    assert!(buffer.is_empty());
+}
+
+#[test]
+fn retain_recent_cuts_across_regions() {
+    let mut buffer = StaticBip::<u8, 6>::default();
+    buffer.extend(&[1, 2, 3, 4, 5, 6]);
+    buffer.decommit(3);
+    buffer.extend(&[7, 8]);
+    assert_eq!(buffer.as_slices(), (&[4, 5, 6][..], &[7, 8][..]));
+
+    assert_eq!(buffer.retain_recent(6), 0);
+    assert_eq!(buffer.retain_recent(1), 4);
+    assert_eq!(buffer.read(), &[8]);
+    assert_eq!(buffer.retain_recent(0), 1);
+    assert!(buffer.is_empty());
+}
+
+// Relies on clamping, which the `strict` feature turns into panics
+#[cfg(not(feature = "strict"))]
+#[test]
+fn consume_stops_at_committed() {
+    let mut buffer = StaticBip::<u8, 4>::default();
+    buffer.extend(&[1, 2]);
+    buffer.consume(5);
+    assert!(buffer.is_empty());
+    #[cfg(feature = "stats")]
+    assert_eq!(buffer.total_read(), 2);
+}
+
 #[test]
 fn reserve_after_full_cycle() {
     let mut buffer = StaticBip::<u8, 4>::default();
@@ -106,3 +156,1043 @@ fn pop() {
         assert_eq!(i, buffer.pop().copied().unwrap());
     }
 }
+
+#[test]
+fn generic_over_bipbuf() {
+    fn produce(buffer: &mut impl BipBuf<u8>, data: &[u8]) -> usize {
+        let reserved = buffer.reserve(data.len());
+        let len = reserved.len();
+        reserved.copy_from_slice(&data[..len]);
+        buffer.commit(len);
+        len
+    }
+
+    let mut buffer = StaticBip::<u8, 4>::default();
+    assert_eq!(produce(&mut buffer, &[1, 2, 3]), 3);
+    assert_eq!(produce(&mut &mut buffer, &[4, 5]), 1);
+    assert_eq!(buffer.read(), &[1, 2, 3, 4]);
+}
+
+#[test]
+fn bytes_through_trait_objects() {
+    let mut buffer = StaticBip::<u8, 6>::default();
+    buffer.extend(&[0, 0, 0, 7, 8, 9]);
+    buffer.decommit(3);
+
+    // Only the space before the data is free
+    let bytes: &mut dyn BipBytes = &mut buffer;
+    assert_eq!(bytes.write_bytes(&[1, 2, 3, 4, 5]), 3);
+    let mut out = [0; 8];
+    assert_eq!(bytes.read_bytes(&mut out), 6);
+    assert_eq!(out[..6], [7, 8, 9, 1, 2, 3]);
+    assert_eq!(bytes.read_bytes(&mut out), 0);
+
+    let mut words = StaticBip::<u32, 2>::default();
+    let erased: &mut dyn BipBuf<u32> = &mut words;
+    erased.reserve(1)[0] = 7;
+    erased.commit(1);
+    assert_eq!(words.read(), &[7]);
+}
+
+#[test]
+fn copy_between_uses_both_regions() {
+    let mut src = StaticBip::<u8, 6>::default();
+    src.extend(&[0, 0, 0, 0, 1, 2]);
+    src.decommit(4);
+    src.extend(&[3, 4, 5]);
+    assert_eq!(src.read(), &[1, 2]);
+
+    let mut dst = StaticBip::<u8, 4>::default();
+    dst.extend(&[0, 0, 0]);
+    dst.decommit(3);
+
+    assert_eq!(copy_between(&mut src, &mut dst), 4);
+    assert!(dst.iter().eq(&[1, 2, 3, 4]));
+    assert!(src.iter().eq(&[5]));
+    assert_eq!(copy_between(&mut src, &mut dst), 0);
+
+    dst.clear();
+    assert_eq!(copy_between(&mut src, &mut &mut dst), 1);
+    assert!(src.is_empty());
+}
+
+#[test]
+fn reserve_aligned_after_data() {
+    let mut buffer = Aligned::<A16, StaticBip<u8, 16>>::default();
+    buffer.reserve(3).copy_from_slice(&[1, 2, 3]);
+    buffer.commit(3);
+
+    let reserved = buffer.reserve_aligned(8, 4);
+    assert_eq!(reserved.as_ptr() as usize % 4, 0);
+    assert_eq!(reserved.len(), 8);
+    reserved[..2].copy_from_slice(&[4, 5]);
+    buffer.commit(2);
+
+    assert_eq!(buffer.committed(), 6);
+    assert_eq!(buffer.read()[..3], [1, 2, 3]);
+    assert_eq!(buffer.read()[4..], [4, 5]);
+}
+
+#[test]
+fn reserve_aligned_new_region() {
+    let mut buffer = Aligned::<A16, StaticBip<u8, 16>>::default();
+    buffer.reserve(1).copy_from_slice(&[1]);
+    buffer.commit(1);
+    assert_eq!(buffer.pop(), Some(&mut 1));
+
+    buffer.reserve_aligned(4, 8).copy_from_slice(&[2, 3, 4, 5]);
+    buffer.commit(4);
+    assert_eq!(buffer.committed(), 4);
+    assert_eq!(buffer.read(), &[2, 3, 4, 5]);
+    assert_eq!(buffer.read().as_ptr() as usize % 8, 0);
+}
+
+#[test]
+fn reserve_aligned_without_room() {
+    let mut buffer = Aligned::<A16, StaticBip<u8, 16>>::default();
+    buffer.reserve(14).fill(0);
+    buffer.commit(14);
+    assert!(buffer.reserve_aligned(1, 16).is_empty());
+}
+
+// Relies on clamping, which the `strict` feature turns into panics
+#[cfg(not(feature = "strict"))]
+#[test]
+fn reserve_frames() {
+    let mut buffer = StaticBip::<i16, 10>::default();
+    assert_eq!(buffer.reserve_frames(4, 8).len(), 8);
+    assert_eq!(buffer.reserve_frames(3, 2).len(), 6);
+    buffer.commit(6);
+    assert!(buffer.reserve_frames(5, 1).is_empty());
+    assert_eq!(buffer.reserve_frames(2, 8).len(), 4);
+}
+
+#[test]
+fn read_frames() {
+    let mut buffer = StaticBip::<i16, 8>::default();
+    buffer.reserve(5).copy_from_slice(&[1, -1, 2, -2, 3]);
+    buffer.commit(5);
+    assert_eq!(buffer.read_frames(2), &[1, -1, 2, -2]);
+    assert!(buffer.read_frames(8).is_empty());
+}
+
+#[test]
+fn read_exact() {
+    let mut buffer = StaticBip::<u8, 6>::default();
+    buffer.reserve(6).copy_from_slice(&[1, 2, 3, 4, 5, 6]);
+    buffer.commit(6);
+    buffer.decommit(4);
+    buffer.reserve(3).copy_from_slice(&[7, 8, 9]);
+    buffer.commit(3);
+
+    assert_eq!(buffer.read_exact(2).unwrap(), &[5, 6]);
+    assert_eq!(buffer.read_exact(4).unwrap(), &[5, 6, 7, 8]);
+    assert_eq!(buffer.read(), &[5, 6, 7, 8, 9]);
+    assert!(buffer.read_exact(6).is_none());
+
+    buffer.decommit(4);
+    buffer.reserve(4).copy_from_slice(&[10, 11, 12, 13]);
+    buffer.commit(4);
+    assert_eq!(buffer.read_exact(5).unwrap(), &[9, 10, 11, 12, 13]);
+}
+
+// Relies on the deterministic wrap choice, which the `chaos` feature randomizes
+#[cfg(not(feature = "chaos"))]
+#[test]
+fn maybe_compact() {
+    let mut buffer = StaticBip::<u8, 8>::default();
+    buffer.reserve(7).copy_from_slice(&[1, 2, 3, 4, 5, 6, 7]);
+    buffer.commit(7);
+    buffer.decommit(5);
+    buffer.reserve(2).copy_from_slice(&[8, 9]);
+    buffer.commit(2);
+    assert_eq!(buffer.read(), &[6, 7]);
+
+    // Contiguous enough, a reservation outstanding, or too much to move
+    assert!(!buffer.maybe_compact(2, 8));
+    buffer.reserve(1);
+    assert!(!buffer.maybe_compact(4, 8));
+    buffer.commit(0);
+    assert!(!buffer.maybe_compact(4, 1));
+
+    // Region B is too large to append behind A, so both are rotated
+    assert!(buffer.maybe_compact(4, 8));
+    assert_eq!(buffer.read(), &[6, 7, 8, 9]);
+    assert!(!buffer.maybe_compact(8, 8));
+
+    // A short region B is appended behind A
+    buffer.decommit(3);
+    buffer.reserve(3).copy_from_slice(&[10, 11, 12]);
+    buffer.commit(3);
+    buffer.decommit(2);
+    buffer.reserve(1).copy_from_slice(&[13]);
+    buffer.commit(1);
+    assert_eq!(buffer.read(), &[11, 12]);
+    assert!(buffer.maybe_compact(8, 1));
+    assert_eq!(buffer.read(), &[11, 12, 13]);
+    assert!(buffer.state().is_valid(8));
+}
+
+#[test]
+fn chunks_exact() {
+    let mut buffer = StaticBip::<u8, 6>::default();
+    buffer.reserve(6).copy_from_slice(&[1, 2, 3, 4, 5, 6]);
+    buffer.commit(6);
+    buffer.decommit(1);
+    buffer.reserve(1).copy_from_slice(&[7]);
+    buffer.commit(1);
+
+    let chunks: Vec<_> = buffer.chunks_exact(2).map(<[u8]>::to_vec).collect();
+    assert_eq!(chunks, [[2, 3], [4, 5], [6, 7]]);
+    assert_eq!(buffer.committed(), 6);
+    assert_eq!(buffer.chunks_exact(4).count(), 1);
+}
+
+#[test]
+fn consume_chunks() {
+    let mut buffer = StaticBip::<u8, 6>::default();
+    buffer.reserve(6).copy_from_slice(&[1, 2, 3, 4, 5, 6]);
+    buffer.commit(6);
+    buffer.decommit(4);
+    buffer.reserve(3).copy_from_slice(&[7, 8, 9]);
+    buffer.commit(3);
+
+    let mut sums = Vec::new();
+    assert_eq!(
+        buffer.consume_chunks(2, |chunk| sums.push(chunk[0] + chunk[1])),
+        2
+    );
+    assert_eq!(sums, [11, 15]);
+    assert_eq!(buffer.read(), &[9]);
+}
+
+#[test]
+fn front_and_back() {
+    let mut buffer = StaticBip::<u8, 4>::default();
+    assert_eq!(buffer.front(), None);
+    assert_eq!(buffer.back(), None);
+
+    buffer.reserve(4).copy_from_slice(&[1, 2, 3, 4]);
+    buffer.commit(4);
+    assert_eq!(buffer.front(), Some(&1));
+    assert_eq!(buffer.back(), Some(&4));
+
+    buffer.decommit(2);
+    buffer.reserve(1).copy_from_slice(&[5]);
+    buffer.commit(1);
+    assert_eq!(buffer.front(), Some(&3));
+    assert_eq!(buffer.back(), Some(&5));
+
+    *buffer.back_mut().unwrap() = 6;
+    *buffer.front_mut().unwrap() = 0;
+    buffer.decommit(2);
+    assert_eq!(buffer.read(), &[6]);
+}
+
+#[test]
+fn truncate_back() {
+    let mut buffer = StaticBip::<u8, 4>::default();
+    buffer.reserve(4).copy_from_slice(&[1, 2, 3, 4]);
+    buffer.commit(4);
+    buffer.decommit(2);
+    buffer.reserve(2).copy_from_slice(&[5, 6]);
+    buffer.commit(2);
+
+    buffer.truncate_back(1);
+    assert_eq!(buffer.back(), Some(&5));
+
+    buffer.truncate_back(2);
+    assert_eq!(buffer.committed(), 1);
+    assert_eq!(buffer.read(), &[3]);
+
+    buffer.reserve(1).copy_from_slice(&[7]);
+    buffer.commit(1);
+    assert_eq!(buffer.back(), Some(&7));
+
+    buffer.truncate_back(10);
+    assert!(buffer.is_empty());
+}
+
+#[test]
+fn extend() {
+    let mut buffer = StaticBip::<u8, 4>::default();
+    buffer.extend(1..4);
+    assert_eq!(buffer.read(), &[1, 2, 3]);
+
+    buffer.decommit(1);
+    buffer.extend(&[4, 5, 6]);
+    assert_eq!(buffer.committed(), 4);
+    assert_eq!(buffer.read(), &[2, 3, 4]);
+    buffer.decommit(3);
+    assert_eq!(buffer.read(), &[5]);
+}
+
+#[test]
+fn into_iter() {
+    let mut buffer = StaticBip::new(<[String; 4]>::default());
+    buffer.extend(["a", "b", "c", "d"].iter().map(|s| s.to_string()));
+    buffer.decommit(3);
+    buffer.extend(["e", "f"].iter().map(|s| s.to_string()));
+
+    let items: Vec<String> = buffer.into_iter().collect();
+    assert_eq!(items, ["d", "e", "f"]);
+}
+
+#[test]
+fn clone_keeps_state() {
+    let mut buffer = StaticBip::<u8, 4>::default();
+    buffer.extend(&[1, 2, 3, 4]);
+    buffer.decommit(2);
+    buffer.reserve(1).copy_from_slice(&[5]);
+
+    let mut snapshot = buffer.clone();
+    buffer.commit(1);
+    buffer.decommit(2);
+
+    assert_eq!(snapshot.reserved(), 1);
+    snapshot.commit(1);
+    assert_eq!(snapshot.read(), &[3, 4]);
+    assert_eq!(buffer.read(), &[5]);
+}
+
+#[test]
+fn eq_compares_logical_contents() {
+    let mut wrapped = StaticBip::<u8, 4>::default();
+    wrapped.extend(&[0, 0, 1, 2]);
+    wrapped.decommit(2);
+    wrapped.extend(&[3]);
+
+    let mut flat = StaticBip::<u8, 8>::default();
+    flat.extend(&[1, 2, 3]);
+
+    assert_eq!(wrapped, flat);
+    assert_eq!(wrapped, [1, 2, 3]);
+    assert_eq!(wrapped, &[1, 2, 3][..]);
+    assert_ne!(wrapped, [1, 2]);
+
+    flat.decommit(1);
+    assert_ne!(wrapped, flat);
+}
+
+#[test]
+fn iter_and_slices() {
+    let mut buffer = StaticBip::<u8, 4>::default();
+    buffer.extend(&[1, 2, 3, 4]);
+    buffer.decommit(3);
+    buffer.extend(&[5, 6]);
+
+    assert_eq!(buffer.as_slices(), (&[4][..], &[5, 6][..]));
+    assert_eq!(buffer.iter().len(), 3);
+    assert!(buffer.iter().rev().eq(&[6, 5, 4]));
+
+    let (a, b) = buffer.as_mut_slices();
+    a[0] = 0;
+    b[1] = 0;
+    assert_eq!(buffer, [0, 5, 0]);
+}
+
+#[test]
+fn hash_ignores_layout() {
+    use std::{collections::hash_map::DefaultHasher, hash::Hash, hash::Hasher};
+
+    fn hash(value: impl Hash) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        value.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    let mut wrapped = StaticBip::<u8, 4>::default();
+    wrapped.extend(&[0, 0, 1, 2]);
+    wrapped.decommit(2);
+    wrapped.extend(&[3]);
+
+    let mut flat = StaticBip::<u8, 3>::default();
+    flat.extend(&[1, 2, 3]);
+
+    assert_eq!(hash(&wrapped), hash(&flat));
+    flat.truncate_back(1);
+    assert_ne!(hash(&wrapped), hash(&flat));
+}
+
+#[test]
+fn as_ref_is_region_a() {
+    fn sum(data: impl AsRef<[u8]>) -> u32 {
+        data.as_ref().iter().map(|&x| u32::from(x)).sum()
+    }
+
+    let mut buffer = StaticBip::<u8, 4>::default();
+    buffer.extend(&[0, 0, 1, 2]);
+    buffer.decommit(2);
+    buffer.extend(&[3]);
+    assert_eq!(sum(&buffer), 3);
+
+    buffer.as_mut()[0] = 5;
+    assert_eq!(buffer, [5, 2, 3]);
+}
+
+#[test]
+fn index_by_logical_position() {
+    let mut buffer = StaticBip::<u8, 4>::default();
+    buffer.extend(&[0, 0, 1, 2]);
+    buffer.decommit(2);
+    buffer.extend(&[3]);
+
+    assert_eq!((buffer[0], buffer[1], buffer[2]), (1, 2, 3));
+    assert_eq!(buffer.get(3), None);
+    buffer[2] = 4;
+    assert_eq!(buffer, [1, 2, 4]);
+
+    assert_eq!(&buffer[..2], &[1, 2]);
+    assert_eq!(&buffer[2..], &[4]);
+    assert_eq!(&buffer[1..=1], &[2]);
+    assert_eq!(buffer.get_slice(1..3), None);
+    assert_eq!(buffer.get_slice(2..5), None);
+    buffer[..2].copy_from_slice(&[5, 6]);
+    assert_eq!(buffer, [5, 6, 4]);
+}
+
+#[test]
+#[should_panic(expected = "index 3 out of bounds for 3 committed elements")]
+fn index_past_committed_panics() {
+    let mut buffer = StaticBip::<u8, 4>::default();
+    buffer.extend(&[1, 2, 3]);
+    let _ = buffer[3];
+}
+
+#[test]
+#[should_panic(expected = "not contiguous")]
+fn index_across_regions_panics() {
+    let mut buffer = StaticBip::<u8, 4>::default();
+    buffer.extend(&[0, 0, 1, 2]);
+    buffer.decommit(2);
+    buffer.extend(&[3]);
+    let _ = &buffer[..];
+}
+
+#[test]
+fn layout_map_marks_each_element() {
+    let mut buffer = StaticBip::<u8, 4>::default();
+    assert_eq!(buffer.debug_layout().to_string(), "[....]");
+    buffer.reserve(2);
+    assert_eq!(buffer.debug_layout().to_string(), "[RR..]");
+
+    let state = BipState {
+        a: Span { offset: 1, len: 2 },
+        b: Span { offset: 0, len: 0 },
+        reserve: Span { offset: 2, len: 2 },
+    };
+    assert_eq!(state.layout(5).to_string(), "[.A!R.]");
+}
+
+// Relies on the deterministic wrap choice, which the `chaos` feature randomizes
+#[cfg(not(feature = "chaos"))]
+#[test]
+fn try_reserve_is_all_or_nothing() {
+    let mut buffer = StaticBip::<u8, 6>::default();
+    buffer
+        .try_reserve(4)
+        .unwrap()
+        .copy_from_slice(&[1, 2, 3, 4]);
+    buffer.commit(4);
+    buffer.decommit(3);
+    assert_eq!(buffer.peek(), &[4]);
+
+    // Neither the 2 slots after the data nor the 3 before it fit 4
+    assert!(buffer.try_reserve(4).is_none());
+    assert_eq!(buffer.reserved(), 0);
+    assert_eq!(buffer.try_reserve(3).map(|slots| slots.len()), Some(3));
+    buffer.commit(0);
+
+    buffer.set_wrap_policy(WrapPolicy::NoWrap);
+    assert!(buffer.try_reserve(3).is_none());
+    buffer.set_wrap_policy(WrapPolicy::Fit);
+    assert!(buffer.try_reserve(3).is_some());
+}
+
+#[test]
+fn from_array_is_committed() {
+    let mut buffer = StaticBip::from([1, 2, 3]);
+    assert_eq!(buffer.committed(), 3);
+    assert!(buffer.reserve(1).is_empty());
+    assert_eq!(buffer.read(), &[1, 2, 3]);
+
+    buffer.decommit(1);
+    buffer.reserve(1).copy_from_slice(&[4]);
+    buffer.commit(1);
+    assert_eq!(buffer, [2, 3, 4]);
+
+    const REPLAY: StaticBip<&str, 2> = StaticBip::new_committed(["a", "b"]);
+    assert_eq!(REPLAY, ["a", "b"]);
+}
+
+#[test]
+fn into_inner() {
+    let mut buffer = StaticBip::<u8, 4>::default();
+    buffer.extend(&[1, 2, 3]);
+    assert_eq!(buffer.into_inner(), [1, 2, 3, 0]);
+}
+
+#[test]
+fn into_parts_round_trip() {
+    let mut buffer = StaticBip::<u8, 4>::default();
+    buffer.extend(&[1, 2, 3, 4]);
+    buffer.decommit(3);
+    buffer.extend(&[5]);
+    buffer.reserve(1);
+
+    let (array, state) = buffer.into_parts();
+    assert_eq!(state.a, Span { offset: 3, len: 1 });
+    assert_eq!(state.b, Span { offset: 0, len: 1 });
+    assert_eq!(state.reserve, Span { offset: 1, len: 1 });
+
+    let buffer = StaticBip::from_parts(array, state).unwrap();
+    assert_eq!(buffer, [4, 5]);
+    assert_eq!(buffer.reserved(), 1);
+
+    let overlapping = BipState {
+        reserve: Span { offset: 3, len: 1 },
+        ..state
+    };
+    assert!(StaticBip::from_parts(array, overlapping).is_none());
+}
+
+#[test]
+fn overflowing_spans_are_invalid() {
+    let wrapped = |span| {
+        [
+            BipState {
+                a: span,
+                ..BipState::default()
+            },
+            BipState {
+                b: span,
+                ..BipState::default()
+            },
+            BipState {
+                reserve: span,
+                ..BipState::default()
+            },
+        ]
+    };
+    for state in wrapped(Span {
+        offset: usize::MAX,
+        len: 1,
+    }) {
+        assert!(!state.is_valid(4));
+        assert!(StaticBip::from_parts([0u8; 4], state).is_none());
+        assert_eq!(StaticBip::<u8, 4>::default().restore(state), None);
+        assert!(state.layout(4).to_string().starts_with("[...."));
+    }
+}
+
+#[test]
+fn unreachable_states_are_invalid() {
+    let span = |offset, len| Span { offset, len };
+    let state = |a, b, reserve| BipState { a, b, reserve };
+    let valid = [
+        state(span(2, 2), span(0, 1), span(1, 1)),
+        state(span(2, 2), span(0, 0), span(4, 2)),
+        state(span(2, 2), span(0, 0), span(0, 2)),
+        state(span(0, 0), span(0, 0), span(0, 6)),
+        state(span(2, 2), span(0, 1), span(5, 0)),
+    ];
+    let invalid = [
+        // `B` holds data while `A` is empty
+        state(span(3, 0), span(0, 2), span(0, 0)),
+        // Reservation after `A` while `B` holds data
+        state(span(2, 2), span(0, 1), span(4, 2)),
+        // Reservation spanning both free blocks of an empty buffer
+        state(span(3, 0), span(0, 0), span(2, 2)),
+        // Reservation overlapping `B`
+        state(span(3, 2), span(0, 2), span(1, 2)),
+    ];
+    for state in valid {
+        assert!(state.is_valid(6), "{:?}", state);
+    }
+    for state in invalid {
+        assert!(!state.is_valid(6), "{:?}", state);
+        assert!(StaticBip::from_parts([0u8; 6], state).is_none());
+        assert_eq!(StaticBip::<u8, 6>::default().restore(state), None);
+    }
+
+    // Popping the last element of `A` hands region `B` over
+    let mut buffer = StaticBip::<u8, 4>::default();
+    buffer.extend(&[1, 2, 3, 4]);
+    buffer.decommit(3);
+    buffer.extend(&[5]);
+    assert_eq!(buffer.pop(), Some(&mut 4));
+    assert!(buffer.state().is_valid(4));
+    assert_eq!(buffer.read(), &[5]);
+}
+
+// Relies on the deterministic wrap choice, which the `chaos` feature randomizes
+#[cfg(not(feature = "chaos"))]
+#[test]
+fn find_spans_regions() {
+    let mut buffer = StaticBip::<u8, 8>::default();
+    buffer.extend(b"xxxxx\r");
+    buffer.decommit(4);
+    buffer.extend(b"\nab");
+
+    assert_eq!(buffer.as_slices(), (&b"x\r"[..], &b"\nab"[..]));
+    assert_eq!(buffer.find(b"\r\n"), Some(1));
+    assert_eq!(buffer.find(b"ab"), Some(3));
+    assert_eq!(buffer.find(b"abc"), None);
+    assert_eq!(buffer.find(b""), Some(0));
+    assert_eq!(buffer.position(|&byte| byte == b'\n'), Some(2));
+    assert_eq!(buffer.position(|&byte| byte == b'y'), None);
+}
+
+// Relies on clamping, which the `strict` feature turns into panics
+// Relies on the deterministic wrap choice, which the `chaos` feature randomizes
+#[cfg(not(any(feature = "strict", feature = "chaos")))]
+#[test]
+fn commit_without_reservation_keeps_regions() {
+    let mut buffer = StaticBip::<u8, 8>::default();
+    buffer.extend(b"abcdef");
+    buffer.decommit(4);
+    buffer.extend(b"gh");
+    buffer.reserve(2).copy_from_slice(b"ij");
+    buffer.commit(2);
+    buffer.commit(2);
+    assert_eq!(buffer.as_slices(), (&b"ef"[..], &b"ghij"[..]));
+}
+
+#[test]
+fn checked_variants() {
+    let mut buffer = StaticBip::<u8, 8>::default();
+    assert!(buffer.checked_reserve_frames(0, 2).is_none());
+    assert!(buffer.checked_read_frames(0).is_none());
+    assert!(buffer.checked_reserve_aligned(4, 3).is_none());
+
+    buffer.reserve(4);
+    assert_eq!(buffer.checked_commit(5), None);
+    assert_eq!(buffer.reserved(), 4);
+    assert_eq!(buffer.checked_commit(4), Some(()));
+
+    assert_eq!(buffer.checked_decommit(5), None);
+    assert_eq!(buffer.committed(), 4);
+    assert_eq!(buffer.checked_decommit(4), Some(()));
+    assert!(buffer.is_empty());
+}
+
+// Relies on clamping, which the `strict` feature turns into panics
+#[cfg(not(feature = "strict"))]
+#[test]
+fn shrink_reserve_keeps_head() {
+    let mut buffer = StaticBip::<u8, 8>::default();
+    buffer.reserve(8)[..3].copy_from_slice(&[1, 2, 3]);
+    assert_eq!(buffer.shrink_reserve(3), &[1, 2, 3]);
+    assert_eq!(buffer.reserved(), 3);
+    assert_eq!(buffer.shrink_reserve(5).len(), 3);
+
+    buffer.commit(8);
+    assert_eq!(buffer.read(), &[1, 2, 3]);
+}
+
+#[test]
+fn with_reserve_commits_returned_length() {
+    let mut buffer = StaticBip::<u8, 4>::default();
+    let written = buffer.with_reserve(3, |slots| {
+        slots[..2].copy_from_slice(&[1, 2]);
+        2
+    });
+    assert_eq!(written, 2);
+    assert_eq!(buffer.reserved(), 0);
+    assert_eq!(buffer.with_reserve(4, |slots| slots.len() + 1), 2);
+    assert_eq!(buffer.committed(), 4);
+}
+
+#[test]
+fn collect_commits_up_to_capacity() {
+    let buffer: StaticBip<u8, 4> = (1..=3).collect();
+    assert_eq!(buffer, [1, 2, 3]);
+
+    let mut items = 1..;
+    let buffer: StaticBip<u8, 4> = items.by_ref().collect();
+    assert_eq!(buffer, [1, 2, 3, 4]);
+    assert_eq!(items.next(), Some(5));
+
+    let buffer = StaticBip::<String, 2>::try_from_iter(vec!["a".into(), "b".into()]).unwrap();
+    assert_eq!(buffer, ["a", "b"]);
+    assert_eq!(StaticBip::<u8, 2>::try_from_iter(1..=3), Err(3));
+}
+
+// Relies on the deterministic wrap choice, which the `chaos` feature randomizes
+#[cfg(not(feature = "chaos"))]
+#[test]
+fn push_iter_stops_when_full() {
+    let mut buffer = StaticBip::<u8, 8>::default();
+    buffer.extend(b"xxxxxx");
+    buffer.decommit(5);
+
+    let mut items = 1..;
+    assert_eq!(buffer.push_iter(&mut items), 5);
+    assert_eq!(items.next(), Some(6));
+    assert_eq!(buffer.as_slices(), (&b"x"[..], &[1, 2, 3, 4, 5][..]));
+    assert_eq!(buffer.push_iter(1..), 0);
+}
+
+// Relies on the deterministic wrap choice, which the `chaos` feature randomizes
+#[cfg(not(feature = "chaos"))]
+#[test]
+fn read_with_consumes_across_regions() {
+    let mut buffer = StaticBip::<u8, 8>::default();
+    buffer.extend(b"xxxxxab");
+    buffer.decommit(5);
+    buffer.extend(b"cd");
+
+    let consumed = buffer.read_with(|a, b| {
+        assert_eq!((a, b), (&b"ab"[..], &b"cd"[..]));
+        3
+    });
+    assert_eq!(consumed, 3);
+    assert_eq!(buffer.read(), b"d");
+    assert_eq!(buffer.read_with(|_, _| 10), 1);
+    assert!(buffer.is_empty());
+}
+
+#[test]
+fn exact_commit_errors() {
+    let mut buffer = StaticBip::<u8, 4>::default();
+    buffer.reserve(2);
+    assert_eq!(buffer.commit_exact(3), Err(CommitError::Reservation));
+    assert_eq!(buffer.reserved(), 2);
+    assert_eq!(buffer.commit_exact(2), Ok(()));
+
+    assert_eq!(buffer.decommit_exact(3), Err(CommitError::Available));
+    assert_eq!(buffer.decommit_exact(2), Ok(()));
+    assert_eq!(
+        CommitError::Reservation.to_string(),
+        "length exceeds the reservation"
+    );
+}
+
+#[test]
+fn restore_rewinds_consumption() {
+    let mut buffer = StaticBip::<u8, 8>::default();
+    buffer.extend(b"abcd");
+    let state = buffer.state();
+
+    buffer.decommit(3);
+    assert_eq!(buffer.restore(state), Some(()));
+    assert_eq!(buffer.read(), b"abcd");
+
+    let invalid = BipState {
+        a: Span { offset: 6, len: 4 },
+        ..state
+    };
+    assert_eq!(buffer.restore(invalid), None);
+    assert_eq!(buffer.state(), state);
+
+    // Region `B` always starts at the beginning of the backing store
+    let detached = BipState {
+        a: Span { offset: 4, len: 4 },
+        b: Span { offset: 1, len: 2 },
+        ..state
+    };
+    assert_eq!(buffer.restore(detached), None);
+}
+
+#[test]
+fn wrap_policies() {
+    // One free slot after A, three before it
+    let fragmented = |policy| {
+        let mut buffer = StaticBip::<u8, 8>::default();
+        buffer.extend(b"xxxxxxx");
+        buffer.decommit(3);
+        buffer.set_wrap_policy(policy);
+        buffer
+    };
+    assert_eq!(
+        StaticBip::<u8, 8>::default().wrap_policy(),
+        WrapPolicy::Largest
+    );
+
+    let cases = [
+        (WrapPolicy::Largest, 1, 0),
+        (WrapPolicy::Largest, 8, 0),
+        (WrapPolicy::NoWrap, 1, 7),
+        (WrapPolicy::NoWrap, 8, 7),
+        (WrapPolicy::Fit, 1, 7),
+        (WrapPolicy::Fit, 2, 0),
+        (WrapPolicy::Fit, 8, 0),
+    ];
+    for &(policy, count, offset) in cases.iter() {
+        let mut buffer = fragmented(policy);
+        buffer.reserve(count);
+        assert_eq!(
+            buffer.state().reserve.offset,
+            offset,
+            "{:?} {}",
+            policy,
+            count
+        );
+    }
+
+    let mut buffer = fragmented(WrapPolicy::NoWrap);
+    let state = buffer.state();
+    buffer.restore(state);
+    assert_eq!(buffer.wrap_policy(), WrapPolicy::NoWrap);
+}
+
+// Relies on the deterministic wrap choice, which the `chaos` feature randomizes
+#[cfg(not(feature = "chaos"))]
+#[test]
+fn spare_capacity_commits_written_elements() {
+    let mut buffer = StaticBip::<u8, 4>::default();
+    let spare = unsafe { buffer.spare_capacity_mut() };
+    assert_eq!(spare.len(), 4);
+    spare[0] = MaybeUninit::new(1);
+    spare[1] = MaybeUninit::new(2);
+    unsafe { buffer.set_committed(2) };
+    assert_eq!(buffer.read(), &[1, 2]);
+    buffer.decommit(1);
+
+    let spare = unsafe { buffer.spare_capacity_mut() };
+    assert_eq!(spare.len(), 2);
+    unsafe { spare.as_mut_ptr().cast::<u8>().write(3) };
+    unsafe { buffer.set_committed(1) };
+    assert_eq!(buffer.read(), &[2, 3]);
+}
+
+#[test]
+fn set_committed_replaces_reservation_quietly() {
+    let mut buffer = StaticBip::<u8, 8>::default();
+    buffer.extend(&[1, 2, 3, 4, 5, 6]);
+    buffer.decommit(3);
+    buffer.reserve(1);
+
+    let spare = unsafe { buffer.spare_capacity_mut() };
+    unsafe { spare.as_mut_ptr().cast::<u8>().write(7) };
+    unsafe { buffer.set_committed(1) };
+
+    #[cfg(feature = "stats")]
+    assert_eq!(buffer.abandoned_reservations(), 0);
+    assert_eq!(buffer.reserved(), 0);
+    // The element lands in the block that was handed out, whichever one it was
+    assert_eq!(buffer.back(), Some(&7));
+}
+
+#[test]
+fn split_mut_reads_while_writing() {
+    let mut buffer = StaticBip::<u8, 6>::default();
+    buffer.reserve(3).copy_from_slice(&[1, 2, 3]);
+    buffer.commit(3);
+
+    buffer.reserve(3);
+    let (read, reserved) = buffer.split_mut();
+    reserved.copy_from_slice(read);
+    read[0] = 0;
+    buffer.commit(3);
+    buffer.decommit(3);
+    assert_eq!(buffer.read(), &[1, 2, 3]);
+
+    buffer.reserve(3);
+    let (read, reserved) = buffer.split_mut();
+    assert_eq!((read.len(), reserved.len()), (3, 3));
+    for (dst, src) in reserved.iter_mut().zip(read.iter()) {
+        *dst = src * 2;
+    }
+    buffer.commit(3);
+    buffer.decommit(3);
+    assert_eq!(buffer.read(), &[2, 4, 6]);
+}
+
+#[test]
+fn reset_drops_removed_elements() {
+    use std::rc::Rc;
+
+    let resource = Rc::new(());
+    let mut buffer = StaticBip::<Option<Rc<()>>, 4>::default();
+    for slot in buffer.reserve(3) {
+        *slot = Some(Rc::clone(&resource));
+    }
+    buffer.commit(3);
+    assert_eq!(Rc::strong_count(&resource), 4);
+
+    buffer.decommit_reset(2);
+    assert_eq!(Rc::strong_count(&resource), 2);
+    assert_eq!(buffer.committed(), 1);
+
+    buffer.clear_reset();
+    assert_eq!(Rc::strong_count(&resource), 1);
+
+    buffer.reserve(1)[0] = Some(Rc::clone(&resource));
+    buffer.commit(1);
+    drop(buffer);
+    assert_eq!(Rc::strong_count(&resource), 1);
+}
+
+// Relies on the deterministic wrap choice, which the `chaos` feature randomizes
+#[cfg(not(feature = "chaos"))]
+#[test]
+fn zero_sized_elements_are_counted() {
+    use std::cell::Cell;
+
+    thread_local!(static DROPS: Cell<usize> = const { Cell::new(0) });
+    #[derive(Default)]
+    struct Token;
+    impl Drop for Token {
+        fn drop(&mut self) {
+            DROPS.with(|drops| drops.set(drops.get() + 1));
+        }
+    }
+
+    let mut buffer = StaticBip::<Token, 4>::default();
+    assert_eq!(buffer.reserve(3).len(), 3);
+    buffer.commit(3);
+    buffer.decommit(2);
+    assert_eq!(buffer.reserve(4).len(), 2);
+    buffer.commit(2);
+    let state = buffer.state();
+    assert_eq!((state.a.len, state.b.len), (1, 2));
+    assert_eq!(buffer.reserve_aligned(1, 64).len(), 0);
+    buffer.commit(0);
+    buffer.decommit(1);
+    assert_eq!(buffer.reserve_aligned(1, 64).len(), 1);
+    buffer.commit(1);
+    assert_eq!(buffer.committed(), 3);
+
+    DROPS.with(|drops| drops.set(0));
+    drop(buffer);
+    assert_eq!(DROPS.with(Cell::get), 4);
+}
+
+#[test]
+fn pop_value_moves_elements_out() {
+    let mut buffer = StaticBip::<String, 4>::default();
+    for (slot, word) in buffer.reserve(3).iter_mut().zip(["a", "b", "c"].iter()) {
+        *slot = word.to_string();
+    }
+    buffer.commit(3);
+
+    assert_eq!(buffer.pop_value().as_deref(), Some("a"));
+    assert_eq!(buffer.drain().len(), 2);
+    assert_eq!(buffer.drain().next().as_deref(), Some("b"));
+    assert_eq!(buffer.committed(), 1);
+    assert_eq!(buffer.drain().collect::<Vec<_>>(), ["c"]);
+    assert_eq!(buffer.pop_value(), None);
+}
+
+#[test]
+fn write_from_slices_gathers_into_one_block() {
+    let mut buffer = StaticBip::<u8, 6>::default();
+    assert_eq!(buffer.write_from_slices(&[&[1, 2], &[], &[3, 4, 5]]), 5);
+    assert_eq!(buffer.read(), &[1, 2, 3, 4, 5]);
+    buffer.decommit(5);
+
+    assert_eq!(buffer.write_from_slices(&[&[6], &[7, 8]]), 3);
+    assert_eq!(buffer.write_from_slices(&[&[9, 10], &[11, 12, 13]]), 3);
+    assert_eq!(buffer.as_slices(), (&[6, 7, 8, 9, 10, 11][..], &[][..]));
+}
+
+#[test]
+fn export_and_import_logical_contents() {
+    let mut buffer = StaticBip::<u8, 6>::default();
+    buffer.extend(&[0, 0, 0, 1, 2, 3]);
+    buffer.decommit(3);
+    buffer.extend(&[4, 5]);
+    assert_eq!(buffer.as_slices(), (&[1, 2, 3][..], &[4, 5][..]));
+
+    let mut dump = [0; 8];
+    assert_eq!(buffer.export_into(&mut dump), 5);
+    assert_eq!(dump[..5], [1, 2, 3, 4, 5]);
+    let mut short = [0; 4];
+    assert_eq!(buffer.export_into(&mut short), 4);
+    assert_eq!(short, [1, 2, 3, 4]);
+    assert_eq!(buffer.committed(), 5);
+
+    let mut restored = StaticBip::<u8, 6>::default();
+    restored.extend(&[9]);
+    assert_eq!(restored.import(&dump[..5]), 5);
+    assert_eq!(restored.read(), &[1, 2, 3, 4, 5]);
+    assert_eq!(restored.import(&[7; 8]), 6);
+    assert_eq!(restored.read(), &[7; 6]);
+}
+
+// Relies on the deterministic wrap choice, which the `chaos` feature randomizes
+#[cfg(not(feature = "chaos"))]
+#[test]
+fn extend_reserve_grows_in_place() {
+    let mut buffer = StaticBip::<u8, 8>::default();
+    buffer.reserve(2).copy_from_slice(&[1, 2]);
+    assert_eq!(buffer.extend_reserve(3), &[1, 2, 0, 0, 0]);
+    assert_eq!(buffer.extend_reserve(8).len(), 8);
+    buffer.commit(6);
+    buffer.decommit(4);
+
+    // Wrapped to the start, where the data at index 4 bounds the reservation
+    buffer.reserve(1)[0] = 3;
+    assert_eq!(buffer.extend_reserve(8), &[3, 2, 0, 0]);
+    buffer.commit(4);
+    assert_eq!(buffer.extend_reserve(1), &[]);
+}
+
+#[test]
+fn commit_iter_fills_reservation() {
+    let mut buffer = StaticBip::<u8, 8>::default();
+    buffer.reserve(4);
+    let mut items = 1..;
+    assert_eq!(buffer.commit_iter(&mut items), 4);
+    assert_eq!(items.next(), Some(5));
+    assert_eq!(buffer.read(), &[1, 2, 3, 4]);
+
+    buffer.reserve(4);
+    assert_eq!(buffer.commit_iter(b"ab".iter().copied()), 2);
+    assert_eq!(buffer.commit_iter(0..), 0);
+    assert_eq!(buffer.read(), &[1, 2, 3, 4, b'a', b'b']);
+}
+
+#[cfg(feature = "stats")]
+#[test]
+fn stream_positions_track_commits_and_reads() {
+    let mut buffer = StaticBip::<u8, 4>::default();
+    for _ in 0..3 {
+        buffer.extend(&[1, 2, 3]);
+        buffer.pop();
+        buffer.decommit(1);
+        assert_eq!(buffer.read_with(|a, b| a.len() + b.len()), 1);
+    }
+    assert_eq!((buffer.total_written(), buffer.total_read()), (9, 9));
+
+    buffer.extend(&[1, 2, 3]);
+    buffer.truncate_back(1);
+    buffer.set_wrap_policy(WrapPolicy::NoWrap);
+    buffer.clear();
+    assert_eq!((buffer.total_written(), buffer.total_read()), (11, 11));
+    assert_eq!(buffer.wrap_policy(), WrapPolicy::NoWrap);
+
+    buffer.extend(&[4, 5]);
+    let state = buffer.state();
+    buffer.decommit(2);
+    buffer.restore(state).unwrap();
+    assert_eq!((buffer.total_written(), buffer.total_read()), (13, 11));
+}
+
+// Abandons reservations, which the `strict` feature turns into panics
+#[cfg(all(feature = "stats", not(feature = "strict")))]
+#[test]
+fn abandoned_reservations_are_counted() {
+    let mut buffer = StaticBip::<u8, 8>::default();
+    buffer.reserve(0);
+    buffer.reserve(3);
+    assert_eq!(buffer.abandoned_reservations(), 0);
+
+    buffer.reserve(2);
+    buffer.commit(0);
+    buffer.reserve(2);
+    assert_eq!(buffer.abandoned_reservations(), 1);
+
+    let state = buffer.state();
+    buffer.restore(state).unwrap();
+    buffer.reserve(1);
+    assert_eq!(buffer.abandoned_reservations(), 2);
+}
+
+#[cfg(not(feature = "stats"))]
+#[test]
+fn bookkeeping_is_five_cursors_and_the_policy() {
+    let word = core::mem::size_of::<usize>();
+    assert_eq!(core::mem::size_of::<StaticBip<u8, 0>>(), 6 * word);
+}
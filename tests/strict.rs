@@ -0,0 +1,46 @@
+#![cfg(all(feature = "strict", debug_assertions))]
+
+use staticbip::{hook::Hooked, BipBuf, StaticBip};
+
+#[test]
+#[should_panic(expected = "exceeds the reservation")]
+fn commit_beyond_reservation() {
+    let mut buffer = StaticBip::<u8, 4>::default();
+    buffer.reserve(2);
+    buffer.commit(3);
+}
+
+#[test]
+#[should_panic(expected = "readable ones")]
+fn decommit_beyond_read_block() {
+    let mut buffer = StaticBip::<u8, 4>::default();
+    buffer.extend(&[1, 2]);
+    buffer.decommit(3);
+}
+
+#[test]
+#[should_panic(expected = "still reserved")]
+fn reserve_while_reserved() {
+    let mut buffer = StaticBip::<u8, 4>::default();
+    buffer.reserve(1);
+    buffer.reserve(1);
+}
+
+#[test]
+fn exact_use_passes() {
+    let mut buffer = StaticBip::<u8, 4>::default();
+    buffer.reserve(2).copy_from_slice(&[1, 2]);
+    buffer.commit(2);
+    buffer.reserve(0);
+    buffer.commit(0);
+    buffer.decommit(2);
+    assert!(buffer.is_empty());
+}
+
+#[test]
+#[should_panic(expected = "exceeds the reservation")]
+fn commit_beyond_reservation_through_hooks() {
+    let mut buffer = Hooked::new(StaticBip::<u8, 4>::default(), ());
+    buffer.reserve(2);
+    buffer.commit(3);
+}
@@ -0,0 +1,36 @@
+use staticbip::{text::Utf8Strs, StaticBip};
+
+#[test]
+fn str_views_stop_at_invalid_bytes() {
+    let mut buffer = StaticBip::<u8, 8>::default();
+    buffer.extend(b"ok\xFFok");
+    assert_eq!(buffer.as_str(), ("ok", &b"\xFFok"[..]));
+    assert_eq!(
+        buffer.as_strs(),
+        Utf8Strs {
+            head: "ok",
+            boundary: None,
+            tail: "",
+            valid_up_to: 2,
+        }
+    );
+}
+
+// Relies on the deterministic wrap choice, which the `chaos` feature randomizes
+#[cfg(not(feature = "chaos"))]
+#[test]
+fn incomplete_character_needs_more_data() {
+    let mut buffer = StaticBip::<u8, 8>::default();
+    buffer.extend(b"xxxx");
+    buffer.decommit(3);
+    buffer.extend(&"€".as_bytes()[..2]);
+    assert_eq!(buffer.as_str(), ("x", &"€".as_bytes()[..2]));
+    assert_eq!(buffer.as_strs().valid_up_to, 1);
+
+    buffer.decommit(1);
+    buffer.extend(&"€".as_bytes()[2..]);
+    buffer.extend(b"ab");
+    let strs = buffer.as_strs();
+    assert_eq!((strs.head, strs.boundary, strs.tail), ("", Some('€'), "ab"));
+    assert_eq!(strs.valid_up_to, 5);
+}
@@ -0,0 +1,41 @@
+use staticbip::timed::TimedBip;
+
+#[test]
+fn records_limit_and_order() {
+    let mut buffer = TimedBip::<u8, u16, 8, 2>::default();
+    assert_eq!(buffer.read(), None);
+    assert_eq!(buffer.age(5), None);
+
+    buffer.reserve(3).copy_from_slice(&[1, 2, 3]);
+    buffer.commit(3, 65_530);
+    buffer.reserve(1);
+    buffer.commit(0, 0);
+    buffer.reserve(2).copy_from_slice(&[4, 5]);
+    buffer.commit(2, 4);
+    assert!(buffer.reserve(2).is_empty());
+    assert_eq!(buffer.records(), 2);
+
+    // Tick counters wrap
+    assert_eq!(buffer.age(10), Some(16));
+    assert_eq!(buffer.read(), Some((65_530, &mut [1, 2, 3][..])));
+    buffer.decommit();
+    assert_eq!(buffer.read(), Some((4, &mut [4, 5][..])));
+    buffer.decommit();
+    buffer.decommit();
+    assert_eq!(buffer.oldest(), None);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn std_instants() {
+    use std::time::{Duration, Instant};
+
+    let start = Instant::now();
+    let mut buffer = TimedBip::<u8, Instant, 4, 2>::default();
+    buffer.reserve(1);
+    buffer.commit(1, start);
+    assert_eq!(
+        buffer.age(start + Duration::from_millis(5)),
+        Some(Duration::from_millis(5))
+    );
+}
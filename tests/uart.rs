@@ -0,0 +1,141 @@
+use staticbip::{
+    bbqueue::BufStorage,
+    uart::{AsyncUart, BufferedUart, TimedOut, UartIrq},
+    StaticBip,
+};
+use std::{
+    cell::Cell,
+    future::{poll_fn, Future},
+    pin::pin,
+    task::{Context, Poll, Waker},
+};
+
+#[test]
+fn counts_overruns() {
+    let mut uart = BufferedUart::new(StaticBip::<u8, 2>::default(), StaticBip::<u8, 2>::default());
+    let mut fifo = [1, 2, 3, 4].iter().copied();
+    assert_eq!(uart.on_rx_irq(|| fifo.next().ok_or(())), 2);
+    assert_eq!(uart.overruns(), 2);
+
+    let mut data = [0; 4];
+    assert_eq!(uart.read(&mut data), Ok(2));
+    assert_eq!(&data[..2], &[1, 2]);
+}
+
+#[test]
+fn transmit_stops_when_port_is_busy() {
+    let mut uart = BufferedUart::new(StaticBip::<u8, 4>::default(), StaticBip::<u8, 4>::default());
+    uart.write(&[1, 2, 3]).unwrap();
+
+    let mut room = 2;
+    let mut sent = Vec::new();
+    let pending = uart.on_tx_irq(|byte| {
+        if room == 0 {
+            return Err(());
+        }
+        room -= 1;
+        sent.push(byte);
+        Ok(())
+    });
+    assert!(pending);
+    assert_eq!(sent, [1, 2]);
+
+    assert!(!uart.on_tx_irq(|byte| {
+        sent.push(byte);
+        Ok::<_, ()>(())
+    }));
+    assert_eq!(sent, [1, 2, 3]);
+}
+
+#[test]
+fn async_port_waits_for_the_interrupt_side() {
+    let rx = BufStorage::<4>::new();
+    let tx = BufStorage::<4>::new();
+    let (rx_producer, rx_consumer) = rx.try_split().unwrap();
+    let (tx_producer, tx_consumer) = tx.try_split().unwrap();
+    let mut irq = UartIrq::new(rx_producer, tx_consumer);
+    let starts = Cell::new(0);
+    let mut uart = AsyncUart::new(rx_consumer, tx_producer, || starts.set(starts.get() + 1));
+    let mut cx = Context::from_waker(Waker::noop());
+
+    let mut data = [0; 8];
+    {
+        let mut read = pin!(uart.read(&mut data));
+        assert_eq!(read.as_mut().poll(&mut cx), Poll::Pending);
+        let mut fifo = [1, 2, 3, 4, 5].iter().copied();
+        assert_eq!(irq.on_rx_irq(|| fifo.next().ok_or(())), 4);
+        assert_eq!(read.as_mut().poll(&mut cx), Poll::Ready(4));
+    }
+    assert_eq!(irq.overruns(), 1);
+    assert_eq!(&data[..4], &[1, 2, 3, 4]);
+
+    {
+        let mut write = pin!(uart.write_all(&[1, 2, 3, 4, 5, 6]));
+        assert_eq!(write.as_mut().poll(&mut cx), Poll::Pending);
+        let mut sent = Vec::new();
+        let mut room = 3;
+        let pending = irq.on_tx_irq(|byte| {
+            if room == 0 {
+                return Err(());
+            }
+            room -= 1;
+            sent.push(byte);
+            Ok::<_, ()>(())
+        });
+        assert!(pending);
+        assert_eq!(sent, [1, 2, 3]);
+        assert_eq!(write.as_mut().poll(&mut cx), Poll::Ready(()));
+    }
+    assert_eq!(starts.get(), 2);
+
+    let mut flush = pin!(uart.flush());
+    assert_eq!(flush.as_mut().poll(&mut cx), Poll::Pending);
+    let mut sent = Vec::new();
+    assert!(!irq.on_tx_irq(|byte| {
+        sent.push(byte);
+        Ok::<_, ()>(())
+    }));
+    assert_eq!(sent, [4, 5, 6]);
+    assert_eq!(flush.as_mut().poll(&mut cx), Poll::Ready(()));
+}
+
+#[test]
+fn read_timeout_returns_what_arrived_by_the_deadline() {
+    let rx = BufStorage::<8>::new();
+    let tx = BufStorage::<8>::new();
+    let (rx_producer, rx_consumer) = rx.try_split().unwrap();
+    let (tx_producer, tx_consumer) = tx.try_split().unwrap();
+    let mut irq = UartIrq::new(rx_producer, tx_consumer);
+    let mut uart = AsyncUart::new(rx_consumer, tx_producer, || {});
+    let mut cx = Context::from_waker(Waker::noop());
+
+    let expired = Cell::new(false);
+    let deadline = || {
+        poll_fn(|_| match expired.get() {
+            true => Poll::Ready(()),
+            false => Poll::Pending,
+        })
+    };
+    let mut data = [0; 8];
+
+    {
+        let mut read = pin!(uart.read_timeout(&mut data, 4, deadline()));
+        assert_eq!(read.as_mut().poll(&mut cx), Poll::Pending);
+        let mut fifo = [1, 2].iter().copied();
+        irq.on_rx_irq(|| fifo.next().ok_or(()));
+        assert_eq!(read.as_mut().poll(&mut cx), Poll::Pending);
+        expired.set(true);
+        assert_eq!(read.as_mut().poll(&mut cx), Poll::Ready(Ok(2)));
+    }
+    assert_eq!(&data[..2], &[1, 2]);
+
+    let read = uart.read_timeout(&mut data, 4, deadline());
+    assert_eq!(pin!(read).poll(&mut cx), Poll::Ready(Err(TimedOut)));
+
+    expired.set(false);
+    let mut fifo = [3, 4, 5, 6].iter().copied();
+    irq.on_rx_irq(|| fifo.next().ok_or(()));
+    let read = uart.read_timeout(&mut data, 4, deadline());
+    assert_eq!(pin!(read).poll(&mut cx), Poll::Ready(Ok(4)));
+    assert_eq!(&data[..4], &[3, 4, 5, 6]);
+}
@@ -0,0 +1,43 @@
+use staticbip::{uninit::ReadUninit, StaticBip};
+use std::mem::MaybeUninit;
+
+/// Hands out queued chunks, then an error.
+struct Chunks(Vec<&'static [u8]>);
+
+unsafe impl ReadUninit for Chunks {
+    type Error = ();
+
+    fn read_uninit(&mut self, buf: &mut [MaybeUninit<u8>]) -> Result<usize, ()> {
+        if self.0.is_empty() {
+            return Err(());
+        }
+        let chunk = self.0.remove(0);
+        for (slot, &byte) in buf.iter_mut().zip(chunk) {
+            slot.write(byte);
+        }
+        Ok(chunk.len().min(buf.len()))
+    }
+}
+
+#[test]
+fn errors_end_the_read_after_data() {
+    let mut buffer = StaticBip::<u8, 4>::default();
+    let mut reader = Chunks(vec![b"ab", b"cd"]);
+    assert_eq!(buffer.read_uninit_from(&mut reader), Ok(2));
+    assert_eq!(buffer.read_uninit_from(&mut reader), Ok(2));
+    assert_eq!(buffer.read(), b"abcd");
+
+    buffer.decommit(4);
+    assert_eq!(buffer.read_uninit_from(&mut reader), Err(()));
+    assert!(buffer.is_empty());
+    assert_eq!(buffer.reserved(), 0);
+}
+
+#[test]
+fn full_reads_continue_until_the_buffer_is_full() {
+    let mut buffer = StaticBip::<u8, 4>::default();
+    let mut reader = Chunks(vec![b"abcd", b"efgh"]);
+    assert_eq!(buffer.read_uninit_from(&mut reader), Ok(4));
+    assert_eq!(buffer.read_uninit_from(&mut reader), Ok(0));
+    assert_eq!(reader.0.len(), 1);
+}
@@ -0,0 +1,41 @@
+use staticbip::{nb::WouldBlock, usb::UsbBuffer, StaticBip};
+
+fn buffer() -> UsbBuffer<8, 16> {
+    UsbBuffer::new(StaticBip::default(), StaticBip::default(), 4)
+}
+
+#[test]
+fn holds_off_packets_that_do_not_fit() {
+    let mut buffer = buffer();
+    for _ in 0..2 {
+        assert_eq!(
+            buffer.read_packet(|packet| Ok::<_, ()>(packet.len())),
+            Ok(4)
+        );
+    }
+    assert_eq!(
+        buffer.read_packet(|_| -> Result<usize, ()> { unreachable!() }),
+        Ok(0)
+    );
+    assert_eq!(buffer.read_packet(|_| Err(())), Ok(0));
+}
+
+#[test]
+fn ends_full_transfers_with_empty_packet() {
+    let mut buffer = buffer();
+    assert_eq!(buffer.write_packet(|_| Ok::<_, ()>(0)), Ok(0));
+    assert_eq!(buffer.write(&[1, 2, 3, 4, 5, 6, 7, 8]), Ok(8));
+
+    let mut packets = 0;
+    while buffer
+        .write_packet(|packet| {
+            packets += 1;
+            Ok::<_, ()>(packet.len())
+        })
+        .unwrap()
+        != 0
+    {}
+    assert_eq!(packets, 3);
+    assert_eq!(buffer.write_packet(|_| Ok::<_, ()>(0)), Ok(0));
+    assert_eq!(buffer.read(&mut [0; 4]), Err(WouldBlock));
+}
@@ -0,0 +1,23 @@
+// Relies on the deterministic wrap choice, which the `chaos` feature randomizes
+#![cfg(not(feature = "chaos"))]
+
+use staticbip::StaticBip;
+
+#[test]
+fn reads_across_regions() {
+    let mut buffer = StaticBip::<u8, 4>::default();
+    let mut volatile = buffer.volatile();
+    assert_eq!(volatile.write(&[1, 2, 3]), 3);
+    let mut out = [0; 2];
+    assert_eq!(volatile.read(&mut out), 2);
+
+    // Wraps to the two free slots at the start
+    assert_eq!(volatile.write(&[4, 5, 6]), 2);
+    assert_eq!(volatile.front(), Some(3));
+    assert_eq!(volatile.committed(), 3);
+
+    let mut out = [0; 4];
+    assert_eq!(volatile.read(&mut out), 3);
+    assert_eq!(out, [3, 4, 5, 0]);
+    assert_eq!(buffer.committed(), 0);
+}
@@ -0,0 +1,62 @@
+use staticbip::waker::AtomicWaker;
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    task::{Wake, Waker},
+    thread,
+};
+
+#[derive(Default)]
+struct Counter(AtomicUsize);
+
+impl Wake for Counter {
+    fn wake(self: Arc<Self>) {
+        self.0.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+#[test]
+fn wakes_the_latest_registration_once() {
+    let first = Arc::new(Counter::default());
+    let second = Arc::new(Counter::default());
+    let slot = AtomicWaker::new();
+
+    slot.wake();
+    slot.register(&Waker::from(first.clone()));
+    slot.register(&Waker::from(second.clone()));
+    slot.wake();
+    slot.wake();
+    assert_eq!(first.0.load(Ordering::Relaxed), 0);
+    assert_eq!(second.0.load(Ordering::Relaxed), 1);
+    assert!(slot.take().is_none());
+}
+
+#[test]
+fn no_wake_up_is_lost_across_threads() {
+    let counter = Arc::new(Counter::default());
+    let waker = Waker::from(counter.clone());
+    let slot = Arc::new(AtomicWaker::new());
+    let flag = Arc::new(AtomicUsize::new(0));
+
+    for round in 1..=1000 {
+        let waking = {
+            let (slot, flag) = (slot.clone(), flag.clone());
+            thread::spawn(move || {
+                flag.store(round, Ordering::Release);
+                slot.wake();
+            })
+        };
+        // Register, then check the condition, as a poll function does
+        slot.register(&waker);
+        let seen = flag.load(Ordering::Acquire) == round;
+        waking.join().unwrap();
+        if !seen {
+            // The condition was not yet met, so the wake-up must have reached the waker
+            assert!(counter.0.load(Ordering::Relaxed) >= 1);
+        }
+        slot.take();
+        counter.0.store(0, Ordering::Relaxed);
+    }
+}
@@ -0,0 +1,45 @@
+use staticbip::watchdog::Watchdog;
+#[cfg(feature = "stats")]
+use staticbip::StaticBip;
+
+#[cfg(feature = "stats")]
+#[test]
+fn busy_consumer_is_not_stalled() {
+    let mut buffer = StaticBip::<u8, 8>::default();
+    let mut watchdog = Watchdog::<u32>::new();
+    for now in 0..20 {
+        buffer.extend(&[1, 2]);
+        buffer.decommit(1);
+        watchdog.observe(&buffer, now * 10);
+        assert_eq!(watchdog.stalled_for(now * 10), Some(0));
+        buffer.decommit(1);
+    }
+}
+
+#[cfg(feature = "stats")]
+#[test]
+fn age_in_committed_elements() {
+    let mut buffer = StaticBip::<u8, 8>::default();
+    let mut watchdog = Watchdog::<u64>::new();
+    watchdog.observe(&buffer, buffer.total_written());
+    assert_eq!(watchdog.stalled_for(buffer.total_written()), None);
+
+    buffer.extend(&[1]);
+    watchdog.observe(&buffer, buffer.total_written());
+    buffer.extend(&[2, 3, 4]);
+    watchdog.observe(&buffer, buffer.total_written());
+    assert_eq!(watchdog.stalled_for(buffer.total_written()), Some(3));
+    assert!(watchdog.is_stalled(buffer.total_written(), 2));
+
+    watchdog.reset();
+    assert!(!watchdog.is_stalled(buffer.total_written(), 2));
+}
+
+#[test]
+fn positions_from_other_buffers() {
+    let mut watchdog = Watchdog::<u16>::new();
+    watchdog.observe_position(5, 3, u16::MAX - 1);
+    watchdog.observe_position(5, 3, 8);
+    // Tick counters wrap
+    assert_eq!(watchdog.stalled_for(8), Some(10));
+}